@@ -0,0 +1,105 @@
+//! Annotations
+//!
+//! Turns labeled comments and highlights into stubs and renders
+//! annotations for export. `%% verify: check the date %%` becomes a
+//! `verify` stub when `verify` is a known stub type; unlabeled annotations
+//! and other labels are only exported.
+
+use doc_doctor_domain::{Annotation, AnnotationKind, Stub, StubTypesConfig};
+
+/// Annotations found in one document
+#[derive(Debug, Clone)]
+pub struct DocumentAnnotations {
+    /// Document path, as displayed
+    pub path: String,
+    /// Annotations in document order
+    pub annotations: Vec<Annotation>,
+}
+
+/// Convert a labeled annotation into a stub
+///
+/// Returns `None` unless the label (optionally `stub/`-prefixed) is a known
+/// stub type and there is text to describe the gap.
+pub fn stub_from_annotation(annotation: &Annotation, stub_types: &StubTypesConfig) -> Option<Stub> {
+    let label = annotation.label.as_deref()?;
+    let stub = Stub::compact(label.strip_prefix("stub/").unwrap_or(label), annotation.text.clone());
+    (stub_types.is_known(&stub.stub_type) && !stub.description.is_empty()).then_some(stub)
+}
+
+/// Render annotations as a Markdown document, one section per file
+///
+/// Files without annotations are left out.
+pub fn annotations_markdown(documents: &[DocumentAnnotations]) -> String {
+    let mut out = String::from("# Annotations\n");
+
+    for document in documents.iter().filter(|d| !d.annotations.is_empty()) {
+        out.push_str(&format!("\n## {}\n\n", document.path));
+        for annotation in &document.annotations {
+            let text = annotation.text.split_whitespace().collect::<Vec<_>>().join(" ");
+            let text = match annotation.kind {
+                AnnotationKind::Highlight => format!("=={}==", text),
+                _ => text,
+            };
+            let label = annotation
+                .label
+                .as_ref()
+                .map(|l| format!("**{}**: ", l))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "- {}{} _({}, line {})_\n",
+                label, text, annotation.kind, annotation.position.line
+            ));
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_domain::SourcePosition;
+
+    fn annotation(kind: AnnotationKind, label: Option<&str>, text: &str) -> Annotation {
+        Annotation {
+            kind,
+            label: label.map(str::to_string),
+            text: text.to_string(),
+            position: SourcePosition::new(3, 1, 20),
+            end_offset: 40,
+        }
+    }
+
+    #[test]
+    fn test_stub_from_annotation() {
+        let config = StubTypesConfig::default();
+
+        let stub = stub_from_annotation(&annotation(AnnotationKind::Comment, Some("verify"), "check the date"), &config);
+        assert_eq!(stub.unwrap().stub_type.as_str(), "verify");
+        assert!(stub_from_annotation(&annotation(AnnotationKind::Comment, Some("stub/link"), "source"), &config).is_some());
+        assert!(stub_from_annotation(&annotation(AnnotationKind::Comment, Some("note"), "aside"), &config).is_none());
+        assert!(stub_from_annotation(&annotation(AnnotationKind::Highlight, None, "key"), &config).is_none());
+    }
+
+    #[test]
+    fn test_annotations_markdown() {
+        let documents = vec![
+            DocumentAnnotations {
+                path: "a.md".to_string(),
+                annotations: vec![
+                    annotation(AnnotationKind::Comment, Some("verify"), "check\nthe date"),
+                    annotation(AnnotationKind::Highlight, None, "key claim"),
+                ],
+            },
+            DocumentAnnotations {
+                path: "empty.md".to_string(),
+                annotations: Vec::new(),
+            },
+        ];
+
+        assert_eq!(
+            annotations_markdown(&documents),
+            "# Annotations\n\n## a.md\n\n- **verify**: check the date _(comment, line 3)_\n- ==key claim== _(highlight, line 3)_\n"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+//! Canvas Analysis
+//!
+//! Analyzes an Obsidian canvas as a document. Metadata comes from the
+//! canvas's sidecar (`board.canvas.dd.yaml`), which the repository delivers
+//! as frontmatter ahead of the canvas JSON. Each text card is read like a
+//! small note, and its open tasks, labeled annotations and `TODO:` lines
+//! become stubs of the canvas.
+
+use doc_doctor_domain::{DocumentAnalysis, MarkdownBody, Stub, StubTypesConfig};
+
+use crate::annotations::stub_from_annotation;
+use crate::inline::stub_from_task;
+use crate::links::LinkEdge;
+use crate::markers::stubs_from_markers;
+
+/// A text card and what was found on it
+#[derive(Debug, Clone)]
+pub struct CanvasCard {
+    /// Canvas node ID
+    pub node_id: String,
+    /// Card text
+    pub text: String,
+    /// Stubs declared on the card
+    pub stubs: Vec<Stub>,
+    /// Wikilink and embed targets in the card text
+    pub links: Vec<String>,
+}
+
+/// Result of analyzing a canvas
+#[derive(Debug, Clone)]
+pub struct CanvasAnalysis {
+    /// Sidecar properties plus the stubs of every card, with dimensions
+    pub analysis: DocumentAnalysis,
+    /// Text cards
+    pub cards: Vec<CanvasCard>,
+    /// Vault files shown on file cards
+    pub files: Vec<String>,
+    /// Arrows between two file cards, as links between those files
+    pub file_edges: Vec<LinkEdge>,
+}
+
+/// Stubs declared in a card's text
+///
+/// Open tasks, labeled annotations (`%% verify: ... %%`), stub markers
+/// (`{{expand: ...}}`, `> [!stub] ...`) and lines starting with `TODO:` or
+/// `TODO ` are stubs.
+pub fn card_stubs(text: &str, body: &MarkdownBody, stub_types: &StubTypesConfig) -> Vec<Stub> {
+    let tasks = body
+        .tasks
+        .iter()
+        .filter(|t| t.status.is_open())
+        .map(|t| stub_from_task(t, stub_types));
+    let annotations = body
+        .annotations
+        .iter()
+        .filter_map(|a| stub_from_annotation(a, stub_types));
+    let todos = text.lines().filter_map(|line| {
+        let line = line.trim_start().trim_start_matches(['-', '*', ' ']);
+        let rest = line.strip_prefix("TODO")?;
+        let description = rest.strip_prefix([':', ' '])?.trim();
+        (!description.is_empty()).then(|| Stub::compact("todo", description))
+    });
+
+    let markers = stubs_from_markers(body, stub_types);
+
+    tasks.chain(annotations).chain(markers).chain(todos).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_domain::BodyParser;
+    use doc_doctor_markdown::MarkdownParser;
+
+    #[test]
+    fn test_card_stubs() {
+        let text = "Plan\n- [ ] verify numbers #verify\n- [x] done\n%% link: add source %%\n\
+                    TODO: ask legal\n- TODO tidy up\nTODOS are fine\n\
+                    {{expand: add examples}} {{date:YYYY}}\n";
+        let body = MarkdownParser::new().parse_body(text, 0);
+        let stubs = card_stubs(text, &body, &StubTypesConfig::default());
+
+        let summary: Vec<_> = stubs
+            .iter()
+            .map(|s| (s.stub_type.as_str(), s.description.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("verify", "verify numbers"),
+                ("link", "add source"),
+                ("expand", "add examples"),
+                ("todo", "ask legal"),
+                ("todo", "tidy up"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,311 @@
+//! Quality Gate
+//!
+//! Per-path thresholds enforced by `ddoc check`, and the baseline of known
+//! failures that keeps legacy debt from failing the build:
+//!
+//! - `min-health`: health below `min_health`
+//! - `public-blocking-stub`: blocking stubs in an `audience: public` document
+//! - `usefulness-margin`: usefulness margin below `min_usefulness_margin`
+//! - `unreadable`: a document that cannot be read or analyzed
+//!
+//! A failure is a regression when the baseline does not list it for the
+//! document, or lists a better value. Rewriting the baseline from the
+//! current failures ratchets it forward: fixed debt drops out of it and
+//! cannot come back unnoticed.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use doc_doctor_domain::{
+    Audience, BlockId, CheckThresholds, ChecksConfig, DocumentAnalysis, SourcePosition,
+};
+
+use crate::findings::{blocking_stub_findings, key_line, Finding, FindingLevel};
+use crate::walk::MATCH_OPTIONS;
+
+/// Current version of the baseline file format
+pub const BASELINE_VERSION: u32 = 1;
+
+/// Thresholds for a document: the vault-wide ones with the overrides of
+/// every path glob it matches
+pub fn thresholds_for(config: &ChecksConfig, path: &str) -> CheckThresholds {
+    let mut thresholds = config.thresholds.clone();
+    for entry in &config.paths {
+        let pattern = glob::Pattern::new(&entry.glob);
+        if pattern.is_ok_and(|pattern| pattern.matches_with(path, MATCH_OPTIONS)) {
+            entry.apply(&mut thresholds);
+        }
+    }
+    thresholds
+}
+
+/// A threshold a document does not meet
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CheckFailure {
+    /// Document path, relative to the vault root
+    pub path: String,
+    /// Rule ID from [`FINDING_RULES`](crate::FINDING_RULES)
+    pub rule: String,
+    /// Measured value: health, usefulness margin or number of blocking stubs
+    pub value: f64,
+    /// The failure located in the document, for CI reports
+    pub findings: Vec<Finding>,
+}
+
+impl CheckFailure {
+    fn new(path: &str, rule: &str, value: f64, findings: Vec<Finding>) -> Self {
+        Self {
+            path: path.to_string(),
+            rule: rule.to_string(),
+            value: rounded(value),
+            findings,
+        }
+    }
+
+    /// Whether this failure is worse than the value recorded in a baseline
+    fn is_worse_than(&self, baseline: f64) -> bool {
+        match self.rule.as_str() {
+            "public-blocking-stub" => self.value > baseline,
+            _ => self.value < baseline,
+        }
+    }
+}
+
+/// Failure of a document that could not be read or analyzed, so that a
+/// newly broken document fails the check
+pub fn unreadable_failure(path: &str, error: &str) -> CheckFailure {
+    let finding = Finding::new(path, "unreadable", FindingLevel::Error, error);
+    CheckFailure::new(path, "unreadable", 1.0, vec![finding])
+}
+
+/// Check a document against its thresholds
+pub fn check_document(
+    path: &str,
+    content: &str,
+    analysis: &DocumentAnalysis,
+    block_ids: &[BlockId],
+    thresholds: &CheckThresholds,
+) -> Vec<CheckFailure> {
+    let at_key = |key| key_line(content, key).map(|line| SourcePosition::new(line, 1, 0));
+    let mut failures = Vec::new();
+
+    let health = analysis.dimensions.health;
+    if health < thresholds.min_health {
+        let message =
+            format!("Health {:.2} is below the minimum of {:.2}", health, thresholds.min_health);
+        let finding = Finding::new(path, "min-health", FindingLevel::Error, message)
+            .with_position(at_key("refinement"));
+        failures.push(CheckFailure::new(path, "min-health", health, vec![finding]));
+    }
+
+    let properties = &analysis.properties;
+    if properties.audience == Audience::Public && !thresholds.allow_public_blocking_stubs {
+        let findings: Vec<Finding> =
+            blocking_stub_findings(path, content, &properties.stubs, block_ids)
+                .into_iter()
+                .map(|finding| Finding {
+                    rule: "public-blocking-stub".to_string(),
+                    ..finding
+                })
+                .collect();
+        if !findings.is_empty() {
+            let count = findings.len() as f64;
+            failures.push(CheckFailure::new(path, "public-blocking-stub", count, findings));
+        }
+    }
+
+    let usefulness = &analysis.dimensions.usefulness;
+    if usefulness.margin < thresholds.min_usefulness_margin {
+        let message = format!(
+            "Usefulness margin {:+.2} is below {:+.2} (refinement {:.2}, {} gate {:.2})",
+            usefulness.margin,
+            thresholds.min_usefulness_margin,
+            usefulness.refinement,
+            usefulness.audience,
+            usefulness.gate
+        );
+        let finding = Finding::new(path, "usefulness-margin", FindingLevel::Error, message)
+            .with_position(at_key("audience"));
+        let margin = usefulness.margin;
+        failures.push(CheckFailure::new(path, "usefulness-margin", margin, vec![finding]));
+    }
+
+    failures
+}
+
+/// Known failures, committed with the vault so that only regressions fail
+/// a check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    /// Measured value of each failing rule, by document path
+    pub failures: BTreeMap<String, BTreeMap<String, f64>>,
+}
+
+impl Default for Baseline {
+    fn default() -> Self {
+        Self {
+            version: BASELINE_VERSION,
+            failures: BTreeMap::new(),
+        }
+    }
+}
+
+impl Baseline {
+    /// A baseline accepting exactly these failures
+    pub fn from_failures(failures: &[CheckFailure]) -> Self {
+        let mut baseline = Self::default();
+        for failure in failures {
+            baseline
+                .failures
+                .entry(failure.path.clone())
+                .or_default()
+                .insert(failure.rule.clone(), failure.value);
+        }
+        baseline
+    }
+
+    /// Recorded value of a rule for a document
+    pub fn get(&self, path: &str, rule: &str) -> Option<f64> {
+        self.failures.get(path)?.get(rule).copied()
+    }
+
+    /// Number of recorded failures
+    pub fn len(&self) -> usize {
+        self.failures.values().map(BTreeMap::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Split failures into regressions and known debt, and list the
+    /// entries that improved since the baseline was written
+    pub fn compare(&self, failures: Vec<CheckFailure>) -> CheckOutcome {
+        let mut outcome = CheckOutcome::default();
+
+        for (path, rules) in &self.failures {
+            for (rule, &baseline) in rules {
+                let current = failures.iter().find(|f| &f.path == path && &f.rule == rule);
+                let improved = match current {
+                    Some(failure) => failure.value != baseline && !failure.is_worse_than(baseline),
+                    None => true,
+                };
+                if improved {
+                    outcome.improved.push(BaselineEntry {
+                        path: path.clone(),
+                        rule: rule.clone(),
+                        baseline,
+                        current: current.map(|f| f.value),
+                    });
+                }
+            }
+        }
+
+        for failure in failures {
+            match self.get(&failure.path, &failure.rule) {
+                Some(baseline) if !failure.is_worse_than(baseline) => outcome.known.push(failure),
+                _ => outcome.regressions.push(failure),
+            }
+        }
+        outcome
+    }
+}
+
+/// A baseline entry that improved: fixed (`current` is `None`) or better
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BaselineEntry {
+    pub path: String,
+    pub rule: String,
+    pub baseline: f64,
+    pub current: Option<f64>,
+}
+
+/// Result of comparing failures with a baseline
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CheckOutcome {
+    /// Failures missing from the baseline, or worse than recorded
+    pub regressions: Vec<CheckFailure>,
+    /// Failures the baseline accepts
+    pub known: Vec<CheckFailure>,
+    /// Entries that improved; updating the baseline tightens them
+    pub improved: Vec<BaselineEntry>,
+}
+
+impl CheckOutcome {
+    /// Whether the check passes: no regressions
+    pub fn passed(&self) -> bool {
+        self.regressions.is_empty()
+    }
+}
+
+/// Values are kept to three decimals, so baselines diff cleanly
+fn rounded(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_domain::{
+        L1Properties, PathChecks, Refinement, StateDimensions, Stub, StubForm,
+    };
+
+    #[test]
+    fn test_check_against_baseline() {
+        let mut config = ChecksConfig::default();
+        config.thresholds.min_health = 0.8;
+        config.paths.push(PathChecks {
+            glob: "drafts/**".to_string(),
+            min_health: Some(0.0),
+            ..Default::default()
+        });
+        config.paths.push(PathChecks {
+            glob: "docs/*".to_string(),
+            min_health: Some(0.9),
+            ..Default::default()
+        });
+        assert_eq!(thresholds_for(&config, "drafts/a.md").min_health, 0.0);
+        assert_eq!(thresholds_for(&config, "drafts/deep/a.md").min_health, 0.0);
+        assert_eq!(thresholds_for(&config, "docs/deep/nested.md").min_health, 0.8);
+        let thresholds = thresholds_for(&config, "docs/a.md");
+        assert_eq!(thresholds.min_health, 0.9);
+
+        let content = "---\naudience: public\nrefinement: 0.5\n---\nBody\n";
+        let mut stub = Stub::compact("source", "cite the study");
+        stub.stub_form = StubForm::Blocking;
+        let properties = L1Properties {
+            audience: Audience::Public,
+            refinement: Refinement::new_clamped(0.5),
+            stubs: vec![stub],
+            ..Default::default()
+        };
+        let dimensions = StateDimensions::calculate(&properties);
+        let analysis = DocumentAnalysis::new(properties, dimensions);
+
+        let failures = check_document("docs/a.md", content, &analysis, &[], &thresholds);
+        let rules: Vec<&str> = failures.iter().map(|f| f.rule.as_str()).collect();
+        assert_eq!(rules, ["min-health", "public-blocking-stub", "usefulness-margin"]);
+        assert_eq!(failures[0].findings[0].line(), Some(3));
+
+        let baseline = Baseline::from_failures(&failures);
+        assert_eq!(baseline.len(), 3);
+        assert!(baseline.compare(failures.clone()).passed());
+
+        let mut worse = failures.clone();
+        worse[1].value = 2.0;
+        worse.truncate(2);
+        let outcome = baseline.compare(worse);
+        assert_eq!(outcome.regressions[0].rule, "public-blocking-stub");
+        assert_eq!(outcome.known.len(), 1);
+        assert_eq!(outcome.improved[0].rule, "usefulness-margin");
+        assert_eq!(outcome.improved[0].current, None);
+
+        let broken = vec![unreadable_failure("b.md", "Invalid YAML")];
+        assert_eq!(baseline.compare(broken.clone()).regressions[0].rule, "unreadable");
+        let baseline = Baseline::from_failures(&broken);
+        assert_eq!(baseline.get("b.md", "unreadable"), Some(1.0));
+        assert!(baseline.compare(broken).passed());
+    }
+}
@@ -0,0 +1,178 @@
+//! Shared Calculation Configuration
+//!
+//! A cloneable handle to the active `CalculationConfig`. The switchboard and
+//! every use case hold clones of the same handle, so replacing or reloading
+//! the configuration at runtime is immediately visible to all of them.
+
+use std::sync::{Arc, RwLock};
+
+use chrono::{DateTime, Utc};
+use doc_doctor_domain::{CalculationConfig, ConfigValidationError};
+
+/// Shared, reloadable handle to the active calculation configuration
+///
+/// Reads return a cheap snapshot (`Arc<CalculationConfig>`), so a calculation
+/// always sees one consistent configuration even if it is replaced mid-flight.
+///
+/// The handle also holds the time calculations are made at: the current
+/// time, or a fixed as-of timestamp that makes freshness, and therefore
+/// every analysis, reproducible.
+#[derive(Debug, Clone)]
+pub struct ConfigHandle {
+    state: Arc<RwLock<ConfigState>>,
+}
+
+#[derive(Debug)]
+struct ConfigState {
+    config: Arc<CalculationConfig>,
+    using_defaults: bool,
+    as_of: Option<DateTime<Utc>>,
+}
+
+impl ConfigHandle {
+    /// Create a handle holding the given configuration
+    ///
+    /// # Errors
+    /// Returns the validation error if the configuration is invalid
+    pub fn new(config: CalculationConfig) -> Result<Self, ConfigValidationError> {
+        config.validate()?;
+        Ok(Self::from_state(config, false))
+    }
+
+    /// Create a handle holding the built-in defaults
+    pub fn defaults() -> Self {
+        Self::from_state(CalculationConfig::default(), true)
+    }
+
+    fn from_state(config: CalculationConfig, using_defaults: bool) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(ConfigState {
+                config: Arc::new(config),
+                using_defaults,
+                as_of: None,
+            })),
+        }
+    }
+
+    /// Snapshot of the active configuration
+    pub fn get(&self) -> Arc<CalculationConfig> {
+        Arc::clone(&self.read().config)
+    }
+
+    /// Whether the active configuration is the built-in defaults
+    pub fn is_default(&self) -> bool {
+        self.read().using_defaults
+    }
+
+    /// Time calculations are made at: the as-of timestamp, else now
+    pub fn now(&self) -> DateTime<Utc> {
+        self.read().as_of.unwrap_or_else(Utc::now)
+    }
+
+    /// The fixed time calculations are made at, if any
+    pub fn as_of(&self) -> Option<DateTime<Utc>> {
+        self.read().as_of
+    }
+
+    /// Calculate as of `as_of`, or at the current time with `None`
+    ///
+    /// Replacing or resetting the configuration keeps this setting.
+    pub fn set_as_of(&self, as_of: Option<DateTime<Utc>>) {
+        self.state.write().unwrap_or_else(|e| e.into_inner()).as_of = as_of;
+    }
+
+    /// Replace the active configuration
+    ///
+    /// The new configuration is validated first; on error the active
+    /// configuration is left untouched.
+    pub fn replace(&self, config: CalculationConfig) -> Result<(), ConfigValidationError> {
+        config.validate()?;
+        let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+        state.config = Arc::new(config);
+        state.using_defaults = false;
+        Ok(())
+    }
+
+    /// Restore the built-in defaults
+    pub fn reset(&self) {
+        let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+        state.config = Arc::new(CalculationConfig::default());
+        state.using_defaults = true;
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, ConfigState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for ConfigHandle {
+    fn default() -> Self {
+        Self::defaults()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let handle = ConfigHandle::defaults();
+        assert!(handle.is_default());
+        assert_eq!(handle.get().health.refinement_weight, 0.7);
+    }
+
+    #[test]
+    fn test_replace_is_shared_between_clones() {
+        let handle = ConfigHandle::defaults();
+        let clone = handle.clone();
+
+        let mut config = CalculationConfig::default();
+        config.health.refinement_weight = 0.6;
+        config.health.stub_weight = 0.4;
+        handle.replace(config).unwrap();
+
+        assert!(!clone.is_default());
+        assert_eq!(clone.get().health.refinement_weight, 0.6);
+    }
+
+    #[test]
+    fn test_replace_rejects_invalid_config() {
+        let handle = ConfigHandle::defaults();
+
+        let mut config = CalculationConfig::default();
+        config.health.refinement_weight = 0.9;
+        config.health.stub_weight = 0.9;
+
+        assert!(handle.replace(config).is_err());
+        assert!(handle.is_default());
+        assert_eq!(handle.get().health.refinement_weight, 0.7);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut config = CalculationConfig::default();
+        config.audience_gates.public = 0.95;
+        let handle = ConfigHandle::new(config).unwrap();
+
+        handle.reset();
+        assert!(handle.is_default());
+        assert_eq!(handle.get().audience_gates.public, 0.90);
+    }
+
+    #[test]
+    fn test_as_of() {
+        use chrono::TimeZone;
+
+        let handle = ConfigHandle::defaults();
+        assert!(handle.as_of().is_none());
+
+        let as_of = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        handle.clone().set_as_of(Some(as_of));
+        handle.reset();
+        assert_eq!(handle.now(), as_of);
+
+        handle.set_as_of(None);
+        assert!(handle.now() > as_of);
+    }
+}
@@ -0,0 +1,468 @@
+//! Stub Detection
+//!
+//! Heuristics that read the body for gaps and propose stubs: TODO markers,
+//! `[citation needed]`, empty or placeholder sections, numeric claims
+//! without a source, sections long enough to split, and questions left
+//! unanswered. Suggestions are `system_generated` and never written on
+//! their own; they are reviewed first and then applied.
+
+use std::fmt;
+use std::str::FromStr;
+
+use doc_doctor_domain::{MarkdownBody, Section, SourcePosition, Stub, StubForm, StubOrigin};
+
+/// Heuristic that proposes a stub
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DetectionRule {
+    /// `TODO`, `FIXME` or `TBD` in the text
+    TodoMarker,
+    /// `[citation needed]`
+    CitationNeeded,
+    /// A section with no content, or only a placeholder
+    EmptySection,
+    /// A percentage or large figure with no link, footnote or citation
+    UnlinkedClaim,
+    /// A section longer than the configured word count
+    LongSection,
+    /// A question that ends its section
+    OpenQuestion,
+}
+
+impl DetectionRule {
+    /// Every rule, in report order
+    pub const ALL: [DetectionRule; 6] = [
+        DetectionRule::TodoMarker,
+        DetectionRule::CitationNeeded,
+        DetectionRule::EmptySection,
+        DetectionRule::UnlinkedClaim,
+        DetectionRule::LongSection,
+        DetectionRule::OpenQuestion,
+    ];
+
+    /// Stable identifier (e.g. `todo-marker`)
+    pub fn id(&self) -> &'static str {
+        match self {
+            DetectionRule::TodoMarker => "todo-marker",
+            DetectionRule::CitationNeeded => "citation-needed",
+            DetectionRule::EmptySection => "empty-section",
+            DetectionRule::UnlinkedClaim => "unlinked-claim",
+            DetectionRule::LongSection => "long-section",
+            DetectionRule::OpenQuestion => "open-question",
+        }
+    }
+}
+
+impl fmt::Display for DetectionRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+impl FromStr for DetectionRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|rule| rule.id() == s).ok_or_else(|| {
+            let ids: Vec<_> = Self::ALL.iter().map(|rule| rule.id()).collect();
+            format!("Unknown detection rule '{}' (expected one of: {})", s, ids.join(", "))
+        })
+    }
+}
+
+/// Which heuristics run, and their thresholds
+#[derive(Debug, Clone)]
+pub struct DetectorOptions {
+    /// Rules to run
+    pub rules: Vec<DetectionRule>,
+    /// Words of its own text above which a section should be split
+    pub long_section_words: usize,
+}
+
+impl Default for DetectorOptions {
+    fn default() -> Self {
+        Self {
+            rules: DetectionRule::ALL.to_vec(),
+            long_section_words: 800,
+        }
+    }
+}
+
+/// A proposed stub
+#[derive(Debug, Clone)]
+pub struct StubSuggestion {
+    /// Rule that found the gap
+    pub rule: DetectionRule,
+    /// Suggested stub, with type, form and anchor filled in
+    pub stub: Stub,
+    /// Where the gap is
+    pub position: SourcePosition,
+    /// The line or heading that triggered the rule
+    pub excerpt: String,
+    /// Where to append the stub's anchor, if the line has no block ID yet
+    pub anchor_insert: Option<usize>,
+}
+
+/// Words that stand in for content not written yet
+const PLACEHOLDERS: &[&str] = &["tbd", "todo", "...", "…", "coming soon", "wip", "lorem ipsum"];
+
+/// Markers, their stub type and form
+const MARKERS: &[(&str, &str, StubForm)] = &[
+    ("TODO", "todo", StubForm::Transient),
+    ("FIXME", "fix", StubForm::Blocking),
+    ("TBD", "incomplete", StubForm::Transient),
+];
+
+/// Figures that make a sentence a claim worth sourcing
+const CLAIM_UNITS: &[&str] = &["%", " percent", " million", " billion", " trillion"];
+
+/// Longest excerpt kept in a suggestion
+const EXCERPT_CHARS: usize = 80;
+
+/// A line of the body, without its line break
+struct Line<'a> {
+    number: usize,
+    start: usize,
+    end: usize,
+    text: &'a str,
+}
+
+/// Propose stubs for the gaps in a document body
+///
+/// `body` is the parsed body of `content`, which starts at `body_offset`.
+/// Suggestions come in document order.
+pub fn detect_stubs(
+    content: &str,
+    body: &MarkdownBody,
+    body_offset: usize,
+    options: &DetectorOptions,
+) -> Vec<StubSuggestion> {
+    let enabled = |rule| options.rules.contains(&rule);
+    let mut found = Vec::new();
+
+    // Sections first: lines of an empty section are its placeholder
+    let mut placeholders: Vec<(usize, usize)> = Vec::new();
+    for (section, own_end) in own_content(&body.sections) {
+        let heading = &section.heading;
+        let start = content[heading.position.offset..]
+            .find('\n')
+            .map_or(own_end, |e| (heading.position.offset + e + 1).min(own_end));
+        let text = content[start..own_end].trim();
+
+        if section.children.is_empty() && is_placeholder(text) {
+            placeholders.push((start, own_end));
+            if enabled(DetectionRule::EmptySection) {
+                let stub = suggested("expand", format!("Write the '{}' section", heading.text));
+                found.push(section_suggestion(DetectionRule::EmptySection, stub, section));
+            }
+        }
+
+        let words = text.split_whitespace().count();
+        if enabled(DetectionRule::LongSection) && words > options.long_section_words {
+            let mut stub = suggested("split", format!("Split '{}' ({} words)", heading.text, words));
+            stub.stub_form = StubForm::Structural;
+            found.push(section_suggestion(DetectionRule::LongSection, stub, section));
+        }
+    }
+
+    let in_literal = |offset: usize| {
+        body.code.iter().any(|c| c.start.offset <= offset && offset < c.end_offset)
+    };
+    let headings: Vec<usize> = body.headings().iter().map(|h| h.position.line).collect();
+    let taken: Vec<usize> = body
+        .tasks
+        .iter()
+        .map(|t| t.position.line)
+        .chain(body.stub_markers.iter().map(|m| m.position.line))
+        .collect();
+
+    let lines = lines(content, body_offset);
+    for (i, line) in lines.iter().enumerate() {
+        let skip = in_literal(line.start)
+            || headings.contains(&line.number)
+            || taken.contains(&line.number)
+            || placeholders.iter().any(|&(s, e)| s <= line.start && line.start < e);
+        if skip || line.text.trim().is_empty() {
+            continue;
+        }
+
+        let block_id = body
+            .block_ids
+            .iter()
+            .find(|b| line.start <= b.position.offset && b.position.offset < line.end);
+        let text_end = block_id.map_or(line.end, |b| b.position.offset);
+        let (lead, text) = plain(&content[line.start..text_end]);
+        let start = line.start + lead;
+
+        let next = lines[i + 1..].iter().find(|l| !l.text.trim().is_empty());
+        // A question answered by nothing before the next heading
+        let answered = next.is_some_and(|l| !headings.contains(&l.number));
+        let Some((rule, at, mut stub)) =
+            line_rule(text, |at| in_literal(start + at), answered, &enabled)
+        else {
+            continue;
+        };
+
+        let anchor_insert = match block_id {
+            Some(block) => {
+                stub.anchor = Some(format!("^{}", block.id));
+                None
+            }
+            None => {
+                stub.anchor = Some(suggested_anchor(rule, text));
+                Some(line.end)
+            }
+        };
+        let column = content[line.start..start + at].chars().count() + 1;
+        found.push(StubSuggestion {
+            rule,
+            stub,
+            position: SourcePosition::new(line.number, column, start + at),
+            excerpt: excerpt(text),
+            anchor_insert,
+        });
+    }
+
+    found.sort_by_key(|s| s.position.offset);
+    found
+}
+
+/// The first enabled line rule that matches `text`, with the offset of
+/// the match within `text` and the stub
+fn line_rule(
+    text: &str,
+    in_literal: impl Fn(usize) -> bool,
+    answered: bool,
+    enabled: &dyn Fn(DetectionRule) -> bool,
+) -> Option<(DetectionRule, usize, Stub)> {
+    let marker = enabled(DetectionRule::TodoMarker)
+        .then(|| find_marker(text, in_literal))
+        .flatten();
+    if let Some((at, marker, stub_type, form)) = marker {
+        let after = text[at + marker.len()..].trim_start_matches([':', '-', '—', ' ']).trim();
+        let description = if after.is_empty() { text } else { after };
+        let mut stub = suggested(stub_type, description);
+        stub.stub_form = form;
+        return Some((DetectionRule::TodoMarker, at, stub));
+    }
+
+    const CITATION_NEEDED: &str = "[citation needed]";
+    let citation = text
+        .as_bytes()
+        .windows(CITATION_NEEDED.len())
+        .position(|w| w.eq_ignore_ascii_case(CITATION_NEEDED.as_bytes()));
+    if let Some(at) = citation.filter(|_| enabled(DetectionRule::CitationNeeded)) {
+        let claim = format!("{}{}", text[..at].trim_end(), &text[at + CITATION_NEEDED.len()..]);
+        let stub = suggested("citation-needed", format!("Cite a source: {}", excerpt(claim.trim())));
+        return Some((DetectionRule::CitationNeeded, at, stub));
+    }
+
+    if enabled(DetectionRule::UnlinkedClaim) && has_figure(text) && !has_reference(text) {
+        let stub = suggested("source", format!("Source the claim: {}", excerpt(text)));
+        return Some((DetectionRule::UnlinkedClaim, 0, stub));
+    }
+
+    if enabled(DetectionRule::OpenQuestion) && text.ends_with('?') && !answered {
+        let stub = suggested("question", format!("Answer: {}", excerpt(text)));
+        return Some((DetectionRule::OpenQuestion, 0, stub));
+    }
+
+    None
+}
+
+/// Earliest whole-word marker in `text` outside code
+fn find_marker(
+    text: &str,
+    in_literal: impl Fn(usize) -> bool,
+) -> Option<(usize, &'static str, &'static str, StubForm)> {
+    MARKERS
+        .iter()
+        .filter_map(|&(marker, stub_type, form)| {
+            text.match_indices(marker)
+                .map(|(at, _)| at)
+                .find(|&at| {
+                    let before = text[..at].chars().next_back();
+                    let after = text[at + marker.len()..].chars().next();
+                    !before.is_some_and(char::is_alphanumeric)
+                        && !after.is_some_and(char::is_alphanumeric)
+                        && !in_literal(at)
+                })
+                .map(|at| (at, marker, stub_type, form))
+        })
+        .min_by_key(|(at, ..)| *at)
+}
+
+/// Whether `text` states a percentage or a large figure
+fn has_figure(text: &str) -> bool {
+    text.char_indices().any(|(at, c)| {
+        let ends_number = c.is_ascii_digit()
+            && !text[at + 1..].starts_with(|n: char| n.is_ascii_digit() || n == '.' || n == ',');
+        ends_number && CLAIM_UNITS.iter().any(|unit| text[at + 1..].starts_with(unit))
+    })
+}
+
+/// Whether `text` links, footnotes or cites something
+fn has_reference(text: &str) -> bool {
+    ["[[", "](", "[^", "http://", "https://"].iter().any(|r| text.contains(r))
+}
+
+/// Whether section text is missing or only a placeholder
+fn is_placeholder(text: &str) -> bool {
+    let text = text.trim_end_matches(['.', ':', '!']).trim().to_lowercase();
+    text.is_empty() || PLACEHOLDERS.contains(&text.as_str())
+}
+
+/// Sections, depth first, with the end of their own text (before the
+/// first subsection)
+fn own_content(sections: &[Section]) -> Vec<(&Section, usize)> {
+    let mut out = Vec::new();
+    for section in sections {
+        let end = section
+            .children
+            .first()
+            .map_or(section.end_offset, |c| c.heading.position.offset);
+        out.push((section, end));
+        out.extend(own_content(&section.children));
+    }
+    out
+}
+
+fn section_suggestion(rule: DetectionRule, stub: Stub, section: &Section) -> StubSuggestion {
+    let heading = &section.heading;
+    StubSuggestion {
+        rule,
+        stub,
+        position: heading.position,
+        excerpt: excerpt(&format!("{} {}", "#".repeat(heading.level as usize), heading.text)),
+        anchor_insert: None,
+    }
+}
+
+fn suggested(stub_type: &str, description: impl Into<String>) -> Stub {
+    let mut stub = Stub::compact(stub_type, description);
+    stub.origin = StubOrigin::SystemGenerated;
+    stub
+}
+
+/// A block ID for a line, stable while the line is unchanged
+fn suggested_anchor(rule: DetectionRule, text: &str) -> String {
+    // FNV-1a
+    let hash = rule
+        .id()
+        .bytes()
+        .chain(text.bytes())
+        .fold(0x811c_9dc5_u32, |h, b| (h ^ b as u32).wrapping_mul(0x0100_0193));
+    format!("^stub-{:06x}", hash & 0xff_ffff)
+}
+
+/// Line text without list or quote markers, and where it starts
+fn plain(line: &str) -> (usize, &str) {
+    let mut text = line.trim_start();
+    loop {
+        let stripped = text
+            .strip_prefix("> ")
+            .or_else(|| text.strip_prefix("- "))
+            .or_else(|| text.strip_prefix("* "))
+            .or_else(|| text.strip_prefix("+ "))
+            .or_else(|| {
+                let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+                (digits > 0).then(|| text[digits..].strip_prefix(". ")).flatten()
+            });
+        match stripped {
+            Some(rest) => text = rest.trim_start(),
+            None => return (line.len() - text.len(), text.trim_end()),
+        }
+    }
+}
+
+fn excerpt(text: &str) -> String {
+    match text.char_indices().nth(EXCERPT_CHARS) {
+        Some((at, _)) => format!("{}…", text[..at].trim_end()),
+        None => text.to_string(),
+    }
+}
+
+/// Lines of `content` from byte `from`
+fn lines(content: &str, from: usize) -> Vec<Line<'_>> {
+    let mut number = content[..from].matches('\n').count() + 1;
+    let mut start = from;
+    let mut lines = Vec::new();
+    while start < content.len() {
+        let next = content[start..].find('\n').map_or(content.len(), |e| start + e + 1);
+        let text = content[start..next].trim_end_matches(['\n', '\r']);
+        lines.push(Line {
+            number,
+            start,
+            end: start + text.len(),
+            text,
+        });
+        number += 1;
+        start = next;
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_domain::BodyParser;
+    use doc_doctor_markdown::MarkdownParser;
+
+    fn detect(content: &str, options: &DetectorOptions) -> Vec<StubSuggestion> {
+        let body = MarkdownParser::new().parse_body(content, 0);
+        detect_stubs(content, &body, 0, options)
+    }
+
+    #[test]
+    fn test_line_rules() {
+        let content = "# Plan\n\
+            - TODO: add benchmarks\n\
+            Adoption grew 40% last year.\n\
+            Adoption grew 40% last year [[Survey]].\n\
+            It was first used in 1998 [citation needed]. ^origin\n\
+            `FIXME` in code, and a TODOLIST.\n\
+            Does it scale?\n";
+        let found = detect(content, &DetectorOptions::default());
+
+        let rules: Vec<_> = found.iter().map(|s| s.rule).collect();
+        assert_eq!(
+            rules,
+            vec![
+                DetectionRule::TodoMarker,
+                DetectionRule::UnlinkedClaim,
+                DetectionRule::CitationNeeded,
+                DetectionRule::OpenQuestion,
+            ]
+        );
+        assert_eq!(found[0].stub.description, "add benchmarks");
+        assert_eq!(found[0].stub.origin, StubOrigin::SystemGenerated);
+        assert_eq!(found[0].position, SourcePosition::new(2, 3, 9));
+        assert_eq!(found[0].anchor_insert, Some(content.find("\nAdoption").unwrap()));
+        assert_eq!(found[2].stub.anchor.as_deref(), Some("^origin"));
+        assert_eq!(found[2].anchor_insert, None);
+        assert_eq!(found[2].stub.description, "Cite a source: It was first used in 1998.");
+        assert_eq!(found[3].stub.stub_type.as_str(), "question");
+    }
+
+    #[test]
+    fn test_section_rules() {
+        let content = "# Guide\n## Setup\nTBD\n## Usage\none two three four five\n## Group\n### Inner\nText?\nYes.\n";
+        let options = DetectorOptions {
+            long_section_words: 4,
+            ..Default::default()
+        };
+        let found = detect(content, &options);
+
+        let summary: Vec<_> = found.iter().map(|s| (s.rule, s.stub.stub_type.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (DetectionRule::EmptySection, "expand"),
+                (DetectionRule::LongSection, "split"),
+            ]
+        );
+        assert_eq!(found[0].stub.description, "Write the 'Setup' section");
+        assert_eq!(found[1].stub.stub_form, StubForm::Structural);
+        assert_eq!("open-question".parse(), Ok(DetectionRule::OpenQuestion));
+        assert!("nope".parse::<DetectionRule>().is_err());
+    }
+}
@@ -0,0 +1,286 @@
+//! In-place Metadata Edits
+//!
+//! Changes one top-level frontmatter value without serializing the whole
+//! frontmatter again, so comments, unknown keys, key order and layout are
+//! kept byte for byte. YAML (`---`), TOML (`+++`) and JSON (`{`) frontmatter
+//! are supported.
+
+use doc_doctor_domain::MetadataSpan;
+
+/// Frontmatter syntax, told apart by its delimiters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Syntax {
+    Yaml,
+    Toml,
+    Json,
+}
+
+/// Where a key's value is written
+struct ValueSpan {
+    start: usize,
+    end: usize,
+    /// Whether the value directly follows the separator, as in `key:`
+    bare: bool,
+}
+
+/// Set the top-level `key` of the metadata at `span` to `value`
+///
+/// Only the text of the existing value changes; quotes around it are kept.
+/// A missing key is added on a line of its own.
+pub(crate) fn set_metadata_value(
+    content: &str,
+    span: &MetadataSpan,
+    key: &str,
+    value: &str,
+) -> String {
+    let (start, end) = (span.start.offset, span.end.offset.min(content.len()));
+    // A YAML span can end inside the `\r\n` before the closing delimiter
+    let region = content[start..end].strip_suffix('\r').unwrap_or(&content[start..end]);
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let syntax = if region.trim_start().starts_with('{') {
+        Syntax::Json
+    } else if content[..start].trim_end().ends_with("+++") {
+        Syntax::Toml
+    } else {
+        Syntax::Yaml
+    };
+
+    let found = match syntax {
+        Syntax::Json => json_value(region, key),
+        Syntax::Yaml | Syntax::Toml => line_value(region, key, syntax),
+    };
+    let mut edited = content.to_string();
+    match found {
+        Some(found) => {
+            let text = if found.bare { format!(" {}", value) } else { value.to_string() };
+            edited.replace_range(start + found.start..start + found.end, &text);
+        }
+        None => {
+            let (offset, line) = new_line(region, key, value, syntax, newline);
+            edited.insert_str(start + offset, &line);
+        }
+    }
+    edited
+}
+
+/// Value of a top-level `key: value` (YAML) or `key = value` (TOML) line
+fn line_value(region: &str, key: &str, syntax: Syntax) -> Option<ValueSpan> {
+    let separator = if syntax == Syntax::Toml { '=' } else { ':' };
+    let mut offset = 0;
+    for line in region.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        // Keys after a TOML table header belong to that table
+        if syntax == Syntax::Toml && line.starts_with('[') {
+            return None;
+        }
+        let Some(rest) = strip_key(line, key) else {
+            continue;
+        };
+        let Some(after) = rest.trim_start().strip_prefix(separator) else {
+            continue;
+        };
+        let value_start = line.len() - after.trim_start_matches([' ', '\t']).len();
+        let text = line[value_start..].trim_end_matches(['\n', '\r']);
+        let length = match text.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let close = text[1..].find(quote)?;
+                return Some(ValueSpan {
+                    start: line_start + value_start + 1,
+                    end: line_start + value_start + 1 + close,
+                    bare: false,
+                });
+            }
+            _ => text.find(" #").unwrap_or(text.len()),
+        };
+        let value = text[..length].trim_end();
+        return Some(ValueSpan {
+            start: line_start + value_start,
+            end: line_start + value_start + value.len(),
+            bare: value_start == line.len() - after.len(),
+        });
+    }
+    None
+}
+
+/// Rest of `line` after `key` at the start of the line, quoted or not
+fn strip_key<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    for quote in ["", "\"", "'"] {
+        let quoted = line
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_prefix(key))
+            .and_then(|rest| rest.strip_prefix(quote));
+        if quoted.is_some() {
+            return quoted;
+        }
+    }
+    None
+}
+
+/// Value of `"key"` in the outermost JSON object
+fn json_value(region: &str, key: &str) -> Option<ValueSpan> {
+    let bytes = region.as_bytes();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            b'"' => {
+                let end = string_end(bytes, i)?;
+                let after = region[end + 1..].trim_start();
+                let is_key = after.starts_with(':');
+                if depth == 1 && is_key && &region[i + 1..end] == key {
+                    let value_start = region.len() - after[1..].trim_start().len();
+                    if bytes.get(value_start) == Some(&b'"') {
+                        let value_end = string_end(bytes, value_start)?;
+                        return Some(ValueSpan {
+                            start: value_start + 1,
+                            end: value_end,
+                            bare: false,
+                        });
+                    }
+                    let length = region[value_start..]
+                        .find([',', '}', '\n'])
+                        .unwrap_or(region.len() - value_start);
+                    let value = region[value_start..value_start + length].trim_end();
+                    return Some(ValueSpan {
+                        start: value_start,
+                        end: value_start + value.len(),
+                        bare: false,
+                    });
+                }
+                i = end;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Index of the quote closing the JSON string opened at `open`
+fn string_end(bytes: &[u8], open: usize) -> Option<usize> {
+    let mut escaped = false;
+    for (i, &byte) in bytes.iter().enumerate().skip(open + 1) {
+        match byte {
+            _ if escaped => escaped = false,
+            b'\\' => escaped = true,
+            b'"' => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Offset in the region and text of a line adding `key`
+fn new_line(
+    region: &str,
+    key: &str,
+    value: &str,
+    syntax: Syntax,
+    newline: &str,
+) -> (usize, String) {
+    match syntax {
+        Syntax::Json => {
+            let brace = region.find('{').map_or(0, |i| i + 1);
+            let rest = &region[brace..];
+            let comma = if rest.trim_start().starts_with('}') { "" } else { "," };
+            let entry = format!("\"{}\": \"{}\"{}", key, value, comma);
+            match rest.strip_prefix(newline) {
+                Some(lines) => {
+                    let width = lines.len() - lines.trim_start_matches([' ', '\t']).len();
+                    let indent = if width == 0 { "  " } else { &lines[..width] };
+                    (brace, format!("{}{}{}", newline, indent, entry))
+                }
+                None if comma.is_empty() => (brace, entry),
+                None => (brace, format!("{} ", entry)),
+            }
+        }
+        Syntax::Yaml | Syntax::Toml => {
+            let line = match syntax {
+                Syntax::Toml => format!("{} = {}", key, value),
+                _ => format!("{}: {}", key, value),
+            };
+            // TOML keys go before the first table
+            let table = (syntax == Syntax::Toml)
+                .then(|| line_offsets(region).find(|&(_, l)| l.starts_with('[')))
+                .flatten();
+            match table {
+                Some((offset, _)) => (offset, format!("{}{}", line, newline)),
+                None if region.is_empty() || region.ends_with('\n') => {
+                    (region.len(), format!("{}{}", line, newline))
+                }
+                None => (region.len(), format!("{}{}", newline, line)),
+            }
+        }
+    }
+}
+
+/// Lines of `text` with their offsets
+fn line_offsets(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split_inclusive('\n').scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len();
+        Some((start, line))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_domain::SourcePosition;
+
+    fn set(content: &str, start: usize, end: usize, value: &str) -> String {
+        let span = MetadataSpan {
+            content: content[start..end].to_string(),
+            start: SourcePosition::from_offset(content, start),
+            end: SourcePosition::from_offset(content, end),
+        };
+        set_metadata_value(content, &span, "modified", value)
+    }
+
+    #[test]
+    fn test_set_yaml_value() {
+        let content = "---\n# keep\nmodified: 2024-01-01 # old\nx: 1\n---\n";
+        let edited = set(content, 4, content.len() - 4, "2024-06-01");
+        assert_eq!(edited, "---\n# keep\nmodified: 2024-06-01 # old\nx: 1\n---\n");
+
+        let content = "---\nmodified: '2024-01-01'\nstubs:\n  - modified: x\n---\n";
+        let edited = set(content, 4, content.len() - 4, "2024-06-01");
+        assert_eq!(edited, "---\nmodified: '2024-06-01'\nstubs:\n  - modified: x\n---\n");
+
+        let content = "---\nmodified:\n---\n";
+        let edited = set(content, 4, content.len() - 4, "2024-06-01");
+        assert_eq!(edited, "---\nmodified: 2024-06-01\n---\n");
+
+        let content = "---\r\ntitle: A\r\n---\r\n";
+        let edited = set(content, 5, content.len() - 7, "2024-06-01");
+        assert_eq!(edited, "---\r\ntitle: A\r\nmodified: 2024-06-01\r\n---\r\n");
+    }
+
+    #[test]
+    fn test_set_toml_value() {
+        let content = "+++\ntitle = \"A\"\n[extra]\nmodified = 1\n+++\n";
+        let edited = set(content, 4, content.len() - 5, "2024-06-01");
+        let expected = "+++\ntitle = \"A\"\nmodified = 2024-06-01\n[extra]\nmodified = 1\n+++\n";
+        assert_eq!(edited, expected);
+
+        let content = "+++\nmodified = \"2024-01-01\"\n+++\n";
+        let edited = set(content, 4, content.len() - 5, "2024-06-01");
+        assert_eq!(edited, "+++\nmodified = \"2024-06-01\"\n+++\n");
+    }
+
+    #[test]
+    fn test_set_json_value() {
+        let content = "{\n  \"a\": {\"modified\": 1},\n  \"modified\": \"2024-01-01\"\n}\n";
+        let edited = set(content, 0, content.len() - 1, "2024-06-01");
+        assert_eq!(edited, "{\n  \"a\": {\"modified\": 1},\n  \"modified\": \"2024-06-01\"\n}\n");
+
+        let content = "{\n  \"title\": \"A\"\n}\n";
+        let edited = set(content, 0, content.len() - 1, "2024-06-01");
+        assert_eq!(edited, "{\n  \"modified\": \"2024-06-01\",\n  \"title\": \"A\"\n}\n");
+
+        assert_eq!(set("{}", 0, 2, "2024-06-01"), "{\"modified\": \"2024-06-01\"}");
+    }
+}
@@ -0,0 +1,240 @@
+//! Findings
+//!
+//! Problems in a document, located as precisely as the source allows, for
+//! CI reports (SARIF, JUnit, GitHub annotations):
+//!
+//! - `schema-error` / `schema-warning`: validation results
+//! - `blocking-stub`: stubs that prevent publication, at their anchored
+//!   block or their entry in the frontmatter
+//! - `audience-gate`: refinement below the gate of the document's audience
+//! - `min-health`, `public-blocking-stub`, `usefulness-margin`: quality
+//!   gate failures of `ddoc check`
+//! - `malformed-stub`: stub entries a commit breaks
+
+use serde::Serialize;
+
+use doc_doctor_domain::{
+    calculate_usefulness_with_config, BlockId, CalculationConfig, L1Properties, SourcePosition,
+    Stub, ValidationResult,
+};
+
+/// Rule IDs with a short description, as listed in SARIF reports
+pub const FINDING_RULES: &[(&str, &str)] = &[
+    ("schema-error", "Frontmatter does not match the J-Editorial schema"),
+    ("schema-warning", "Frontmatter may not be read as intended"),
+    ("blocking-stub", "A blocking stub prevents publication"),
+    ("audience-gate", "Refinement is below the gate of the document's audience"),
+    ("min-health", "Health is below the minimum for the path"),
+    ("public-blocking-stub", "A public document has a blocking stub"),
+    ("usefulness-margin", "Usefulness margin is below the minimum for the path"),
+    ("malformed-stub", "A stub entry cannot be read"),
+    ("unreadable", "The document cannot be read or analyzed"),
+];
+
+/// Severity of a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FindingLevel {
+    Error,
+    Warning,
+    Note,
+}
+
+impl FindingLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Note => "note",
+        }
+    }
+}
+
+/// A problem in a document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Finding {
+    /// Document path, as given
+    pub path: String,
+    /// Rule ID from [`FINDING_RULES`]
+    pub rule: String,
+    pub level: FindingLevel,
+    pub message: String,
+    /// Position in the document, when known
+    pub position: Option<SourcePosition>,
+}
+
+impl Finding {
+    /// Create a finding without a position
+    pub fn new(
+        path: impl Into<String>,
+        rule: impl Into<String>,
+        level: FindingLevel,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            rule: rule.into(),
+            level,
+            message: message.into(),
+            position: None,
+        }
+    }
+
+    /// Set the position in the document
+    pub fn with_position(mut self, position: Option<SourcePosition>) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Line of the finding, if known
+    pub fn line(&self) -> Option<usize> {
+        self.position.map(|p| p.line)
+    }
+}
+
+/// Schema errors and warnings of a validation
+pub fn schema_findings(path: &str, result: &ValidationResult) -> Vec<Finding> {
+    let errors = result.errors.iter().map(|error| {
+        Finding::new(path, "schema-error", FindingLevel::Error, &error.message)
+            .with_position(error.position)
+    });
+    let warnings = result.warnings.iter().map(|warning| {
+        let message = match &warning.suggestion {
+            Some(suggestion) => format!("{} ({})", warning.message, suggestion),
+            None => warning.message.clone(),
+        };
+        Finding::new(path, "schema-warning", FindingLevel::Warning, message)
+            .with_position(warning.position)
+    });
+    errors.chain(warnings).collect()
+}
+
+/// Blocking stubs of a document
+///
+/// Each stub is located at its anchored block in the body, where the gap
+/// is, or else at its entry in the frontmatter.
+pub fn blocking_stub_findings(
+    path: &str,
+    content: &str,
+    stubs: &[Stub],
+    block_ids: &[BlockId],
+) -> Vec<Finding> {
+    stubs
+        .iter()
+        .filter(|stub| stub.is_blocking())
+        .map(|stub| {
+            let stub_type = stub.stub_type.as_str();
+            let message = format!("Blocking {} stub: {}", stub_type, stub.description);
+            Finding::new(path, "blocking-stub", FindingLevel::Error, message)
+                .with_position(stub_position(content, stub, block_ids))
+        })
+        .collect()
+}
+
+/// Where a stub is in the document: its anchored block, or the first line
+/// mentioning its description
+pub fn stub_position(content: &str, stub: &Stub, block_ids: &[BlockId]) -> Option<SourcePosition> {
+    let anchored = stub.anchor.iter().chain(&stub.inline_anchors).find_map(|anchor| {
+        let id = anchor.trim_start_matches('^');
+        block_ids.iter().find(|b| b.id == id).map(|b| b.position)
+    });
+    if anchored.is_some() {
+        return anchored;
+    }
+
+    let description = stub.description.trim();
+    if description.is_empty() {
+        return None;
+    }
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        if line.contains(description) {
+            let indent = line.len() - line.trim_start().len();
+            return Some(SourcePosition::from_offset(content, offset + indent));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// A finding when refinement is below the gate of the document's audience,
+/// as in [`calculate_usefulness_with_config`]
+///
+/// Located at the `audience` line when there is one.
+pub fn gate_finding(
+    path: &str,
+    content: &str,
+    properties: &L1Properties,
+    config: &CalculationConfig,
+) -> Option<Finding> {
+    let refinement = properties.refinement.value();
+    let usefulness = calculate_usefulness_with_config(refinement, properties.audience, config);
+    if usefulness.is_useful {
+        return None;
+    }
+
+    let message = format!(
+        "Refinement {:.2} is below the {} gate {:.2}",
+        refinement, usefulness.audience, usefulness.gate
+    );
+    let position = key_line(content, "audience").map(|line| SourcePosition::new(line, 1, 0));
+    Some(Finding::new(path, "audience-gate", FindingLevel::Error, message).with_position(position))
+}
+
+/// Line of a frontmatter key: `key:` in YAML, `key =` in TOML, `"key":` in JSON
+pub(crate) fn key_line(content: &str, key: &str) -> Option<usize> {
+    content
+        .lines()
+        .position(|line| {
+            let rest = line.trim_start().trim_start_matches('"');
+            rest.strip_prefix(key)
+                .is_some_and(|r| r.trim_start_matches('"').trim_start().starts_with([':', '=']))
+        })
+        .map(|index| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_domain::{Audience, Refinement, StateDimensions, StubForm};
+
+    #[test]
+    fn test_findings() {
+        let content = "---\naudience: public\nstubs:\n  - type: source\n    \
+                       description: cite the study\n    stub_form: blocking\n---\n\
+                       A claim. ^claim\n";
+        let mut stub = Stub::compact("source", "cite the study");
+        stub.stub_form = StubForm::Blocking;
+
+        let found = blocking_stub_findings("a.md", content, std::slice::from_ref(&stub), &[]);
+        assert_eq!(found[0].line(), Some(5));
+        assert_eq!(found[0].position.unwrap().column, 5);
+
+        stub.anchor = Some("^claim".to_string());
+        let block = BlockId {
+            id: "claim".to_string(),
+            position: SourcePosition::new(8, 10, 0),
+        };
+        assert_eq!(stub_position(content, &stub, &[block]).unwrap().line, 8);
+
+        let config = CalculationConfig::default();
+        let mut properties = L1Properties {
+            audience: Audience::Public,
+            refinement: Refinement::new_clamped(0.5),
+            ..Default::default()
+        };
+        let finding = gate_finding("a.md", content, &properties, &config).unwrap();
+        assert_eq!((finding.rule.as_str(), finding.line()), ("audience-gate", Some(2)));
+
+        // Refinement decides, even with health above the gate
+        properties.refinement = Refinement::new_clamped(0.88);
+        assert!(StateDimensions::calculate(&properties).health > config.audience_gates.public);
+        let finding = gate_finding("a.md", content, &properties, &config).unwrap();
+        assert_eq!(finding.message, "Refinement 0.88 is below the public gate 0.90");
+
+        properties.stubs = vec![stub; 3];
+        properties.refinement = Refinement::new_clamped(0.95);
+        assert!(StateDimensions::calculate(&properties).health < config.audience_gates.public);
+        assert!(gate_finding("a.md", content, &properties, &config).is_none());
+    }
+}
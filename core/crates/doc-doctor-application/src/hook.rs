@@ -0,0 +1,114 @@
+//! Pre-commit Checks
+//!
+//! What a commit changes in a document, compared with the version in HEAD:
+//!
+//! - `audience-gate`: a public document whose refinement drops below its
+//!   gate, or that is published below it
+//! - `malformed-stub`: stub entries that no longer parse, and were not
+//!   broken before
+//!
+//! Debt that is already committed does not block a commit.
+
+use doc_doctor_domain::{Audience, CalculationConfig, DocumentAnalysis};
+
+use crate::findings::{gate_finding, Finding, FindingLevel};
+
+/// Problems a commit introduces in a document
+///
+/// `head` is the analysis of the committed version, `None` for new
+/// documents.
+pub fn commit_findings(
+    path: &str,
+    staged_content: &str,
+    staged: &DocumentAnalysis,
+    head: Option<&DocumentAnalysis>,
+    config: &CalculationConfig,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    if staged.properties.audience == Audience::Public {
+        let finding = gate_finding(path, staged_content, &staged.properties, config);
+        let was_below = head.is_some_and(|head| {
+            head.properties.audience == Audience::Public
+                && gate_finding(path, "", &head.properties, config).is_some()
+        });
+        if let (Some(mut finding), false) = (finding, was_below) {
+            if let Some(head) = head {
+                let was = head.properties.refinement.value();
+                finding.message = format!("{} (was {:.2})", finding.message, was);
+            }
+            findings.push(finding);
+        }
+    }
+
+    let was_malformed = |message: &str| {
+        head.is_some_and(|head| head.diagnostics.iter().any(|d| d.message == message))
+    };
+    for diagnostic in &staged.diagnostics {
+        if !diagnostic.field.starts_with("stubs") || !diagnostic.is_skipped() {
+            continue;
+        }
+        if was_malformed(&diagnostic.message) {
+            continue;
+        }
+        let message = format!("Malformed stub {}: {}", diagnostic.field, diagnostic.message);
+        findings.push(
+            Finding::new(path, "malformed-stub", FindingLevel::Error, message)
+                .with_position(diagnostic.position),
+        );
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_domain::{
+        L1Properties, ParseDiagnostic, Refinement, StateDimensions, Stub, StubForm,
+    };
+
+    fn analysis(audience: Audience, refinement: f64) -> DocumentAnalysis {
+        let properties = L1Properties {
+            audience,
+            refinement: Refinement::new_clamped(refinement),
+            ..Default::default()
+        };
+        let dimensions = StateDimensions::calculate(&properties);
+        DocumentAnalysis::new(properties, dimensions)
+    }
+
+    #[test]
+    fn test_commit_findings() {
+        let config = CalculationConfig::default();
+        let content = "---\naudience: public\n---\n";
+        let dropped = analysis(Audience::Public, 0.5);
+
+        let good = analysis(Audience::Public, 0.98);
+        let found = commit_findings("a.md", content, &dropped, Some(&good), &config);
+        assert_eq!(found[0].rule, "audience-gate");
+        assert_eq!(found[0].line(), Some(2));
+
+        let below = analysis(Audience::Public, 0.6);
+        assert!(commit_findings("a.md", content, &dropped, Some(&below), &config).is_empty());
+        assert_eq!(commit_findings("a.md", content, &dropped, None, &config).len(), 1);
+
+        // The gate is on refinement, whatever the health
+        let unready = analysis(Audience::Public, 0.88);
+        let found = commit_findings("a.md", content, &unready, None, &config);
+        assert_eq!(found[0].rule, "audience-gate");
+        let mut blocked = analysis(Audience::Public, 0.95);
+        let mut stub = Stub::compact("source", "cite the study");
+        stub.stub_form = StubForm::Blocking;
+        blocked.properties.stubs = vec![stub; 3];
+        blocked.dimensions = StateDimensions::calculate(&blocked.properties);
+        assert!(blocked.dimensions.health < config.audience_gates.public);
+        assert!(commit_findings("a.md", content, &blocked, None, &config).is_empty());
+
+        let broken = analysis(Audience::Internal, 0.5)
+            .with_diagnostics(vec![ParseDiagnostic::new("stubs[1]", "missing field `type`")]);
+        let found = commit_findings("a.md", content, &broken, None, &config);
+        assert_eq!(found[0].rule, "malformed-stub");
+        assert!(commit_findings("a.md", content, &broken, Some(&broken), &config).is_empty());
+    }
+}
@@ -0,0 +1,533 @@
+//! Vault Index
+//!
+//! Analyses of vault documents kept between runs, so vault-wide tools only
+//! re-parse what changed. Entries are keyed by vault-relative path and carry
+//! the file's modification time, size and content hash. On refresh:
+//!
+//! - an unchanged time and size reuses the entry without reading the file
+//! - otherwise the file is read, and an unchanged hash keeps the entry
+//! - anything else is analyzed again
+//!
+//! Times have one-second resolution, so files modified in the second the
+//! index was refreshed are always read again. Dimensions are recalculated on
+//! every refresh, as freshness decays over time, and a changed calculation
+//! configuration discards the whole index.
+//!
+//! An index can hold entries of documents that have since left the walk,
+//! e.g. newly ignored ones, so vault-wide tools read the entries of the
+//! walked documents with [`VaultIndex::documents`].
+//!
+//! Storage is behind the [`IndexStore`] port.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Component, Path, PathBuf};
+
+use chrono::Utc;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use doc_doctor_domain::{
+    is_canvas, DocumentRepository, L1Properties, ParseDiagnostic, RepositoryError,
+    StateDimensions,
+};
+
+use crate::links::{LinkEdge, LinkGraph};
+use crate::switchboard::Switchboard;
+use crate::use_cases::read_for_batch;
+
+/// Format version of stored indexes; indexes of other versions are rebuilt
+pub const INDEX_VERSION: u32 = 1;
+
+/// Index storage port
+///
+/// Adapters persist a [`VaultIndex`] between runs, e.g. as a file in the
+/// vault.
+pub trait IndexStore: Send + Sync {
+    /// Load the stored index, if there is one
+    fn load(&self) -> Result<Option<VaultIndex>, RepositoryError>;
+
+    /// Store the index
+    fn save(&self, index: &VaultIndex) -> Result<(), RepositoryError>;
+}
+
+/// Analysis of one document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexEntry {
+    /// Modification time (Unix timestamp)
+    pub modified: Option<i64>,
+    /// File size in bytes
+    pub size: u64,
+    /// Hash of the content that was analyzed
+    pub hash: String,
+    /// Whether the document is a canvas
+    pub canvas: bool,
+    /// Parsed L1 properties
+    pub properties: L1Properties,
+    /// Calculated L2 dimensions
+    pub dimensions: StateDimensions,
+    /// Entries skipped or coerced while parsing
+    #[serde(default)]
+    pub diagnostics: Vec<ParseDiagnostic>,
+    /// True when the analysis covers only part of the document
+    #[serde(default)]
+    pub partial: bool,
+    /// Block IDs in the body, without `^`
+    #[serde(default)]
+    pub anchors: Vec<String>,
+    /// Links to other documents, with vault-relative sources
+    #[serde(default)]
+    pub links: Vec<LinkEdge>,
+    /// Set when only the start of a large file was read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+/// A document that could not be read or analyzed
+#[derive(Debug, Clone)]
+pub struct IndexFailure {
+    /// Document path
+    pub path: PathBuf,
+    /// Error message
+    pub error: String,
+}
+
+/// What a refresh did
+#[derive(Debug, Clone, Default)]
+pub struct IndexRefresh {
+    /// Entries reused as they were
+    pub reused: usize,
+    /// Entries kept after reading, as their content was unchanged
+    pub rehashed: usize,
+    /// Documents analyzed again
+    pub analyzed: usize,
+    /// Entries dropped because their file is gone
+    pub removed: usize,
+    /// Documents that could not be indexed
+    pub failed: Vec<IndexFailure>,
+    /// Set when the refreshed index could not be stored
+    pub store_error: Option<String>,
+}
+
+impl IndexRefresh {
+    /// Whether the index differs from the one loaded
+    pub fn changed(&self) -> bool {
+        self.rehashed > 0 || self.analyzed > 0 || self.removed > 0
+    }
+
+    /// Error for a path that could not be indexed
+    pub fn failure(&self, path: &Path) -> Option<&str> {
+        self.failed
+            .iter()
+            .find(|f| f.path == path)
+            .map(|f| f.error.as_str())
+    }
+}
+
+/// Indexed analyses of a vault
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultIndex {
+    /// Format version
+    pub version: u32,
+    /// Hash of the calculation configuration the entries were made with
+    pub config_hash: String,
+    /// Time of the last refresh (Unix timestamp)
+    pub refreshed: Option<i64>,
+    /// Entries by vault-relative path
+    pub entries: BTreeMap<String, IndexEntry>,
+}
+
+impl Default for VaultIndex {
+    fn default() -> Self {
+        Self {
+            version: INDEX_VERSION,
+            config_hash: String::new(),
+            refreshed: None,
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+impl VaultIndex {
+    /// Create an empty index
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load an index from `store`, or start an empty one
+    ///
+    /// Unreadable indexes and those of another format version are
+    /// replaced.
+    pub fn load(store: &dyn IndexStore) -> Self {
+        match store.load() {
+            Ok(Some(index)) if index.version == INDEX_VERSION => index,
+            _ => Self::new(),
+        }
+    }
+
+    /// Key of `path` within the vault at `root`
+    ///
+    /// `.` and `..` are resolved first, so `./a.md` and `a.md` share a key.
+    pub fn key(root: &Path, path: &Path) -> String {
+        let (root, path) = (normalized(root), normalized(path));
+        let relative = path.strip_prefix(&root).unwrap_or(&path);
+        relative.to_string_lossy().replace('\\', "/")
+    }
+
+    /// Entry for `path` within the vault at `root`
+    pub fn get(&self, root: &Path, path: &Path) -> Option<&IndexEntry> {
+        self.entries.get(&Self::key(root, path))
+    }
+
+    /// Entries of the documents at `paths`, by key in path order
+    ///
+    /// Paths without an entry, e.g. ones that failed to index, are left out.
+    pub fn documents(&self, root: &Path, paths: &[PathBuf]) -> Vec<(&str, &IndexEntry)> {
+        let keys: BTreeSet<String> = paths.iter().map(|path| Self::key(root, path)).collect();
+        self.entries
+            .iter()
+            .filter(|(key, _)| keys.contains(*key))
+            .map(|(key, entry)| (key.as_str(), entry))
+            .collect()
+    }
+
+    /// Bring the entries for `paths` up to date
+    ///
+    /// Changed documents are analyzed in parallel; files larger than
+    /// `max_bytes` have only their start read. Entries whose file no
+    /// longer exists are dropped, other entries are kept as they are.
+    pub fn refresh(
+        &mut self,
+        switchboard: &dyn Switchboard,
+        repository: &dyn DocumentRepository,
+        root: &Path,
+        paths: &[PathBuf],
+        max_bytes: u64,
+    ) -> IndexRefresh {
+        let mut refresh = IndexRefresh::default();
+
+        let config_hash = config_hash(switchboard);
+        if config_hash != self.config_hash {
+            self.entries.clear();
+            self.config_hash = config_hash;
+        }
+
+        let before = self.entries.len();
+        self.entries.retain(|key, _| repository.exists(&root.join(key)));
+        refresh.removed = before - self.entries.len();
+
+        let refreshed = self.refreshed;
+        let results: Vec<_> = paths
+            .par_iter()
+            .map(|path| {
+                let key = Self::key(root, path);
+                let metadata = repository.metadata(path).ok();
+                let current = self.entries.get(&key);
+                let stat_matches = match (current, &metadata) {
+                    (Some(entry), Some(metadata)) => {
+                        entry.size == metadata.size
+                            && entry.modified.is_some()
+                            && entry.modified == metadata.modified
+                            && metadata.modified < refreshed
+                    }
+                    _ => false,
+                };
+                if stat_matches {
+                    return (key, Ok(None));
+                }
+
+                let read = match read_for_batch(repository, path, max_bytes) {
+                    Ok(read) => read,
+                    Err(e) => return (key, Err(e.message)),
+                };
+                let hash = content_hash(&read.content);
+                let (modified, size) = metadata.map_or((None, 0), |m| (m.modified, m.size));
+                if let Some(entry) = current.filter(|e| e.hash == hash) {
+                    let mut entry = entry.clone();
+                    entry.modified = modified;
+                    entry.size = size;
+                    return (key, Ok(Some((entry, false))));
+                }
+
+                let entry = analyze(switchboard, &key, path, &read.content).map(|mut entry| {
+                    entry.modified = modified;
+                    entry.size = size;
+                    entry.hash = hash;
+                    entry.warning = read.warning;
+                    Some((entry, true))
+                });
+                (key, entry)
+            })
+            .collect();
+
+        for ((key, result), path) in results.into_iter().zip(paths) {
+            match result {
+                Ok(None) => refresh.reused += 1,
+                Ok(Some((_, false))) => refresh.rehashed += 1,
+                Ok(Some((_, true))) => refresh.analyzed += 1,
+                Err(_) => {}
+            }
+            match result {
+                Ok(None) => {}
+                Ok(Some((entry, _))) => {
+                    self.entries.insert(key, entry);
+                }
+                Err(error) => {
+                    self.entries.remove(&key);
+                    refresh.failed.push(IndexFailure {
+                        path: path.clone(),
+                        error,
+                    });
+                }
+            }
+        }
+
+        for entry in self.entries.values_mut() {
+            entry.dimensions = switchboard.calc_dimensions(&entry.properties);
+        }
+        self.refreshed = Some(Utc::now().timestamp());
+        refresh
+    }
+
+    /// Load the index from `store`, refresh it for `paths` and store it again
+    ///
+    /// A failure to store the index is reported in the refresh, as the
+    /// refreshed entries are still good to use.
+    pub fn refresh_stored(
+        store: &dyn IndexStore,
+        switchboard: &dyn Switchboard,
+        repository: &dyn DocumentRepository,
+        root: &Path,
+        paths: &[PathBuf],
+        max_bytes: u64,
+    ) -> (Self, IndexRefresh) {
+        let mut index = Self::load(store);
+        let mut refresh = index.refresh(switchboard, repository, root, paths, max_bytes);
+        if refresh.changed() {
+            refresh.store_error = store.save(&index).err().map(|e| e.to_string());
+        }
+        (index, refresh)
+    }
+
+    /// Links between the documents at `paths`
+    pub fn link_graph(&self, root: &Path, paths: &[PathBuf]) -> LinkGraph {
+        let mut graph = LinkGraph::new();
+        for entry in paths.iter().filter_map(|path| self.get(root, path)) {
+            graph.add_edges(entry.links.iter().cloned());
+        }
+        graph
+    }
+}
+
+/// `path` without `.` components and with `..` resolved where possible
+fn normalized(path: &Path) -> PathBuf {
+    let mut normal = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(normal.components().next_back(), Some(Component::Normal(_))) =>
+            {
+                normal.pop();
+            }
+            component => normal.push(component),
+        }
+    }
+    normal
+}
+
+/// Analyze a document for the index
+fn analyze(
+    switchboard: &dyn Switchboard,
+    key: &str,
+    path: &Path,
+    content: &str,
+) -> Result<IndexEntry, String> {
+    let mut graph = LinkGraph::new();
+    let (analysis, anchors) = if is_canvas(path) {
+        let canvas = switchboard.analyze_canvas(content).map_err(|e| e.to_string())?;
+        graph.add_canvas(key, &canvas);
+        (canvas.analysis, Vec::new())
+    } else {
+        let analysis = switchboard.analyze_document(content).map_err(|e| e.to_string())?;
+        let anchors = match switchboard.parse_body(content) {
+            Ok(body) => {
+                graph.add_note(key, &body);
+                body.block_ids.into_iter().map(|b| b.id).collect()
+            }
+            Err(_) => Vec::new(),
+        };
+        (analysis, anchors)
+    };
+
+    Ok(IndexEntry {
+        modified: None,
+        size: 0,
+        hash: String::new(),
+        canvas: is_canvas(path),
+        properties: analysis.properties,
+        dimensions: analysis.dimensions,
+        diagnostics: analysis.diagnostics,
+        partial: analysis.partial,
+        anchors,
+        links: graph.edges().to_vec(),
+        warning: None,
+    })
+}
+
+/// Hash of the calculation configuration, which shapes every analysis
+fn config_hash(switchboard: &dyn Switchboard) -> String {
+    let config = serde_yaml::to_string(&*switchboard.config()).unwrap_or_default();
+    content_hash(&config)
+}
+
+/// FNV-1a hash of `content`, as hex
+fn content_hash(content: &str) -> String {
+    let hash = content.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use doc_doctor_domain::{DocumentMetadata, EmbeddedSchemaProvider, RepositoryErrorKind};
+    use doc_doctor_markdown::MarkdownParser;
+    use doc_doctor_parser_yaml::YamlParser;
+
+    use crate::switchboard::ApplicationSwitchboard;
+    use crate::use_cases::DEFAULT_MAX_FILE_SIZE;
+
+    /// Files with a modification time, counting reads
+    #[derive(Default)]
+    struct MemoryRepository {
+        files: Mutex<HashMap<PathBuf, (String, i64)>>,
+        reads: Mutex<usize>,
+    }
+
+    impl MemoryRepository {
+        fn put(&self, path: &str, content: &str, modified: i64) {
+            let file = (content.to_string(), modified);
+            self.files.lock().unwrap().insert(PathBuf::from(path), file);
+        }
+
+        fn reads(&self) -> usize {
+            std::mem::take(&mut *self.reads.lock().unwrap())
+        }
+    }
+
+    impl DocumentRepository for MemoryRepository {
+        fn read(&self, path: &Path) -> Result<String, RepositoryError> {
+            *self.reads.lock().unwrap() += 1;
+            let files = self.files.lock().unwrap();
+            files.get(path).map(|f| f.0.clone()).ok_or_else(|| RepositoryError::not_found(path))
+        }
+
+        fn write(&self, path: &Path, _content: &str) -> Result<(), RepositoryError> {
+            Err(RepositoryError::new(RepositoryErrorKind::Other, "read-only").with_path(path))
+        }
+
+        fn list(&self, _pattern: &str) -> Result<Vec<PathBuf>, RepositoryError> {
+            Ok(self.files.lock().unwrap().keys().cloned().collect())
+        }
+
+        fn exists(&self, path: &Path) -> bool {
+            self.files.lock().unwrap().contains_key(path)
+        }
+
+        fn metadata(&self, path: &Path) -> Result<DocumentMetadata, RepositoryError> {
+            let files = self.files.lock().unwrap();
+            let (content, modified) = files.get(path).ok_or_else(|| RepositoryError::not_found(path))?;
+            Ok(DocumentMetadata {
+                size: content.len() as u64,
+                modified: Some(*modified),
+                created: None,
+                is_directory: false,
+            })
+        }
+    }
+
+    #[test]
+    fn test_refresh_is_incremental() {
+        let parser = Arc::new(YamlParser::new());
+        let switchboard =
+            ApplicationSwitchboard::new(Arc::clone(&parser), parser, Arc::new(EmbeddedSchemaProvider))
+                .with_body_parser(Arc::new(MarkdownParser::new()));
+        let repository = MemoryRepository::default();
+        repository.put("vault/a.md", "---\nrefinement: 0.8\n---\nSee [[b]] ^intro\n", 100);
+        repository.put("vault/b.md", "---\nrefinement: 0.4\n---\n", 100);
+        let root = Path::new("vault");
+        let paths = vec![PathBuf::from("vault/a.md"), PathBuf::from("vault/b.md")];
+        let refresh = |index: &mut VaultIndex| {
+            index.refresh(&switchboard, &repository, root, &paths, DEFAULT_MAX_FILE_SIZE)
+        };
+
+        let mut index = VaultIndex::new();
+        let first = refresh(&mut index);
+        assert_eq!((first.analyzed, first.reused), (2, 0));
+        assert_eq!(repository.reads(), 2);
+        let entry = index.get(root, Path::new("vault/a.md")).unwrap();
+        assert_eq!(entry.properties.refinement.value(), 0.8);
+        assert_eq!(entry.anchors, vec!["intro"]);
+        assert_eq!(index.link_graph(root, &paths).backlinks("b.md").len(), 1);
+
+        // Unchanged files are not read again
+        let second = refresh(&mut index);
+        assert_eq!((second.analyzed, second.reused), (0, 2));
+        assert_eq!(repository.reads(), 0);
+        assert!(!second.changed());
+
+        // A touched file is read, but only a changed one is analyzed
+        repository.put("vault/a.md", "---\nrefinement: 0.8\n---\nSee [[b]] ^intro\n", 200);
+        repository.put("vault/b.md", "---\nrefinement: 0.5\n---\n", 200);
+        let third = refresh(&mut index);
+        assert_eq!((third.analyzed, third.rehashed), (1, 1));
+        assert_eq!(repository.reads(), 2);
+        let entry = index.get(root, Path::new("vault/b.md")).unwrap();
+        assert_eq!(entry.properties.refinement.value(), 0.5);
+
+        // Deleted files leave the index
+        repository.files.lock().unwrap().remove(Path::new("vault/b.md"));
+        let fourth = refresh(&mut index);
+        assert_eq!(fourth.removed, 1);
+        assert!(fourth.failure(Path::new("vault/b.md")).is_some());
+        assert_eq!(index.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_keys_are_normalized() {
+        let root = Path::new("vault");
+        assert_eq!(VaultIndex::key(Path::new("."), Path::new("./a.md")), "a.md");
+        assert_eq!(VaultIndex::key(Path::new(""), Path::new("a.md")), "a.md");
+        assert_eq!(VaultIndex::key(root, Path::new("./vault/sub/../a.md")), "a.md");
+        assert_eq!(VaultIndex::key(Path::new("./vault/."), Path::new("vault/a.md")), "a.md");
+
+        let mut index = VaultIndex::new();
+        let entry = |key: &str| (key.to_string(), entry_for(key));
+        index.entries.extend([entry("a.md"), entry("ignored/b.md")]);
+        let paths = [PathBuf::from("./vault/a.md"), PathBuf::from("vault/gone.md")];
+        let keys: Vec<&str> = index.documents(root, &paths).into_iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, ["a.md"]);
+    }
+
+    fn entry_for(key: &str) -> IndexEntry {
+        IndexEntry {
+            modified: None,
+            size: 0,
+            hash: key.to_string(),
+            canvas: false,
+            properties: L1Properties::default(),
+            dimensions: StateDimensions::calculate(&L1Properties::default()),
+            diagnostics: Vec::new(),
+            partial: false,
+            anchors: Vec::new(),
+            links: Vec::new(),
+            warning: None,
+        }
+    }
+}
@@ -0,0 +1,359 @@
+//! Inline Metadata
+//!
+//! Reads Dataview inline fields and Obsidian Tasks checkboxes from the
+//! document body as `L1Properties`, for vaults that keep metadata there
+//! instead of (or as well as) in frontmatter.
+//!
+//! - Inline fields (`refinement:: 0.7`) fill properties the frontmatter
+//!   leaves at their defaults; `tags` and `aliases` are added to.
+//! - Open tasks become stubs. The type comes from a tag (`#verify` or
+//!   `#stub/verify`) or a marker emoji (`🔗` → `link`), falling back to
+//!   `todo`; a form tag (`#blocking`), the Tasks priority, the `📅` due date
+//!   and a trailing block ID are kept.
+//!
+//! Both are opt-in through `vault.inline_fields` and `vault.task_stubs`.
+//! Stub markers (`> [!stub] ...`, `{{type: ...}}`) declare stubs
+//! explicitly, so they are read unless `vault.stub_markers` is off.
+
+use std::sync::Arc;
+
+use serde_yaml::{Mapping, Value};
+
+use doc_doctor_domain::{
+    BodyParser, DocumentParser, InlineField, L1Properties, LintFinding, MetadataSpan,
+    ParseDiagnostic, ParseError, RecoveredParse, Stub, StubForm, StubType, StubTypesConfig,
+    TaskItem,
+};
+
+use crate::config::ConfigHandle;
+use crate::markers::stubs_from_markers;
+
+/// Marker emojis and the stub type they stand for
+const MARKER_TYPES: &[(&str, &str)] = &[
+    ("🔗", "link"),
+    ("📚", "cite"),
+    ("🔍", "verify"),
+    ("❓", "question"),
+    ("🐛", "fix"),
+    ("📝", "draft"),
+    ("✏️", "expand"),
+    ("✏", "expand"),
+    ("💡", "idea"),
+    ("🚧", "blocker"),
+];
+
+/// Properties that inline fields may set
+const SCALAR_FIELDS: &[&str] = &[
+    "uid", "title", "created", "modified", "refinement", "origin", "form", "audience",
+];
+const LIST_FIELDS: &[&str] = &["tags", "aliases"];
+
+/// Parser that adds inline fields and tasks to what another parser reads
+///
+/// Wraps the frontmatter parser for read paths (analysis, validation,
+/// listing). Writers keep using the plain parser, so editing a stub never
+/// copies tasks into frontmatter behind the user's back; that is what
+/// [`crate::Switchboard::import_tasks`] is for.
+pub struct InlineMetadataParser {
+    parser: Arc<dyn DocumentParser>,
+    body_parser: Arc<dyn BodyParser>,
+    config: ConfigHandle,
+}
+
+impl InlineMetadataParser {
+    /// Wrap `parser`, reading the body with `body_parser`
+    pub fn new(
+        parser: Arc<dyn DocumentParser>,
+        body_parser: Arc<dyn BodyParser>,
+        config: ConfigHandle,
+    ) -> Self {
+        Self {
+            parser,
+            body_parser,
+            config,
+        }
+    }
+
+    /// Merge the body's fields and tasks into a frontmatter parse
+    fn enrich(
+        &self,
+        content: &str,
+        parsed: Result<RecoveredParse, ParseError>,
+    ) -> Result<RecoveredParse, ParseError> {
+        let config = self.config.get();
+        let vault = &config.vault;
+        if !vault.inline_fields && !vault.task_stubs && !vault.stub_markers {
+            return parsed;
+        }
+
+        let body = self
+            .body_parser
+            .parse_body(content, self.parser.body_offset(content));
+        let markers = if vault.stub_markers {
+            stubs_from_markers(&body, &config.stub_types)
+        } else {
+            Vec::new()
+        };
+        let fields = if vault.inline_fields { body.inline_fields } else { Vec::new() };
+        let tasks = if vault.task_stubs { body.tasks } else { Vec::new() };
+
+        // A note may carry all its metadata inline
+        let mut parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(_) if self.parser.extract_metadata(content).is_none()
+                && (!fields.is_empty()
+                    || !markers.is_empty()
+                    || tasks.iter().any(|t| t.status.is_open())) =>
+            {
+                RecoveredParse::complete(L1Properties::default())
+            }
+            Err(e) => return Err(e),
+        };
+
+        let diagnostics = apply_inline_fields(&mut parsed.properties, &fields);
+        parsed.diagnostics.extend(diagnostics);
+        for marker in markers {
+            if !parsed.properties.stubs.iter().any(|s| same_stub(s, &marker)) {
+                parsed.properties.stubs.push(marker);
+            }
+        }
+        let imported = stubs_from_tasks(&parsed.properties.stubs, &tasks, &config.stub_types);
+        parsed.properties.stubs.extend(imported);
+        Ok(parsed)
+    }
+}
+
+impl DocumentParser for InlineMetadataParser {
+    fn parse(&self, content: &str) -> Result<L1Properties, ParseError> {
+        let parsed = self.parser.parse(content).map(RecoveredParse::complete);
+        self.enrich(content, parsed).map(|p| p.properties)
+    }
+
+    fn parse_recovering(&self, content: &str) -> Result<RecoveredParse, ParseError> {
+        self.enrich(content, self.parser.parse_recovering(content))
+    }
+
+    fn lint(&self, content: &str) -> Vec<LintFinding> {
+        self.parser.lint(content)
+    }
+
+    fn extract_metadata(&self, content: &str) -> Option<MetadataSpan> {
+        self.parser.extract_metadata(content)
+    }
+
+    fn body_offset(&self, content: &str) -> usize {
+        self.parser.body_offset(content)
+    }
+
+    fn format_id(&self) -> &'static str {
+        self.parser.format_id()
+    }
+}
+
+/// Convert a task checkbox into a stub
+pub fn stub_from_task(task: &TaskItem, stub_types: &StubTypesConfig) -> Stub {
+    let tag_type = task
+        .tags
+        .iter()
+        .map(|tag| StubType::new(tag.strip_prefix("stub/").unwrap_or(tag)))
+        .find(|stub_type| stub_types.is_known(stub_type));
+    let marker_type = || {
+        task.markers.iter().find_map(|marker| {
+            MARKER_TYPES
+                .iter()
+                .find(|(emoji, _)| emoji == marker)
+                .map(|(_, stub_type)| StubType::new(*stub_type))
+        })
+    };
+    let stub_form = task
+        .tags
+        .iter()
+        .find_map(|tag| tag.strip_prefix("stub/").unwrap_or(tag).parse::<StubForm>().ok());
+
+    let mut stub = Stub::compact("todo", task.text.clone());
+    stub.stub_type = tag_type.or_else(marker_type).unwrap_or_default();
+    stub.stub_form = stub_form.unwrap_or_default();
+    stub.priority = task.priority.unwrap_or_default();
+    stub.due = task.due.as_deref().and_then(|d| d.parse().ok());
+    stub.anchor = task.block_id.as_ref().map(|id| format!("^{}", id));
+    stub
+}
+
+/// Stubs for open tasks that `existing` does not already cover
+///
+/// A task is covered by a stub with the same anchor or description, so
+/// importing twice, or reading a note whose tasks were imported, adds
+/// nothing.
+pub fn stubs_from_tasks(
+    existing: &[Stub],
+    tasks: &[TaskItem],
+    stub_types: &StubTypesConfig,
+) -> Vec<Stub> {
+    let mut stubs: Vec<Stub> = Vec::new();
+    for task in tasks.iter().filter(|t| t.status.is_open()) {
+        let stub = stub_from_task(task, stub_types);
+        if !existing.iter().chain(&stubs).any(|s| same_stub(s, &stub)) {
+            stubs.push(stub);
+        }
+    }
+    stubs
+}
+
+/// Whether two stubs track the same gap: same anchor or same description
+pub(crate) fn same_stub(a: &Stub, b: &Stub) -> bool {
+    let same_anchor = a.anchor.is_some() && a.anchor == b.anchor;
+    same_anchor || a.description.trim().eq_ignore_ascii_case(b.description.trim())
+}
+
+/// Apply inline fields to `props`, returning a diagnostic per field that
+/// could not be read
+fn apply_inline_fields(props: &mut L1Properties, fields: &[InlineField]) -> Vec<ParseDiagnostic> {
+    if fields.is_empty() {
+        return Vec::new();
+    }
+
+    // Stubs don't come from inline fields; keep them out of the round trip
+    let stubs = std::mem::take(&mut props.stubs);
+    let defaults = to_mapping(&L1Properties::default());
+    let mut current = to_mapping(props);
+    let mut diagnostics = Vec::new();
+
+    for field in fields {
+        let key = field.key.to_lowercase().replace([' ', '-'], "_");
+        let mut candidate = current.clone();
+
+        if LIST_FIELDS.contains(&key.as_str()) {
+            let mut items = candidate
+                .get(key.as_str())
+                .and_then(Value::as_sequence)
+                .cloned()
+                .unwrap_or_default();
+            for item in field.value.split(',').map(|i| i.trim().trim_start_matches('#')) {
+                let item = Value::String(item.to_string());
+                if !item.as_str().is_some_and(str::is_empty) && !items.contains(&item) {
+                    items.push(item);
+                }
+            }
+            candidate.insert(Value::String(key.clone()), Value::Sequence(items));
+        } else if SCALAR_FIELDS.contains(&key.as_str()) {
+            // Frontmatter wins: only fill properties still at their default
+            if candidate.get(key.as_str()) != defaults.get(key.as_str()) {
+                continue;
+            }
+            candidate.insert(Value::String(key.clone()), scalar(&field.value));
+        } else {
+            continue;
+        }
+
+        match serde_yaml::from_value::<L1Properties>(Value::Mapping(candidate.clone())) {
+            Ok(_) => current = candidate,
+            Err(e) => diagnostics.push(
+                ParseDiagnostic::new(key, format!("Inline field ignored: {}", e))
+                    .with_position(field.position),
+            ),
+        }
+    }
+
+    if let Ok(updated) = serde_yaml::from_value(Value::Mapping(current)) {
+        *props = updated;
+    }
+    props.stubs = stubs;
+    diagnostics
+}
+
+fn to_mapping(props: &L1Properties) -> Mapping {
+    match serde_yaml::to_value(props) {
+        Ok(Value::Mapping(mapping)) => mapping,
+        _ => Mapping::new(),
+    }
+}
+
+/// Numbers and booleans keep their type; anything else is a string
+fn scalar(raw: &str) -> Value {
+    match serde_yaml::from_str::<Value>(raw) {
+        Ok(value @ (Value::Number(_) | Value::Bool(_))) => value,
+        _ => Value::String(raw.trim_matches('"').to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_domain::{Audience, Priority, SourcePosition, TaskStatus};
+    use doc_doctor_markdown::MarkdownParser;
+    use doc_doctor_parser_yaml::YamlParser;
+
+    fn task(text: &str, tags: &[&str], markers: &[&str]) -> TaskItem {
+        TaskItem {
+            status: TaskStatus::Open,
+            text: text.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            markers: markers.iter().map(|m| m.to_string()).collect(),
+            priority: Some(Priority::High),
+            due: Some("2024-05-01".to_string()),
+            block_id: Some("fig3".to_string()),
+            position: SourcePosition::new(1, 1, 0),
+            end_offset: 0,
+        }
+    }
+
+    fn parser(inline_fields: bool, task_stubs: bool) -> InlineMetadataParser {
+        let mut config = doc_doctor_domain::CalculationConfig::default();
+        config.vault.inline_fields = inline_fields;
+        config.vault.task_stubs = task_stubs;
+        InlineMetadataParser::new(
+            Arc::new(YamlParser::new()),
+            Arc::new(MarkdownParser::new()),
+            ConfigHandle::new(config).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_stub_from_task() {
+        let config = StubTypesConfig::default();
+
+        let stub = stub_from_task(&task("verify figure 3", &["stub/verify", "blocking"], &[]), &config);
+        assert_eq!(stub.stub_type.as_str(), "verify");
+        assert_eq!(stub.stub_form, StubForm::Blocking);
+        assert_eq!(stub.priority, Priority::High);
+        assert_eq!(stub.due.unwrap().to_string(), "2024-05-01");
+        assert_eq!(stub.anchor.as_deref(), Some("^fig3"));
+
+        let linked = stub_from_task(&task("add source", &["project"], &["🔗"]), &config);
+        assert_eq!(linked.stub_type.as_str(), "link");
+        let plain = stub_from_task(&task("tidy up", &[], &[]), &config);
+        assert_eq!(plain.stub_type.as_str(), "todo");
+    }
+
+    #[test]
+    fn test_inline_fields_fill_defaults() {
+        let content = "---\ntitle: Frontmatter\n---\ntitle:: Inline\nrefinement:: 0.7\naudience:: internal\ntags:: #a, b\nrefinement:: 0.9\nform:: nonsense\n";
+        let parsed = parser(true, false).parse_recovering(content).unwrap();
+
+        assert_eq!(parsed.properties.title.as_deref(), Some("Frontmatter"));
+        assert_eq!(parsed.properties.refinement.value(), 0.7);
+        assert_eq!(parsed.properties.audience, Audience::Internal);
+        assert_eq!(parsed.properties.tags, vec!["a", "b"]);
+        assert_eq!(parsed.diagnostics.len(), 1);
+        assert_eq!(parsed.diagnostics[0].field, "form");
+    }
+
+    #[test]
+    fn test_tasks_become_stubs() {
+        let content = "---\nstubs:\n  - type: link\n    description: Add source\n---\n- [ ] Add source\n- [ ] verify figure 3 #verify 📅 2024-05-01\n- [x] done already\n";
+
+        let stubs = parser(false, true).parse(content).unwrap().stubs;
+        assert_eq!(stubs.len(), 2);
+        assert_eq!(stubs[1].stub_type.as_str(), "verify");
+
+        // Disabled by default
+        assert_eq!(parser(false, false).parse(content).unwrap().stubs.len(), 1);
+    }
+
+    #[test]
+    fn test_inline_only_note() {
+        let content = "# Note\n\nrefinement:: 0.4\n";
+        assert_eq!(parser(true, true).parse(content).unwrap().refinement.value(), 0.4);
+        assert!(parser(true, true).parse("# Note\n").is_err());
+    }
+}
@@ -39,22 +39,103 @@
 //!
 //! - [`Switchboard`]: Central routing trait for all operations
 //! - [`ApplicationSwitchboard`]: Default implementation
+//! - [`ConfigHandle`]: Shared, reloadable calculation configuration injected
+//!   into the switchboard and every use case
+//! - [`InlineMetadataParser`]: Reads Dataview inline fields and Tasks
+//!   checkboxes as properties and stubs when the vault opts in
+//! - [`annotations_markdown`]: Exports comments and highlights across
+//!   documents as Markdown
+//! - [`CanvasAnalysis`]: Obsidian canvases analyzed as documents, with stubs
+//!   from their text cards
+//! - [`LinkGraph`]: Links between notes and canvases, for backlinks
+//! - [`SyncStatus`]: How `[!stub]` callouts and `{{type: ...}}` markers in
+//!   the body line up with frontmatter stubs
+//! - [`detect_stubs`]: Heuristics that propose system-generated stubs for
+//!   gaps in the body (TODOs, missing citations, empty sections, ...)
+//! - [`VaultIndex`]: Analyses of a vault kept between runs and refreshed
+//!   incrementally, stored through the [`IndexStore`] port
+//! - [`VaultWatch`]: Re-analyzes changed files and publishes [`VaultEvent`]s
+//!   (health gates, stubs added or resolved, orphaned anchors)
+//! - [`Query`]: A small query language over documents and their stubs,
+//!   evaluated against the vault index or any [`QueryDocument`]
+//! - [`Finding`]: Schema errors, blocking stubs and gate failures located
+//!   in their documents, for CI reports
+//! - [`check_document`]: Per-path quality gate thresholds, compared with a
+//!   [`Baseline`] of known failures so only regressions fail a check
+//! - [`commit_findings`]: What a commit breaks in a document compared with
+//!   HEAD, for pre-commit hooks
+//! - [`VaultReport`]: Health, gate readiness, stub backlog and stale
+//!   documents of a vault as HTML, Markdown or CSV
+//! - [`VaultWalker`]: The files of a vault, honouring ignore files,
+//!   include/exclude globs, hidden and template folders and symlinks
 //!
 //! # Use Cases (Legacy)
 //!
 //! - [`AnalyzeDocumentUseCase`]: Full document analysis (parse + calculate)
 //! - [`ValidateDocumentUseCase`]: Schema validation
-//! - [`BatchProcessUseCase`]: Process multiple documents with glob patterns
+//! - [`BatchProcessUseCase`]: Process multiple documents with glob patterns, or stream them
 
+mod annotations;
+mod canvas;
+mod check;
+mod config;
+mod detect;
+mod edit;
 mod error;
+mod findings;
+mod hook;
+mod index;
+mod inline;
+mod links;
+mod markers;
+mod query;
+mod registry;
+mod report;
 pub mod switchboard;
 pub mod use_cases;
+mod walk;
+mod watch;
 
+pub use annotations::{annotations_markdown, stub_from_annotation, DocumentAnnotations};
+pub use canvas::{card_stubs, CanvasAnalysis, CanvasCard};
+pub use check::{
+    check_document, thresholds_for, unreadable_failure, Baseline, BaselineEntry, CheckFailure,
+    CheckOutcome, BASELINE_VERSION,
+};
+pub use config::ConfigHandle;
+pub use detect::{detect_stubs, DetectionRule, DetectorOptions, StubSuggestion};
 pub use error::{ApplicationError, ApplicationResult};
+pub use findings::{
+    blocking_stub_findings, gate_finding, schema_findings, stub_position, Finding, FindingLevel,
+    FINDING_RULES,
+};
+pub use hook::commit_findings;
+pub use index::{
+    IndexEntry, IndexFailure, IndexRefresh, IndexStore, VaultIndex, INDEX_VERSION,
+};
+pub use inline::{stub_from_task, stubs_from_tasks, InlineMetadataParser};
+pub use links::{LinkEdge, LinkGraph};
+pub use markers::{stub_from_marker, stubs_from_markers, SyncStatus};
+pub use query::{
+    IndexedDocument, Query, QueryDocument, QueryError, QueryRow, QueryValue, DOCUMENT_FIELDS,
+    STUB_FIELDS,
+};
+pub use registry::ParserRegistry;
+pub use report::{
+    AttentionItem, AudienceHealth, BacklogCount, GateReadiness, HealthBucket, HealthDistribution,
+    ReportOptions, ReportSection, ReportSummary, StaleDocument, StubBacklog, VaultReport,
+};
 pub use switchboard::{
     AnchorLinkResult, AnchorMatches, ApplicationSwitchboard, NewStub, StubAddResult, StubFilter,
-    StubResolveResult, StubUpdateResult, StubUpdates, Switchboard, SwitchboardError,
+    StubImportResult, StubResolveResult, StubSyncResult, StubUpdateResult, StubUpdates,
+    Switchboard, SwitchboardError,
 };
 pub use use_cases::{
-    AnalyzeDocumentUseCase, BatchProcessUseCase, DocumentService, ValidateDocumentUseCase,
+    read_for_batch, AnalyzeDocumentUseCase, BatchCheckpoint, BatchProcessUseCase, BatchRead,
+    BatchSink, DocumentService, DocumentServiceBuilder, ValidateDocumentUseCase,
+    DEFAULT_MAX_FILE_SIZE,
+};
+pub use walk::{
+    is_document_path, split_pattern, DirEntry, DirectoryLister, VaultWalker, Walk,
 };
+pub use watch::{vault_events, EventBus, VaultEvent, VaultWatch};
@@ -0,0 +1,130 @@
+//! Link Graph
+//!
+//! Directed links between vault documents: wikilinks and embeds in notes,
+//! and the file cards and arrows of canvases. Targets are kept as written
+//! (`Plan`, `Notes/Plan.md`) and matched against paths when queried, so the
+//! graph can be built before every file is known.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use doc_doctor_domain::MarkdownBody;
+
+use crate::canvas::CanvasAnalysis;
+
+/// A link from one document to another
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkEdge {
+    /// Linking document
+    pub source: String,
+    /// Link target as written: a note name or vault-relative path
+    pub target: String,
+    /// Canvas edge label
+    pub label: Option<String>,
+}
+
+impl LinkEdge {
+    /// Create an unlabeled link
+    pub fn new(source: impl Into<String>, target: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            target: target.into(),
+            label: None,
+        }
+    }
+
+    /// Whether the link points at `path`
+    ///
+    /// Matches a full or vault-relative path (with or without `.md`) and,
+    /// like a wikilink, a bare note name.
+    pub fn points_to(&self, path: &str) -> bool {
+        let target = self.target.trim_end_matches(".md");
+        let path = path.trim_end_matches(".md");
+        if target.is_empty() {
+            return false;
+        }
+        let stem = Path::new(path).file_name().and_then(|n| n.to_str());
+        path == target || path.ends_with(&format!("/{}", target)) || stem == Some(target)
+    }
+}
+
+/// Links across a vault
+#[derive(Debug, Clone, Default)]
+pub struct LinkGraph {
+    edges: Vec<LinkEdge>,
+}
+
+impl LinkGraph {
+    /// Create an empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a note's wikilinks and embeds
+    pub fn add_note(&mut self, path: &str, body: &MarkdownBody) {
+        let targets = body.wikilinks.iter().chain(&body.embeds).map(|l| &l.target);
+        for target in targets.filter(|t| !t.is_empty()) {
+            self.edges.push(LinkEdge::new(path, target.as_str()));
+        }
+    }
+
+    /// Add a canvas: links to its file cards and card wikilinks, plus its
+    /// arrows between file cards
+    pub fn add_canvas(&mut self, path: &str, canvas: &CanvasAnalysis) {
+        let card_links = canvas.cards.iter().flat_map(|c| &c.links);
+        for target in canvas.files.iter().chain(card_links) {
+            self.edges.push(LinkEdge::new(path, target.as_str()));
+        }
+        self.edges.extend(canvas.file_edges.iter().cloned());
+    }
+
+    /// Add links recorded earlier, e.g. from the vault index
+    pub fn add_edges(&mut self, edges: impl IntoIterator<Item = LinkEdge>) {
+        self.edges.extend(edges);
+    }
+
+    /// Every link, in insertion order
+    pub fn edges(&self) -> &[LinkEdge] {
+        &self.edges
+    }
+
+    /// Links from a document
+    pub fn outgoing(&self, path: &str) -> Vec<&LinkEdge> {
+        self.edges.iter().filter(|e| e.source == path).collect()
+    }
+
+    /// Links to a document from other documents
+    pub fn backlinks(&self, path: &str) -> Vec<&LinkEdge> {
+        self.edges
+            .iter()
+            .filter(|e| e.source != path && e.points_to(path))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_points_to() {
+        let path = "vault/Notes/Plan.md";
+        assert!(LinkEdge::new("a", "Plan").points_to(path));
+        assert!(LinkEdge::new("a", "Notes/Plan.md").points_to(path));
+        assert!(LinkEdge::new("a", "Notes/Plan").points_to(path));
+        assert!(!LinkEdge::new("a", "lan").points_to(path));
+        assert!(!LinkEdge::new("a", "Other/Plan.md").points_to(path));
+    }
+
+    #[test]
+    fn test_backlinks() {
+        let mut graph = LinkGraph::new();
+        graph.edges.push(LinkEdge::new("vault/A.md", "B"));
+        graph.edges.push(LinkEdge::new("vault/B.md", "B"));
+        graph.edges.push(LinkEdge::new("vault/board.canvas", "B.md"));
+
+        assert_eq!(graph.backlinks("vault/B.md").len(), 2);
+        assert_eq!(graph.outgoing("vault/A.md").len(), 1);
+    }
+}
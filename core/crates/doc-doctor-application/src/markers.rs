@@ -0,0 +1,139 @@
+//! Stub Markers
+//!
+//! Stubs declared in the body, as `> [!stub] expand: ...` callouts or
+//! `{{expand: ...}}` markers, and how they line up with the stubs in
+//! frontmatter. A marker matches the frontmatter stub with its anchor or,
+//! failing that, with its description.
+
+use doc_doctor_domain::{MarkdownBody, Stub, StubMarker, StubTypesConfig};
+
+use crate::inline::same_stub;
+
+/// Convert a stub marker into a stub
+///
+/// The label, optionally `stub/`-prefixed, must be a known stub type, so
+/// template placeholders such as `{{date:YYYY-MM-DD}}` are not stubs.
+pub fn stub_from_marker(marker: &StubMarker, stub_types: &StubTypesConfig) -> Option<Stub> {
+    let label = marker.label.strip_prefix("stub/").unwrap_or(&marker.label);
+    let mut stub = Stub::compact(label.to_lowercase(), marker.text.clone());
+    if !stub_types.is_known(&stub.stub_type) || stub.description.is_empty() {
+        return None;
+    }
+    stub.anchor = marker.block_id.as_ref().map(|id| format!("^{}", id));
+    Some(stub)
+}
+
+/// Stubs for the markers in a body, without duplicates
+pub fn stubs_from_markers(body: &MarkdownBody, stub_types: &StubTypesConfig) -> Vec<Stub> {
+    let mut stubs: Vec<Stub> = Vec::new();
+    for stub in body.stub_markers.iter().filter_map(|m| stub_from_marker(m, stub_types)) {
+        if !stubs.iter().any(|s| same_stub(s, &stub)) {
+            stubs.push(stub);
+        }
+    }
+    stubs
+}
+
+/// Index of the frontmatter stub a marker stub belongs to
+///
+/// An anchor match wins over a description match.
+pub(crate) fn matching_stub(stubs: &[Stub], marker: &Stub) -> Option<usize> {
+    let by_anchor = || {
+        let anchor = marker.anchor.as_ref()?;
+        stubs.iter().position(|s| s.anchor.as_ref() == Some(anchor))
+    };
+    by_anchor().or_else(|| stubs.iter().position(|s| same_stub(s, marker)))
+}
+
+/// How frontmatter stubs and body markers line up
+#[derive(Debug, Clone, Default)]
+pub struct SyncStatus {
+    /// Frontmatter stubs with a marker or a block anchor in the body
+    pub synced: Vec<Stub>,
+    /// Body markers with no frontmatter stub yet
+    pub missing_in_frontmatter: Vec<Stub>,
+    /// Frontmatter stubs with neither a marker nor a block anchor in the body
+    pub missing_in_body: Vec<Stub>,
+}
+
+impl SyncStatus {
+    /// Compare frontmatter `stubs` with the markers and block IDs of `body`
+    pub fn new(stubs: &[Stub], body: &MarkdownBody, stub_types: &StubTypesConfig) -> Self {
+        let markers = stubs_from_markers(body, stub_types);
+        let mut status = Self::default();
+        let mut matched = vec![false; stubs.len()];
+
+        for marker in markers {
+            match matching_stub(stubs, &marker) {
+                Some(index) => matched[index] = true,
+                None => status.missing_in_frontmatter.push(marker),
+            }
+        }
+
+        for (stub, matched) in stubs.iter().zip(matched) {
+            let anchored = stub.anchor.iter().chain(&stub.inline_anchors).any(|anchor| {
+                let id = anchor.trim_start_matches('^');
+                body.block_ids.iter().any(|block| block.id == id)
+            });
+            if matched || anchored {
+                status.synced.push(stub.clone());
+            } else {
+                status.missing_in_body.push(stub.clone());
+            }
+        }
+
+        status
+    }
+
+    /// Whether every marker has a stub and every stub is in the body
+    pub fn is_synced(&self) -> bool {
+        self.missing_in_frontmatter.is_empty() && self.missing_in_body.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_domain::{BlockId, SourcePosition, StubMarkerSyntax};
+
+    fn marker(label: &str, text: &str, block_id: Option<&str>) -> StubMarker {
+        StubMarker {
+            syntax: StubMarkerSyntax::Braces,
+            label: label.to_string(),
+            text: text.to_string(),
+            block_id: block_id.map(str::to_string),
+            position: SourcePosition::new(1, 1, 0),
+            end_offset: 0,
+        }
+    }
+
+    #[test]
+    fn test_sync_status() {
+        let stub_types = StubTypesConfig::default();
+        let body = MarkdownBody {
+            block_ids: vec![BlockId {
+                id: "intro".to_string(),
+                position: SourcePosition::new(1, 1, 0),
+            }],
+            stub_markers: vec![
+                marker("expand", "add examples", Some("examples")),
+                marker("stub/cite", "source for 40%", None),
+                marker("date", "YYYY-MM-DD", None),
+            ],
+            ..Default::default()
+        };
+        let mut anchored = Stub::compact("link", "link the intro");
+        anchored.anchor = Some("^intro".to_string());
+        let mut renamed = Stub::compact("expand", "old wording");
+        renamed.anchor = Some("^examples".to_string());
+        let stubs = vec![anchored, renamed, Stub::compact("fix", "frontmatter only")];
+
+        let status = SyncStatus::new(&stubs, &body, &stub_types);
+
+        assert_eq!(status.synced.len(), 2);
+        assert_eq!(status.missing_in_frontmatter.len(), 1);
+        assert_eq!(status.missing_in_frontmatter[0].stub_type.as_str(), "cite");
+        assert_eq!(status.missing_in_body[0].description, "frontmatter only");
+        assert!(!status.is_synced());
+    }
+}
@@ -0,0 +1,880 @@
+//! Vault Queries
+//!
+//! A small query language over documents and their stubs:
+//!
+//! ```text
+//! audience = public and health < 0.8
+//!     and any(stubs, form = blocking and family = retrieval)
+//!     order by health desc, path
+//!     select path, title, health
+//!     limit 20
+//! ```
+//!
+//! - Conditions combine with `and`, `or`, `not` and parentheses
+//! - Comparisons: `=`, `!=`, `<`, `<=`, `>`, `>=`, `~` (contains) and
+//!   `in (a, b, ...)`; text compares case-insensitively, a list field
+//!   matches when any element does
+//! - `any(stubs, ...)`, `all(stubs, ...)` and `none(stubs, ...)` test the
+//!   stubs of a document; `count(stubs[, ...])` counts them
+//! - A bare field is a condition on its own, e.g. `partial`
+//! - Words that are not fields are text, so `form = blocking` needs no
+//!   quotes; quote text that contains spaces or collides with a field
+//!
+//! Queries run against anything implementing [`QueryDocument`], such as
+//! the entries of a [`VaultIndex`].
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use doc_doctor_domain::{Stub, StubTypesConfig};
+
+use crate::index::{IndexEntry, VaultIndex};
+
+/// Document fields available in queries
+pub const DOCUMENT_FIELDS: &[&str] = &[
+    "path", "title", "uid", "audience", "form", "origin", "refinement", "health",
+    "usefulness", "trust", "freshness", "tags", "aliases", "created", "modified",
+    "stub_count", "blocking", "partial", "canvas", "links",
+];
+
+/// Stub fields available inside `any`, `all`, `none` and `count`
+pub const STUB_FIELDS: &[&str] = &[
+    "type", "family", "form", "priority", "origin", "description", "anchor", "assignees",
+    "urgency", "impact", "complexity", "due",
+];
+
+/// Columns of a query without `select`
+const DEFAULT_COLUMNS: &[&str] = &["path", "title", "audience", "health", "stub_count"];
+
+/// Query syntax error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    /// Error message
+    pub message: String,
+    /// Byte offset in the query text
+    pub position: usize,
+}
+
+impl QueryError {
+    /// Create a new query error
+    pub fn new(message: impl Into<String>, position: usize) -> Self {
+        Self {
+            message: message.into(),
+            position,
+        }
+    }
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+/// Value of a field or literal in a query
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum QueryValue {
+    Null,
+    Bool(bool),
+    Number(#[serde(serialize_with = "serialize_number")] f64),
+    Text(String),
+    List(Vec<String>),
+}
+
+/// Counts and other whole numbers serialize as integers
+fn serialize_number<S: serde::Serializer>(n: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        serializer.serialize_i64(*n as i64)
+    } else {
+        serializer.serialize_f64(*n)
+    }
+}
+
+impl QueryValue {
+    fn text(value: impl ToString) -> Self {
+        Self::Text(value.to_string())
+    }
+
+    fn optional(value: Option<impl ToString>) -> Self {
+        value.map_or(Self::Null, Self::text)
+    }
+
+    fn is_truthy(&self) -> bool {
+        match self {
+            Self::Null => false,
+            Self::Bool(b) => *b,
+            Self::Number(n) => *n != 0.0,
+            Self::Text(s) => !s.is_empty(),
+            Self::List(items) => !items.is_empty(),
+        }
+    }
+
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Number(n) => Some(*n),
+            Self::Text(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Ordering used by `order by`: numbers, then text, nulls last
+    fn sort_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Null, Self::Null) => Ordering::Equal,
+            (Self::Null, _) => Ordering::Greater,
+            (_, Self::Null) => Ordering::Less,
+            _ => compare(self, other).unwrap_or_else(|| {
+                self.to_string().to_lowercase().cmp(&other.to_string().to_lowercase())
+            }),
+        }
+    }
+}
+
+impl fmt::Display for QueryValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => Ok(()),
+            Self::Bool(b) => write!(f, "{}", b),
+            Self::Number(n) => write!(f, "{}", n),
+            Self::Text(s) => write!(f, "{}", s),
+            Self::List(items) => write!(f, "{}", items.join(", ")),
+        }
+    }
+}
+
+/// A document as seen by queries
+///
+/// `stub_count` and `blocking` are derived from [`QueryDocument::stubs`];
+/// every other name in [`DOCUMENT_FIELDS`] is looked up through
+/// [`QueryDocument::field`], which returns [`QueryValue::Null`] for fields
+/// the document does not know.
+pub trait QueryDocument {
+    /// Vault-relative path
+    fn path(&self) -> &str;
+
+    /// Value of a document field
+    fn field(&self, name: &str) -> QueryValue;
+
+    /// Stubs of the document
+    fn stubs(&self) -> &[Stub];
+}
+
+/// An index entry under its vault-relative path
+#[derive(Debug, Clone, Copy)]
+pub struct IndexedDocument<'a> {
+    pub path: &'a str,
+    pub entry: &'a IndexEntry,
+}
+
+impl QueryDocument for IndexedDocument<'_> {
+    fn path(&self) -> &str {
+        self.path
+    }
+
+    fn field(&self, name: &str) -> QueryValue {
+        let props = &self.entry.properties;
+        let dimensions = &self.entry.dimensions;
+        match name {
+            "title" => QueryValue::optional(props.title.as_ref()),
+            "uid" => QueryValue::optional(props.uid.as_ref()),
+            "audience" => QueryValue::text(props.audience),
+            "form" => QueryValue::text(props.form),
+            "origin" => QueryValue::text(props.origin),
+            "refinement" => QueryValue::Number(props.refinement.value()),
+            "health" => QueryValue::Number(dimensions.health),
+            "usefulness" => QueryValue::Number(dimensions.usefulness.margin),
+            "trust" => QueryValue::Number(dimensions.trust_level),
+            "freshness" => QueryValue::Number(dimensions.freshness),
+            "tags" => QueryValue::List(props.tags.clone()),
+            "aliases" => QueryValue::List(props.aliases.clone()),
+            "created" => QueryValue::optional(props.created.as_ref()),
+            "modified" => QueryValue::optional(props.modified.as_ref()),
+            "partial" => QueryValue::Bool(self.entry.partial),
+            "canvas" => QueryValue::Bool(self.entry.canvas),
+            "links" => QueryValue::Number(self.entry.links.len() as f64),
+            _ => QueryValue::Null,
+        }
+    }
+
+    fn stubs(&self) -> &[Stub] {
+        &self.entry.properties.stubs
+    }
+}
+
+/// A document matched by a query, with the selected columns
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueryRow {
+    /// Vault-relative path
+    pub path: String,
+    /// Values in the order of [`Query::columns`]
+    pub values: Vec<QueryValue>,
+}
+
+/// A parsed query
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    filter: Option<Expr>,
+    order: Vec<(String, bool)>,
+    columns: Vec<String>,
+    limit: Option<usize>,
+}
+
+impl Query {
+    /// Parse query text; empty text matches every document
+    pub fn parse(text: &str) -> Result<Self, QueryError> {
+        Parser::new(text)?.query()
+    }
+
+    /// Columns of each row
+    pub fn columns(&self) -> Vec<&str> {
+        if self.columns.is_empty() {
+            DEFAULT_COLUMNS.to_vec()
+        } else {
+            self.columns.iter().map(String::as_str).collect()
+        }
+    }
+
+    /// Whether the query has a condition, as opposed to only ordering
+    /// or projecting
+    pub fn has_filter(&self) -> bool {
+        self.filter.is_some()
+    }
+
+    /// Whether a document satisfies the condition of the query
+    pub fn matches(&self, document: &dyn QueryDocument, stub_types: &StubTypesConfig) -> bool {
+        let context = Context { document, stub: None, stub_types };
+        self.filter.as_ref().is_none_or(|filter| filter.eval(&context))
+    }
+
+    /// Matching documents, ordered, limited and projected onto the columns
+    ///
+    /// Without `order by`, documents keep the order they are given in.
+    pub fn run<D: QueryDocument>(
+        &self,
+        documents: impl IntoIterator<Item = D>,
+        stub_types: &StubTypesConfig,
+    ) -> Vec<QueryRow> {
+        let mut matched: Vec<D> =
+            documents.into_iter().filter(|d| self.matches(d, stub_types)).collect();
+
+        if !self.order.is_empty() {
+            let mut keyed: Vec<(Vec<QueryValue>, D)> = matched
+                .into_iter()
+                .map(|d| {
+                    let keys = self.order.iter().map(|(f, _)| document_field(&d, f)).collect();
+                    (keys, d)
+                })
+                .collect();
+            keyed.sort_by(|(a, _), (b, _)| {
+                a.iter()
+                    .zip(b)
+                    .zip(&self.order)
+                    .map(|((a, b), (_, descending))| match (a, b) {
+                        // Nulls stay last in either direction
+                        (QueryValue::Null, _) | (_, QueryValue::Null) => a.sort_cmp(b),
+                        _ if *descending => b.sort_cmp(a),
+                        _ => a.sort_cmp(b),
+                    })
+                    .find(|o| o.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            });
+            matched = keyed.into_iter().map(|(_, d)| d).collect();
+        }
+
+        let columns = self.columns();
+        matched
+            .iter()
+            .take(self.limit.unwrap_or(usize::MAX))
+            .map(|d| QueryRow {
+                path: d.path().to_string(),
+                values: columns.iter().map(|c| document_field(d, c)).collect(),
+            })
+            .collect()
+    }
+}
+
+impl VaultIndex {
+    /// Run a query over the indexed documents at `paths` within the vault at
+    /// `root`, in path order
+    pub fn query(
+        &self,
+        root: &Path,
+        paths: &[PathBuf],
+        query: &Query,
+        stub_types: &StubTypesConfig,
+    ) -> Vec<QueryRow> {
+        let documents = self.documents(root, paths).into_iter();
+        query.run(documents.map(|(path, entry)| IndexedDocument { path, entry }), stub_types)
+    }
+}
+
+/// Value of a document field, including those derived from stubs
+fn document_field(document: &dyn QueryDocument, name: &str) -> QueryValue {
+    match name {
+        "path" => QueryValue::text(document.path()),
+        "stub_count" => QueryValue::Number(document.stubs().len() as f64),
+        "blocking" => {
+            QueryValue::Number(document.stubs().iter().filter(|s| s.is_blocking()).count() as f64)
+        }
+        _ => document.field(name),
+    }
+}
+
+fn stub_field(stub: &Stub, name: &str, stub_types: &StubTypesConfig) -> QueryValue {
+    match name {
+        "type" => QueryValue::text(stub.stub_type.as_str()),
+        "family" => {
+            QueryValue::text(stub_types.family(&stub.stub_type).display_name().to_lowercase())
+        }
+        "form" => QueryValue::text(stub.stub_form),
+        "priority" => QueryValue::text(stub.priority),
+        "origin" => QueryValue::text(stub.origin),
+        "description" => QueryValue::text(&stub.description),
+        "anchor" => QueryValue::optional(stub.anchor.as_ref()),
+        "assignees" => QueryValue::List(stub.assignees.clone()),
+        "urgency" => stub.urgency.map_or(QueryValue::Null, QueryValue::Number),
+        "impact" => stub.impact.map_or(QueryValue::Null, QueryValue::Number),
+        "complexity" => stub.complexity.map_or(QueryValue::Null, QueryValue::Number),
+        "due" => QueryValue::optional(stub.due.as_ref()),
+        _ => QueryValue::Null,
+    }
+}
+
+/// Evaluation scope: a document, and a stub inside stub functions
+struct Context<'a> {
+    document: &'a dyn QueryDocument,
+    stub: Option<&'a Stub>,
+    stub_types: &'a StubTypesConfig,
+}
+
+impl<'a> Context<'a> {
+    fn with_stub(&self, stub: &'a Stub) -> Self {
+        Context {
+            document: self.document,
+            stub: Some(stub),
+            stub_types: self.stub_types,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Quantifier {
+    Any,
+    All,
+    None,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Field(String),
+    Literal(QueryValue),
+    Count(Option<Box<Expr>>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare(Operand, CompareOp, Operand),
+    In(Operand, Vec<Operand>),
+    Stubs(Quantifier, Box<Expr>),
+    Truthy(Operand),
+}
+
+impl Operand {
+    fn eval(&self, context: &Context) -> QueryValue {
+        match self {
+            Self::Field(name) => match context.stub {
+                Some(stub) => stub_field(stub, name, context.stub_types),
+                None => document_field(context.document, name),
+            },
+            Self::Literal(value) => value.clone(),
+            Self::Count(filter) => {
+                let stubs = context.document.stubs();
+                let count = match filter {
+                    Some(filter) => {
+                        stubs.iter().filter(|s| filter.eval(&context.with_stub(s))).count()
+                    }
+                    None => stubs.len(),
+                };
+                QueryValue::Number(count as f64)
+            }
+        }
+    }
+}
+
+impl Expr {
+    fn eval(&self, context: &Context) -> bool {
+        match self {
+            Self::And(a, b) => a.eval(context) && b.eval(context),
+            Self::Or(a, b) => a.eval(context) || b.eval(context),
+            Self::Not(e) => !e.eval(context),
+            Self::Compare(left, op, right) => test(&left.eval(context), *op, &right.eval(context)),
+            Self::In(left, items) => {
+                let left = left.eval(context);
+                items.iter().any(|i| test(&left, CompareOp::Eq, &i.eval(context)))
+            }
+            Self::Stubs(quantifier, filter) => {
+                let mut stubs = context.document.stubs().iter();
+                let test = |s: &Stub| filter.eval(&context.with_stub(s));
+                match quantifier {
+                    Quantifier::Any => stubs.any(test),
+                    Quantifier::All => stubs.all(test),
+                    Quantifier::None => !stubs.any(test),
+                }
+            }
+            Self::Truthy(operand) => operand.eval(context).is_truthy(),
+        }
+    }
+}
+
+/// Apply a comparison; list values match when any element does
+fn test(left: &QueryValue, op: CompareOp, right: &QueryValue) -> bool {
+    if let QueryValue::List(items) = left {
+        let any = |op| items.iter().any(|i| test(&QueryValue::text(i), op, right));
+        return match op {
+            CompareOp::Ne => !any(CompareOp::Eq),
+            _ => any(op),
+        };
+    }
+    match (left, right, op) {
+        (_, QueryValue::Null, CompareOp::Eq) => *left == QueryValue::Null,
+        (_, QueryValue::Null, CompareOp::Ne) => *left != QueryValue::Null,
+        (QueryValue::Null, _, CompareOp::Ne) => true,
+        (QueryValue::Null, _, _) | (_, QueryValue::Null, _) => false,
+        (_, _, CompareOp::Contains) => {
+            left.to_string().to_lowercase().contains(&right.to_string().to_lowercase())
+        }
+        _ => compare(left, right).is_some_and(|ordering| match op {
+            CompareOp::Eq => ordering.is_eq(),
+            CompareOp::Ne => ordering.is_ne(),
+            CompareOp::Lt => ordering.is_lt(),
+            CompareOp::Le => ordering.is_le(),
+            CompareOp::Gt => ordering.is_gt(),
+            CompareOp::Ge => ordering.is_ge(),
+            CompareOp::Contains => unreachable!(),
+        }),
+    }
+}
+
+/// Compare two non-null scalar values, numerically when both are numbers
+fn compare(left: &QueryValue, right: &QueryValue) -> Option<Ordering> {
+    match (left, right) {
+        (QueryValue::Bool(a), QueryValue::Bool(b)) => Some(a.cmp(b)),
+        (QueryValue::Bool(a), QueryValue::Text(b)) => b.parse::<bool>().ok().map(|b| a.cmp(&b)),
+        (QueryValue::Number(_), _) | (_, QueryValue::Number(_)) => {
+            left.as_number()?.partial_cmp(&right.as_number()?)
+        }
+        (QueryValue::Text(a), QueryValue::Text(b)) => Some(a.to_lowercase().cmp(&b.to_lowercase())),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Text(String),
+    Number(f64),
+    Op(CompareOp),
+    Open,
+    Close,
+    Comma,
+}
+
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        let token = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '(' | ')' | ',' | '=' | '~' => {
+                chars.next();
+                match c {
+                    '(' => Token::Open,
+                    ')' => Token::Close,
+                    ',' => Token::Comma,
+                    '=' => Token::Op(CompareOp::Eq),
+                    _ => Token::Op(CompareOp::Contains),
+                }
+            }
+            '!' | '<' | '>' => {
+                chars.next();
+                let equals = chars.next_if(|&(_, c)| c == '=').is_some();
+                match (c, equals) {
+                    ('!', true) => Token::Op(CompareOp::Ne),
+                    ('<', false) => Token::Op(CompareOp::Lt),
+                    ('<', true) => Token::Op(CompareOp::Le),
+                    ('>', false) => Token::Op(CompareOp::Gt),
+                    ('>', true) => Token::Op(CompareOp::Ge),
+                    _ => return Err(QueryError::new("Expected '!='", start)),
+                }
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, q)) if q == c => break,
+                        Some((_, ch)) => value.push(ch),
+                        None => return Err(QueryError::new("Unterminated text", start)),
+                    }
+                }
+                Token::Text(value)
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '.' => {
+                let mut value = String::new();
+                while let Some((_, ch)) = chars.next_if(|&(_, ch)| is_word_char(ch)) {
+                    value.push(ch);
+                }
+                match value.parse() {
+                    Ok(number) => Token::Number(number),
+                    Err(_) => Token::Word(value),
+                }
+            }
+            c if is_word_char(c) => {
+                let mut value = String::new();
+                while let Some((_, ch)) = chars.next_if(|&(_, ch)| is_word_char(ch)) {
+                    value.push(ch);
+                }
+                Token::Word(value)
+            }
+            _ => return Err(QueryError::new(format!("Unexpected '{}'", c), start)),
+        };
+        tokens.push((token, start));
+    }
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | '#' | '^')
+}
+
+/// Recursive descent parser over the tokens of a query
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    end: usize,
+    /// Inside a stub function, where names refer to stub fields
+    in_stubs: bool,
+}
+
+impl Parser {
+    fn new(text: &str) -> Result<Self, QueryError> {
+        Ok(Self {
+            tokens: tokenize(text)?,
+            position: 0,
+            end: text.len(),
+            in_stubs: false,
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, ahead: usize) -> Option<&Token> {
+        self.tokens.get(self.position + ahead).map(|(t, _)| t)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens.get(self.position).map_or(self.end, |(_, o)| *o)
+    }
+
+    fn error(&self, message: impl Into<String>) -> QueryError {
+        QueryError::new(message, self.offset())
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.peek_keyword(keyword);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, token: Token, description: &str) -> Result<(), QueryError> {
+        if self.peek() == Some(&token) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected {}", description)))
+        }
+    }
+
+    fn at_clause(&self) -> bool {
+        ["order", "select", "limit"].iter().any(|k| self.peek_keyword(k))
+    }
+
+    fn query(&mut self) -> Result<Query, QueryError> {
+        let filter = if self.peek().is_some() && !self.at_clause() {
+            Some(self.or()?)
+        } else {
+            None
+        };
+        let mut query = Query { filter, order: Vec::new(), columns: Vec::new(), limit: None };
+
+        while self.peek().is_some() {
+            if self.eat_keyword("order") {
+                if !self.eat_keyword("by") {
+                    return Err(self.error("Expected 'by'"));
+                }
+                query.order = self.list(|p| {
+                    let field = p.document_field()?;
+                    let descending = p.eat_keyword("desc");
+                    if !descending {
+                        p.eat_keyword("asc");
+                    }
+                    Ok((field, descending))
+                })?;
+            } else if self.eat_keyword("select") {
+                query.columns = self.list(Self::document_field)?;
+            } else if self.eat_keyword("limit") {
+                match self.next() {
+                    Some(Token::Number(n)) if n >= 0.0 && n.fract() == 0.0 => {
+                        query.limit = Some(n as usize)
+                    }
+                    _ => {
+                        self.position -= 1;
+                        return Err(self.error("Expected a row count"));
+                    }
+                }
+            } else {
+                return Err(self.error("Expected 'and', 'or', 'order by', 'select' or 'limit'"));
+            }
+        }
+        Ok(query)
+    }
+
+    fn list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, QueryError>,
+    ) -> Result<Vec<T>, QueryError> {
+        let mut items = vec![item(self)?];
+        while self.peek() == Some(&Token::Comma) {
+            self.position += 1;
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    fn document_field(&mut self) -> Result<String, QueryError> {
+        match self.peek() {
+            Some(Token::Word(w)) if DOCUMENT_FIELDS.contains(&w.to_lowercase().as_str()) => {
+                let field = w.to_lowercase();
+                self.position += 1;
+                Ok(field)
+            }
+            Some(Token::Word(w)) => Err(self.error(format!("Unknown document field '{}'", w))),
+            _ => Err(self.error("Expected a document field")),
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, QueryError> {
+        let mut expr = self.and()?;
+        while self.eat_keyword("or") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, QueryError> {
+        let mut expr = self.unary()?;
+        while self.eat_keyword("and") {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, QueryError> {
+        if self.eat_keyword("not") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.peek() == Some(&Token::Open) {
+            self.position += 1;
+            let expr = self.or()?;
+            self.expect(Token::Close, "')'")?;
+            return Ok(expr);
+        }
+
+        let quantifiers =
+            [("any", Quantifier::Any), ("all", Quantifier::All), ("none", Quantifier::None)];
+        let called = self.peek_at(1) == Some(&Token::Open);
+        let quantifier = quantifiers.into_iter().find(|(k, _)| called && self.peek_keyword(k));
+        if let Some((_, quantifier)) = quantifier {
+            self.position += 1;
+            let filter = self.stub_arguments()?.ok_or_else(|| self.error("Expected ','"))?;
+            return Ok(Expr::Stubs(quantifier, filter));
+        }
+
+        let left = self.operand(true)?;
+        let negated_in = self.peek_keyword("not")
+            && matches!(self.peek_at(1), Some(Token::Word(w)) if w.eq_ignore_ascii_case("in"));
+        if negated_in || self.peek_keyword("in") {
+            self.position += if negated_in { 2 } else { 1 };
+            self.expect(Token::Open, "'('")?;
+            let items = self.list(|p| p.operand(false))?;
+            self.expect(Token::Close, "')'")?;
+            let expr = Expr::In(left, items);
+            return Ok(if negated_in { Expr::Not(Box::new(expr)) } else { expr });
+        }
+        match self.peek() {
+            Some(Token::Op(op)) => {
+                let op = *op;
+                self.position += 1;
+                Ok(Expr::Compare(left, op, self.operand(false)?))
+            }
+            _ => Ok(Expr::Truthy(left)),
+        }
+    }
+
+    /// `(stubs[, condition])` after a stub function name
+    fn stub_arguments(&mut self) -> Result<Option<Box<Expr>>, QueryError> {
+        if self.in_stubs {
+            return Err(self.error("Stub functions cannot be nested"));
+        }
+        self.expect(Token::Open, "'('")?;
+        if !self.eat_keyword("stubs") {
+            return Err(self.error("Expected 'stubs'"));
+        }
+        let filter = if self.peek() == Some(&Token::Comma) {
+            self.position += 1;
+            self.in_stubs = true;
+            let filter = self.or();
+            self.in_stubs = false;
+            Some(Box::new(filter?))
+        } else {
+            None
+        };
+        self.expect(Token::Close, "')'")?;
+        Ok(filter)
+    }
+
+    /// A field, literal or `count(...)`; on the left only fields are
+    /// words, on the right unknown words are text
+    fn operand(&mut self, left: bool) -> Result<Operand, QueryError> {
+        let fields = if self.in_stubs { STUB_FIELDS } else { DOCUMENT_FIELDS };
+        let offset = self.offset();
+        let token = self.next();
+        let word = match &token {
+            Some(Token::Word(w)) => w.to_lowercase(),
+            _ => String::new(),
+        };
+        match token {
+            Some(Token::Word(_)) if word == "count" && self.peek() == Some(&Token::Open) => {
+                Ok(Operand::Count(self.stub_arguments()?))
+            }
+            Some(Token::Word(_)) if fields.contains(&word.as_str()) => Ok(Operand::Field(word)),
+            Some(Token::Word(_)) if word == "null" => Ok(Operand::Literal(QueryValue::Null)),
+            Some(Token::Word(_)) if word == "true" || word == "false" => {
+                Ok(Operand::Literal(QueryValue::Bool(word == "true")))
+            }
+            Some(Token::Word(w)) if left => {
+                let scope = if self.in_stubs { "stub" } else { "document" };
+                Err(QueryError::new(format!("Unknown {} field '{}'", scope, w), offset))
+            }
+            Some(Token::Word(w) | Token::Text(w)) => Ok(Operand::Literal(QueryValue::Text(w))),
+            Some(Token::Number(n)) => Ok(Operand::Literal(QueryValue::Number(n))),
+            _ => Err(QueryError::new("Expected a field or value", offset)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_domain::{Audience, L1Properties, Refinement, StateDimensions, StubForm};
+
+    fn entry(audience: Audience, refinement: f64, stubs: Vec<Stub>) -> IndexEntry {
+        let properties = L1Properties {
+            audience,
+            refinement: Refinement::new_clamped(refinement),
+            tags: vec!["Draft".to_string()],
+            stubs,
+            ..Default::default()
+        };
+        IndexEntry {
+            modified: None,
+            size: 0,
+            hash: String::new(),
+            canvas: false,
+            dimensions: StateDimensions::calculate(&properties),
+            properties,
+            diagnostics: Vec::new(),
+            partial: false,
+            anchors: Vec::new(),
+            links: Vec::new(),
+            warning: None,
+        }
+    }
+
+    #[test]
+    fn test_query() {
+        let mut blocking = Stub::compact("source", "needs a citation");
+        blocking.stub_form = StubForm::Blocking;
+        let mut index = VaultIndex::new();
+        index.entries.insert("a.md".into(), entry(Audience::Public, 0.4, vec![blocking]));
+        index.entries.insert("b.md".into(), entry(Audience::Public, 0.9, Vec::new()));
+        let expand = Stub::compact("expand", "more");
+        index.entries.insert("c.md".into(), entry(Audience::Internal, 0.2, vec![expand]));
+        // Left the walk, e.g. newly ignored
+        index.entries.insert("old.md".into(), entry(Audience::Public, 0.1, Vec::new()));
+        let stub_types = StubTypesConfig::default();
+        let root = Path::new("vault");
+        let walked = ["vault/a.md", "vault/b.md", "vault/c.md"].map(PathBuf::from);
+        let paths = |text: &str| -> Vec<String> {
+            let query = Query::parse(text).unwrap();
+            let rows = index.query(root, &walked, &query, &stub_types);
+            rows.into_iter().map(|r| r.path).collect()
+        };
+
+        assert_eq!(
+            paths("audience = PUBLIC and any(stubs, form = blocking and family = retrieval)"),
+            ["a.md"]
+        );
+        assert_eq!(paths("refinement < 0.5 order by refinement desc"), ["a.md", "c.md"]);
+        assert_eq!(paths("count(stubs) = 0 or audience in (internal)"), ["b.md", "c.md"]);
+        assert_eq!(paths("none(stubs, type ~ exp) and tags = draft limit 1"), ["a.md"]);
+        assert_eq!(paths("not blocking and audience != public"), ["c.md"]);
+
+        let query = Query::parse("select path, refinement").unwrap();
+        assert_eq!(query.columns(), ["path", "refinement"]);
+        let rows = index.query(root, &walked, &query, &stub_types);
+        assert_eq!(rows[1].values[1], QueryValue::Number(0.9));
+
+        let error = Query::parse("health < 0.8 and colour = red").unwrap_err();
+        assert_eq!(error.position, 17);
+        assert!(Query::parse("any(stubs, health > 1)").is_err());
+        assert!(Query::parse("title = 'unterminated").is_err());
+    }
+}
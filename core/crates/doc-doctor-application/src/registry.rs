@@ -0,0 +1,201 @@
+//! Parser Registry
+//!
+//! Chooses a frontmatter adapter per document from its opening delimiter,
+//! so YAML (`---`), TOML (`+++`) and JSON (`{`) documents can share a vault.
+//! The registry is itself a `DocumentParser` and `DocumentWriter`, so it can
+//! be handed to the switchboard and use cases in place of a single adapter.
+
+use std::sync::Arc;
+
+use doc_doctor_domain::{
+    DocumentParser, DocumentWriter, L1Properties, LintFinding, MetadataSpan, ParseError,
+    RecoveredParse, SerializeError,
+};
+
+/// A registered format adapter
+struct Adapter {
+    parser: Arc<dyn DocumentParser>,
+    writer: Arc<dyn DocumentWriter>,
+}
+
+/// Format-detecting parser and writer
+///
+/// Adapters are tried in registration order; the first one that finds its
+/// metadata block handles the document. Documents without recognizable
+/// metadata go to the first adapter, which reports the error and writes
+/// new frontmatter.
+///
+/// # Example
+///
+/// ```ignore
+/// let registry = ParserRegistry::new()
+///     .register(YamlParser::new())
+///     .register(TomlParser::new())
+///     .register(JsonParser::new());
+/// assert_eq!(registry.detect("+++\ntitle = \"Hugo\"\n+++\n"), Some("toml"));
+/// ```
+#[derive(Default)]
+pub struct ParserRegistry {
+    adapters: Vec<Adapter>,
+}
+
+impl ParserRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an adapter that both parses and writes its format
+    pub fn register<A>(mut self, adapter: A) -> Self
+    where
+        A: DocumentParser + DocumentWriter + 'static,
+    {
+        let adapter = Arc::new(adapter);
+        self.adapters.push(Adapter {
+            parser: Arc::clone(&adapter) as Arc<dyn DocumentParser>,
+            writer: adapter,
+        });
+        self
+    }
+
+    /// Format identifiers of the registered adapters, in order
+    pub fn formats(&self) -> Vec<&'static str> {
+        self.adapters.iter().map(|a| a.parser.format_id()).collect()
+    }
+
+    /// Detect the metadata format of a document
+    ///
+    /// Returns `None` when no adapter recognizes a metadata block.
+    pub fn detect(&self, content: &str) -> Option<&'static str> {
+        self.matching(content).map(|a| a.parser.format_id())
+    }
+
+    fn matching(&self, content: &str) -> Option<&Adapter> {
+        self.adapters
+            .iter()
+            .find(|a| a.parser.extract_metadata(content).is_some())
+    }
+
+    fn select(&self, content: &str) -> Option<&Adapter> {
+        self.matching(content).or_else(|| self.adapters.first())
+    }
+
+    fn no_adapters() -> ParseError {
+        ParseError::new("No document parsers are registered")
+    }
+}
+
+impl DocumentParser for ParserRegistry {
+    fn parse(&self, content: &str) -> Result<L1Properties, ParseError> {
+        self.select(content).ok_or_else(Self::no_adapters)?.parser.parse(content)
+    }
+
+    fn parse_recovering(&self, content: &str) -> Result<RecoveredParse, ParseError> {
+        self.select(content)
+            .ok_or_else(Self::no_adapters)?
+            .parser
+            .parse_recovering(content)
+    }
+
+    fn lint(&self, content: &str) -> Vec<LintFinding> {
+        self.matching(content)
+            .map(|a| a.parser.lint(content))
+            .unwrap_or_default()
+    }
+
+    fn extract_metadata(&self, content: &str) -> Option<MetadataSpan> {
+        self.matching(content)?.parser.extract_metadata(content)
+    }
+
+    fn body_offset(&self, content: &str) -> usize {
+        self.matching(content).map_or(0, |a| a.parser.body_offset(content))
+    }
+
+    fn format_id(&self) -> &'static str {
+        "auto"
+    }
+}
+
+impl DocumentWriter for ParserRegistry {
+    fn serialize_document(
+        &self,
+        original_content: &str,
+        properties: &L1Properties,
+    ) -> Result<String, SerializeError> {
+        self.select(original_content)
+            .ok_or_else(|| SerializeError::new("No document writers are registered"))?
+            .writer
+            .serialize_document(original_content, properties)
+    }
+
+    fn serialize_frontmatter(&self, properties: &L1Properties) -> Result<String, SerializeError> {
+        self.adapters
+            .first()
+            .ok_or_else(|| SerializeError::new("No document writers are registered"))?
+            .writer
+            .serialize_frontmatter(properties)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_parser_json::JsonParser;
+    use doc_doctor_parser_toml::TomlParser;
+    use doc_doctor_parser_yaml::YamlParser;
+
+    fn registry() -> ParserRegistry {
+        ParserRegistry::new()
+            .register(YamlParser::new())
+            .register(TomlParser::new())
+            .register(JsonParser::new())
+    }
+
+    #[test]
+    fn test_detects_format_by_delimiter() {
+        let registry = registry();
+
+        assert_eq!(registry.formats(), vec!["yaml", "toml", "json"]);
+        assert_eq!(registry.detect("---\ntitle: A\n---\n"), Some("yaml"));
+        assert_eq!(registry.detect("+++\ntitle = \"A\"\n+++\n"), Some("toml"));
+        assert_eq!(registry.detect("{\"title\": \"A\"}\n"), Some("json"));
+        assert_eq!(registry.detect("# No metadata\n"), None);
+    }
+
+    #[test]
+    fn test_parses_each_format() {
+        let registry = registry();
+
+        for content in [
+            "---\ntitle: Same\nrefinement: 0.5\n---\n",
+            "+++\ntitle = \"Same\"\nrefinement = 0.5\n+++\n",
+            "{\"title\": \"Same\", \"refinement\": 0.5}\n",
+        ] {
+            let props = registry.parse(content).unwrap();
+            assert_eq!(props.title.as_deref(), Some("Same"));
+            assert_eq!(props.refinement.value(), 0.5);
+        }
+    }
+
+    #[test]
+    fn test_writes_in_original_format() {
+        let registry = registry();
+        let content = "+++\ntitle = \"Hugo\"\n+++\nBody\n";
+
+        let mut props = registry.parse(content).unwrap();
+        props.title = Some("Renamed".to_string());
+
+        let written = registry.serialize_document(content, &props).unwrap();
+        assert!(written.starts_with("+++\n"));
+        assert_eq!(registry.parse(&written).unwrap().title.as_deref(), Some("Renamed"));
+
+        // New documents get frontmatter from the first adapter
+        let fresh = registry.serialize_document("Body\n", &props).unwrap();
+        assert!(fresh.starts_with("---\n"));
+    }
+
+    #[test]
+    fn test_empty_registry() {
+        assert!(ParserRegistry::new().parse("---\n---\n").is_err());
+    }
+}
@@ -0,0 +1,901 @@
+//! Vault Reports
+//!
+//! A [`VaultReport`] summarizes the vault index for editorial review:
+//! health distribution, audience-gate readiness, the stub backlog by type,
+//! family and assignee, stale documents and the items that need attention
+//! first. Reports render as a self-contained HTML page, a Markdown note for
+//! the vault and CSV tables.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use doc_doctor_domain::{Audience, CalculationConfig};
+
+use crate::index::IndexEntry;
+
+/// Health ranges of the distribution, as `[low, high)` with the last
+/// range closed
+const HEALTH_BUCKETS: &[(f64, f64)] = &[(0.0, 0.2), (0.2, 0.4), (0.4, 0.6), (0.6, 0.8), (0.8, 1.0)];
+
+const AUDIENCES: &[Audience] =
+    &[Audience::Personal, Audience::Internal, Audience::Trusted, Audience::Public];
+
+/// Section of a vault report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportSection {
+    /// Document, health and stub totals
+    Summary,
+    /// Documents per health range and per audience
+    Health,
+    /// Documents that pass the gate of their audience
+    Gates,
+    /// Open stubs by type, family and assignee
+    Stubs,
+    /// Documents past their form cadence
+    Stale,
+    /// Documents to look at first
+    Attention,
+}
+
+impl ReportSection {
+    /// Every section, in report order
+    pub const ALL: [ReportSection; 6] = [
+        Self::Summary,
+        Self::Health,
+        Self::Gates,
+        Self::Stubs,
+        Self::Stale,
+        Self::Attention,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Summary => "summary",
+            Self::Health => "health",
+            Self::Gates => "gates",
+            Self::Stubs => "stubs",
+            Self::Stale => "stale",
+            Self::Attention => "attention",
+        }
+    }
+
+    /// Section heading
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Summary => "Summary",
+            Self::Health => "Health Distribution",
+            Self::Gates => "Audience Gate Readiness",
+            Self::Stubs => "Stub Backlog",
+            Self::Stale => "Stale Documents",
+            Self::Attention => "Needs Attention",
+        }
+    }
+}
+
+impl fmt::Display for ReportSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ReportSection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|section| section.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|s| s.as_str()).collect();
+                format!("Unknown report section '{}' (expected one of: {})", s, names.join(", "))
+            })
+    }
+}
+
+/// What a report contains
+#[derive(Debug, Clone)]
+pub struct ReportOptions {
+    /// Sections to include, rendered in report order
+    pub sections: Vec<ReportSection>,
+    /// Most documents listed under stale documents and attention items
+    pub limit: usize,
+    /// Date documents without `modified` by their file modification time;
+    /// off for reports that must not depend on the checkout
+    pub file_times: bool,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            sections: ReportSection::ALL.to_vec(),
+            limit: 10,
+            file_times: true,
+        }
+    }
+}
+
+/// Document, health and stub totals
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportSummary {
+    pub documents: usize,
+    pub average_health: f64,
+    pub average_refinement: f64,
+    pub stubs: usize,
+    pub blocking_stubs: usize,
+    pub documents_with_blocking: usize,
+    pub partial: usize,
+}
+
+/// Documents in a health range
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthBucket {
+    pub low: f64,
+    pub high: f64,
+    pub documents: usize,
+}
+
+/// Health of the documents written for one audience
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudienceHealth {
+    pub audience: String,
+    pub documents: usize,
+    pub average_health: f64,
+}
+
+/// Health distribution across the vault
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthDistribution {
+    pub buckets: Vec<HealthBucket>,
+    pub by_audience: Vec<AudienceHealth>,
+}
+
+/// Gate readiness of the documents written for one audience
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GateReadiness {
+    pub audience: String,
+    pub gate: f64,
+    pub documents: usize,
+    /// Documents whose health reaches the gate
+    pub ready: usize,
+    /// Documents that reach the gate but still have blocking stubs
+    pub blocked: usize,
+}
+
+/// Open stubs under one name (type, family or assignee)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BacklogCount {
+    pub name: String,
+    pub stubs: usize,
+    pub blocking: usize,
+}
+
+/// Open stubs across the vault, largest groups first
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StubBacklog {
+    pub total: usize,
+    pub by_type: Vec<BacklogCount>,
+    pub by_family: Vec<BacklogCount>,
+    /// Stubs without assignees are counted as `unassigned`
+    pub by_assignee: Vec<BacklogCount>,
+}
+
+/// A document not touched within its form cadence
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleDocument {
+    pub path: String,
+    pub title: Option<String>,
+    pub form: String,
+    pub cadence_days: u32,
+    pub days_since_modified: i64,
+    /// Days since modified over the cadence; above 1 is stale
+    pub staleness_ratio: f64,
+}
+
+/// A document to look at first, with the reasons why
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttentionItem {
+    pub path: String,
+    pub title: Option<String>,
+    pub audience: String,
+    pub health: f64,
+    pub blocking_stubs: usize,
+    pub reasons: Vec<String>,
+}
+
+/// Vault health report
+///
+/// Sections that were not requested are `None`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultReport {
+    /// Vault name or path
+    pub vault: String,
+    /// Time the report describes
+    pub generated: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<ReportSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<HealthDistribution>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gates: Option<Vec<GateReadiness>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stubs: Option<StubBacklog>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale: Option<Vec<StaleDocument>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attention: Option<Vec<AttentionItem>>,
+}
+
+impl VaultReport {
+    /// Build the report for indexed `documents` as of `now`, e.g. the
+    /// walked ones from [`VaultIndex::documents`](crate::VaultIndex::documents)
+    pub fn build(
+        vault: impl Into<String>,
+        documents: &[(&str, &IndexEntry)],
+        config: &CalculationConfig,
+        now: DateTime<Utc>,
+        options: &ReportOptions,
+    ) -> Self {
+        let documents = documents.to_vec();
+        let wants = |section| options.sections.contains(&section);
+        let stale = stale_documents(&documents, config, now, options.file_times);
+
+        Self {
+            vault: vault.into(),
+            generated: now,
+            summary: wants(ReportSection::Summary).then(|| summary(&documents)),
+            health: wants(ReportSection::Health).then(|| health_distribution(&documents)),
+            gates: wants(ReportSection::Gates).then(|| gate_readiness(&documents, config)),
+            stubs: wants(ReportSection::Stubs).then(|| stub_backlog(&documents, config)),
+            attention: wants(ReportSection::Attention)
+                .then(|| attention_items(&documents, &stale, config, options.limit)),
+            stale: wants(ReportSection::Stale)
+                .then(|| stale.into_iter().take(options.limit).collect()),
+        }
+    }
+
+    /// Sections present in the report, in report order
+    pub fn sections(&self) -> Vec<ReportSection> {
+        ReportSection::ALL
+            .into_iter()
+            .filter(|section| match section {
+                ReportSection::Summary => self.summary.is_some(),
+                ReportSection::Health => self.health.is_some(),
+                ReportSection::Gates => self.gates.is_some(),
+                ReportSection::Stubs => self.stubs.is_some(),
+                ReportSection::Stale => self.stale.is_some(),
+                ReportSection::Attention => self.attention.is_some(),
+            })
+            .collect()
+    }
+
+    /// Rows of a section as a table, header first
+    ///
+    /// Returns `None` when the section is not in the report.
+    pub fn table(&self, section: ReportSection) -> Option<Vec<Vec<String>>> {
+        let header = |names: &[&str]| vec![names.iter().map(|n| n.to_string()).collect()];
+        match section {
+            ReportSection::Summary => {
+                let s = self.summary.as_ref()?;
+                let mut rows = header(&["metric", "value"]);
+                for (metric, value) in [
+                    ("documents", s.documents.to_string()),
+                    ("average_health", number(s.average_health)),
+                    ("average_refinement", number(s.average_refinement)),
+                    ("stubs", s.stubs.to_string()),
+                    ("blocking_stubs", s.blocking_stubs.to_string()),
+                    ("documents_with_blocking", s.documents_with_blocking.to_string()),
+                    ("partial", s.partial.to_string()),
+                ] {
+                    rows.push(vec![metric.to_string(), value]);
+                }
+                Some(rows)
+            }
+            ReportSection::Health => {
+                let h = self.health.as_ref()?;
+                let mut rows = header(&["group", "range", "documents", "average_health"]);
+                for b in &h.buckets {
+                    let range = format!("{:.1}-{:.1}", b.low, b.high);
+                    rows.push(vec!["health".into(), range, b.documents.to_string(), String::new()]);
+                }
+                for a in &h.by_audience {
+                    rows.push(vec![
+                        "audience".into(),
+                        a.audience.clone(),
+                        a.documents.to_string(),
+                        number(a.average_health),
+                    ]);
+                }
+                Some(rows)
+            }
+            ReportSection::Gates => {
+                let mut rows = header(&["audience", "gate", "documents", "ready", "blocked"]);
+                for g in self.gates.as_ref()? {
+                    rows.push(vec![
+                        g.audience.clone(),
+                        number(g.gate),
+                        g.documents.to_string(),
+                        g.ready.to_string(),
+                        g.blocked.to_string(),
+                    ]);
+                }
+                Some(rows)
+            }
+            ReportSection::Stubs => {
+                let backlog = self.stubs.as_ref()?;
+                let mut rows = header(&["group", "name", "stubs", "blocking"]);
+                for (group, counts) in [
+                    ("type", &backlog.by_type),
+                    ("family", &backlog.by_family),
+                    ("assignee", &backlog.by_assignee),
+                ] {
+                    for c in counts {
+                        rows.push(vec![
+                            group.to_string(),
+                            c.name.clone(),
+                            c.stubs.to_string(),
+                            c.blocking.to_string(),
+                        ]);
+                    }
+                }
+                Some(rows)
+            }
+            ReportSection::Stale => {
+                let mut rows = header(&[
+                    "path",
+                    "title",
+                    "form",
+                    "cadence_days",
+                    "days_since_modified",
+                    "staleness_ratio",
+                ]);
+                for d in self.stale.as_ref()? {
+                    rows.push(vec![
+                        d.path.clone(),
+                        d.title.clone().unwrap_or_default(),
+                        d.form.clone(),
+                        d.cadence_days.to_string(),
+                        d.days_since_modified.to_string(),
+                        number(d.staleness_ratio),
+                    ]);
+                }
+                Some(rows)
+            }
+            ReportSection::Attention => {
+                let mut rows =
+                    header(&["path", "title", "audience", "health", "blocking_stubs", "reasons"]);
+                for item in self.attention.as_ref()? {
+                    rows.push(vec![
+                        item.path.clone(),
+                        item.title.clone().unwrap_or_default(),
+                        item.audience.clone(),
+                        number(item.health),
+                        item.blocking_stubs.to_string(),
+                        item.reasons.join("; "),
+                    ]);
+                }
+                Some(rows)
+            }
+        }
+    }
+
+    /// A section as CSV, or `None` when it is not in the report
+    pub fn csv(&self, section: ReportSection) -> Option<String> {
+        let rows = self.table(section)?;
+        let mut out = String::new();
+        for row in rows {
+            let cells: Vec<String> = row.iter().map(|c| csv_cell(c)).collect();
+            out.push_str(&cells.join(","));
+            out.push('\n');
+        }
+        Some(out)
+    }
+
+    /// The report as a Markdown note, with frontmatter so it can live in
+    /// the vault
+    pub fn markdown(&self) -> String {
+        let date = self.generated.format("%Y-%m-%d");
+        let mut out = format!(
+            "---\ntitle: Vault Health Report {}\ncreated: {}\nform: transient\n\
+             audience: internal\ntags: [doc-doctor/report]\n---\n\n",
+            date,
+            self.generated.format("%Y-%m-%dT%H:%M:%SZ"),
+        );
+        out.push_str(&format!("# Vault Health Report: {}\n\n", self.vault));
+        out.push_str(&format!("Generated {}\n", self.generated.format("%Y-%m-%d %H:%M UTC")));
+
+        for section in self.sections() {
+            out.push_str(&format!("\n## {}\n\n", section.title()));
+            match section {
+                ReportSection::Stale if self.stale.as_ref().is_some_and(Vec::is_empty) => {
+                    out.push_str("No stale documents.\n");
+                }
+                ReportSection::Attention if self.attention.as_ref().is_some_and(Vec::is_empty) => {
+                    out.push_str("Nothing needs attention.\n");
+                }
+                _ => {
+                    let mut rows = self.table(section).unwrap_or_default();
+                    if matches!(section, ReportSection::Stale | ReportSection::Attention) {
+                        for row in rows.iter_mut().skip(1) {
+                            row[0] = wiki_link(&row[0]);
+                        }
+                    }
+                    out.push_str(&markdown_table(&rows));
+                }
+            }
+        }
+        out
+    }
+
+    /// The report as a self-contained HTML page
+    pub fn html(&self) -> String {
+        let mut body = format!(
+            "<h1>Vault Health Report: {}</h1>\n<p class=\"meta\">Generated {}</p>\n",
+            escape_html(&self.vault),
+            self.generated.format("%Y-%m-%d %H:%M UTC")
+        );
+
+        for section in self.sections() {
+            body.push_str(&format!("<section id=\"{}\">\n<h2>{}</h2>\n", section, section.title()));
+            match section {
+                ReportSection::Summary => {
+                    if let Some(s) = &self.summary {
+                        body.push_str("<div class=\"cards\">\n");
+                        for (label, value) in [
+                            ("Documents", s.documents.to_string()),
+                            ("Average health", percent(s.average_health)),
+                            ("Open stubs", s.stubs.to_string()),
+                            ("Blocking stubs", s.blocking_stubs.to_string()),
+                        ] {
+                            body.push_str(&format!(
+                                "<div class=\"card\"><span>{}</span><strong>{}</strong></div>\n",
+                                label, value
+                            ));
+                        }
+                        body.push_str("</div>\n");
+                    }
+                }
+                ReportSection::Health => {
+                    if let Some(h) = &self.health {
+                        let max = h.buckets.iter().map(|b| b.documents).max().unwrap_or(0).max(1);
+                        body.push_str("<table class=\"bars\">\n");
+                        for b in &h.buckets {
+                            body.push_str(&format!(
+                                "<tr><th>{:.0}–{:.0}%</th><td><div class=\"bar {}\" \
+                                 style=\"width: {:.0}%\"></div></td><td>{}</td></tr>\n",
+                                b.low * 100.0,
+                                b.high * 100.0,
+                                health_class(b.low),
+                                b.documents as f64 / max as f64 * 100.0,
+                                b.documents
+                            ));
+                        }
+                        body.push_str("</table>\n");
+                    }
+                    body.push_str(&self.html_table(section));
+                }
+                ReportSection::Stale if self.stale.as_ref().is_some_and(Vec::is_empty) => {
+                    body.push_str("<p>No stale documents.</p>\n");
+                }
+                ReportSection::Attention if self.attention.as_ref().is_some_and(Vec::is_empty) => {
+                    body.push_str("<p>Nothing needs attention.</p>\n");
+                }
+                _ => body.push_str(&self.html_table(section)),
+            }
+            body.push_str("</section>\n");
+        }
+
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Vault Health Report: {}</title>\n<style>{}</style>\n</head>\n\
+             <body>\n{}</body>\n</html>\n",
+            escape_html(&self.vault),
+            HTML_STYLE,
+            body
+        )
+    }
+
+    /// A section table in HTML; health ranges are drawn as bars instead
+    fn html_table(&self, section: ReportSection) -> String {
+        let Some(rows) = self.table(section) else {
+            return String::new();
+        };
+        let mut out = String::from("<table>\n<tr>");
+        for cell in &rows[0] {
+            out.push_str(&format!("<th>{}</th>", escape_html(&cell.replace('_', " "))));
+        }
+        out.push_str("</tr>\n");
+        for row in rows.iter().skip(1) {
+            if section == ReportSection::Health && row[0] == "health" {
+                continue;
+            }
+            out.push_str("<tr>");
+            for cell in row {
+                out.push_str(&format!("<td>{}</td>", escape_html(cell)));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n");
+        out
+    }
+}
+
+const HTML_STYLE: &str = "
+body { font-family: system-ui, sans-serif; max-width: 960px; margin: 2rem auto; color: #222; }
+h1 { font-size: 1.6rem; } h2 { font-size: 1.2rem; margin-top: 2rem; }
+.meta { color: #666; }
+table { border-collapse: collapse; width: 100%; margin: 0.5rem 0; }
+th, td { text-align: left; padding: 0.3rem 0.6rem; border-bottom: 1px solid #ddd; }
+.cards { display: flex; gap: 1rem; }
+.card { flex: 1; border: 1px solid #ddd; border-radius: 6px; padding: 0.8rem; }
+.card span { display: block; color: #666; font-size: 0.85rem; }
+.card strong { font-size: 1.5rem; }
+.bars td:nth-child(2) { width: 70%; }
+.bar { height: 1rem; border-radius: 3px; min-width: 2px; }
+.bar.low { background: #d9534f; }
+.bar.mid { background: #f0ad4e; }
+.bar.high { background: #5cb85c; }
+";
+
+fn health_class(low: f64) -> &'static str {
+    if low >= 0.8 {
+        "high"
+    } else if low >= 0.5 {
+        "mid"
+    } else {
+        "low"
+    }
+}
+
+fn summary(documents: &[(&str, &IndexEntry)]) -> ReportSummary {
+    let count = documents.len();
+    let average = |value: &dyn Fn(&IndexEntry) -> f64| {
+        if count == 0 {
+            0.0
+        } else {
+            documents.iter().map(|(_, e)| value(e)).sum::<f64>() / count as f64
+        }
+    };
+    let blocking = |e: &IndexEntry| e.properties.stubs.iter().filter(|s| s.is_blocking()).count();
+
+    ReportSummary {
+        documents: count,
+        average_health: average(&|e| e.dimensions.health),
+        average_refinement: average(&|e| e.properties.refinement.value()),
+        stubs: documents.iter().map(|(_, e)| e.properties.stubs.len()).sum(),
+        blocking_stubs: documents.iter().map(|(_, e)| blocking(e)).sum(),
+        documents_with_blocking: documents.iter().filter(|(_, e)| blocking(e) > 0).count(),
+        partial: documents.iter().filter(|(_, e)| e.partial).count(),
+    }
+}
+
+fn health_distribution(documents: &[(&str, &IndexEntry)]) -> HealthDistribution {
+    let last = HEALTH_BUCKETS.len() - 1;
+    let buckets = HEALTH_BUCKETS
+        .iter()
+        .enumerate()
+        .map(|(i, &(low, high))| HealthBucket {
+            low,
+            high,
+            documents: documents
+                .iter()
+                .filter(|(_, e)| {
+                    let health = e.dimensions.health;
+                    health >= low && (health < high || (i == last && health <= high))
+                })
+                .count(),
+        })
+        .collect();
+
+    let by_audience = AUDIENCES
+        .iter()
+        .filter_map(|audience| {
+            let healths: Vec<f64> = documents
+                .iter()
+                .filter(|(_, e)| e.properties.audience == *audience)
+                .map(|(_, e)| e.dimensions.health)
+                .collect();
+            (!healths.is_empty()).then(|| AudienceHealth {
+                audience: audience.to_string(),
+                documents: healths.len(),
+                average_health: healths.iter().sum::<f64>() / healths.len() as f64,
+            })
+        })
+        .collect();
+
+    HealthDistribution { buckets, by_audience }
+}
+
+fn gate_readiness(
+    documents: &[(&str, &IndexEntry)],
+    config: &CalculationConfig,
+) -> Vec<GateReadiness> {
+    AUDIENCES
+        .iter()
+        .filter_map(|audience| {
+            let gate = config.audience_gates.get(&audience.to_string())?;
+            let members: Vec<&IndexEntry> = documents
+                .iter()
+                .filter(|(_, e)| e.properties.audience == *audience)
+                .map(|(_, e)| *e)
+                .collect();
+            if members.is_empty() {
+                return None;
+            }
+            let ready: Vec<_> = members.iter().filter(|e| e.dimensions.health >= gate).collect();
+            Some(GateReadiness {
+                audience: audience.to_string(),
+                gate,
+                documents: members.len(),
+                ready: ready.len(),
+                blocked: ready.iter().filter(|e| e.properties.has_blocking_stubs()).count(),
+            })
+        })
+        .collect()
+}
+
+fn stub_backlog(documents: &[(&str, &IndexEntry)], config: &CalculationConfig) -> StubBacklog {
+    let mut by_type: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    let mut by_family: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    let mut by_assignee: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    let mut total = 0;
+
+    let count = |map: &mut BTreeMap<String, (usize, usize)>, name: &str, blocking: bool| {
+        let entry = map.entry(name.to_string()).or_default();
+        entry.0 += 1;
+        entry.1 += usize::from(blocking);
+    };
+    for (_, entry) in documents {
+        for stub in &entry.properties.stubs {
+            total += 1;
+            let blocking = stub.is_blocking();
+            count(&mut by_type, stub.stub_type.as_str(), blocking);
+            let family = config.stub_types.family(&stub.stub_type).display_name().to_lowercase();
+            count(&mut by_family, &family, blocking);
+            if stub.assignees.is_empty() {
+                count(&mut by_assignee, "unassigned", blocking);
+            }
+            for assignee in &stub.assignees {
+                count(&mut by_assignee, assignee, blocking);
+            }
+        }
+    }
+
+    let ranked = |map: BTreeMap<String, (usize, usize)>| {
+        let mut counts: Vec<BacklogCount> = map
+            .into_iter()
+            .map(|(name, (stubs, blocking))| BacklogCount { name, stubs, blocking })
+            .collect();
+        // Stable sort keeps names alphabetical within equal counts
+        counts.sort_by_key(|c| std::cmp::Reverse(c.stubs));
+        counts
+    };
+    StubBacklog {
+        total,
+        by_type: ranked(by_type),
+        by_family: ranked(by_family),
+        by_assignee: ranked(by_assignee),
+    }
+}
+
+/// Documents past their form cadence, most overdue first
+fn stale_documents(
+    documents: &[(&str, &IndexEntry)],
+    config: &CalculationConfig,
+    now: DateTime<Utc>,
+    file_times: bool,
+) -> Vec<StaleDocument> {
+    let tz = config.vault.tz();
+    let mut stale: Vec<StaleDocument> = documents
+        .iter()
+        .filter_map(|(path, entry)| {
+            let props = &entry.properties;
+            let cadence = config.form_cadences.get(&props.form.to_string())?;
+            let file_time = entry.modified.filter(|_| file_times);
+            let touched = props
+                .last_touched(tz)
+                .or_else(|| DateTime::from_timestamp(file_time?, 0))?;
+            let days = (now - touched).num_days();
+            let ratio = days as f64 / f64::from(cadence.max(1));
+            (ratio > 1.0).then(|| StaleDocument {
+                path: path.to_string(),
+                title: props.title.clone(),
+                form: props.form.to_string(),
+                cadence_days: cadence,
+                days_since_modified: days,
+                staleness_ratio: ratio,
+            })
+        })
+        .collect();
+    stale.sort_by(|a, b| b.staleness_ratio.total_cmp(&a.staleness_ratio));
+    stale
+}
+
+/// Documents with blocking stubs, below their audience gate or stale;
+/// blocking stubs first, then lowest health
+fn attention_items(
+    documents: &[(&str, &IndexEntry)],
+    stale: &[StaleDocument],
+    config: &CalculationConfig,
+    limit: usize,
+) -> Vec<AttentionItem> {
+    let mut items: Vec<AttentionItem> = documents
+        .iter()
+        .filter_map(|(path, entry)| {
+            let props = &entry.properties;
+            let health = entry.dimensions.health;
+            let audience = props.audience.to_string();
+            let blocking = props.stubs.iter().filter(|s| s.is_blocking()).count();
+
+            let mut reasons = Vec::new();
+            if blocking > 0 {
+                reasons.push(format!("{} blocking stub(s)", blocking));
+            }
+            if let Some(gate) = config.audience_gates.get(&audience).filter(|g| health < *g) {
+                reasons.push(format!("below {} gate ({:.2} < {:.2})", audience, health, gate));
+            }
+            if let Some(s) = stale.iter().find(|s| s.path == *path) {
+                let days = s.days_since_modified;
+                reasons.push(format!("stale ({} days, cadence {})", days, s.cadence_days));
+            }
+            if entry.partial {
+                reasons.push("frontmatter partially parsed".to_string());
+            }
+
+            (!reasons.is_empty()).then(|| AttentionItem {
+                path: path.to_string(),
+                title: props.title.clone(),
+                audience,
+                health,
+                blocking_stubs: blocking,
+                reasons,
+            })
+        })
+        .collect();
+    items.sort_by(|a, b| {
+        b.blocking_stubs.cmp(&a.blocking_stubs).then(a.health.total_cmp(&b.health))
+    });
+    items.truncate(limit);
+    items
+}
+
+fn number(value: f64) -> String {
+    format!("{:.2}", value)
+}
+
+fn percent(value: f64) -> String {
+    format!("{:.0}%", value * 100.0)
+}
+
+fn csv_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Link to a vault document from a note in the same vault
+fn wiki_link(path: &str) -> String {
+    format!("[[{}]]", path.strip_suffix(".md").unwrap_or(path))
+}
+
+fn markdown_table(rows: &[Vec<String>]) -> String {
+    let Some((header, body)) = rows.split_first() else {
+        return String::new();
+    };
+    let line = |cells: &[String]| {
+        let cells: Vec<String> = cells.iter().map(|c| c.replace('|', "\\|")).collect();
+        format!("| {} |\n", cells.join(" | "))
+    };
+    let mut out = line(header);
+    out.push_str(&format!("|{}\n", " --- |".repeat(header.len())));
+    for row in body {
+        out.push_str(&line(row));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{Path, PathBuf};
+
+    use chrono::TimeZone;
+    use doc_doctor_domain::{Form, L1Properties, Refinement, StateDimensions, Stub, StubForm};
+
+    use crate::index::VaultIndex;
+
+    fn entry(audience: Audience, refinement: f64, stubs: Vec<Stub>) -> IndexEntry {
+        let properties = L1Properties {
+            audience,
+            form: Form::Transient,
+            refinement: Refinement::new_clamped(refinement),
+            stubs,
+            ..Default::default()
+        };
+        IndexEntry {
+            modified: Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap().timestamp()),
+            size: 0,
+            hash: String::new(),
+            canvas: false,
+            dimensions: StateDimensions::calculate(&properties),
+            properties,
+            diagnostics: Vec::new(),
+            partial: false,
+            anchors: Vec::new(),
+            links: Vec::new(),
+            warning: None,
+        }
+    }
+
+    #[test]
+    fn test_vault_report() {
+        let mut blocking = Stub::compact("source", "needs a citation");
+        blocking.stub_form = StubForm::Blocking;
+        blocking.assignees = vec!["ana".to_string()];
+        let mut index = VaultIndex::new();
+        index.entries.insert("a.md".into(), entry(Audience::Public, 0.95, vec![blocking]));
+        let expand = Stub::compact("expand", "more");
+        index.entries.insert("b.md".into(), entry(Audience::Public, 0.3, vec![expand]));
+        // Left the walk, e.g. newly ignored
+        index.entries.insert("old.md".into(), entry(Audience::Public, 0.1, Vec::new()));
+        let walked = [PathBuf::from("a.md"), PathBuf::from("b.md")];
+        let documents = index.documents(Path::new(""), &walked);
+
+        let config = CalculationConfig::default();
+        let now = Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap();
+        let options = ReportOptions::default();
+        let report = VaultReport::build("vault", &documents, &config, now, &options);
+
+        let summary = report.summary.as_ref().unwrap();
+        assert_eq!((summary.documents, summary.stubs, summary.blocking_stubs), (2, 2, 1));
+        let gates = report.gates.as_ref().unwrap();
+        assert_eq!((gates[0].audience.as_str(), gates[0].documents), ("public", 2));
+        let stubs = report.stubs.as_ref().unwrap();
+        let assignees: Vec<_> = stubs.by_assignee.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(assignees, ["ana", "unassigned"]);
+        assert_eq!(report.stale.as_ref().unwrap().len(), 2);
+        let attention = report.attention.as_ref().unwrap();
+        assert_eq!(attention[0].path, "a.md");
+
+        let csv = report.csv(ReportSection::Stubs).unwrap();
+        assert!(csv.starts_with("group,name,stubs,blocking\n"));
+        assert!(report.markdown().contains("| [[a]] |"));
+        assert!(report.html().contains("<h2>Stub Backlog</h2>"));
+
+        let options = ReportOptions { file_times: false, ..Default::default() };
+        let report = VaultReport::build("vault", &documents, &config, now, &options);
+        assert!(report.stale.as_ref().unwrap().is_empty());
+
+        let options = ReportOptions {
+            sections: vec![ReportSection::Gates],
+            ..Default::default()
+        };
+        let report = VaultReport::build("vault", &documents, &config, now, &options);
+        assert_eq!(report.sections(), [ReportSection::Gates]);
+        assert!(report.csv(ReportSection::Summary).is_none());
+        assert_eq!("STALE".parse::<ReportSection>(), Ok(ReportSection::Stale));
+    }
+}
@@ -35,13 +35,25 @@
 
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
 use doc_doctor_domain::{
-    calculate_health, calculate_usefulness, Audience, DocumentAnalysis, DocumentParser,
-    DocumentWriter, L1Properties, ParseError, SchemaProvider, SerializeError, StateDimensions,
-    Stub, StubContext, StubForm, StubType, Usefulness, ValidationResult, VectorPhysics,
+    calculate_health_with_config, calculate_usefulness_with_config, Annotation, AnnotationKind,
+    Audience, BodyParser, CanvasNodeKind, CanvasParser,
+    CalculationConfig, ConfigError, ConfigProvider, ConfigValidationError, DocumentAnalysis, DocumentParser,
+    DocumentWriter, L1Properties, MarkdownBody, ParseError, RecoveredParse, SchemaProvider, SerializeError,
+    StateDimensions,
+    Stub, StubContext, StubForm, StubType, Timestamp, Usefulness, ValidationResult, VectorPhysics,
 };
 
+use crate::config::ConfigHandle;
 use crate::error::ApplicationError;
+use crate::annotations::stub_from_annotation;
+use crate::canvas::{card_stubs, CanvasAnalysis, CanvasCard};
+use crate::links::LinkEdge;
+use crate::detect::{detect_stubs, DetectorOptions, StubSuggestion};
+use crate::edit::set_metadata_value;
+use crate::inline::{same_stub, stubs_from_tasks, InlineMetadataParser};
+use crate::markers::{matching_stub, stubs_from_markers, SyncStatus};
 use crate::use_cases::{AnalyzeDocumentUseCase, ValidateDocumentUseCase};
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -59,6 +71,8 @@ pub enum SwitchboardError {
     Validation(String),
     /// Stub operation error
     StubOperation(String),
+    /// Configuration error
+    Config(String),
     /// Generic operation error
     Operation(String),
 }
@@ -70,6 +84,7 @@ impl std::fmt::Display for SwitchboardError {
             Self::Serialize(msg) => write!(f, "Serialize error: {}", msg),
             Self::Validation(msg) => write!(f, "Validation error: {}", msg),
             Self::StubOperation(msg) => write!(f, "Stub operation error: {}", msg),
+            Self::Config(msg) => write!(f, "Configuration error: {}", msg),
             Self::Operation(msg) => write!(f, "Operation error: {}", msg),
         }
     }
@@ -89,6 +104,18 @@ impl From<SerializeError> for SwitchboardError {
     }
 }
 
+impl From<ConfigError> for SwitchboardError {
+    fn from(e: ConfigError) -> Self {
+        Self::Config(e.to_string())
+    }
+}
+
+impl From<ConfigValidationError> for SwitchboardError {
+    fn from(e: ConfigValidationError) -> Self {
+        Self::Config(e.to_string())
+    }
+}
+
 impl From<ApplicationError> for SwitchboardError {
     fn from(e: ApplicationError) -> Self {
         Self::Operation(e.to_string())
@@ -133,6 +160,30 @@ pub struct StubAddResult {
     pub stub: Stub,
 }
 
+/// Result of importing task checkboxes or annotations as stubs
+#[derive(Debug, Clone)]
+pub struct StubImportResult {
+    /// Updated document content
+    pub updated_content: String,
+    /// Stubs added to frontmatter
+    pub imported: Vec<Stub>,
+    /// Tasks or annotations already covered by an existing stub
+    pub skipped: usize,
+}
+
+/// Result of syncing stub markers into frontmatter
+#[derive(Debug, Clone)]
+pub struct StubSyncResult {
+    /// Updated document content
+    pub updated_content: String,
+    /// Stubs added for markers that had none
+    pub created: Vec<Stub>,
+    /// Existing stubs that took a marker's anchor, type or text
+    pub updated: Vec<Stub>,
+    /// Sync status after the update
+    pub status: SyncStatus,
+}
+
 /// Result of resolving a stub
 #[derive(Debug, Clone)]
 pub struct StubResolveResult {
@@ -191,6 +242,18 @@ pub struct StubFilter {
     pub priority: Option<String>,
 }
 
+impl StubFilter {
+    /// Whether `stub` passes the filter
+    pub fn matches(&self, stub: &Stub) -> bool {
+        self.stub_type.as_ref().is_none_or(|t| stub.stub_type.as_str() == t)
+            && (!self.blocking_only || matches!(stub.stub_form, StubForm::Blocking))
+            && self
+                .priority
+                .as_ref()
+                .is_none_or(|p| stub.priority.to_string().to_lowercase() == p.to_lowercase())
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 //                          SWITCHBOARD TRAIT
 // ═══════════════════════════════════════════════════════════════════════════
@@ -212,9 +275,20 @@ pub trait Switchboard: Send + Sync {
     /// Parse document content, extract L1 properties
     fn parse_document(&self, content: &str) -> Result<L1Properties, SwitchboardError>;
 
+    /// Parse document content, skipping malformed fields and stubs
+    ///
+    /// Each skipped entry is reported as a positioned diagnostic.
+    fn parse_document_recovering(&self, content: &str) -> Result<RecoveredParse, SwitchboardError>;
+
     /// Full analysis: parse + L2 dimensions
     fn analyze_document(&self, content: &str) -> Result<DocumentAnalysis, SwitchboardError>;
 
+    /// Analyze an Obsidian canvas
+    ///
+    /// `content` is the canvas JSON, optionally preceded by frontmatter from
+    /// its sidecar. Stubs on text cards are added to the canvas's own.
+    fn analyze_canvas(&self, content: &str) -> Result<CanvasAnalysis, SwitchboardError>;
+
     /// Validate frontmatter against schema
     fn validate_document(
         &self,
@@ -229,7 +303,13 @@ pub trait Switchboard: Send + Sync {
         filter: Option<StubFilter>,
     ) -> Result<Vec<Stub>, SwitchboardError>;
 
+    /// Parse the document body: headings, block IDs, links, footnotes and code
+    fn parse_body(&self, content: &str) -> Result<MarkdownBody, SwitchboardError>;
+
     /// Find stub anchors in content
+    ///
+    /// Anchors are block IDs in the body; `^` inside frontmatter, code,
+    /// math and footnote references is ignored.
     fn find_stub_anchors(&self, content: &str) -> Result<AnchorMatches, SwitchboardError>;
 
     // ═══════════════════════════════════════════════════════════════
@@ -270,6 +350,65 @@ pub trait Switchboard: Send + Sync {
         anchor_id: &str,
     ) -> Result<AnchorLinkResult, SwitchboardError>;
 
+    /// Move open task checkboxes into frontmatter stubs
+    ///
+    /// Tasks an existing stub already covers are skipped. With `remove`, the
+    /// task lines are deleted from the body once imported.
+    fn import_tasks(&self, content: &str, remove: bool) -> Result<StubImportResult, SwitchboardError>;
+
+    /// Turn labeled comments and highlights into frontmatter stubs
+    ///
+    /// `%% verify: check the date %%` becomes a `verify` stub; labels that
+    /// are not stub types are left alone. With `remove`, converted comments
+    /// are deleted and converted highlights are replaced by their text.
+    fn import_annotations(
+        &self,
+        content: &str,
+        remove: bool,
+    ) -> Result<StubImportResult, SwitchboardError>;
+
+    /// Compare body stub markers with frontmatter stubs, in both directions
+    fn stub_sync_status(&self, content: &str) -> Result<SyncStatus, SwitchboardError>;
+
+    /// Create or update frontmatter stubs from body stub markers
+    ///
+    /// A marker without a stub gets one, carrying the marker's block ID as
+    /// its anchor. A stub matched by anchor takes the marker's type and
+    /// text; one matched by description takes its anchor. The body is left
+    /// as it is.
+    fn sync_stub_markers(&self, content: &str) -> Result<StubSyncResult, SwitchboardError>;
+
+    /// Propose stubs for gaps found in the body
+    ///
+    /// Nothing is written. Gaps an existing stub already covers, by anchor
+    /// or description, are left out.
+    fn suggest_stubs(
+        &self,
+        content: &str,
+        options: &DetectorOptions,
+    ) -> Result<Vec<StubSuggestion>, SwitchboardError>;
+
+    /// Add reviewed suggestions to frontmatter
+    ///
+    /// Suggested anchors are appended to their lines as block IDs, so the
+    /// stubs stay attached to the text they are about.
+    fn apply_stub_suggestions(
+        &self,
+        content: &str,
+        suggestions: &[StubSuggestion],
+    ) -> Result<StubImportResult, SwitchboardError>;
+
+    /// Set `modified` to `now`
+    ///
+    /// An existing value keeps its style (date only, local time, offset);
+    /// a new one is written as RFC 3339. Only the `modified` line changes,
+    /// so comments and unknown keys are kept.
+    fn touch_modified(
+        &self,
+        content: &str,
+        now: DateTime<Utc>,
+    ) -> Result<String, SwitchboardError>;
+
     // ═══════════════════════════════════════════════════════════════
     //                     CALCULATIONS
     // ═══════════════════════════════════════════════════════════════
@@ -286,6 +425,19 @@ pub trait Switchboard: Send + Sync {
     /// Calculate vector physics for stub prioritization
     fn calc_vector_physics(&self, stub: &Stub, context: &StubContext) -> VectorPhysics;
 
+    // ═══════════════════════════════════════════════════════════════
+    //                     CONFIGURATION
+    // ═══════════════════════════════════════════════════════════════
+
+    /// Snapshot of the active calculation configuration
+    fn config(&self) -> Arc<CalculationConfig>;
+
+    /// Replace the active configuration at runtime (validated first)
+    fn set_config(&self, config: CalculationConfig) -> Result<(), SwitchboardError>;
+
+    /// Re-read the configuration from the attached provider
+    fn reload_config(&self) -> Result<Arc<CalculationConfig>, SwitchboardError>;
+
     // ═══════════════════════════════════════════════════════════════
     //                     INFO/SCHEMA
     // ═══════════════════════════════════════════════════════════════
@@ -297,6 +449,30 @@ pub trait Switchboard: Send + Sync {
     fn get_stubs_schema(&self) -> &str;
 }
 
+/// Delete a comment, or unwrap a highlight, from `content`
+///
+/// A comment on a line of its own takes the line with it; one at the end of
+/// a line takes the space before it.
+fn remove_annotation(content: &mut String, annotation: &Annotation) {
+    let start = annotation.position.offset;
+    let end = annotation.end_offset;
+    if annotation.kind == AnnotationKind::Highlight {
+        content.replace_range(start..end, &annotation.text);
+        return;
+    }
+
+    let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = content[end..].find('\n').map_or(content.len(), |i| end + i + 1);
+    if content[line_start..start].trim().is_empty() && content[end..line_end].trim().is_empty() {
+        content.replace_range(line_start..line_end, "");
+    } else if content[end..line_end].trim().is_empty() {
+        let before = content[..start].trim_end_matches([' ', '\t']).len();
+        content.replace_range(before..end, "");
+    } else {
+        content.replace_range(start..end, "");
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 //                       SWITCHBOARD IMPLEMENTATION
 // ═══════════════════════════════════════════════════════════════════════════
@@ -313,6 +489,10 @@ where
     parser: Arc<P>,
     writer: Arc<W>,
     schema_provider: Arc<S>,
+    config: ConfigHandle,
+    config_provider: Option<Arc<dyn ConfigProvider>>,
+    body_parser: Option<Arc<dyn BodyParser>>,
+    canvas_parser: Option<Arc<dyn CanvasParser>>,
     analyze_use_case: AnalyzeDocumentUseCase,
     validate_use_case: ValidateDocumentUseCase,
 }
@@ -324,21 +504,95 @@ where
     S: SchemaProvider + 'static,
 {
     /// Create a new application switchboard
+    ///
+    /// Calculations use the built-in defaults until a configuration is
+    /// injected with [`with_config`](Self::with_config) or loaded from a
+    /// provider with [`Switchboard::reload_config`].
     pub fn new(parser: Arc<P>, writer: Arc<W>, schema_provider: Arc<S>) -> Self {
         let parser_dyn: Arc<dyn DocumentParser> = Arc::clone(&parser) as Arc<dyn DocumentParser>;
         let schema_dyn: Arc<dyn SchemaProvider> = Arc::clone(&schema_provider) as Arc<dyn SchemaProvider>;
+        let config = ConfigHandle::defaults();
 
         Self {
             parser: Arc::clone(&parser),
             writer,
             schema_provider: Arc::clone(&schema_provider),
-            analyze_use_case: AnalyzeDocumentUseCase::new(parser_dyn),
+            config: config.clone(),
+            config_provider: None,
+            body_parser: None,
+            canvas_parser: None,
+            analyze_use_case: AnalyzeDocumentUseCase::new(parser_dyn).with_config(config.clone()),
             validate_use_case: ValidateDocumentUseCase::new(
                 Arc::clone(&parser) as Arc<dyn DocumentParser>,
                 schema_dyn,
-            ),
+            )
+            .with_config(config),
+        }
+    }
+
+    /// Share a configuration handle with this switchboard and its use cases
+    pub fn with_config(self, config: ConfigHandle) -> Self {
+        let switchboard = Self {
+            validate_use_case: self.validate_use_case.with_config(config.clone()),
+            config,
+            ..self
+        };
+        switchboard.with_reading_parser()
+    }
+
+    /// Attach the provider that [`Switchboard::reload_config`] reads from
+    pub fn with_config_provider(mut self, provider: Arc<dyn ConfigProvider>) -> Self {
+        self.config_provider = Some(provider);
+        self
+    }
+
+    /// Attach the parser behind [`Switchboard::parse_body`] and anchor detection
+    ///
+    /// Analysis and stub listing also pick up Dataview inline fields and
+    /// Tasks checkboxes once a body parser is attached, if the vault
+    /// configuration enables them.
+    pub fn with_body_parser(mut self, parser: Arc<dyn BodyParser>) -> Self {
+        self.body_parser = Some(parser);
+        self.with_reading_parser()
+    }
+
+    /// Attach the parser behind [`Switchboard::analyze_canvas`]
+    pub fn with_canvas_parser(mut self, parser: Arc<dyn CanvasParser>) -> Self {
+        self.canvas_parser = Some(parser);
+        self
+    }
+
+    /// Parser for read paths: frontmatter plus inline metadata
+    fn reading_parser(&self) -> Arc<dyn DocumentParser> {
+        let parser = Arc::clone(&self.parser) as Arc<dyn DocumentParser>;
+        match &self.body_parser {
+            Some(body_parser) => Arc::new(InlineMetadataParser::new(
+                parser,
+                Arc::clone(body_parser),
+                self.config.clone(),
+            )),
+            None => parser,
         }
     }
+
+    fn with_reading_parser(mut self) -> Self {
+        self.analyze_use_case =
+            AnalyzeDocumentUseCase::new(self.reading_parser()).with_config(self.config.clone());
+        self
+    }
+
+    /// Frontmatter properties; a note without frontmatter starts from defaults
+    fn frontmatter_or_default(&self, content: &str) -> Result<L1Properties, SwitchboardError> {
+        match self.parser.extract_metadata(content) {
+            Some(_) => Ok(self.parser.parse(content)?),
+            None => Ok(L1Properties::default()),
+        }
+    }
+
+    /// Handle to the active configuration, for wiring into other use cases
+    pub fn config_handle(&self) -> ConfigHandle {
+        self.config.clone()
+    }
 }
 
 impl<P, W, S> Switchboard for ApplicationSwitchboard<P, W, S>
@@ -351,6 +605,10 @@ where
         self.parser.parse(content).map_err(SwitchboardError::from)
     }
 
+    fn parse_document_recovering(&self, content: &str) -> Result<RecoveredParse, SwitchboardError> {
+        self.parser.parse_recovering(content).map_err(SwitchboardError::from)
+    }
+
     fn analyze_document(&self, content: &str) -> Result<DocumentAnalysis, SwitchboardError> {
         use doc_doctor_domain::AnalyzeDocument;
         self.analyze_use_case
@@ -358,6 +616,70 @@ where
             .map_err(|e| SwitchboardError::Operation(e.to_string()))
     }
 
+    fn analyze_canvas(&self, content: &str) -> Result<CanvasAnalysis, SwitchboardError> {
+        let canvas_parser = self.canvas_parser.as_ref().ok_or_else(|| {
+            SwitchboardError::Operation("No canvas parser attached".to_string())
+        })?;
+        let body_parser = self.body_parser.as_ref().ok_or_else(|| {
+            SwitchboardError::Operation("No body parser attached".to_string())
+        })?;
+
+        // Sidecar metadata arrives as frontmatter ahead of the canvas JSON
+        let (parsed, canvas) = match canvas_parser.parse_canvas(content) {
+            Ok(canvas) => (RecoveredParse::complete(L1Properties::default()), canvas),
+            Err(e) if self.parser.extract_metadata(content).is_none() => return Err(e.into()),
+            Err(_) => {
+                let body = &content[self.parser.body_offset(content)..];
+                (self.parser.parse_recovering(content)?, canvas_parser.parse_canvas(body)?)
+            }
+        };
+
+        let config = self.config.get();
+        let mut properties = parsed.properties;
+        let cards: Vec<CanvasCard> = canvas
+            .text_cards()
+            .map(|(node_id, text)| {
+                let body = body_parser.parse_body(text, 0);
+                let links = body.wikilinks.iter().chain(&body.embeds);
+                CanvasCard {
+                    node_id: node_id.to_string(),
+                    text: text.to_string(),
+                    stubs: card_stubs(text, &body, &config.stub_types),
+                    links: links.map(|l| l.target.clone()).filter(|t| !t.is_empty()).collect(),
+                }
+            })
+            .collect();
+        for stub in cards.iter().flat_map(|c| &c.stubs) {
+            if !properties.stubs.iter().any(|s| same_stub(s, stub)) {
+                properties.stubs.push(stub.clone());
+            }
+        }
+
+        let file_of = |id: &str| match canvas.node(id).map(|n| &n.kind) {
+            Some(CanvasNodeKind::File { file, .. }) => Some(file.clone()),
+            _ => None,
+        };
+        let file_edges = canvas
+            .edges
+            .iter()
+            .filter_map(|edge| {
+                Some(LinkEdge {
+                    source: file_of(&edge.from_node)?,
+                    target: file_of(&edge.to_node)?,
+                    label: edge.label.clone(),
+                })
+            })
+            .collect();
+
+        let dimensions = self.calc_dimensions(&properties);
+        Ok(CanvasAnalysis {
+            analysis: DocumentAnalysis::new(properties, dimensions).with_diagnostics(parsed.diagnostics),
+            cards,
+            files: canvas.files().into_iter().map(str::to_string).collect(),
+            file_edges,
+        })
+    }
+
     fn validate_document(
         &self,
         content: &str,
@@ -374,46 +696,31 @@ where
         content: &str,
         filter: Option<StubFilter>,
     ) -> Result<Vec<Stub>, SwitchboardError> {
-        let props = self.parser.parse(content)?;
+        let props = self.reading_parser().parse(content)?;
         let mut stubs = props.stubs;
 
         if let Some(f) = filter {
-            if let Some(stub_type) = f.stub_type {
-                stubs.retain(|s| s.stub_type.as_str() == stub_type);
-            }
-            if f.blocking_only {
-                stubs.retain(|s| matches!(s.stub_form, StubForm::Blocking));
-            }
-            if let Some(priority) = f.priority {
-                stubs.retain(|s| s.priority.to_string().to_lowercase() == priority.to_lowercase());
-            }
+            stubs.retain(|s| f.matches(s));
         }
 
         Ok(stubs)
     }
 
+    fn parse_body(&self, content: &str) -> Result<MarkdownBody, SwitchboardError> {
+        let parser = self.body_parser.as_ref().ok_or_else(|| {
+            SwitchboardError::Operation("No body parser attached".to_string())
+        })?;
+        Ok(parser.parse_body(content, self.parser.body_offset(content)))
+    }
+
     fn find_stub_anchors(&self, content: &str) -> Result<AnchorMatches, SwitchboardError> {
         let props = self.parser.parse(content)?;
-
-        // Find all ^anchor patterns in content
-        let mut anchors = Vec::new();
-        for (line_num, line) in content.lines().enumerate() {
-            let mut pos = 0;
-            while let Some(start) = line[pos..].find("^") {
-                let anchor_start = pos + start + 1;
-                // Find end of anchor (alphanumeric + hyphens)
-                let anchor_end = line[anchor_start..]
-                    .find(|c: char| !c.is_alphanumeric() && c != '-' && c != '_')
-                    .map(|i| anchor_start + i)
-                    .unwrap_or(line.len());
-
-                if anchor_end > anchor_start {
-                    let anchor_id = &line[anchor_start..anchor_end];
-                    anchors.push((anchor_id.to_string(), line_num + 1));
-                }
-                pos = anchor_end;
-            }
-        }
+        let anchors: Vec<(String, usize)> = self
+            .parse_body(content)?
+            .block_ids
+            .into_iter()
+            .map(|block| (block.id, block.position.line))
+            .collect();
 
         // Match stubs to anchors
         let stub_anchors: Vec<(usize, Vec<String>)> = props
@@ -424,7 +731,7 @@ where
                 let matched: Vec<String> = stub
                     .inline_anchors
                     .iter()
-                    .filter(|a| anchors.iter().any(|(id, _)| id == *a))
+                    .filter(|a| anchors.iter().any(|(id, _)| id == a.trim_start_matches('^')))
                     .cloned()
                     .collect();
                 (i, matched)
@@ -594,20 +901,237 @@ where
         })
     }
 
+    fn import_tasks(&self, content: &str, remove: bool) -> Result<StubImportResult, SwitchboardError> {
+        let mut props = self.frontmatter_or_default(content)?;
+
+        let open: Vec<_> = self
+            .parse_body(content)?
+            .tasks
+            .into_iter()
+            .filter(|task| task.status.is_open())
+            .collect();
+        let imported = stubs_from_tasks(&props.stubs, &open, &self.config.get().stub_types);
+        let skipped = open.len() - imported.len();
+        props.stubs.extend(imported.iter().cloned());
+
+        let mut body = content.to_string();
+        if remove {
+            for task in open.iter().rev() {
+                let start = body[..task.position.offset].rfind('\n').map_or(0, |i| i + 1);
+                body.replace_range(start..task.end_offset, "");
+            }
+        }
+        let updated_content = self.writer.serialize_document(&body, &props)?;
+
+        Ok(StubImportResult {
+            updated_content,
+            imported,
+            skipped,
+        })
+    }
+
+    fn import_annotations(
+        &self,
+        content: &str,
+        remove: bool,
+    ) -> Result<StubImportResult, SwitchboardError> {
+        let mut props = self.frontmatter_or_default(content)?;
+        let parsed = self.parse_body(content)?;
+        let config = self.config.get();
+
+        let mut imported: Vec<Stub> = Vec::new();
+        let mut converted = Vec::new();
+        for annotation in &parsed.annotations {
+            let Some(mut stub) = stub_from_annotation(annotation, &config.stub_types) else {
+                continue;
+            };
+            // A block ID on the same line anchors the stub
+            stub.anchor = parsed
+                .block_ids
+                .iter()
+                .find(|block| block.position.line == annotation.position.line)
+                .map(|block| format!("^{}", block.id));
+            if !props.stubs.iter().chain(&imported).any(|s| same_stub(s, &stub)) {
+                imported.push(stub);
+            }
+            converted.push(annotation);
+        }
+        let skipped = converted.len() - imported.len();
+        props.stubs.extend(imported.iter().cloned());
+
+        let mut body = content.to_string();
+        if remove {
+            for annotation in converted.iter().rev() {
+                remove_annotation(&mut body, annotation);
+            }
+        }
+        let updated_content = self.writer.serialize_document(&body, &props)?;
+
+        Ok(StubImportResult {
+            updated_content,
+            imported,
+            skipped,
+        })
+    }
+
+    fn stub_sync_status(&self, content: &str) -> Result<SyncStatus, SwitchboardError> {
+        let props = self.frontmatter_or_default(content)?;
+        let body = self.parse_body(content)?;
+        Ok(SyncStatus::new(&props.stubs, &body, &self.config.get().stub_types))
+    }
+
+    fn sync_stub_markers(&self, content: &str) -> Result<StubSyncResult, SwitchboardError> {
+        let mut props = self.frontmatter_or_default(content)?;
+        let body = self.parse_body(content)?;
+        let stub_types = &self.config.get().stub_types;
+
+        let mut created = Vec::new();
+        let mut updated: Vec<usize> = Vec::new();
+        for marker in stubs_from_markers(&body, stub_types) {
+            let Some(index) = matching_stub(&props.stubs, &marker) else {
+                props.stubs.push(marker.clone());
+                created.push(marker);
+                continue;
+            };
+
+            let stub = &mut props.stubs[index];
+            let changed = if stub.anchor.is_some() && stub.anchor == marker.anchor {
+                let changed = stub.stub_type != marker.stub_type
+                    || stub.description != marker.description;
+                stub.stub_type = marker.stub_type;
+                stub.description = marker.description;
+                changed
+            } else if stub.anchor.is_none() && marker.anchor.is_some() {
+                stub.anchor = marker.anchor;
+                true
+            } else {
+                false
+            };
+            if changed && !updated.contains(&index) {
+                updated.push(index);
+            }
+        }
+
+        let updated_content = if created.is_empty() && updated.is_empty() {
+            content.to_string()
+        } else {
+            self.writer.serialize_document(content, &props)?
+        };
+
+        Ok(StubSyncResult {
+            updated_content,
+            created,
+            updated: updated.iter().map(|&i| props.stubs[i].clone()).collect(),
+            status: SyncStatus::new(&props.stubs, &body, stub_types),
+        })
+    }
+
+    fn suggest_stubs(
+        &self,
+        content: &str,
+        options: &DetectorOptions,
+    ) -> Result<Vec<StubSuggestion>, SwitchboardError> {
+        let props = self.frontmatter_or_default(content)?;
+        let body = self.parse_body(content)?;
+        let mut suggestions =
+            detect_stubs(content, &body, self.parser.body_offset(content), options);
+        suggestions.retain(|s| !props.stubs.iter().any(|stub| same_stub(stub, &s.stub)));
+        Ok(suggestions)
+    }
+
+    fn apply_stub_suggestions(
+        &self,
+        content: &str,
+        suggestions: &[StubSuggestion],
+    ) -> Result<StubImportResult, SwitchboardError> {
+        let mut props = self.frontmatter_or_default(content)?;
+
+        let mut applied: Vec<&StubSuggestion> = Vec::new();
+        for suggestion in suggestions {
+            let covered = props
+                .stubs
+                .iter()
+                .chain(applied.iter().map(|s| &s.stub))
+                .any(|stub| same_stub(stub, &suggestion.stub));
+            if !covered {
+                applied.push(suggestion);
+            }
+        }
+        let skipped = suggestions.len() - applied.len();
+        props.stubs.extend(applied.iter().map(|s| s.stub.clone()));
+
+        let mut body = content.to_string();
+        let mut inserts: Vec<(usize, &str)> = applied
+            .iter()
+            .filter_map(|s| Some((s.anchor_insert?, s.stub.anchor.as_deref()?)))
+            .collect();
+        inserts.sort_by_key(|&(offset, _)| std::cmp::Reverse(offset));
+        inserts.dedup_by_key(|&mut (offset, _)| offset);
+        for (offset, anchor) in inserts {
+            body.insert_str(offset, &format!(" {}", anchor));
+        }
+        let updated_content = self.writer.serialize_document(&body, &props)?;
+
+        Ok(StubImportResult {
+            updated_content,
+            imported: applied.into_iter().map(|s| s.stub.clone()).collect(),
+            skipped,
+        })
+    }
+
+    fn touch_modified(
+        &self,
+        content: &str,
+        now: DateTime<Utc>,
+    ) -> Result<String, SwitchboardError> {
+        let props = self.parser.parse(content)?;
+        let span = self
+            .parser
+            .extract_metadata(content)
+            .ok_or_else(|| SwitchboardError::Parse("Document has no frontmatter".into()))?;
+        let tz = self.config().vault.tz();
+        let modified = match &props.modified {
+            Some(modified) => modified.restyled(now, tz),
+            None => Timestamp::from(now),
+        };
+        Ok(set_metadata_value(content, &span, "modified", &modified.to_string()))
+    }
+
     fn calc_health(&self, refinement: f64, stubs: &[Stub]) -> f64 {
-        calculate_health(refinement, stubs)
+        calculate_health_with_config(refinement, stubs, &self.config.get())
     }
 
     fn calc_usefulness(&self, refinement: f64, audience: Audience) -> Usefulness {
-        calculate_usefulness(refinement, audience)
+        calculate_usefulness_with_config(refinement, audience, &self.config.get())
     }
 
     fn calc_dimensions(&self, props: &L1Properties) -> StateDimensions {
-        StateDimensions::calculate(props)
+        StateDimensions::calculate_with_config(
+            props,
+            self.config.now(),
+            &self.config.get(),
+            self.config.is_default(),
+        )
     }
 
     fn calc_vector_physics(&self, stub: &Stub, context: &StubContext) -> VectorPhysics {
-        VectorPhysics::calculate(stub, context)
+        VectorPhysics::calculate_with_config(stub, context, &self.config.get().vector_physics)
+    }
+
+    fn config(&self) -> Arc<CalculationConfig> {
+        self.config.get()
+    }
+
+    fn set_config(&self, config: CalculationConfig) -> Result<(), SwitchboardError> {
+        self.config.replace(config).map_err(SwitchboardError::from)
+    }
+
+    fn reload_config(&self) -> Result<Arc<CalculationConfig>, SwitchboardError> {
+        let provider = self.config_provider.as_ref().ok_or_else(|| {
+            SwitchboardError::Config("No configuration provider attached".to_string())
+        })?;
+        self.config.replace(provider.load()?)?;
+        Ok(self.config.get())
     }
 
     fn get_frontmatter_schema(&self) -> &str {
@@ -622,7 +1146,9 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use doc_doctor_canvas::JsonCanvasParser;
     use doc_doctor_domain::EmbeddedSchemaProvider;
+    use doc_doctor_markdown::MarkdownParser;
     use doc_doctor_parser_yaml::YamlParser;
 
     fn create_test_switchboard(
@@ -631,6 +1157,8 @@ mod tests {
         let writer = Arc::clone(&parser);
         let schema = Arc::new(EmbeddedSchemaProvider);
         ApplicationSwitchboard::new(parser, writer, schema)
+            .with_body_parser(Arc::new(MarkdownParser::new()))
+            .with_canvas_parser(Arc::new(JsonCanvasParser::new()))
     }
 
     #[test]
@@ -643,6 +1171,173 @@ mod tests {
         assert_eq!(props.refinement.value(), 0.75);
     }
 
+    #[test]
+    fn test_import_tasks() {
+        let switchboard = create_test_switchboard();
+        let content = "---\ntitle: Test\nstubs:\n  - link: \"Add source\"\n---\n# Notes\n\n- [ ] Add source\n- [ ] check dates #verify 📅 2024-05-01\n\n- [x] shipped\nText\n";
+
+        let result = switchboard.import_tasks(content, true).unwrap();
+        assert_eq!(result.imported.len(), 1);
+        assert_eq!(result.skipped, 1);
+        assert_eq!(result.imported[0].stub_type.as_str(), "verify");
+        assert!(result.updated_content.ends_with("# Notes\n\n\n- [x] shipped\nText\n"));
+
+        let props = switchboard.parse_document(&result.updated_content).unwrap();
+        assert_eq!(props.stubs.len(), 2);
+        assert_eq!(props.stubs[1].due.as_ref().unwrap().to_string(), "2024-05-01");
+
+        let fresh = switchboard.import_tasks("- [ ] draft intro\n", false).unwrap();
+        assert!(fresh.updated_content.starts_with("---\n"));
+        assert!(fresh.updated_content.ends_with("- [ ] draft intro\n"));
+    }
+
+    #[test]
+    fn test_import_annotations() {
+        let switchboard = create_test_switchboard();
+        let content = "---\ntitle: Test\n---\nClaim ==verify: dated 2019== here ^c1\n%% link: add source %%\nText %% note: aside %%\nEnd %% todo: tidy %%\n";
+
+        let result = switchboard.import_annotations(content, true).unwrap();
+        let types: Vec<_> = result.imported.iter().map(|s| s.stub_type.as_str()).collect();
+        assert_eq!(types, vec!["verify", "link", "todo"]);
+        assert_eq!(result.imported[0].anchor.as_deref(), Some("^c1"));
+        assert!(result
+            .updated_content
+            .ends_with("---\nClaim dated 2019 here ^c1\nText %% note: aside %%\nEnd\n"));
+
+        let again = switchboard.import_annotations(content, false).unwrap();
+        assert_eq!(again.imported.len(), 3);
+        let twice = switchboard.import_annotations(&again.updated_content, false).unwrap();
+        assert!(twice.imported.is_empty());
+        assert_eq!(twice.skipped, 3);
+    }
+
+    #[test]
+    fn test_sync_stub_markers() {
+        let switchboard = create_test_switchboard();
+        let content = "---\ntitle: Test\nstubs:\n  - type: expand\n    description: old wording\n    anchor: ^deploy\n  - type: link\n    description: add source\n---\n> [!stub] expand: add deployment examples ^deploy\n\nClaim {{link: add source}} ^claim\n{{verify: check 40%}}\n";
+
+        let status = switchboard.stub_sync_status(content).unwrap();
+        assert_eq!(status.synced.len(), 2);
+        assert_eq!(status.missing_in_frontmatter[0].description, "check 40%");
+
+        let result = switchboard.sync_stub_markers(content).unwrap();
+        assert_eq!(result.created.len(), 1);
+        assert_eq!(result.updated.len(), 2);
+        assert_eq!(result.updated[0].description, "add deployment examples");
+        assert_eq!(result.updated[1].anchor.as_deref(), Some("^claim"));
+        assert!(result.status.is_synced());
+
+        let again = switchboard.sync_stub_markers(&result.updated_content).unwrap();
+        assert!(again.created.is_empty() && again.updated.is_empty());
+        assert_eq!(again.updated_content, result.updated_content);
+    }
+
+    #[test]
+    fn test_suggest_stubs() {
+        let switchboard = create_test_switchboard();
+        let content = "---\ntitle: Test\nstubs:\n  - type: todo\n    description: add benchmarks\n---\n# Plan\nTODO: add benchmarks\nFIXME: broken link\n## Later\n";
+
+        let suggestions = switchboard
+            .suggest_stubs(content, &DetectorOptions::default())
+            .unwrap();
+        let descriptions: Vec<_> = suggestions.iter().map(|s| s.stub.description.as_str()).collect();
+        assert_eq!(descriptions, vec!["broken link", "Write the 'Later' section"]);
+
+        let result = switchboard.apply_stub_suggestions(content, &suggestions).unwrap();
+        let anchor = suggestions[0].stub.anchor.clone().unwrap();
+        assert_eq!(result.imported.len(), 2);
+        assert!(result.updated_content.contains(&format!("FIXME: broken link {}\n", anchor)));
+        assert!(switchboard
+            .suggest_stubs(&result.updated_content, &DetectorOptions::default())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_touch_modified() {
+        let switchboard = create_test_switchboard();
+        let now = DateTime::parse_from_rfc3339("2024-06-01T10:30:00Z").unwrap().to_utc();
+
+        let content = "---\ntitle: Test\nmodified: 2024-03-05\n---\nBody\n";
+        let touched = switchboard.touch_modified(content, now).unwrap();
+        assert!(touched.contains("modified: 2024-06-01\n"));
+        assert!(touched.ends_with("---\nBody\n"));
+
+        let touched = switchboard.touch_modified("---\ntitle: Test\n---\n", now).unwrap();
+        assert!(touched.contains("2024-06-01T10:30:00Z"));
+
+        let content = "---\n# Reviewed\ntitle: Test\ncustom_key: keep me\ntags: [x, y]\n\
+                       modified: 2024-03-05 # by hand\n---\nBody\n";
+        let touched = switchboard.touch_modified(content, now).unwrap();
+        assert_eq!(touched, content.replace("2024-03-05", "2024-06-01"));
+
+        let content = "---\n# Reviewed\ncustom_key: keep me\ntags: [x, y]\n---\nBody\n";
+        let touched = switchboard.touch_modified(content, now).unwrap();
+        let line = "modified: 2024-06-01T10:30:00Z\n";
+        assert_eq!(touched, content.replace("y]\n", &format!("y]\n{}", line)));
+
+        let content = "---\r\ntitle: Test\r\n---\r\nBody\r\n";
+        let touched = switchboard.touch_modified(content, now).unwrap();
+        let line = "modified: 2024-06-01T10:30:00Z\r\n";
+        assert_eq!(touched, content.replace("Test\r\n", &format!("Test\r\n{}", line)));
+    }
+
+    #[test]
+    fn test_analyze_canvas() {
+        let switchboard = create_test_switchboard();
+        let canvas = r#"{"nodes": [
+            {"id": "t", "type": "text", "text": "- [ ] verify numbers #verify\nSee [[Budget]]"},
+            {"id": "a", "type": "file", "file": "Notes/Plan.md"},
+            {"id": "b", "type": "file", "file": "Notes/Launch.md"}
+        ], "edges": [
+            {"id": "e1", "fromNode": "a", "toNode": "b", "label": "precedes"},
+            {"id": "e2", "fromNode": "t", "toNode": "a"}
+        ]}"#;
+
+        let result = switchboard.analyze_canvas(canvas).unwrap();
+        assert_eq!(result.analysis.properties.stubs.len(), 1);
+        assert_eq!(result.cards[0].links, vec!["Budget"]);
+        assert_eq!(result.files, vec!["Notes/Plan.md", "Notes/Launch.md"]);
+        assert_eq!(result.file_edges.len(), 1);
+        assert_eq!(result.file_edges[0].label.as_deref(), Some("precedes"));
+
+        // Metadata from a sidecar
+        let with_sidecar = format!("---\nrefinement: 0.6\nstubs:\n  - verify: verify numbers\n---\n{}", canvas);
+        let result = switchboard.analyze_canvas(&with_sidecar).unwrap();
+        assert_eq!(result.analysis.properties.refinement.value(), 0.6);
+        assert_eq!(result.analysis.properties.stubs.len(), 1);
+
+        assert!(switchboard.analyze_canvas("# Not a canvas").is_err());
+    }
+
+    #[test]
+    fn test_analyze_reads_inline_metadata() {
+        let mut config = CalculationConfig::default();
+        config.vault.inline_fields = true;
+        config.vault.task_stubs = true;
+        let switchboard = create_test_switchboard().with_config(ConfigHandle::new(config).unwrap());
+        let content = "---\ntitle: Test\n---\nrefinement:: 0.6\n- [ ] 🔗 cite the paper\n";
+
+        let analysis = switchboard.analyze_document(content).unwrap();
+        assert_eq!(analysis.properties.refinement.value(), 0.6);
+        assert_eq!(switchboard.list_stubs(content, None).unwrap().len(), 1);
+        // Writes only see frontmatter
+        assert!(switchboard.parse_document(content).unwrap().stubs.is_empty());
+    }
+
+    #[test]
+    fn test_analyze_partial_document() {
+        let switchboard = create_test_switchboard();
+        let content = "---\ntitle: Test\nrefinement: 0.75\nstubs:\n  - link: \"ok\"\n  - [broken]\n---\n";
+
+        assert!(switchboard.parse_document(content).is_err());
+
+        let analysis = switchboard.analyze_document(content).unwrap();
+        assert!(analysis.partial);
+        assert_eq!(analysis.properties.stubs.len(), 1);
+        assert_eq!(analysis.diagnostics[0].field, "stubs[1]");
+    }
+
     #[test]
     fn test_add_stub() {
         let switchboard = create_test_switchboard();
@@ -679,10 +1374,119 @@ stubs:
         assert!(!result.updated_content.contains("expand"));
     }
 
+    #[test]
+    fn test_find_stub_anchors() {
+        let switchboard = create_test_switchboard();
+        let content = r#"---
+title: Test
+stubs:
+  - type: source
+    description: "Cite this"
+    inline_anchors: ["^claim"]
+  - type: expand
+    description: "Missing"
+    inline_anchors: ["gone"]
+---
+A claim that needs a source. ^claim
+
+```
+not ^gone
+```
+Math $x^2$ and a note[^gone]
+"#;
+
+        let matches = switchboard.find_stub_anchors(content).unwrap();
+        assert_eq!(matches.anchors, vec![("claim".to_string(), 11)]);
+        assert_eq!(matches.stub_anchors, vec![(0, vec!["^claim".to_string()]), (1, vec![])]);
+    }
+
+    #[test]
+    fn test_parse_body_requires_parser() {
+        let switchboard = ApplicationSwitchboard::new(
+            Arc::new(YamlParser::new()),
+            Arc::new(YamlParser::new()),
+            Arc::new(EmbeddedSchemaProvider),
+        );
+        assert!(switchboard.parse_body("# Title").is_err());
+    }
+
     #[test]
     fn test_calc_health() {
         let switchboard = create_test_switchboard();
         let health = switchboard.calc_health(0.8, &[]);
         assert!(health > 0.5);
     }
+
+    #[test]
+    fn test_calc_uses_injected_config() {
+        let mut config = CalculationConfig::default();
+        config.health.refinement_weight = 0.5;
+        config.health.stub_weight = 0.5;
+        config.audience_gates.internal = 0.6;
+        let switchboard =
+            create_test_switchboard().with_config(ConfigHandle::new(config).unwrap());
+
+        // 0.5 * 0.8 + 0.5 * 1.0
+        assert!((switchboard.calc_health(0.8, &[]) - 0.9).abs() < 0.001);
+        assert!(switchboard.calc_usefulness(0.65, Audience::Internal).is_useful);
+
+        let analysis = switchboard
+            .analyze_document("---\nrefinement: 0.65\naudience: internal\n---\n")
+            .unwrap();
+        assert!(analysis.dimensions.usefulness.is_useful);
+    }
+
+    #[test]
+    fn test_set_config_at_runtime() {
+        let switchboard = create_test_switchboard();
+        let handle = switchboard.config_handle();
+
+        let mut config = CalculationConfig::default();
+        config.audience_gates.public = 0.95;
+        switchboard.set_config(config).unwrap();
+
+        assert_eq!(switchboard.config().audience_gates.public, 0.95);
+        assert_eq!(handle.get().audience_gates.public, 0.95);
+
+        let mut invalid = CalculationConfig::default();
+        invalid.health.stub_weight = 0.9;
+        assert!(matches!(
+            switchboard.set_config(invalid),
+            Err(SwitchboardError::Config(_))
+        ));
+        assert_eq!(switchboard.config().health.stub_weight, 0.3);
+    }
+
+    #[test]
+    fn test_reload_config() {
+        struct FixedProvider;
+
+        impl ConfigProvider for FixedProvider {
+            fn load(&self) -> Result<CalculationConfig, ConfigError> {
+                let mut config = CalculationConfig::default();
+                config.audience_gates.personal = 0.4;
+                Ok(config)
+            }
+
+            fn save(&self, _config: &CalculationConfig) -> Result<(), ConfigError> {
+                Ok(())
+            }
+
+            fn exists(&self) -> bool {
+                true
+            }
+
+            fn source(&self) -> String {
+                "fixed".to_string()
+            }
+        }
+
+        let switchboard = create_test_switchboard();
+        assert!(switchboard.reload_config().is_err());
+
+        let switchboard = switchboard.with_config_provider(Arc::new(FixedProvider));
+        let config = switchboard.reload_config().unwrap();
+        assert_eq!(config.audience_gates.personal, 0.4);
+        assert!(switchboard.calc_usefulness(0.45, Audience::Personal).is_useful);
+    }
 }
@@ -8,45 +8,59 @@ use doc_doctor_domain::{
 };
 use std::sync::Arc;
 
+use crate::config::ConfigHandle;
+
 /// Analyze document use case implementation
 ///
 /// Parses a document and calculates all L2 dimensions.
 pub struct AnalyzeDocumentUseCase {
     parser: Arc<dyn DocumentParser>,
+    config: ConfigHandle,
 }
 
 impl AnalyzeDocumentUseCase {
     /// Create a new analyze document use case
     pub fn new(parser: Arc<dyn DocumentParser>) -> Self {
-        Self { parser }
+        Self {
+            parser,
+            config: ConfigHandle::defaults(),
+        }
     }
 
     /// Create with a boxed parser
     pub fn with_parser(parser: Box<dyn DocumentParser>) -> Self {
-        Self {
-            parser: Arc::from(parser),
-        }
+        Self::new(Arc::from(parser))
+    }
+
+    /// Use a shared configuration handle for calculations
+    pub fn with_config(mut self, config: ConfigHandle) -> Self {
+        self.config = config;
+        self
     }
 }
 
 impl AnalyzeDocument for AnalyzeDocumentUseCase {
     fn analyze(&self, content: &str) -> Result<DocumentAnalysis, AnalysisError> {
-        // Parse the document
-        let properties = self.parser.parse(content).map_err(|e| {
+        // Parse the document, keeping valid entries when others are malformed
+        let parsed = self.parser.parse_recovering(content).map_err(|e| {
             AnalysisError::new(e.message).with_cause("parse")
         })?;
+        let properties = parsed.properties;
 
         // Calculate state dimensions
-        let dimensions = StateDimensions::calculate(&properties);
+        let dimensions = StateDimensions::calculate_with_config(
+            &properties,
+            self.config.now(),
+            &self.config.get(),
+            self.config.is_default(),
+        );
 
         // Collect any warnings (currently empty, would come from validation)
         let warnings: Vec<ValidationWarning> = Vec::new();
 
-        Ok(DocumentAnalysis {
-            properties,
-            dimensions,
-            warnings,
-        })
+        Ok(DocumentAnalysis::new(properties, dimensions)
+            .with_warnings(warnings)
+            .with_diagnostics(parsed.diagnostics))
     }
 }
 
@@ -133,4 +147,22 @@ mod tests {
         // Usefulness should be calculated for Public audience
         assert!(!analysis.dimensions.usefulness.is_useful); // 0.8 < 0.9 gate
     }
+
+    #[test]
+    fn test_analyze_uses_config_gates() {
+        let props = L1Properties::new()
+            .refinement(0.85)
+            .audience(Audience::Public);
+
+        let mut custom = doc_doctor_domain::CalculationConfig::default();
+        custom.audience_gates.public = 0.8;
+        let config = ConfigHandle::new(custom).unwrap();
+
+        let use_case = AnalyzeDocumentUseCase::with_parser(Box::new(MockParser::success(props)))
+            .with_config(config);
+
+        let analysis = use_case.analyze("content").unwrap();
+        assert!(analysis.dimensions.usefulness.is_useful); // 0.85 >= 0.8 custom gate
+        assert!(!analysis.dimensions.using_defaults);
+    }
 }
@@ -1,20 +1,113 @@
 //! Batch Process Use Case
 //!
 //! Process multiple documents matching a glob pattern.
+//!
+//! [`BatchProcessUseCase::process_stream`] processes paths as a walk yields
+//! them, a chunk at a time, and hands each result to a [`BatchSink`]
+//! instead of collecting them: memory stays bounded on very large
+//! archives, and a [`BatchCheckpoint`] lets an interrupted run resume.
+//!
+//! Files larger than the size limit are read only up to the limit: the
+//! frontmatter sits at the start, so health and stubs are still computed,
+//! and the result carries a warning saying how much was read.
 
 use doc_doctor_domain::{
-    BatchDocumentResult, BatchError, BatchProcess, BatchResult, DocumentParser,
-    DocumentRepository, StateDimensions,
+    is_canvas, AnalyzeDocument, BatchDocumentResult, BatchError, BatchProcess, BatchResult,
+    BatchSummary, DocumentParser, DocumentRepository, RepositoryError, StateDimensions,
 };
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use crate::config::ConfigHandle;
+
+/// Default size limit for reading a whole document (8 MiB)
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 8 * 1024 * 1024;
+
+/// A document read for batch processing
+#[derive(Debug, Clone)]
+pub struct BatchRead {
+    /// Document content, possibly only its start
+    pub content: String,
+    /// Set when the file exceeded the size limit
+    pub warning: Option<String>,
+}
+
+/// Read a document, or only its first `max_bytes` when it is larger
+pub fn read_for_batch(
+    repository: &dyn DocumentRepository,
+    path: &Path,
+    max_bytes: u64,
+) -> Result<BatchRead, RepositoryError> {
+    let size = repository.metadata(path).map(|m| m.size).unwrap_or(0);
+    if size <= max_bytes {
+        return Ok(BatchRead {
+            content: repository.read(path)?,
+            warning: None,
+        });
+    }
+
+    Ok(BatchRead {
+        content: repository.read_prefix(path, max_bytes)?,
+        warning: Some(format!(
+            "Large file ({}): analyzed the first {} only",
+            format_size(size),
+            format_size(max_bytes)
+        )),
+    })
+}
+
+/// Human-readable byte count (e.g. `12.5 MiB`)
+fn format_size(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= KIB * KIB {
+        format!("{:.1} MiB", bytes / (KIB * KIB))
+    } else if bytes >= KIB {
+        format!("{:.1} KiB", bytes / KIB)
+    } else {
+        format!("{} bytes", bytes)
+    }
+}
+
+/// Progress of a streaming batch
+///
+/// Written after every chunk, so that a run interrupted on a long archive
+/// can resume where it stopped instead of starting over.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchCheckpoint {
+    /// Patterns walked; a resumed run must walk the same ones
+    pub patterns: Vec<String>,
+    /// Number of paths taken from the walk
+    pub processed: usize,
+    /// Last path taken, to detect a walk that changed since
+    pub last: Option<PathBuf>,
+    /// Aggregates of every result so far
+    pub summary: BatchSummary,
+}
+
+/// Receives the results of a streaming batch
+pub trait BatchSink {
+    /// A document was processed; results arrive in walk order
+    fn result(&mut self, result: BatchDocumentResult) -> Result<(), BatchError>;
+
+    /// A chunk was processed; `checkpoint` covers every result so far
+    fn checkpoint(&mut self, _checkpoint: &BatchCheckpoint) -> Result<(), BatchError> {
+        Ok(())
+    }
+}
+
 /// Batch process use case implementation
 ///
 /// Processes multiple documents in parallel using glob patterns.
 pub struct BatchProcessUseCase {
     parser: Arc<dyn DocumentParser>,
     repository: Arc<dyn DocumentRepository>,
+    canvas_analyzer: Option<Arc<dyn AnalyzeDocument + Send + Sync>>,
+    config: ConfigHandle,
+    max_file_size: u64,
 }
 
 impl BatchProcessUseCase {
@@ -23,7 +116,13 @@ impl BatchProcessUseCase {
         parser: Arc<dyn DocumentParser>,
         repository: Arc<dyn DocumentRepository>,
     ) -> Self {
-        Self { parser, repository }
+        Self {
+            parser,
+            repository,
+            canvas_analyzer: None,
+            config: ConfigHandle::defaults(),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+        }
     }
 
     /// Create with boxed dependencies
@@ -31,34 +130,126 @@ impl BatchProcessUseCase {
         parser: Box<dyn DocumentParser>,
         repository: Box<dyn DocumentRepository>,
     ) -> Self {
-        Self {
-            parser: Arc::from(parser),
-            repository: Arc::from(repository),
+        Self::new(Arc::from(parser), Arc::from(repository))
+    }
+
+    /// Use a shared configuration handle for calculations
+    pub fn with_config(mut self, config: ConfigHandle) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Read only the first `bytes` of larger documents
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+
+    /// Analyze Obsidian canvases with `analyzer`; without one they fail
+    /// to parse like any document without frontmatter
+    pub fn with_canvas_analyzer(
+        mut self,
+        analyzer: Arc<dyn AnalyzeDocument + Send + Sync>,
+    ) -> Self {
+        self.canvas_analyzer = Some(analyzer);
+        self
+    }
+
+    /// Process the documents at `paths` as they are yielded, `chunk_size`
+    /// at a time
+    ///
+    /// Each chunk is processed in parallel and its results are passed to
+    /// `sink` in walk order, followed by the updated checkpoint. Resuming
+    /// from a checkpoint skips the paths it covers.
+    pub fn process_stream(
+        &self,
+        paths: impl IntoIterator<Item = PathBuf>,
+        chunk_size: usize,
+        mut checkpoint: BatchCheckpoint,
+        sink: &mut dyn BatchSink,
+    ) -> Result<BatchCheckpoint, BatchError> {
+        let mut paths = paths.into_iter();
+        if checkpoint.processed > 0 {
+            let last = paths.by_ref().take(checkpoint.processed).last();
+            if last != checkpoint.last {
+                return Err(BatchError::new(
+                    "The files changed since the checkpoint was written; start over without it",
+                ));
+            }
         }
+
+        loop {
+            let chunk: Vec<PathBuf> = paths.by_ref().take(chunk_size.max(1)).collect();
+            let Some(last) = chunk.last().cloned() else {
+                break;
+            };
+            checkpoint.processed += chunk.len();
+            checkpoint.last = Some(last);
+
+            let results: Vec<BatchDocumentResult> = chunk
+                .into_par_iter()
+                .map(|path| self.process_document(path))
+                .collect();
+            for result in results {
+                checkpoint.summary.add(&result);
+                sink.result(result)?;
+            }
+            sink.checkpoint(&checkpoint)?;
+        }
+        Ok(checkpoint)
     }
 
     /// Process a single document
     fn process_document(&self, path: std::path::PathBuf) -> BatchDocumentResult {
         // Read content
-        let content = match self.repository.read(&path) {
-            Ok(c) => c,
+        let read = match read_for_batch(self.repository.as_ref(), &path, self.max_file_size) {
+            Ok(r) => r,
             Err(e) => {
                 return BatchDocumentResult::failure(path, format!("Read error: {}", e.message));
             }
         };
 
-        // Parse document
-        let properties = match self.parser.parse(&content) {
+        if let Some(analyzer) = self.canvas_analyzer.as_ref().filter(|_| is_canvas(&path)) {
+            let result = match analyzer.analyze(&read.content) {
+                Ok(analysis) => {
+                    BatchDocumentResult::success(path, analysis.properties, analysis.dimensions)
+                        .with_diagnostics(analysis.diagnostics)
+                }
+                Err(e) => BatchDocumentResult::failure(path, format!("Parse error: {}", e)),
+            };
+            return match read.warning {
+                Some(warning) => result.with_warning(warning),
+                None => result,
+            };
+        }
+
+        // Parse document, keeping valid entries when others are malformed
+        let parsed = match self.parser.parse_recovering(&read.content) {
             Ok(p) => p,
             Err(e) => {
-                return BatchDocumentResult::failure(path, format!("Parse error: {}", e.message));
+                let error = match &read.warning {
+                    Some(warning) => format!("Parse error: {} ({})", e.message, warning),
+                    None => format!("Parse error: {}", e.message),
+                };
+                return BatchDocumentResult::failure(path, error);
             }
         };
 
         // Calculate dimensions
-        let dimensions = StateDimensions::calculate(&properties);
-
-        BatchDocumentResult::success(path, properties, dimensions)
+        let config = self.config.get();
+        let dimensions = StateDimensions::calculate_with_config(
+            &parsed.properties,
+            self.config.now(),
+            &config,
+            self.config.is_default(),
+        );
+
+        let result = BatchDocumentResult::success(path, parsed.properties, dimensions)
+            .with_diagnostics(parsed.diagnostics);
+        match read.warning {
+            Some(warning) => result.with_warning(warning),
+            None => result,
+        }
     }
 }
 
@@ -87,8 +278,8 @@ impl BatchProcess for BatchProcessUseCase {
 mod tests {
     use super::*;
     use doc_doctor_domain::{
-        DocumentMetadata, L1Properties, MetadataSpan, ParseError, RepositoryError,
-        RepositoryErrorKind,
+        DocumentMetadata, L1Properties, MetadataSpan, ParseDiagnostic, ParseError,
+        RecoveredParse, RepositoryError, RepositoryErrorKind,
     };
     use std::path::{Path, PathBuf};
 
@@ -196,6 +387,41 @@ mod tests {
         assert_eq!(result.failed, 1);
     }
 
+    #[test]
+    fn test_batch_process_partial_parse() {
+        struct RecoveringParser;
+
+        impl DocumentParser for RecoveringParser {
+            fn parse(&self, _content: &str) -> Result<L1Properties, ParseError> {
+                Err(ParseError::new("stubs[0]: malformed"))
+            }
+
+            fn parse_recovering(&self, _content: &str) -> Result<RecoveredParse, ParseError> {
+                Ok(RecoveredParse {
+                    properties: L1Properties::new().refinement(0.5),
+                    diagnostics: vec![ParseDiagnostic::new("stubs[0]", "malformed")],
+                })
+            }
+
+            fn extract_metadata(&self, _content: &str) -> Option<MetadataSpan> {
+                None
+            }
+
+            fn format_id(&self) -> &'static str {
+                "recovering"
+            }
+        }
+
+        let repo = MockRepository::new(vec![("doc1.md", "title: Doc 1")]);
+        let use_case =
+            BatchProcessUseCase::with_deps(Box::new(RecoveringParser), Box::new(repo));
+
+        let result = use_case.process("**/*.md").unwrap();
+        assert_eq!(result.succeeded, 1);
+        assert_eq!(result.partial, 1);
+        assert_eq!(result.documents[0].diagnostics[0].field, "stubs[0]");
+    }
+
     #[test]
     fn test_batch_process_empty() {
         let repo = MockRepository::new(vec![]);
@@ -207,6 +433,69 @@ mod tests {
         assert_eq!(result.total, 0);
     }
 
+    #[test]
+    fn test_batch_process_large_file() {
+        let repo = MockRepository::new(vec![("doc1.md", "title: Doc 1")]);
+
+        let use_case = BatchProcessUseCase::with_deps(Box::new(MockParser), Box::new(repo))
+            .with_max_file_size(10);
+
+        let result = use_case.process("**/*.md").unwrap();
+        assert_eq!(result.succeeded, 1);
+        assert_eq!(
+            result.documents[0].warnings,
+            vec!["Large file (100 bytes): analyzed the first 10 bytes only"]
+        );
+    }
+
+    #[test]
+    fn test_batch_process_stream() {
+        struct Collect(Vec<PathBuf>, Vec<usize>);
+
+        impl BatchSink for Collect {
+            fn result(&mut self, result: BatchDocumentResult) -> Result<(), BatchError> {
+                self.0.push(result.path);
+                Ok(())
+            }
+
+            fn checkpoint(&mut self, checkpoint: &BatchCheckpoint) -> Result<(), BatchError> {
+                self.1.push(checkpoint.processed);
+                Ok(())
+            }
+        }
+
+        let files = vec![
+            ("doc1.md", "title: Doc 1"),
+            ("doc2.md", "error: bad content"),
+            ("doc3.md", "title: Doc 3"),
+        ];
+        let paths = || files.iter().map(|(p, _)| PathBuf::from(p));
+        let repo = MockRepository::new(files.clone());
+        let use_case = BatchProcessUseCase::with_deps(Box::new(MockParser), Box::new(repo));
+
+        let mut sink = Collect(Vec::new(), Vec::new());
+        let start = BatchCheckpoint::default();
+        let done = use_case.process_stream(paths(), 2, start, &mut sink).unwrap();
+        assert_eq!(sink.0, paths().collect::<Vec<_>>());
+        assert_eq!(sink.1, [2, 3]);
+        assert_eq!((done.summary.total, done.summary.failed), (3, 1));
+        assert!((done.summary.average_health().unwrap() - 0.825).abs() < 0.001);
+
+        // Resuming after the first chunk processes only the rest
+        let mut sink = Collect(Vec::new(), Vec::new());
+        let checkpoint = BatchCheckpoint {
+            processed: 2,
+            last: Some(PathBuf::from("doc2.md")),
+            ..Default::default()
+        };
+        let resumed = use_case.process_stream(paths(), 2, checkpoint.clone(), &mut sink).unwrap();
+        assert_eq!(sink.0, [PathBuf::from("doc3.md")]);
+        assert_eq!(resumed.summary.total, 1);
+
+        let moved = paths().skip(1);
+        assert!(use_case.process_stream(moved, 2, checkpoint, &mut sink).is_err());
+    }
+
     #[test]
     fn test_average_health() {
         let repo = MockRepository::new(vec![
@@ -223,4 +512,26 @@ mod tests {
         assert!(avg_health.is_some());
         assert!(avg_health.unwrap() > 0.0);
     }
+
+    #[test]
+    fn test_batch_process_uses_shared_config() {
+        let repo = MockRepository::new(vec![("doc1.md", "title: Doc 1")]);
+        let config = ConfigHandle::defaults();
+
+        let use_case = BatchProcessUseCase::with_deps(Box::new(MockParser), Box::new(repo))
+            .with_config(config.clone());
+
+        // Default weights: 0.7 * 0.75 + 0.3 * 1.0
+        let before = use_case.process("**/*.md").unwrap().average_health().unwrap();
+        assert!((before - 0.825).abs() < 0.001);
+
+        let mut custom = doc_doctor_domain::CalculationConfig::default();
+        custom.health.refinement_weight = 0.5;
+        custom.health.stub_weight = 0.5;
+        config.replace(custom).unwrap();
+
+        // Replaced weights: 0.5 * 0.75 + 0.5 * 1.0
+        let after = use_case.process("**/*.md").unwrap().average_health().unwrap();
+        assert!((after - 0.875).abs() < 0.001);
+    }
 }
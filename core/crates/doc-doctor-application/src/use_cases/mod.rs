@@ -9,6 +9,9 @@ mod service;
 mod validate;
 
 pub use analyze::AnalyzeDocumentUseCase;
-pub use batch::BatchProcessUseCase;
-pub use service::DocumentService;
+pub use batch::{
+    read_for_batch, BatchCheckpoint, BatchProcessUseCase, BatchRead, BatchSink,
+    DEFAULT_MAX_FILE_SIZE,
+};
+pub use service::{DocumentService, DocumentServiceBuilder};
 pub use validate::ValidateDocumentUseCase;
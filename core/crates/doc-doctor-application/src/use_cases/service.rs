@@ -9,6 +9,8 @@ use doc_doctor_domain::{
 };
 use std::sync::Arc;
 
+use crate::config::ConfigHandle;
+
 use super::{AnalyzeDocumentUseCase, BatchProcessUseCase, ValidateDocumentUseCase};
 
 /// Document service combining all use cases
@@ -35,6 +37,15 @@ impl DocumentService {
         }
     }
 
+    /// Use a shared configuration handle for all calculations
+    pub fn with_config(self, config: ConfigHandle) -> Self {
+        Self {
+            analyze: self.analyze.with_config(config.clone()),
+            validate: self.validate.with_config(config.clone()),
+            batch: self.batch.with_config(config),
+        }
+    }
+
     /// Analyze a document (parse + calculate dimensions)
     pub fn analyze(&self, content: &str) -> Result<DocumentAnalysis, AnalysisError> {
         self.analyze.analyze(content)
@@ -56,6 +67,7 @@ pub struct DocumentServiceBuilder {
     parser: Option<Arc<dyn DocumentParser>>,
     repository: Option<Arc<dyn DocumentRepository>>,
     schema_provider: Option<Arc<dyn SchemaProvider>>,
+    config: Option<ConfigHandle>,
 }
 
 impl DocumentServiceBuilder {
@@ -65,6 +77,7 @@ impl DocumentServiceBuilder {
             parser: None,
             repository: None,
             schema_provider: None,
+            config: None,
         }
     }
 
@@ -104,6 +117,12 @@ impl DocumentServiceBuilder {
         self
     }
 
+    /// Set the shared configuration handle (defaults if not set)
+    pub fn config(mut self, config: ConfigHandle) -> Self {
+        self.config = Some(config);
+        self
+    }
+
     /// Build the service
     ///
     /// # Panics
@@ -114,15 +133,15 @@ impl DocumentServiceBuilder {
             self.repository.expect("Repository is required"),
             self.schema_provider.expect("Schema provider is required"),
         )
+        .with_config(self.config.unwrap_or_default())
     }
 
     /// Try to build the service
     pub fn try_build(self) -> Option<DocumentService> {
-        Some(DocumentService::new(
-            self.parser?,
-            self.repository?,
-            self.schema_provider?,
-        ))
+        Some(
+            DocumentService::new(self.parser?, self.repository?, self.schema_provider?)
+                .with_config(self.config.unwrap_or_default()),
+        )
     }
 }
 
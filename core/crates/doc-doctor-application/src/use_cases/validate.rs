@@ -3,17 +3,20 @@
 //! Schema validation for document frontmatter.
 
 use doc_doctor_domain::{
-    DocumentParser, SchemaError, SchemaProvider, SchemaWarning, ValidateDocument,
-    ValidationError, ValidationResult,
+    DocumentParser, L1Properties, LintFinding, ParseDiagnostic, SchemaError, SchemaProvider, SchemaWarning,
+    ValidateDocument, ValidationError, ValidationResult,
 };
 use std::sync::Arc;
 
+use crate::config::ConfigHandle;
+
 /// Validate document use case implementation
 ///
 /// Validates document content against the J-Editorial schema.
 pub struct ValidateDocumentUseCase {
     parser: Arc<dyn DocumentParser>,
     schema_provider: Arc<dyn SchemaProvider>,
+    config: ConfigHandle,
 }
 
 impl ValidateDocumentUseCase {
@@ -25,6 +28,7 @@ impl ValidateDocumentUseCase {
         Self {
             parser,
             schema_provider,
+            config: ConfigHandle::defaults(),
         }
     }
 
@@ -33,66 +37,71 @@ impl ValidateDocumentUseCase {
         parser: Box<dyn DocumentParser>,
         schema_provider: Box<dyn SchemaProvider>,
     ) -> Self {
-        Self {
-            parser: Arc::from(parser),
-            schema_provider: Arc::from(schema_provider),
-        }
+        Self::new(Arc::from(parser), Arc::from(schema_provider))
+    }
+
+    /// Use a shared configuration handle for policies and stub types
+    pub fn with_config(mut self, config: ConfigHandle) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Version of the schema documents are validated against
+    pub fn schema_version(&self) -> &str {
+        self.schema_provider.version()
     }
 
     /// Validate value ranges
-    fn validate_ranges(&self, content: &str) -> Vec<SchemaError> {
+    fn validate_ranges(&self, props: &L1Properties) -> Vec<SchemaError> {
         let mut errors = Vec::new();
 
-        // Try to parse - if it fails, that's a different error
-        if let Ok(props) = self.parser.parse(content) {
-            // Check refinement range
-            let refinement = props.refinement.value();
-            if !(0.0..=1.0).contains(&refinement) {
-                errors.push(
-                    SchemaError::new(format!(
-                        "Refinement value {} is out of range (must be 0.0-1.0)",
-                        refinement
-                    ))
-                    .with_path("/refinement"),
-                );
-            }
+        // Check refinement range
+        let refinement = props.refinement.value();
+        if !(0.0..=1.0).contains(&refinement) {
+            errors.push(
+                SchemaError::new(format!(
+                    "Refinement value {} is out of range (must be 0.0-1.0)",
+                    refinement
+                ))
+                .with_path("/refinement"),
+            );
+        }
 
-            // Check stub urgency/impact/complexity ranges
-            for (i, stub) in props.stubs.iter().enumerate() {
-                if let Some(urgency) = stub.urgency {
-                    if !(0.0..=1.0).contains(&urgency) {
-                        errors.push(
-                            SchemaError::new(format!(
-                                "Stub urgency {} is out of range (must be 0.0-1.0)",
-                                urgency
-                            ))
-                            .with_path(format!("/stubs/{}/urgency", i)),
-                        );
-                    }
+        // Check stub urgency/impact/complexity ranges
+        for (i, stub) in props.stubs.iter().enumerate() {
+            if let Some(urgency) = stub.urgency {
+                if !(0.0..=1.0).contains(&urgency) {
+                    errors.push(
+                        SchemaError::new(format!(
+                            "Stub urgency {} is out of range (must be 0.0-1.0)",
+                            urgency
+                        ))
+                        .with_path(format!("/stubs/{}/urgency", i)),
+                    );
                 }
+            }
 
-                if let Some(impact) = stub.impact {
-                    if !(0.0..=1.0).contains(&impact) {
-                        errors.push(
-                            SchemaError::new(format!(
-                                "Stub impact {} is out of range (must be 0.0-1.0)",
-                                impact
-                            ))
-                            .with_path(format!("/stubs/{}/impact", i)),
-                        );
-                    }
+            if let Some(impact) = stub.impact {
+                if !(0.0..=1.0).contains(&impact) {
+                    errors.push(
+                        SchemaError::new(format!(
+                            "Stub impact {} is out of range (must be 0.0-1.0)",
+                            impact
+                        ))
+                        .with_path(format!("/stubs/{}/impact", i)),
+                    );
                 }
+            }
 
-                if let Some(complexity) = stub.complexity {
-                    if !(0.0..=1.0).contains(&complexity) {
-                        errors.push(
-                            SchemaError::new(format!(
-                                "Stub complexity {} is out of range (must be 0.0-1.0)",
-                                complexity
-                            ))
-                            .with_path(format!("/stubs/{}/complexity", i)),
-                        );
-                    }
+            if let Some(complexity) = stub.complexity {
+                if !(0.0..=1.0).contains(&complexity) {
+                    errors.push(
+                        SchemaError::new(format!(
+                            "Stub complexity {} is out of range (must be 0.0-1.0)",
+                            complexity
+                        ))
+                        .with_path(format!("/stubs/{}/complexity", i)),
+                    );
                 }
             }
         }
@@ -100,40 +109,86 @@ impl ValidateDocumentUseCase {
         errors
     }
 
-    /// Check for warnings (non-fatal issues)
-    fn check_warnings(&self, content: &str) -> Vec<SchemaWarning> {
-        let mut warnings = Vec::new();
+    /// Check configured policies and stub vocabulary
+    fn check_policies(&self, props: &L1Properties) -> Vec<SchemaError> {
+        let config = self.config.get();
+        let policies = &config.policies;
+        let mut errors = Vec::new();
 
-        if let Ok(props) = self.parser.parse(content) {
-            // Warn if refinement is 0.0 (default)
-            if props.refinement.value() == 0.0 {
-                warnings.push(
-                    SchemaWarning::new("Refinement is 0.0 (default). Consider setting an explicit value.")
-                        .with_path("/refinement"),
+        if policies.require_title && props.title.is_none() {
+            errors.push(
+                SchemaError::new("Document has no title (required by policy)").with_path("/title"),
+            );
+        }
+
+        for (i, stub) in props.stubs.iter().enumerate() {
+            if policies.require_stub_description && stub.description.is_empty() {
+                errors.push(
+                    SchemaError::new("Stub has empty description (required by policy)")
+                        .with_path(format!("/stubs/{}/description", i)),
                 );
             }
 
-            // Warn about stubs without descriptions
-            for (i, stub) in props.stubs.iter().enumerate() {
-                if stub.description.is_empty() {
-                    warnings.push(
-                        SchemaWarning::new("Stub has empty description")
-                            .with_path(format!("/stubs/{}/description", i))
-                            .with_suggestion("Add a meaningful description for the stub"),
-                    );
-                }
+            if !config.stub_types.allow_unknown && !config.stub_types.is_known(&stub.stub_type) {
+                errors.push(
+                    SchemaError::new(format!(
+                        "Unknown stub type '{}'. Declare it under stub_types.custom or enable stub_types.allow_unknown",
+                        stub.stub_type
+                    ))
+                    .with_path(format!("/stubs/{}/type", i)),
+                );
+            }
+        }
+
+        if let Some(max) = policies.max_blocking_stubs {
+            let blocking = props.stubs.iter().filter(|s| s.is_blocking()).count();
+            if blocking > max {
+                errors.push(
+                    SchemaError::new(format!(
+                        "{} blocking stubs exceed the policy maximum of {}",
+                        blocking, max
+                    ))
+                    .with_path("/stubs"),
+                );
             }
+        }
+
+        errors
+    }
+
+    /// Check for warnings (non-fatal issues)
+    fn check_warnings(&self, props: &L1Properties) -> Vec<SchemaWarning> {
+        let policies = self.config.get().policies.clone();
+        let mut warnings = Vec::new();
+
+        // Warn if refinement is 0.0 (default)
+        if props.refinement.value() == 0.0 {
+            warnings.push(
+                SchemaWarning::new("Refinement is 0.0 (default). Consider setting an explicit value.")
+                    .with_path("/refinement"),
+            );
+        }
 
-            // Warn about missing title
-            if props.title.is_none() {
+        // Warn about stubs without descriptions
+        for (i, stub) in props.stubs.iter().enumerate() {
+            if stub.description.is_empty() && !policies.require_stub_description {
                 warnings.push(
-                    SchemaWarning::new("Document has no title")
-                        .with_path("/title")
-                        .with_suggestion("Add a title for better organization"),
+                    SchemaWarning::new("Stub has empty description")
+                        .with_path(format!("/stubs/{}/description", i))
+                        .with_suggestion("Add a meaningful description for the stub"),
                 );
             }
         }
 
+        // Warn about missing title
+        if props.title.is_none() && !policies.require_title {
+            warnings.push(
+                SchemaWarning::new("Document has no title")
+                    .with_path("/title")
+                    .with_suggestion("Add a title for better organization"),
+            );
+        }
+
         warnings
     }
 }
@@ -143,12 +198,22 @@ impl ValidateDocument for ValidateDocumentUseCase {
         let mut errors = Vec::new();
         let mut warnings = Vec::new();
 
-        // First, try to parse - collect syntax errors
-        match self.parser.parse(content) {
-            Ok(_) => {
-                // Parse succeeded, check semantic validity
-                errors.extend(self.validate_ranges(content));
-                warnings.extend(self.check_warnings(content));
+        // First, try to parse - collect syntax errors and per-entry diagnostics
+        match self.parser.parse_recovering(content) {
+            Ok(parsed) => {
+                for diagnostic in &parsed.diagnostics {
+                    if diagnostic.is_skipped() {
+                        errors.push(diagnostic_error(diagnostic));
+                    } else {
+                        warnings.push(diagnostic_warning(diagnostic));
+                    }
+                }
+
+                // Check semantic validity of what was parsed
+                let props = &parsed.properties;
+                errors.extend(self.validate_ranges(props));
+                errors.extend(self.check_policies(props));
+                warnings.extend(self.check_warnings(props));
             }
             Err(parse_err) => {
                 // Parse failed
@@ -156,10 +221,13 @@ impl ValidateDocument for ValidateDocumentUseCase {
             }
         }
 
-        // If strict mode, we might add additional checks
-        if strict {
-            // Could add unknown field detection here
-            // (already handled in parser for YAML)
+        // Format hazards are warnings, and errors in strict mode
+        for finding in self.parser.lint(content) {
+            if strict {
+                errors.push(lint_error(&finding));
+            } else {
+                warnings.push(lint_warning(&finding));
+            }
         }
 
         if errors.is_empty() {
@@ -170,6 +238,77 @@ impl ValidateDocument for ValidateDocumentUseCase {
     }
 }
 
+/// Error for an entry that was skipped while parsing
+fn diagnostic_error(diagnostic: &ParseDiagnostic) -> SchemaError {
+    let message = match &diagnostic.suggestion {
+        Some(suggestion) => format!("{} ({})", diagnostic.message, suggestion),
+        None => diagnostic.message.clone(),
+    };
+    SchemaError::new(message)
+        .with_path(json_pointer(&diagnostic.field))
+        .with_position_opt(diagnostic.position)
+}
+
+/// Warning for a value that was replaced by a default while parsing
+fn diagnostic_warning(diagnostic: &ParseDiagnostic) -> SchemaWarning {
+    let mut warning = SchemaWarning::new(&diagnostic.message).with_path(json_pointer(&diagnostic.field));
+    if let Some(position) = diagnostic.position {
+        warning = warning.with_position(position);
+    }
+    if let Some(suggestion) = &diagnostic.suggestion {
+        warning = warning.with_suggestion(suggestion);
+    }
+    warning
+}
+
+/// Error for a lint finding in strict mode
+fn lint_error(finding: &LintFinding) -> SchemaError {
+    let message = match &finding.suggestion {
+        Some(suggestion) => format!("{} ({})", finding.message, suggestion),
+        None => finding.message.clone(),
+    };
+    let error = SchemaError::new(message).with_position_opt(finding.position);
+    match &finding.field {
+        Some(field) => error.with_path(json_pointer(field)),
+        None => error,
+    }
+}
+
+/// Warning for a lint finding
+fn lint_warning(finding: &LintFinding) -> SchemaWarning {
+    let mut warning = SchemaWarning::new(&finding.message);
+    if let Some(field) = &finding.field {
+        warning = warning.with_path(json_pointer(field));
+    }
+    if let Some(position) = finding.position {
+        warning = warning.with_position(position);
+    }
+    if let Some(suggestion) = &finding.suggestion {
+        warning = warning.with_suggestion(suggestion);
+    }
+    warning
+}
+
+/// Convert a diagnostic field path (`stubs[1].priority`) to a JSON pointer
+fn json_pointer(field: &str) -> String {
+    let mut pointer = String::new();
+    for segment in field.split('.') {
+        match segment.split_once('[') {
+            Some((name, index)) => {
+                pointer.push('/');
+                pointer.push_str(name);
+                pointer.push('/');
+                pointer.push_str(index.trim_end_matches(']'));
+            }
+            None => {
+                pointer.push('/');
+                pointer.push_str(segment);
+            }
+        }
+    }
+    pointer
+}
+
 /// Extension trait for SchemaError to add optional position
 trait SchemaErrorExt {
     fn with_position_opt(self, position: Option<doc_doctor_domain::SourcePosition>) -> Self;
@@ -188,7 +327,10 @@ impl SchemaErrorExt for SchemaError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use doc_doctor_domain::{EmbeddedSchemaProvider, L1Properties, MetadataSpan, ParseError};
+    use doc_doctor_domain::{
+        EmbeddedSchemaProvider, L1Properties, LintRule, MetadataSpan, ParseError, RecoveredParse,
+        SourcePosition,
+    };
 
     /// Mock parser for testing
     struct MockParser {
@@ -261,4 +403,100 @@ mod tests {
         assert!(result.is_valid); // Warnings don't make it invalid
         assert!(!result.warnings.is_empty());
     }
+
+    /// Parser that reports a coerced stub priority and a duplicate key
+    struct CoercingParser;
+
+    impl DocumentParser for CoercingParser {
+        fn parse(&self, _content: &str) -> Result<L1Properties, ParseError> {
+            Ok(L1Properties::with_title("Doc").refinement(0.75))
+        }
+
+        fn parse_recovering(&self, content: &str) -> Result<RecoveredParse, ParseError> {
+            Ok(RecoveredParse {
+                properties: self.parse(content)?,
+                diagnostics: vec![ParseDiagnostic::coerced(
+                    "stubs[0].priority",
+                    "Unknown value 'hgih' for 'stubs[0].priority'; using 'low' instead",
+                )
+                .with_position(SourcePosition::new(5, 5, 0))
+                .with_suggestion("Did you mean 'high'?")],
+            })
+        }
+
+        fn lint(&self, _content: &str) -> Vec<LintFinding> {
+            vec![LintFinding::new(LintRule::DuplicateKey, "Duplicate key 'title'")
+                .with_field("title")
+                .with_suggestion("Remove one of the 'title' entries or merge them")]
+        }
+
+        fn extract_metadata(&self, _content: &str) -> Option<MetadataSpan> {
+            None
+        }
+
+        fn format_id(&self) -> &'static str {
+            "mock"
+        }
+    }
+
+    #[test]
+    fn test_validate_lint_strictness() {
+        let use_case = ValidateDocumentUseCase::with_deps(
+            Box::new(CoercingParser),
+            Box::new(EmbeddedSchemaProvider),
+        );
+
+        let lenient = use_case.validate("content", false).unwrap();
+        assert!(lenient.is_valid);
+        assert!(lenient.warnings.iter().any(|w| w.path.as_deref() == Some("/title")));
+
+        let strict = use_case.validate("content", true).unwrap();
+        assert!(!strict.is_valid);
+        assert!(strict.errors[0].message.contains("Duplicate key 'title'"));
+    }
+
+    #[test]
+    fn test_validate_reports_coercions() {
+        let use_case = ValidateDocumentUseCase::with_deps(
+            Box::new(CoercingParser),
+            Box::new(EmbeddedSchemaProvider),
+        );
+
+        let result = use_case.validate("content", false).unwrap();
+        assert!(result.is_valid);
+
+        let warning = &result.warnings[0];
+        assert_eq!(warning.path.as_deref(), Some("/stubs/0/priority"));
+        assert_eq!(warning.position.unwrap().line, 5);
+        assert_eq!(warning.suggestion.as_deref(), Some("Did you mean 'high'?"));
+    }
+
+    #[test]
+    fn test_json_pointer() {
+        assert_eq!(json_pointer("audience"), "/audience");
+        assert_eq!(json_pointer("stubs[1]"), "/stubs/1");
+        assert_eq!(json_pointer("stubs[1].stub_form"), "/stubs/1/stub_form");
+    }
+
+    #[test]
+    fn test_validate_policies() {
+        let mut props = L1Properties::new().refinement(0.75);
+        props.stubs.push(doc_doctor_domain::Stub::compact("mystery", "Unknown kind"));
+
+        let mut config = doc_doctor_domain::CalculationConfig::default();
+        config.policies.require_title = true;
+        config.stub_types.allow_unknown = false;
+
+        let use_case = ValidateDocumentUseCase::with_deps(
+            Box::new(MockParser::success(props)),
+            Box::new(EmbeddedSchemaProvider),
+        )
+        .with_config(ConfigHandle::new(config).unwrap());
+
+        let result = use_case.validate("content", false).unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.errors.len(), 2);
+        assert!(result.errors.iter().any(|e| e.path.as_deref() == Some("/title")));
+        assert!(result.errors.iter().any(|e| e.path.as_deref() == Some("/stubs/0/type")));
+    }
 }
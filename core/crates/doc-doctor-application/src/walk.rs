@@ -0,0 +1,538 @@
+//! Vault Walker
+//!
+//! The one walk of a vault's files behind every command and MCP tool. It
+//! skips:
+//!
+//! - hidden folders (`.obsidian/`, `.trash/`, `.git/`), unless enabled
+//! - template folders: configured ones, and the one set in Obsidian's
+//!   Templates plugin
+//! - paths listed in ignore files (`.gitignore`, `.ddignore`), which use
+//!   gitignore syntax and apply to the folder they are in
+//! - paths matching an `exclude` glob, or files matching no `include` glob
+//! - symbolic links, unless followed
+//!
+//! Listing folders is a port, [`DirectoryLister`], implemented on the file
+//! system by doc-doctor-fs. A [`Walk`] is lazy and yields files in name
+//! order, holding one folder listing per level.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use glob::{MatchOptions, Pattern};
+
+use doc_doctor_domain::{RepositoryError, RepositoryErrorKind, WalkConfig, CANVAS_EXTENSION};
+
+/// Settings of Obsidian's Templates plugin, relative to the vault root
+const OBSIDIAN_TEMPLATES: &str = ".obsidian/templates.json";
+
+/// Globs of the documents in a folder given without a pattern
+const DOCUMENT_GLOBS: [&str; 2] = ["**/*.md", "**/*.canvas"];
+
+/// `*` and `?` do not cross folders, as in shell globs
+pub(crate) const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Directory listing port
+pub trait DirectoryLister: Send + Sync {
+    /// Entries of a folder, in any order; an empty path is the current
+    /// folder
+    fn entries(&self, dir: &Path) -> Result<Vec<DirEntry>, RepositoryError>;
+
+    /// The entry at `path`, if it exists
+    fn entry(&self, path: &Path) -> Option<DirEntry>;
+
+    /// Content of a small text file (ignore files, settings)
+    fn read_to_string(&self, path: &Path) -> Option<String>;
+
+    /// Canonical path of a folder, to visit linked folders once
+    fn canonical(&self, path: &Path) -> Option<PathBuf>;
+}
+
+/// An entry of a folder
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirEntry {
+    /// File or folder name
+    pub name: String,
+    /// Whether the entry, or the target of a link, is a folder
+    pub is_dir: bool,
+    /// Whether the entry is a symbolic link
+    pub is_symlink: bool,
+}
+
+/// Split a glob into the folder to walk and the pattern below it, e.g.
+/// `vault/**/*.md` into `vault` and `**/*.md`
+///
+/// Without glob characters the pattern is a plain path.
+pub fn split_pattern(pattern: &str) -> (PathBuf, Option<String>) {
+    let parts: Vec<&str> = pattern.split('/').collect();
+    match parts.iter().position(|part| part.contains(['*', '?', '['])) {
+        Some(i) => (PathBuf::from(parts[..i].join("/")), Some(parts[i..].join("/"))),
+        None => (PathBuf::from(pattern), None),
+    }
+}
+
+/// A line of an ignore file
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    /// Folder of the ignore file, relative to the walk root
+    base: String,
+    pattern: Pattern,
+    negated: bool,
+    dir_only: bool,
+}
+
+impl IgnoreRule {
+    fn parse(base: &str, line: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+
+        // Patterns with a slash are relative to the ignore file's folder,
+        // others match names at any depth
+        let glob = match line.strip_prefix('/') {
+            Some(anchored) => anchored.to_string(),
+            None if line.contains('/') => line.to_string(),
+            None => format!("**/{}", line),
+        };
+        Some(Self {
+            base: base.to_string(),
+            pattern: Pattern::new(&glob).ok()?,
+            negated,
+            dir_only,
+        })
+    }
+
+    fn matches(&self, path: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let below = if self.base.is_empty() {
+            Some(path)
+        } else {
+            path.strip_prefix(self.base.as_str()).and_then(|rest| rest.strip_prefix('/'))
+        };
+        below.is_some_and(|below| self.pattern.matches_with(below, MATCH_OPTIONS))
+    }
+}
+
+/// Whether the last rule matching `path` ignores it
+fn ignored_by(rules: &[IgnoreRule], path: &str, is_dir: bool) -> bool {
+    rules
+        .iter()
+        .rev()
+        .find(|rule| rule.matches(path, is_dir))
+        .is_some_and(|rule| !rule.negated)
+}
+
+/// Walks vaults with the settings of a [`WalkConfig`]
+#[derive(Clone)]
+pub struct VaultWalker {
+    lister: Arc<dyn DirectoryLister>,
+    config: WalkConfig,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    /// Deepest file the includes can match, when none contains `**`
+    max_depth: Option<usize>,
+}
+
+impl VaultWalker {
+    /// Walker with the configured settings; invalid globs are ignored
+    pub fn new(lister: Arc<dyn DirectoryLister>, config: &WalkConfig) -> Self {
+        let walker = Self {
+            lister,
+            config: config.clone(),
+            include: Vec::new(),
+            exclude: patterns(&config.exclude),
+            max_depth: None,
+        };
+        walker.including(&config.include)
+    }
+
+    /// Only walk files matching one of `globs`, instead of the configured
+    /// includes
+    pub fn with_include(self, globs: &[String]) -> Result<Self, RepositoryError> {
+        for glob in globs {
+            Pattern::new(glob).map_err(|e| {
+                RepositoryError::new(
+                    RepositoryErrorKind::InvalidPath,
+                    format!("Invalid pattern '{}': {}", glob, e),
+                )
+            })?;
+        }
+        Ok(self.including(globs))
+    }
+
+    fn including(mut self, globs: &[String]) -> Self {
+        self.include = patterns(globs);
+        self.max_depth = match globs.iter().any(|glob| glob.contains("**")) {
+            true => None,
+            false => globs.iter().map(|glob| glob.split('/').count()).max(),
+        };
+        self
+    }
+
+    /// Walk the files under the folder `root`
+    pub fn walk(&self, root: &Path) -> Result<Walk, RepositoryError> {
+        let mut walk = Walk {
+            walker: self.clone(),
+            templates: self.templates(root),
+            stack: Vec::new(),
+            visited: HashSet::new(),
+            single: None,
+        };
+        walk.enter(root.to_path_buf(), String::new(), Arc::new(Vec::new()))?;
+        Ok(walk)
+    }
+
+    /// Walk the files matching a glob such as `vault/**/*.md`
+    ///
+    /// A plain file path yields that file, ignored or not; a plain folder
+    /// path yields its documents.
+    pub fn walk_pattern(&self, pattern: &str) -> Result<Walk, RepositoryError> {
+        match split_pattern(pattern) {
+            (root, Some(glob)) => self.clone().with_include(&[glob])?.walk(&root),
+            (path, None) => match self.lister.entry(&path) {
+                Some(entry) if entry.is_dir => {
+                    let globs = DOCUMENT_GLOBS.map(String::from);
+                    self.clone().including(&globs).walk(&path)
+                }
+                Some(_) => Ok(Walk::single(self.clone(), Some(path))),
+                None => Ok(Walk::single(self.clone(), None)),
+            },
+        }
+    }
+
+    /// Whether a walk of `root` skips `path`, e.g. a path reported by a
+    /// file watcher or by git
+    pub fn is_ignored(&self, root: &Path, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(root) else {
+            return false;
+        };
+        let names: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        let is_dir = self.lister.entry(path).is_some_and(|entry| entry.is_dir);
+        let templates = self.templates(root);
+
+        let mut rules = Arc::new(Vec::new());
+        let mut dir = root.to_path_buf();
+        let mut current = String::new();
+        for (i, name) in names.iter().enumerate() {
+            rules = self.ignore_rules(&dir, &current, rules);
+            current = join(&current, name);
+            dir = dir.join(name);
+            let entry = DirEntry {
+                name: name.clone(),
+                is_dir: is_dir || i + 1 < names.len(),
+                is_symlink: false,
+            };
+            if self.skips(&entry, &current, &rules, &templates) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Whether the walk skips an entry at `relative` (to the walk root)
+    fn skips(
+        &self,
+        entry: &DirEntry,
+        relative: &str,
+        rules: &[IgnoreRule],
+        templates: &[String],
+    ) -> bool {
+        if entry.is_symlink && !self.config.follow_symlinks {
+            return true;
+        }
+        if entry.is_dir {
+            if !self.config.hidden && entry.name.starts_with('.') {
+                return true;
+            }
+            if templates.iter().any(|folder| folder == relative) {
+                return true;
+            }
+        }
+        if self.exclude.iter().any(|p| p.matches_with(relative, MATCH_OPTIONS)) {
+            return true;
+        }
+        if ignored_by(rules, relative, entry.is_dir) {
+            return true;
+        }
+
+        let depth = relative.split('/').count();
+        match (entry.is_dir, self.max_depth) {
+            (true, Some(max)) => depth >= max,
+            (true, None) => false,
+            (false, Some(max)) if depth > max => true,
+            (false, _) => {
+                !self.include.is_empty()
+                    && !self.include.iter().any(|p| p.matches_with(relative, MATCH_OPTIONS))
+            }
+        }
+    }
+
+    /// `parent` with the rules of the ignore files in `dir`
+    fn ignore_rules(
+        &self,
+        dir: &Path,
+        relative: &str,
+        parent: Arc<Vec<IgnoreRule>>,
+    ) -> Arc<Vec<IgnoreRule>> {
+        let lines: Vec<IgnoreRule> = self
+            .config
+            .ignore_files
+            .iter()
+            .filter_map(|name| self.lister.read_to_string(&dir.join(name)))
+            .flat_map(|text| {
+                text.lines()
+                    .filter_map(|line| IgnoreRule::parse(relative, line))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        if lines.is_empty() {
+            return parent;
+        }
+        let mut rules = (*parent).clone();
+        rules.extend(lines);
+        Arc::new(rules)
+    }
+
+    /// Template folders relative to `root`
+    fn templates(&self, root: &Path) -> Vec<String> {
+        let obsidian = self
+            .lister
+            .read_to_string(&root.join(OBSIDIAN_TEMPLATES))
+            .and_then(|json| serde_yaml::from_str::<serde_yaml::Value>(&json).ok())
+            .and_then(|settings| settings.get("folder")?.as_str().map(str::to_string));
+        self.config
+            .templates
+            .iter()
+            .cloned()
+            .chain(obsidian)
+            .map(|folder| folder.trim_matches('/').to_string())
+            .filter(|folder| !folder.is_empty())
+            .collect()
+    }
+}
+
+/// Files of a vault walk, lazily and in name order
+pub struct Walk {
+    walker: VaultWalker,
+    templates: Vec<String>,
+    stack: Vec<Frame>,
+    /// Canonical folders entered, when following links
+    visited: HashSet<PathBuf>,
+    single: Option<PathBuf>,
+}
+
+/// A folder being walked
+struct Frame {
+    dir: PathBuf,
+    relative: String,
+    entries: std::vec::IntoIter<DirEntry>,
+    rules: Arc<Vec<IgnoreRule>>,
+}
+
+impl Walk {
+    /// A walk yielding only `path`
+    fn single(walker: VaultWalker, path: Option<PathBuf>) -> Self {
+        Self {
+            walker,
+            templates: Vec::new(),
+            stack: Vec::new(),
+            visited: HashSet::new(),
+            single: path,
+        }
+    }
+
+    fn enter(
+        &mut self,
+        dir: PathBuf,
+        relative: String,
+        rules: Arc<Vec<IgnoreRule>>,
+    ) -> Result<(), RepositoryError> {
+        if self.walker.config.follow_symlinks {
+            let canonical = self.walker.lister.canonical(&dir).unwrap_or_else(|| dir.clone());
+            if !self.visited.insert(canonical) {
+                return Ok(());
+            }
+        }
+
+        let mut entries = self.walker.lister.entries(&dir)?;
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        let rules = self.walker.ignore_rules(&dir, &relative, rules);
+        self.stack.push(Frame {
+            dir,
+            relative,
+            entries: entries.into_iter(),
+            rules,
+        });
+        Ok(())
+    }
+}
+
+impl Iterator for Walk {
+    type Item = PathBuf;
+
+    fn next(&mut self) -> Option<PathBuf> {
+        if let Some(path) = self.single.take() {
+            return Some(path);
+        }
+
+        loop {
+            let frame = self.stack.last_mut()?;
+            let Some(entry) = frame.entries.next() else {
+                self.stack.pop();
+                continue;
+            };
+            let path = frame.dir.join(&entry.name);
+            let relative = join(&frame.relative, &entry.name);
+            let rules = Arc::clone(&frame.rules);
+            if self.walker.skips(&entry, &relative, &rules, &self.templates) {
+                continue;
+            }
+
+            if entry.is_dir {
+                // Unreadable folders are skipped
+                let _ = self.enter(path, relative, rules);
+                continue;
+            }
+            return Some(path);
+        }
+    }
+}
+
+/// `relative/name`
+fn join(relative: &str, name: &str) -> String {
+    if relative.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", relative, name)
+    }
+}
+
+fn patterns(globs: &[String]) -> Vec<Pattern> {
+    globs.iter().filter_map(|glob| Pattern::new(glob).ok()).collect()
+}
+
+/// Whether `path` is a markdown document or a canvas
+pub fn is_document_path(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "md" || e == CANVAS_EXTENSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    /// Files by path; folders are implied
+    struct MemoryLister(BTreeMap<PathBuf, String>);
+
+    impl MemoryLister {
+        fn new(files: &[(&str, &str)]) -> Arc<Self> {
+            let files = files.iter().map(|(p, c)| (PathBuf::from(p), c.to_string()));
+            Arc::new(Self(files.collect()))
+        }
+    }
+
+    impl DirectoryLister for MemoryLister {
+        fn entries(&self, dir: &Path) -> Result<Vec<DirEntry>, RepositoryError> {
+            let mut entries: Vec<DirEntry> = Vec::new();
+            for path in self.0.keys() {
+                let Ok(rest) = path.strip_prefix(dir) else {
+                    continue;
+                };
+                let mut names = rest.components();
+                let name = names.next().unwrap().as_os_str().to_string_lossy().into_owned();
+                let is_dir = names.next().is_some();
+                if !entries.iter().any(|e| e.name == name) {
+                    entries.push(DirEntry {
+                        name,
+                        is_dir,
+                        is_symlink: false,
+                    });
+                }
+            }
+            Ok(entries)
+        }
+
+        fn entry(&self, path: &Path) -> Option<DirEntry> {
+            let name = path.file_name()?.to_string_lossy().into_owned();
+            let is_dir = self.0.keys().any(|p| p != path && p.starts_with(path));
+            (is_dir || self.0.contains_key(path)).then_some(DirEntry {
+                name,
+                is_dir,
+                is_symlink: false,
+            })
+        }
+
+        fn read_to_string(&self, path: &Path) -> Option<String> {
+            self.0.get(path).cloned()
+        }
+
+        fn canonical(&self, path: &Path) -> Option<PathBuf> {
+            Some(path.to_path_buf())
+        }
+    }
+
+    #[test]
+    fn test_split_pattern() {
+        let split = |p| split_pattern(p);
+        assert_eq!(split("vault/**/*.md"), ("vault".into(), Some("**/*.md".into())));
+        assert_eq!(split("*.md"), ("".into(), Some("*.md".into())));
+        assert_eq!(split("docs/a.md"), ("docs/a.md".into(), None));
+    }
+
+    #[test]
+    fn test_walk_vault() {
+        let lister = MemoryLister::new(&[
+            ("v/.gitignore", "# build output\ndrafts/\n*.tmp.md\n"),
+            ("v/.obsidian/templates.json", "{\"folder\": \"Templates\"}"),
+            ("v/.trash/old.md", ""),
+            ("v/Templates/daily.md", ""),
+            ("v/a.md", ""),
+            ("v/archive/x.md", ""),
+            ("v/drafts/d.md", ""),
+            ("v/notes/.ddignore", "/private.md\n!keep.tmp.md\n"),
+            ("v/notes/b.md", ""),
+            ("v/notes/keep.tmp.md", ""),
+            ("v/notes/private.md", ""),
+            ("v/notes/scratch.tmp.md", ""),
+        ]);
+        let config = WalkConfig {
+            exclude: vec!["archive/**".to_string()],
+            ..Default::default()
+        };
+        let walker = VaultWalker::new(lister, &config);
+
+        let files: Vec<PathBuf> = walker.walk(Path::new("v")).unwrap().collect();
+        let expected = ["v/.gitignore", "v/a.md", "v/notes/.ddignore", "v/notes/b.md"];
+        let mut expected: Vec<PathBuf> = expected.iter().map(PathBuf::from).collect();
+        expected.push("v/notes/keep.tmp.md".into());
+        assert_eq!(files, expected);
+
+        assert!(walker.is_ignored(Path::new("v"), Path::new("v/drafts/d.md")));
+        assert!(walker.is_ignored(Path::new("v"), Path::new("v/notes/private.md")));
+        assert!(!walker.is_ignored(Path::new("v"), Path::new("v/notes/b.md")));
+
+        let top: Vec<PathBuf> = walker.walk_pattern("v/*.md").unwrap().collect();
+        assert_eq!(top, [PathBuf::from("v/a.md")]);
+        let single: Vec<PathBuf> = walker.walk_pattern("v/drafts/d.md").unwrap().collect();
+        assert_eq!(single, [PathBuf::from("v/drafts/d.md")]);
+        assert_eq!(walker.walk_pattern("v/none.md").unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,336 @@
+//! Vault Watch
+//!
+//! Live recomputation for watch mode. File system events arrive from an
+//! inbound adapter as changed paths; [`VaultWatch`] re-analyzes them through
+//! the vault index and turns the difference between the old and new entries
+//! into [`VaultEvent`]s, which it publishes to every subscriber.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Mutex;
+
+use serde::Serialize;
+
+use doc_doctor_domain::{AudienceGatesConfig, DocumentRepository, Stub};
+
+use crate::index::{IndexEntry, IndexStore, VaultIndex};
+use crate::inline::same_stub;
+use crate::switchboard::Switchboard;
+
+/// Something that changed in a watched vault
+///
+/// Paths are vault-relative. Serialized with an `event` tag, one event per
+/// line for NDJSON streams.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum VaultEvent {
+    /// A document was created or its analysis changed
+    DocumentChanged {
+        path: String,
+        health: f64,
+        /// Health before the change; absent for new documents
+        previous_health: Option<f64>,
+        refinement: f64,
+        stub_count: usize,
+    },
+    /// A document was deleted
+    DocumentRemoved { path: String },
+    /// Health moved across the gate of the document's audience
+    HealthGateCrossed {
+        path: String,
+        audience: String,
+        gate: f64,
+        health: f64,
+        /// True when health rose to the gate, false when it fell below
+        passed: bool,
+    },
+    /// A stub appeared in a document
+    StubAdded {
+        path: String,
+        stub_type: String,
+        description: String,
+        anchor: Option<String>,
+    },
+    /// A stub was removed from a document
+    StubResolved {
+        path: String,
+        stub_type: String,
+        description: String,
+    },
+    /// A stub anchor no longer has a block ID in the body
+    AnchorOrphaned {
+        path: String,
+        anchor: String,
+        description: String,
+    },
+}
+
+impl VaultEvent {
+    /// Vault-relative path of the document the event is about
+    pub fn path(&self) -> &str {
+        match self {
+            Self::DocumentChanged { path, .. }
+            | Self::DocumentRemoved { path }
+            | Self::HealthGateCrossed { path, .. }
+            | Self::StubAdded { path, .. }
+            | Self::StubResolved { path, .. }
+            | Self::AnchorOrphaned { path, .. } => path,
+        }
+    }
+}
+
+/// Events for a document whose index entry went from `before` to `after`
+///
+/// Nothing is reported when the analysis is unchanged, e.g. after a save
+/// that only touched the file.
+pub fn vault_events(
+    path: &str,
+    before: Option<&IndexEntry>,
+    after: Option<&IndexEntry>,
+    gates: &AudienceGatesConfig,
+) -> Vec<VaultEvent> {
+    let path = path.to_string();
+    let Some(after) = after else {
+        return match before {
+            Some(_) => vec![VaultEvent::DocumentRemoved { path }],
+            None => Vec::new(),
+        };
+    };
+    if before.is_some_and(|b| b.hash == after.hash) {
+        return Vec::new();
+    }
+
+    let props = &after.properties;
+    let health = after.dimensions.health;
+    let previous_health = before.map(|b| b.dimensions.health);
+    let mut events = vec![VaultEvent::DocumentChanged {
+        path: path.clone(),
+        health,
+        previous_health,
+        refinement: props.refinement.value(),
+        stub_count: props.stubs.len(),
+    }];
+
+    let audience = props.audience.to_string();
+    if let (Some(previous), Some(gate)) = (previous_health, gates.get(&audience)) {
+        if (previous < gate) != (health < gate) {
+            events.push(VaultEvent::HealthGateCrossed {
+                path: path.clone(),
+                audience,
+                gate,
+                health,
+                passed: health >= gate,
+            });
+        }
+    }
+
+    let old_stubs = before.map_or(&[][..], |b| &b.properties.stubs[..]);
+    for stub in props.stubs.iter().filter(|s| !old_stubs.iter().any(|o| same_stub(o, s))) {
+        events.push(VaultEvent::StubAdded {
+            path: path.clone(),
+            stub_type: stub.stub_type.as_str().to_string(),
+            description: stub.description.clone(),
+            anchor: stub.anchor.clone(),
+        });
+    }
+    for stub in old_stubs.iter().filter(|o| !props.stubs.iter().any(|s| same_stub(o, s))) {
+        events.push(VaultEvent::StubResolved {
+            path: path.clone(),
+            stub_type: stub.stub_type.as_str().to_string(),
+            description: stub.description.clone(),
+        });
+    }
+
+    let old_orphans = before.map(orphaned_anchors).unwrap_or_default();
+    for (anchor, stub) in orphaned_anchors(after) {
+        if !old_orphans.iter().any(|(a, _)| *a == anchor) {
+            events.push(VaultEvent::AnchorOrphaned {
+                path: path.clone(),
+                anchor,
+                description: stub.description.clone(),
+            });
+        }
+    }
+
+    events
+}
+
+/// Stub anchors without a matching block ID
+fn orphaned_anchors(entry: &IndexEntry) -> Vec<(String, &Stub)> {
+    if entry.canvas {
+        return Vec::new();
+    }
+    let mut orphans = Vec::new();
+    for stub in &entry.properties.stubs {
+        for anchor in stub.anchor.iter().chain(&stub.inline_anchors) {
+            let id = anchor.trim_start_matches('^');
+            if !entry.anchors.iter().any(|a| a == id) {
+                orphans.push((anchor.clone(), stub));
+            }
+        }
+    }
+    orphans
+}
+
+/// Fan-out of vault events to subscribers
+///
+/// Subscribers that hang up are dropped on the next publish.
+#[derive(Debug, Default)]
+pub struct EventBus {
+    subscribers: Mutex<Vec<Sender<VaultEvent>>>,
+}
+
+impl EventBus {
+    /// Create a bus without subscribers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Receive every event published from now on
+    pub fn subscribe(&self) -> Receiver<VaultEvent> {
+        let (sender, receiver) = channel();
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    /// Send an event to every subscriber
+    pub fn publish(&self, event: &VaultEvent) {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        subscribers.retain(|s| s.send(event.clone()).is_ok());
+    }
+}
+
+/// A vault kept up to date from file system changes
+pub struct VaultWatch {
+    root: PathBuf,
+    index: VaultIndex,
+    store: Option<Box<dyn IndexStore>>,
+    bus: EventBus,
+}
+
+impl VaultWatch {
+    /// Watch the vault at `root`, starting from an up-to-date `index`
+    pub fn new(root: impl Into<PathBuf>, index: VaultIndex) -> Self {
+        Self {
+            root: root.into(),
+            index,
+            store: None,
+            bus: EventBus::new(),
+        }
+    }
+
+    /// Store the index after every update
+    pub fn with_store(mut self, store: Box<dyn IndexStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Vault root
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Current index
+    pub fn index(&self) -> &VaultIndex {
+        &self.index
+    }
+
+    /// Receive the events of every later update
+    pub fn subscribe(&self) -> Receiver<VaultEvent> {
+        self.bus.subscribe()
+    }
+
+    /// Re-analyze the documents at `paths` and publish what changed
+    ///
+    /// Paths that no longer exist are reported as removed. Returns the
+    /// events in the order they were published.
+    pub fn update(
+        &mut self,
+        switchboard: &dyn Switchboard,
+        repository: &dyn DocumentRepository,
+        paths: &[PathBuf],
+    ) -> Vec<VaultEvent> {
+        let before: BTreeMap<String, Option<IndexEntry>> = paths
+            .iter()
+            .map(|p| {
+                let key = VaultIndex::key(&self.root, p);
+                let entry = self.index.entries.get(&key).cloned();
+                (key, entry)
+            })
+            .collect();
+
+        let existing: Vec<PathBuf> = paths.iter().filter(|p| repository.exists(p)).cloned().collect();
+        let refresh = self.index.refresh(switchboard, repository, &self.root, &existing, u64::MAX);
+        if refresh.changed() {
+            if let Some(store) = &self.store {
+                // The index in memory stays authoritative; a later update retries
+                let _ = store.save(&self.index);
+            }
+        }
+
+        let gates = &switchboard.config().audience_gates;
+        let mut events = Vec::new();
+        for (key, before) in &before {
+            let after = self.index.entries.get(key);
+            events.extend(vault_events(key, before.as_ref(), after, gates));
+        }
+        for event in &events {
+            self.bus.publish(event);
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_domain::{L1Properties, Refinement, StateDimensions, StubForm};
+
+    fn entry(hash: &str, refinement: f64, stubs: Vec<Stub>, anchors: &[&str]) -> IndexEntry {
+        let properties = L1Properties {
+            refinement: Refinement::new_clamped(refinement),
+            stubs,
+            ..Default::default()
+        };
+        IndexEntry {
+            modified: None,
+            size: 0,
+            hash: hash.to_string(),
+            canvas: false,
+            dimensions: StateDimensions::calculate(&properties),
+            properties,
+            diagnostics: Vec::new(),
+            partial: false,
+            anchors: anchors.iter().map(|a| a.to_string()).collect(),
+            links: Vec::new(),
+            warning: None,
+        }
+    }
+
+    #[test]
+    fn test_vault_events() {
+        let gates = AudienceGatesConfig::default();
+        let mut anchored = Stub::compact("expand", "add examples");
+        anchored.anchor = Some("^examples".to_string());
+        let mut blocking = Stub::compact("fix", "broken link");
+        blocking.stub_form = StubForm::Blocking;
+
+        let before = entry("a", 0.3, vec![blocking, anchored.clone()], &["examples"]);
+        let after = entry("b", 0.95, vec![anchored, Stub::compact("cite", "source")], &[]);
+
+        let events = vault_events("notes/a.md", Some(&before), Some(&after), &gates);
+        assert_eq!(events.len(), 5);
+        assert!(matches!(&events[0], VaultEvent::DocumentChanged { previous_health: Some(_), .. }));
+        assert!(matches!(&events[1], VaultEvent::HealthGateCrossed { passed: true, .. }));
+        assert!(matches!(&events[2], VaultEvent::StubAdded { stub_type, .. } if stub_type == "cite"));
+        assert!(matches!(&events[3], VaultEvent::StubResolved { stub_type, .. } if stub_type == "fix"));
+        assert!(matches!(&events[4], VaultEvent::AnchorOrphaned { anchor, .. } if anchor == "^examples"));
+
+        assert!(vault_events("notes/a.md", Some(&after), Some(&after), &gates).is_empty());
+        assert_eq!(
+            vault_events("notes/a.md", Some(&after), None, &gates),
+            [VaultEvent::DocumentRemoved { path: "notes/a.md".to_string() }]
+        );
+    }
+}
@@ -0,0 +1,34 @@
+//! Obsidian Canvas Parser
+//!
+//! Implements the `CanvasParser` trait from the domain layer for `.canvas`
+//! files in the JSON Canvas format.
+//!
+//! # Features
+//!
+//! - Text, file, link and group cards
+//! - Edges with their labels
+//! - Unknown card types and extra fields are ignored, so canvases written
+//!   by newer Obsidian versions still parse
+//!
+//! # Example
+//!
+//! ```rust
+//! use doc_doctor_canvas::JsonCanvasParser;
+//! use doc_doctor_domain::CanvasParser;
+//!
+//! let content = r#"{
+//!   "nodes": [
+//!     {"id": "a", "type": "text", "text": "- [ ] draft intro", "x": 0, "y": 0, "width": 250, "height": 60},
+//!     {"id": "b", "type": "file", "file": "Notes/Plan.md", "x": 300, "y": 0, "width": 400, "height": 400}
+//!   ],
+//!   "edges": [{"id": "e", "fromNode": "a", "toNode": "b"}]
+//! }"#;
+//!
+//! let canvas = JsonCanvasParser::new().parse_canvas(content).unwrap();
+//! assert_eq!(canvas.files(), vec!["Notes/Plan.md"]);
+//! assert_eq!(canvas.edges[0].to_node, "b");
+//! ```
+
+mod parser;
+
+pub use parser::JsonCanvasParser;
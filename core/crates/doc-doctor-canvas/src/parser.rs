@@ -0,0 +1,136 @@
+//! JSON Canvas Parser Implementation
+
+use serde::Deserialize;
+
+use doc_doctor_domain::{
+    strip_bom, Canvas, CanvasEdge, CanvasNode, CanvasNodeKind, CanvasParser, ParseError,
+    SourcePosition,
+};
+
+/// JSON Canvas parser
+#[derive(Debug, Clone, Default)]
+pub struct JsonCanvasParser;
+
+impl JsonCanvasParser {
+    /// Create a new canvas parser
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// A canvas as stored on disk
+#[derive(Deserialize)]
+struct RawCanvas {
+    nodes: Vec<RawNode>,
+    #[serde(default)]
+    edges: Vec<RawEdge>,
+}
+
+#[derive(Deserialize)]
+struct RawNode {
+    id: String,
+    #[serde(rename = "type")]
+    node_type: String,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    file: Option<String>,
+    #[serde(default)]
+    subpath: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    label: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawEdge {
+    id: String,
+    from_node: String,
+    to_node: String,
+    #[serde(default)]
+    label: Option<String>,
+}
+
+impl RawNode {
+    /// Domain node, or `None` for card types this parser does not know
+    fn into_node(self) -> Option<CanvasNode> {
+        let kind = match self.node_type.as_str() {
+            "text" => CanvasNodeKind::Text {
+                text: self.text.unwrap_or_default(),
+            },
+            "file" => CanvasNodeKind::File {
+                file: self.file?,
+                subpath: self.subpath,
+            },
+            "link" => CanvasNodeKind::Link { url: self.url? },
+            "group" => CanvasNodeKind::Group { label: self.label },
+            _ => return None,
+        };
+        Some(CanvasNode { id: self.id, kind })
+    }
+}
+
+impl CanvasParser for JsonCanvasParser {
+    fn parse_canvas(&self, content: &str) -> Result<Canvas, ParseError> {
+        let raw: RawCanvas = serde_json::from_str(strip_bom(content)).map_err(|e| {
+            let offset = content.len() - strip_bom(content).len();
+            ParseError::new(format!("Invalid canvas: {}", e))
+                .with_position(SourcePosition::new(e.line(), e.column(), offset))
+        })?;
+
+        Ok(Canvas {
+            nodes: raw.nodes.into_iter().filter_map(RawNode::into_node).collect(),
+            edges: raw
+                .edges
+                .into_iter()
+                .map(|e| CanvasEdge {
+                    id: e.id,
+                    from_node: e.from_node,
+                    to_node: e.to_node,
+                    label: e.label,
+                })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_canvas() {
+        let content = r##"{
+            "nodes": [
+                {"id": "t", "type": "text", "text": "TODO: check", "x": 0, "y": 0, "color": "1"},
+                {"id": "f", "type": "file", "file": "Plan.md", "subpath": "#Goals"},
+                {"id": "l", "type": "link", "url": "https://example.com"},
+                {"id": "g", "type": "group", "label": "Q3"},
+                {"id": "x", "type": "future-card"}
+            ],
+            "edges": [{"id": "e", "fromNode": "t", "toNode": "f", "label": "blocks"}]
+        }"##;
+        let canvas = JsonCanvasParser::new().parse_canvas(content).unwrap();
+
+        assert_eq!(canvas.nodes.len(), 4);
+        assert_eq!(canvas.text_cards().collect::<Vec<_>>(), vec![("t", "TODO: check")]);
+        assert_eq!(
+            canvas.node("f").unwrap().kind,
+            CanvasNodeKind::File {
+                file: "Plan.md".to_string(),
+                subpath: Some("#Goals".to_string()),
+            }
+        );
+        assert_eq!(canvas.edges[0].label.as_deref(), Some("blocks"));
+    }
+
+    #[test]
+    fn test_not_a_canvas() {
+        let parser = JsonCanvasParser::new();
+        assert!(parser.parse_canvas("{\"title\": \"Note\"}\n# Body").is_err());
+        assert!(parser.parse_canvas("{\"title\": \"Note\"}").is_err());
+        assert!(parser.parse_canvas("{\"nodes\": []}").unwrap().nodes.is_empty());
+    }
+}
@@ -0,0 +1,111 @@
+//! Annotations Command
+//!
+//! List and export comments (`%% %%`, `<!-- -->`) and highlights
+//! (`==text==`) across documents.
+
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use std::path::PathBuf;
+
+use doc_doctor_application::{annotations_markdown, DocumentAnnotations, Switchboard};
+
+use crate::commands::{create_switchboard, read_file, walk_pattern};
+use crate::output::{format_output, AnnotationFileOutput, AnnotationOutput, AnnotationsOutput, OutputFormat};
+
+/// Export format for `--export`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    /// One section per document
+    Markdown,
+    /// Same structure as `--format json`
+    Json,
+}
+
+#[derive(Args)]
+pub struct AnnotationsCommand {
+    /// File pattern (glob)
+    pub pattern: String,
+
+    /// Only annotations with this label (e.g. "verify")
+    #[arg(long)]
+    pub label: Option<String>,
+
+    /// Export instead of listing
+    #[arg(long, value_enum)]
+    pub export: Option<ExportFormat>,
+
+    /// Write the export to a file instead of stdout
+    #[arg(short, long, requires = "export")]
+    pub output: Option<PathBuf>,
+}
+
+impl AnnotationsCommand {
+    pub fn run(&self, format: OutputFormat, verbose: bool) -> Result<()> {
+        let switchboard = create_switchboard();
+        let paths = walk_pattern(&self.pattern)?;
+
+        if paths.is_empty() {
+            println!("No files match pattern: {}", self.pattern);
+            return Ok(());
+        }
+
+        let mut documents = Vec::new();
+        for path in paths {
+            if verbose {
+                eprintln!("Reading annotations: {}", path.display());
+            }
+
+            let content = read_file(&path)?;
+            let mut annotations = switchboard
+                .parse_body(&content)
+                .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?
+                .annotations;
+            if let Some(label) = &self.label {
+                annotations.retain(|a| a.label.as_deref().is_some_and(|l| l.eq_ignore_ascii_case(label)));
+            }
+            documents.push(DocumentAnnotations {
+                path: path.display().to_string(),
+                annotations,
+            });
+        }
+
+        let rendered = match self.export {
+            Some(ExportFormat::Markdown) => annotations_markdown(&documents),
+            Some(ExportFormat::Json) => serde_json::to_string_pretty(&to_output(&documents))?,
+            None => format_output(&to_output(&documents), format)?,
+        };
+
+        match &self.output {
+            Some(output) => std::fs::write(output, rendered)
+                .map_err(|e| anyhow::anyhow!("Failed to write '{}': {}", output.display(), e))?,
+            None => println!("{}", rendered),
+        }
+        Ok(())
+    }
+}
+
+fn to_output(documents: &[DocumentAnnotations]) -> AnnotationsOutput {
+    let files: Vec<_> = documents
+        .iter()
+        .filter(|d| !d.annotations.is_empty())
+        .map(|d| AnnotationFileOutput {
+            path: d.path.clone(),
+            annotations: d
+                .annotations
+                .iter()
+                .map(|a| AnnotationOutput {
+                    kind: a.kind.to_string(),
+                    label: a.label.clone(),
+                    text: a.text.clone(),
+                    line: a.position.line,
+                    column: a.position.column,
+                })
+                .collect(),
+        })
+        .collect();
+
+    AnnotationsOutput {
+        total: files.iter().map(|f| f.annotations.len()).sum(),
+        files,
+    }
+}
@@ -1,22 +1,57 @@
 //! Batch Command
 //!
-//! Process multiple documents matching a glob pattern.
+//! Process multiple documents matching glob patterns. Obsidian canvases
+//! (`.canvas`) are analyzed with the stubs of their text cards. Paths the
+//! `walk` settings ignore (`.gitignore`, `.ddignore`, templates) are skipped.
+//!
+//! Analyses are kept in the index of the folder the patterns walk
+//! (`.doc-doctor/index.json`), the one `ddoc query` and `ddoc report` use
+//! for that folder, so later runs only parse changed files. Patterns
+//! without a common folder are analyzed without the index.
+//!
+//! For very large archives, `--stream` walks the patterns lazily and prints
+//! one JSON record per document (NDJSON) as chunks finish, ending with a
+//! summary record. Memory stays bounded, and with `--checkpoint` an
+//! interrupted run resumes where it stopped. A resumed run only prints the
+//! remaining records, so append its output (`>>`) rather than overwrite it:
+//!
+//! ```bash
+//! ddoc batch "archive/**/*.md" --stream --checkpoint batch.json >> results.ndjson
+//! ```
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Args;
-use glob::glob;
 use rayon::prelude::*;
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::io::{BufWriter, Stdout, Write};
+use std::path::{Component, Path, PathBuf};
+
+use doc_doctor_application::{
+    read_for_batch, split_pattern, BatchCheckpoint, BatchRead, BatchSink, Switchboard,
+    VaultIndex, DEFAULT_MAX_FILE_SIZE,
+};
+use doc_doctor_domain::{
+    is_canvas, AnalyzeDocument, BatchDocumentResult, BatchError, BatchSummary,
+};
+use doc_doctor_fs::JsonIndexStore;
 
-use doc_doctor_domain::AnalyzeDocument;
+use crate::commands::{
+    create_analyze_use_case, create_batch_use_case, create_parser, create_repository,
+    create_switchboard, create_walker, pattern_walk, read_file, walk_pattern,
+};
+use crate::output::{
+    format_output, BatchDocumentOutput, BatchOutput, BatchRecord, BatchSummaryOutput,
+    OutputFormat,
+};
 
-use crate::commands::{create_analyze_use_case, create_parser, read_file};
-use crate::output::{format_output, BatchDocumentOutput, BatchOutput, OutputFormat};
+/// Documents per thread in each chunk of a streaming batch
+const STREAM_CHUNK_PER_JOB: usize = 64;
 
 #[derive(Args)]
 pub struct BatchCommand {
-    /// File pattern (glob)
-    pub pattern: String,
+    /// File patterns (glob), e.g. "vault/**/*.md" "vault/**/*.canvas"
+    #[arg(required = true)]
+    pub patterns: Vec<String>,
 
     /// Include L2 dimensions
     #[arg(long)]
@@ -25,23 +60,54 @@ pub struct BatchCommand {
     /// Number of parallel jobs
     #[arg(short, long, default_value = "4")]
     pub jobs: usize,
+
+    /// Read only the first N MiB of larger files (their frontmatter is still analyzed)
+    #[arg(long, value_name = "MIB", default_value_t = DEFAULT_MAX_FILE_SIZE / (1024 * 1024))]
+    pub max_file_size: u64,
+
+    /// Analyze every file instead of reusing the index
+    #[arg(long)]
+    pub no_index: bool,
+
+    /// Print NDJSON records as documents are processed, with bounded memory
+    /// (does not use the index)
+    #[arg(long)]
+    pub stream: bool,
+
+    /// Checkpoint file of a streaming batch: resumed from when present,
+    /// removed when the batch completes. A resumed run only prints the
+    /// records after the checkpoint, so append to earlier output
+    #[arg(long, value_name = "FILE", requires = "stream")]
+    pub checkpoint: Option<PathBuf>,
 }
 
 impl BatchCommand {
     pub fn run(&self, format: OutputFormat, verbose: bool) -> Result<()> {
+        let patterns = self.patterns.join(" ");
         if verbose {
-            eprintln!("Batch processing: {}", self.pattern);
+            eprintln!("Batch processing: {}", patterns);
+        }
+
+        // Configure thread pool
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(self.jobs)
+            .build_global()
+            .ok(); // Ignore if already set
+
+        if self.stream {
+            return self.run_stream(verbose);
         }
 
         // Collect paths
-        let paths: Vec<PathBuf> = glob(&self.pattern)
-            .map_err(|e| anyhow::anyhow!("Invalid pattern: {}", e))?
-            .filter_map(|p| p.ok())
-            .filter(|p| p.is_file())
-            .collect();
+        let mut paths: Vec<PathBuf> = Vec::new();
+        for pattern in &self.patterns {
+            paths.extend(walk_pattern(pattern)?);
+        }
+        paths.sort();
+        paths.dedup();
 
         if paths.is_empty() {
-            println!("No files match pattern: {}", self.pattern);
+            println!("No files match pattern: {}", patterns);
             return Ok(());
         }
 
@@ -49,14 +115,11 @@ impl BatchCommand {
             eprintln!("Found {} files", paths.len());
         }
 
-        // Configure thread pool
-        rayon::ThreadPoolBuilder::new()
-            .num_threads(self.jobs)
-            .build_global()
-            .ok(); // Ignore if already set
-
         // Process documents in parallel
-        let results: Vec<BatchDocumentOutput> = if self.dimensions {
+        let index_root = index_root(&self.patterns).filter(|_| !self.no_index);
+        let results: Vec<BatchDocumentOutput> = if let Some(root) = index_root {
+            self.process_indexed(&root, &paths, verbose)
+        } else if self.dimensions {
             self.process_with_dimensions(&paths, verbose)
         } else {
             self.process_parse_only(&paths, verbose)
@@ -65,6 +128,7 @@ impl BatchCommand {
         // Calculate statistics
         let succeeded = results.iter().filter(|r| r.success).count();
         let failed = results.len() - succeeded;
+        let partial = results.iter().filter(|r| r.partial).count();
 
         let average_health = if self.dimensions {
             let healths: Vec<f64> = results.iter().filter_map(|r| r.health).collect();
@@ -81,6 +145,7 @@ impl BatchCommand {
             total: results.len(),
             succeeded,
             failed,
+            partial,
             average_health,
             results,
         };
@@ -89,8 +154,59 @@ impl BatchCommand {
         Ok(())
     }
 
+    fn process_indexed(
+        &self,
+        root: &Path,
+        paths: &[PathBuf],
+        verbose: bool,
+    ) -> Vec<BatchDocumentOutput> {
+        let store = JsonIndexStore::for_vault(root);
+        let switchboard = create_switchboard();
+        let max_bytes = self.max_file_size.saturating_mul(1024 * 1024);
+        let (index, refresh) = VaultIndex::refresh_stored(
+            &store,
+            &*switchboard,
+            &create_repository(),
+            root,
+            paths,
+            max_bytes,
+        );
+
+        if verbose {
+            eprintln!(
+                "Index: {} reused, {} analyzed, {} removed",
+                refresh.reused + refresh.rehashed,
+                refresh.analyzed,
+                refresh.removed
+            );
+        }
+        if let Some(error) = &refresh.store_error {
+            eprintln!("Warning: could not store the index: {}", error);
+        }
+
+        paths
+            .iter()
+            .map(|path| match index.get(root, path) {
+                Some(entry) => BatchDocumentOutput {
+                    path: path.display().to_string(),
+                    success: true,
+                    partial: entry.partial,
+                    health: self.dimensions.then_some(entry.dimensions.health),
+                    error: None,
+                    diagnostics: entry.diagnostics.iter().map(|d| d.to_string()).collect(),
+                    warnings: entry.warning.iter().cloned().collect(),
+                },
+                None => {
+                    let error = refresh.failure(path).unwrap_or("Not indexed");
+                    BatchDocumentOutput::failure(path, error)
+                }
+            })
+            .collect()
+    }
+
     fn process_with_dimensions(&self, paths: &[PathBuf], verbose: bool) -> Vec<BatchDocumentOutput> {
         let use_case = create_analyze_use_case();
+        let switchboard = create_switchboard();
 
         paths
             .par_iter()
@@ -99,27 +215,31 @@ impl BatchCommand {
                     eprintln!("  Processing: {}", path.display());
                 }
 
-                match read_file(path) {
-                    Ok(content) => match use_case.analyze(&content) {
+                let analyze = |content: &str| {
+                    if is_canvas(path) {
+                        switchboard
+                            .analyze_canvas(content)
+                            .map(|c| c.analysis)
+                            .map_err(|e| e.to_string())
+                    } else {
+                        use_case.analyze(content).map_err(|e| e.to_string())
+                    }
+                };
+
+                match self.read(path) {
+                    Ok(read) => match analyze(&read.content) {
                         Ok(analysis) => BatchDocumentOutput {
                             path: path.display().to_string(),
                             success: true,
+                            partial: analysis.partial,
                             health: Some(analysis.dimensions.health),
                             error: None,
+                            diagnostics: analysis.diagnostics.iter().map(|d| d.to_string()).collect(),
+                            warnings: read.warning.into_iter().collect(),
                         },
-                        Err(e) => BatchDocumentOutput {
-                            path: path.display().to_string(),
-                            success: false,
-                            health: None,
-                            error: Some(e.to_string()),
-                        },
-                    },
-                    Err(e) => BatchDocumentOutput {
-                        path: path.display().to_string(),
-                        success: false,
-                        health: None,
-                        error: Some(e.to_string()),
+                        Err(e) => BatchDocumentOutput::failure(path, with_warning(e, &read)),
                     },
+                    Err(e) => BatchDocumentOutput::failure(path, e),
                 }
             })
             .collect()
@@ -127,6 +247,7 @@ impl BatchCommand {
 
     fn process_parse_only(&self, paths: &[PathBuf], verbose: bool) -> Vec<BatchDocumentOutput> {
         let parser = create_parser();
+        let switchboard = create_switchboard();
 
         paths
             .par_iter()
@@ -135,29 +256,271 @@ impl BatchCommand {
                     eprintln!("  Processing: {}", path.display());
                 }
 
-                match read_file(path) {
-                    Ok(content) => match parser.parse(&content) {
-                        Ok(_) => BatchDocumentOutput {
+                let parse = |content: &str| {
+                    if is_canvas(path) {
+                        switchboard
+                            .analyze_canvas(content)
+                            .map(|c| (c.analysis.partial, c.analysis.diagnostics))
+                            .map_err(|e| e.to_string())
+                    } else {
+                        parser
+                            .parse_recovering(content)
+                            .map(|p| (p.is_partial(), p.diagnostics))
+                            .map_err(|e| e.to_string())
+                    }
+                };
+
+                match self.read(path) {
+                    Ok(read) => match parse(&read.content) {
+                        Ok((partial, diagnostics)) => BatchDocumentOutput {
                             path: path.display().to_string(),
                             success: true,
+                            partial,
                             health: None,
                             error: None,
+                            diagnostics: diagnostics.iter().map(|d| d.to_string()).collect(),
+                            warnings: read.warning.into_iter().collect(),
                         },
-                        Err(e) => BatchDocumentOutput {
-                            path: path.display().to_string(),
-                            success: false,
-                            health: None,
-                            error: Some(e.to_string()),
-                        },
-                    },
-                    Err(e) => BatchDocumentOutput {
-                        path: path.display().to_string(),
-                        success: false,
-                        health: None,
-                        error: Some(e.to_string()),
+                        Err(e) => BatchDocumentOutput::failure(path, with_warning(e, &read)),
                     },
+                    Err(e) => BatchDocumentOutput::failure(path, e),
                 }
             })
             .collect()
     }
 }
+
+impl BatchCommand {
+    /// Stream NDJSON records while walking the patterns
+    fn run_stream(&self, verbose: bool) -> Result<()> {
+        let checkpoint = match &self.checkpoint {
+            Some(path) if path.exists() => {
+                let checkpoint: BatchCheckpoint = serde_json::from_str(&read_file(path)?)
+                    .with_context(|| format!("Invalid checkpoint '{}'", path.display()))?;
+                if checkpoint.patterns != self.patterns {
+                    anyhow::bail!(
+                        "Checkpoint '{}' is for the patterns {:?}",
+                        path.display(),
+                        checkpoint.patterns
+                    );
+                }
+                if verbose {
+                    eprintln!("Resuming after {} documents", checkpoint.processed);
+                }
+                checkpoint
+            }
+            _ => BatchCheckpoint {
+                patterns: self.patterns.clone(),
+                ..Default::default()
+            },
+        };
+
+        let walker = create_walker();
+        let walks = self
+            .patterns
+            .iter()
+            .map(|pattern| pattern_walk(&walker, pattern))
+            .collect::<Result<Vec<_>>>()?;
+        // Only overlapping patterns need the paths seen so far
+        let overlapping = self.patterns.len() > 1;
+        let mut seen = HashSet::new();
+        let paths = walks
+            .into_iter()
+            .flatten()
+            .filter(move |p| !overlapping || seen.insert(p.clone()));
+
+        let max_bytes = self.max_file_size.saturating_mul(1024 * 1024);
+        let mut sink = NdjsonSink {
+            out: BufWriter::new(std::io::stdout()),
+            dimensions: self.dimensions,
+            checkpoint: self.checkpoint.clone(),
+        };
+        let chunk_size = self.jobs.max(1) * STREAM_CHUNK_PER_JOB;
+        let done = create_batch_use_case(max_bytes)
+            .process_stream(paths, chunk_size, checkpoint, &mut sink)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+        sink.summary(&done.summary)?;
+        if let Some(path) = &self.checkpoint {
+            std::fs::remove_file(path).ok();
+        }
+        Ok(())
+    }
+
+    /// Read a document, only its start when it exceeds `--max-file-size`
+    fn read(&self, path: &Path) -> Result<BatchRead> {
+        let max_bytes = self.max_file_size.saturating_mul(1024 * 1024);
+        read_for_batch(&create_repository(), path, max_bytes)
+            .map_err(|e| anyhow::anyhow!("Failed to read '{}': {}", path.display(), e.message))
+    }
+}
+
+/// Folder holding the walk roots of every pattern, whose index a batch
+/// uses; none when the patterns share no folder, e.g. relative and absolute
+/// ones
+fn index_root(patterns: &[String]) -> Option<PathBuf> {
+    let roots: Vec<PathBuf> = patterns
+        .iter()
+        .map(|pattern| match split_pattern(pattern) {
+            (path, None) if path.is_file() => {
+                path.parent().map(Path::to_path_buf).unwrap_or_default()
+            }
+            (root, _) => root,
+        })
+        .collect();
+    let (first, rest) = roots.split_first()?;
+    if rest.iter().any(|root| root.has_root() != first.has_root()) {
+        return None;
+    }
+
+    fn components(root: &Path) -> Vec<Component<'_>> {
+        root.components().filter(|c| *c != Component::CurDir).collect()
+    }
+    let mut common = components(first);
+    for root in rest {
+        let shared = common.iter().zip(components(root)).take_while(|(a, b)| **a == *b).count();
+        common.truncate(shared);
+    }
+    let root: PathBuf = common.into_iter().collect();
+    Some(if root.as_os_str().is_empty() { PathBuf::from(".") } else { root })
+}
+
+/// Mention a partial read in an error, since it may be the cause
+fn with_warning(error: impl std::fmt::Display, read: &BatchRead) -> String {
+    match &read.warning {
+        Some(warning) => format!("{} ({})", error, warning),
+        None => error.to_string(),
+    }
+}
+
+/// Writes streamed results to stdout, and checkpoints to a file
+struct NdjsonSink {
+    out: BufWriter<Stdout>,
+    dimensions: bool,
+    checkpoint: Option<PathBuf>,
+}
+
+impl NdjsonSink {
+    fn write(&mut self, record: &BatchRecord) -> std::io::Result<()> {
+        serde_json::to_writer(&mut self.out, record)?;
+        writeln!(self.out)
+    }
+
+    /// End the stream with the aggregates
+    fn summary(&mut self, summary: &BatchSummary) -> Result<()> {
+        self.write(&BatchRecord::Summary(BatchSummaryOutput {
+            total: summary.total,
+            succeeded: summary.succeeded,
+            failed: summary.failed,
+            partial: summary.partial,
+            average_health: summary.average_health().filter(|_| self.dimensions),
+        }))?;
+        Ok(self.out.flush()?)
+    }
+}
+
+impl BatchSink for NdjsonSink {
+    fn result(&mut self, result: BatchDocumentResult) -> Result<(), BatchError> {
+        let record = BatchRecord::Document(BatchDocumentOutput::from_result(
+            &result,
+            self.dimensions,
+        ));
+        self.write(&record).map_err(|e| BatchError::new(e.to_string()))
+    }
+
+    fn checkpoint(&mut self, checkpoint: &BatchCheckpoint) -> Result<(), BatchError> {
+        // Flush first, so a checkpoint never covers records not yet written
+        self.out.flush().map_err(|e| BatchError::new(e.to_string()))?;
+        let Some(path) = &self.checkpoint else {
+            return Ok(());
+        };
+
+        // Replace the checkpoint atomically, an interruption may come anytime
+        let json = serde_json::to_string(checkpoint).map_err(|e| BatchError::new(e.to_string()))?;
+        let partial = path.with_extension("tmp");
+        std::fs::write(&partial, json)
+            .and_then(|_| std::fs::rename(&partial, path))
+            .map_err(|e| {
+                BatchError::new(format!("Failed to write '{}': {}", path.display(), e))
+            })
+    }
+}
+
+impl BatchDocumentOutput {
+    fn from_result(result: &BatchDocumentResult, dimensions: bool) -> Self {
+        Self {
+            path: result.path.display().to_string(),
+            success: result.is_success(),
+            partial: result.is_partial(),
+            health: result.dimensions.as_ref().map(|d| d.health).filter(|_| dimensions),
+            error: result.error.clone(),
+            diagnostics: result.diagnostics.iter().map(|d| d.to_string()).collect(),
+            warnings: result.warnings.clone(),
+        }
+    }
+
+    fn failure(path: &Path, error: impl std::fmt::Display) -> Self {
+        Self {
+            path: path.display().to_string(),
+            success: false,
+            partial: false,
+            health: None,
+            error: Some(error.to_string()),
+            diagnostics: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use doc_doctor_application::Query;
+    use doc_doctor_domain::StubTypesConfig;
+
+    use crate::commands::index_vault;
+    use crate::commands::watch::vault_documents;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        batch: BatchCommand,
+    }
+
+    #[test]
+    fn test_index_root() {
+        let root = |patterns: &[&str]| {
+            index_root(&patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>())
+        };
+        assert_eq!(root(&["."]), Some(PathBuf::from(".")));
+        assert_eq!(root(&["*.md"]), Some(PathBuf::from(".")));
+        let both = root(&["vault/**/*.md", "./vault/drafts/*.canvas"]);
+        assert_eq!(both, Some(PathBuf::from("vault")));
+        assert_eq!(root(&["/vault/**/*.md", "vault/**/*.md"]), None);
+    }
+
+    #[test]
+    fn test_batch_shares_the_vault_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = dir.path();
+        std::fs::write(vault.join("a.md"), "---\ntitle: A\n---\n").unwrap();
+        std::fs::write(vault.join("b.md"), "---\ntitle: B\n---\n").unwrap();
+
+        // As `ddoc batch .` run in the vault
+        let cli = Cli::parse_from(["batch", &format!("{}/.", vault.display())]);
+        let paths = walk_pattern(&cli.batch.patterns[0]).unwrap();
+        let root = index_root(&cli.batch.patterns).unwrap();
+        let results = cli.batch.process_indexed(&root, &paths, false);
+        assert!(results.iter().all(|r| r.success));
+
+        // `ddoc query .` finds the same entries
+        let files = vault_documents(vault).unwrap();
+        let (index, refresh) = index_vault(vault, &files, true);
+        assert_eq!(refresh.analyzed, 0);
+        assert_eq!(index.entries.len(), 2);
+        let query = Query::parse("title = \"A\"").unwrap();
+        let rows = index.query(vault, &files, &query, &StubTypesConfig::default());
+        assert_eq!(rows.len(), 1);
+    }
+}
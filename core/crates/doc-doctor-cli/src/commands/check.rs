@@ -0,0 +1,164 @@
+//! Check Command
+//!
+//! Quality gate for CI: the `checks` thresholds of the configuration,
+//! compared with a baseline committed in the vault so that legacy debt
+//! passes and only regressions fail.
+//!
+//! ```bash
+//! ddoc check vault                    # exit 1 on regressions
+//! ddoc check vault --update-baseline  # accept the current failures
+//! ddoc check vault -f github          # annotate regressions on the PR
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Args;
+
+use doc_doctor_application::{
+    check_document, thresholds_for, unreadable_failure, Baseline, CheckFailure, Finding,
+    Switchboard, VaultIndex,
+};
+use doc_doctor_domain::DocumentAnalysis;
+
+use crate::commands::watch::vault_documents;
+use crate::commands::{create_switchboard, index_vault, read_file, write_file};
+use crate::config::resolve_path;
+use crate::output::{format_findings, format_output, CheckOutput, OutputFormat};
+
+/// Baseline file in the vault root, unless `--baseline` is given
+pub const BASELINE_FILE: &str = ".doc-doctor-baseline.json";
+
+#[derive(Args)]
+pub struct CheckCommand {
+    /// Path to the vault (can be a path alias from config)
+    #[arg(default_value = ".")]
+    pub path: String,
+
+    /// Baseline of known failures (default: .doc-doctor-baseline.json in the vault)
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+
+    /// Record the current failures as the baseline, instead of checking
+    #[arg(long)]
+    pub update_baseline: bool,
+
+    /// Analyze every file instead of reusing the vault index
+    #[arg(long)]
+    pub no_index: bool,
+}
+
+impl CheckCommand {
+    pub fn run(&self, format: OutputFormat, verbose: bool) -> Result<()> {
+        let root = resolve_path(&self.path);
+        let files = vault_documents(&root)?;
+        let (index, refresh) = index_vault(&root, &files, !self.no_index);
+
+        if let Some(error) = &refresh.store_error {
+            eprintln!("Warning: could not store the index: {}", error);
+        }
+
+        let switchboard = create_switchboard();
+        let config = switchboard.config();
+        // Documents that could not be analyzed fail, rather than pass unchecked
+        let mut failures: Vec<CheckFailure> = refresh
+            .failed
+            .iter()
+            .map(|failure| {
+                unreadable_failure(&VaultIndex::key(&root, &failure.path), &failure.error)
+            })
+            .collect();
+        for (key, entry) in index.documents(&root, &files) {
+            let thresholds = thresholds_for(&config.checks, key);
+            let analysis =
+                DocumentAnalysis::new(entry.properties.clone(), entry.dimensions.clone());
+            let mut found = check_document(key, "", &analysis, &[], &thresholds);
+
+            // Read the source only to locate failures
+            if !found.is_empty() && !entry.canvas {
+                if let Ok(content) = read_file(&root.join(key)) {
+                    let block_ids = switchboard
+                        .parse_body(&content)
+                        .map(|body| body.block_ids)
+                        .unwrap_or_default();
+                    found = check_document(key, &content, &analysis, &block_ids, &thresholds);
+                }
+            }
+            failures.extend(found);
+        }
+
+        let baseline_path = self.baseline.clone().unwrap_or_else(|| root.join(BASELINE_FILE));
+        if self.update_baseline {
+            let baseline = Baseline::from_failures(&failures);
+            let json = serde_json::to_string_pretty(&baseline)?;
+            write_file(&baseline_path, &format!("{}\n", json))?;
+            eprintln!(
+                "Recorded {} failure(s) in {}",
+                baseline.len(),
+                baseline_path.display()
+            );
+            return Ok(());
+        }
+
+        let baseline = load_baseline(&baseline_path)?;
+        if verbose {
+            eprintln!(
+                "Checked {} document(s), {} failure(s), {} in the baseline",
+                files.len(),
+                failures.len(),
+                baseline.len()
+            );
+        }
+        let outcome = baseline.compare(failures);
+        let passed = outcome.passed();
+
+        if format.is_ci() {
+            let findings = located_findings(&root, &outcome.regressions);
+            let checked: Vec<String> =
+                files.iter().map(|file| vault_path(&root, &VaultIndex::key(&root, file))).collect();
+            println!("{}", format_findings(&findings, &checked, format).unwrap_or_default());
+        } else {
+            let output = CheckOutput {
+                baseline: baseline_path.display().to_string(),
+                checked: files.len(),
+                passed,
+                regressions: outcome.regressions,
+                known: outcome.known,
+                improved: outcome.improved,
+            };
+            println!("{}", format_output(&output, format)?);
+        }
+
+        if !passed {
+            std::process::exit(1);
+        }
+        Ok(())
+    }
+}
+
+/// The baseline at `path`; none yet means every failure is a regression
+fn load_baseline(path: &Path) -> Result<Baseline> {
+    if !path.exists() {
+        return Ok(Baseline::default());
+    }
+    let content = read_file(path)?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Invalid baseline '{}'", path.display()))
+}
+
+/// Findings of the failures, with paths as given on the command line
+fn located_findings(root: &Path, failures: &[CheckFailure]) -> Vec<Finding> {
+    failures
+        .iter()
+        .flat_map(|failure| &failure.findings)
+        .map(|finding| Finding {
+            path: vault_path(root, &finding.path),
+            ..finding.clone()
+        })
+        .collect()
+}
+
+/// Vault-relative `key` joined to the vault root
+fn vault_path(root: &Path, key: &str) -> String {
+    root.join(key).to_string_lossy().replace('\\', "/")
+}
@@ -3,15 +3,23 @@
 //! Show, initialize, and manage configuration.
 
 use anyhow::Result;
-use clap::Args;
+use clap::{Args, Subcommand};
 
-use doc_doctor_config_yaml::{config_sources, user_config_path, project_config_path};
+use doc_doctor_config_yaml::{
+    config_sources, project_config_path, user_config_path, user_profiles_dir,
+};
+use doc_doctor_domain::CalculationConfig;
 
-use crate::config::{get_config, get_cli_config, init_user_config};
-use crate::output::OutputFormat;
+use crate::config::{
+    config_provider, get_config, get_cli_config, init_user_config, print_config_sources,
+};
+use crate::output::{unsupported_format, OutputFormat};
 
 #[derive(Args)]
 pub struct ConfigCommand {
+    #[command(subcommand)]
+    pub subcommand: Option<ConfigSubcommand>,
+
     /// Show current configuration
     #[arg(long)]
     pub show: bool,
@@ -33,8 +41,25 @@ pub struct ConfigCommand {
     pub aliases: bool,
 }
 
+#[derive(Subcommand)]
+pub enum ConfigSubcommand {
+    /// List available profiles, or diff two of them
+    Profiles {
+        /// Compare two profiles (or one profile against the active config)
+        #[arg(long, num_args = 1..=2, value_names = ["LEFT", "RIGHT"])]
+        diff: Option<Vec<String>>,
+    },
+}
+
 impl ConfigCommand {
     pub fn run(&self, format: OutputFormat, verbose: bool) -> Result<()> {
+        if let Some(ConfigSubcommand::Profiles { diff }) = &self.subcommand {
+            return match diff {
+                Some(names) => self.diff_profiles(names, format),
+                None => self.list_profiles(format),
+            };
+        }
+
         // Default to --show if no flags specified
         let show_config = self.show || (!self.init && !self.sources && !self.paths && !self.aliases);
 
@@ -93,6 +118,7 @@ impl ConfigCommand {
             OutputFormat::Yaml => {
                 println!("{}", serde_yaml::to_string(&cli_config.paths)?);
             }
+            format => return Err(unsupported_format(format)),
         }
 
         Ok(())
@@ -102,17 +128,16 @@ impl ConfigCommand {
         let config = get_config();
 
         if verbose {
-            eprintln!("Loading configuration from layered sources...");
-            for (source, exists) in config_sources() {
-                let status = if exists { "loaded" } else { "skipped" };
-                eprintln!("  {} [{}]", source, status);
-            }
+            print_config_sources();
         }
 
         match format {
             OutputFormat::Human => {
                 println!("Doc Doctor Configuration");
                 println!("========================\n");
+                if let Ok(Some(profile)) = config_provider().active_profile() {
+                    println!("Profile: {}\n", profile);
+                }
                 println!("Health Calculation:");
                 println!("  refinement_weight: {}", config.health.refinement_weight);
                 println!("  stub_weight: {}", config.health.stub_weight);
@@ -139,13 +164,142 @@ impl ConfigCommand {
                 println!("  stable: {}", config.form_cadences.stable);
                 println!("  evergreen: {}", config.form_cadences.evergreen);
                 println!("  canonical: {:?}", config.form_cadences.canonical);
+                println!("\nStub Types:");
+                println!("  allow_unknown: {}", config.stub_types.allow_unknown);
+                for (name, family) in &config.stub_types.custom {
+                    println!("  {}: {}", name, family.display_name());
+                }
+                println!("\nPolicies:");
+                println!("  require_title: {}", config.policies.require_title);
+                println!("  require_stub_description: {}", config.policies.require_stub_description);
+                println!("  max_blocking_stubs: {:?}", config.policies.max_blocking_stubs);
+                println!("\nVault:");
+                println!("  timezone: {}", config.vault.timezone);
+                println!("  inline_fields: {}", config.vault.inline_fields);
+                println!("  task_stubs: {}", config.vault.task_stubs);
+                println!("  stub_markers: {}", config.vault.stub_markers);
+                println!("\nWalk:");
+                println!("  include: {}", config.walk.include.join(", "));
+                println!("  exclude: {}", config.walk.exclude.join(", "));
+                println!("  ignore_files: {}", config.walk.ignore_files.join(", "));
+                println!("  templates: {}", config.walk.templates.join(", "));
+                println!("  hidden: {}", config.walk.hidden);
+                println!("  follow_symlinks: {}", config.walk.follow_symlinks);
+                let checks = &config.checks;
+                println!("\nChecks:");
+                println!("  min_health: {}", checks.thresholds.min_health);
+                println!(
+                    "  allow_public_blocking_stubs: {}",
+                    checks.thresholds.allow_public_blocking_stubs
+                );
+                println!("  min_usefulness_margin: {}", checks.thresholds.min_usefulness_margin);
+                for entry in &checks.paths {
+                    let overrides = [
+                        entry.min_health.map(|v| format!("min_health {}", v)),
+                        entry
+                            .allow_public_blocking_stubs
+                            .map(|v| format!("allow_public_blocking_stubs {}", v)),
+                        entry.min_usefulness_margin.map(|v| format!("min_usefulness_margin {}", v)),
+                    ];
+                    let overrides: Vec<String> = overrides.into_iter().flatten().collect();
+                    println!("  {}: {}", entry.glob, overrides.join(", "));
+                }
+            }
+            OutputFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&*config)?);
+            }
+            OutputFormat::Yaml => {
+                println!("{}", serde_yaml::to_string(&*config)?);
+            }
+            format => return Err(unsupported_format(format)),
+        }
+
+        Ok(())
+    }
+
+    fn list_profiles(&self, format: OutputFormat) -> Result<()> {
+        let provider = config_provider();
+        let profiles = provider.profiles().list();
+        let active = provider.active_profile().ok().flatten();
+
+        match format {
+            OutputFormat::Human => {
+                println!("Configuration Profiles:");
+                for profile in &profiles {
+                    let marker = if active.as_deref() == Some(profile.name.as_str()) { "*" } else { " " };
+                    println!(
+                        "{} {:<16} {} [{}]",
+                        marker,
+                        profile.name,
+                        profile.description.as_deref().unwrap_or(""),
+                        profile.source
+                    );
+                }
+                if let Some(dir) = user_profiles_dir() {
+                    println!();
+                    println!("User profiles directory: {}", dir.display());
+                }
+            }
+            OutputFormat::Json => {
+                let output = serde_json::json!({ "active": active, "profiles": profiles });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            }
+            OutputFormat::Yaml => {
+                let output = serde_json::json!({ "active": active, "profiles": profiles });
+                println!("{}", serde_yaml::to_string(&output)?);
+            }
+            format => return Err(unsupported_format(format)),
+        }
+
+        Ok(())
+    }
+
+    fn diff_profiles(&self, names: &[String], format: OutputFormat) -> Result<()> {
+        let provider = config_provider();
+        let load = |name: &str| -> Result<CalculationConfig> {
+            provider.profiles().load(name).map_err(|e| anyhow::anyhow!("{}", e))
+        };
+
+        let (left_name, left, right_name, right) = match names {
+            [left, right] => (left.clone(), load(left)?, right.clone(), load(right)?),
+            [right] => ("active".to_string(), (*get_config()).clone(), right.clone(), load(right)?),
+            _ => anyhow::bail!("--diff takes one or two profile names"),
+        };
+
+        let differences = left.diff(&right);
+
+        match format {
+            OutputFormat::Human => {
+                println!("{} vs {}:", left_name, right_name);
+                if differences.is_empty() {
+                    println!("  No differences");
+                }
+                for diff in &differences {
+                    println!(
+                        "  {:<36} {:>10} -> {}",
+                        diff.path,
+                        diff.left.as_deref().unwrap_or("-"),
+                        diff.right.as_deref().unwrap_or("-")
+                    );
+                }
             }
             OutputFormat::Json => {
-                println!("{}", serde_json::to_string_pretty(config)?);
+                let output = serde_json::json!({
+                    "left": left_name,
+                    "right": right_name,
+                    "differences": differences
+                });
+                println!("{}", serde_json::to_string_pretty(&output)?);
             }
             OutputFormat::Yaml => {
-                println!("{}", serde_yaml::to_string(config)?);
+                let output = serde_json::json!({
+                    "left": left_name,
+                    "right": right_name,
+                    "differences": differences
+                });
+                println!("{}", serde_yaml::to_string(&output)?);
             }
+            format => return Err(unsupported_format(format)),
         }
 
         Ok(())
@@ -176,6 +330,7 @@ impl ConfigCommand {
             OutputFormat::Yaml => {
                 println!("{}", serde_yaml::to_string(&sources)?);
             }
+            format => return Err(unsupported_format(format)),
         }
 
         Ok(())
@@ -207,6 +362,7 @@ impl ConfigCommand {
                 });
                 println!("{}", serde_yaml::to_string(&paths)?);
             }
+            format => return Err(unsupported_format(format)),
         }
 
         Ok(())
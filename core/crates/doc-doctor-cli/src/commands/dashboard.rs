@@ -1,22 +1,34 @@
 //! Dashboard Command
 //!
-//! Interactive TUI dashboard for vault health overview.
+//! Interactive TUI dashboard for vault health overview. Analyses are kept
+//! in the vault index (`.doc-doctor/index.json`), so only changed files are
+//! parsed again, and the dashboard refreshes live from the events of a
+//! vault watch while it runs.
 
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 
 use anyhow::Result;
 use clap::{Args, Subcommand};
 
-use crate::config::{get_dashboard_columns, get_test_dir, resolve_path, should_ignore_path};
+use doc_doctor_application::Switchboard;
+use doc_doctor_domain::L1Properties;
+
+use crate::commands::watch::{open_watch, run_watch, vault_documents};
+use crate::commands::{create_switchboard, read_file};
+use crate::config::{get_dashboard_columns, get_test_dir, resolve_path};
 use crate::output::OutputFormat;
 use crate::tui::{
-    app::{App, AppMode, DocumentSummary, VaultStats},
+    app::{App, AppMode, DocumentSummary, LiveUpdates, VaultStats},
     run_tui,
     widgets::ConsoleProgress,
 };
 
+/// How long the live dashboard waits for a burst of changes to settle
+const LIVE_DEBOUNCE_MS: u64 = 300;
+
 #[derive(Args)]
 pub struct DashboardCommand {
     #[command(subcommand)]
@@ -29,6 +41,10 @@ pub struct DashboardCommand {
     /// Run in non-interactive mode (just show stats and exit)
     #[arg(long)]
     pub no_interactive: bool,
+
+    /// Analyze every file instead of reusing the vault index
+    #[arg(long)]
+    pub no_index: bool,
 }
 
 #[derive(Subcommand)]
@@ -45,6 +61,10 @@ pub enum DashboardSubcommand {
         /// Run in non-interactive mode
         #[arg(long)]
         no_interactive: bool,
+
+        /// Analyze every file instead of reusing the vault index
+        #[arg(long)]
+        no_index: bool,
     },
 }
 
@@ -55,8 +75,8 @@ impl DashboardCommand {
             Some(DashboardSubcommand::Tests) => {
                 return self.run_tests(verbose);
             }
-            Some(DashboardSubcommand::Vault { path, no_interactive }) => {
-                return self.run_vault(path, *no_interactive, verbose);
+            Some(DashboardSubcommand::Vault { path, no_interactive, no_index }) => {
+                return self.run_vault(path, *no_interactive, *no_index, verbose);
             }
             None => {
                 // Default: run vault dashboard with top-level args
@@ -73,19 +93,8 @@ impl DashboardCommand {
             eprintln!("Scanning vault: {}", path.display());
         }
 
-        // Scan vault for markdown files
-        let pattern = path.join("**/*.md");
-        let pattern_str = pattern.to_string_lossy();
-
-        let all_files: Vec<PathBuf> = glob::glob(&pattern_str)?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        // Filter out ignored paths
-        let files: Vec<PathBuf> = all_files
-            .into_iter()
-            .filter(|f| !should_ignore_path(f))
-            .collect();
+        // Scan vault for markdown files, canvases and sidecar documents
+        let files = vault_documents(&path)?;
 
         if files.is_empty() {
             println!("No markdown files found in {}", path.display());
@@ -99,73 +108,53 @@ impl DashboardCommand {
         // Create progress bar for scanning
         let progress = ConsoleProgress::new(files.len() as u64, "Analyzing documents...");
 
-        // Analyze each document
-        let parser = doc_doctor_parser_yaml::YamlParser::new();
+        // Analyze each document, reusing the index for unchanged files
+        let switchboard = create_switchboard();
+        let (watch, refresh) = open_watch(&path, &files, !self.no_index);
+        let index = watch.index();
+        if verbose {
+            eprintln!(
+                "Index: {} reused, {} analyzed, {} removed",
+                refresh.reused + refresh.rehashed,
+                refresh.analyzed,
+                refresh.removed
+            );
+        }
+        if let Some(error) = &refresh.store_error {
+            eprintln!("Warning: could not store the vault index: {}", error);
+        }
+
         let mut documents = Vec::new();
         let mut total_health = 0.0;
         let mut total_refinement = 0.0;
         let mut total_stubs = 0;
         let mut blocking_stubs = 0;
+        let mut partial = 0;
 
         for file in &files {
             progress.set_message(&format!("Analyzing {}", file.file_name().unwrap().to_string_lossy()));
 
-            if let Ok(content) = std::fs::read_to_string(file) {
-                use doc_doctor_domain::DocumentParser;
+            if let Ok(content) = read_file(file) {
+                let parsed = index
+                    .get(&path, file)
+                    .map(|entry| (entry.partial, entry.properties.clone()));
 
-                if let Ok(props) = parser.parse(&content) {
-                    let health = doc_doctor_domain::calculate_health(
-                        props.refinement.value(),
-                        &props.stubs,
-                    );
+                if let Some((is_partial, props)) = parsed {
+                    if is_partial {
+                        partial += 1;
+                    }
+                    let health = switchboard.calc_health(props.refinement.value(), &props.stubs);
 
-                    let stub_count = props.stubs.len();
                     let blocking = props.stubs.iter()
                         .filter(|s| matches!(s.stub_form, doc_doctor_domain::StubForm::Blocking))
                         .count();
 
                     total_health += health;
                     total_refinement += props.refinement.value();
-                    total_stubs += stub_count;
+                    total_stubs += props.stubs.len();
                     blocking_stubs += blocking;
 
-                    // Get file metadata
-                    let metadata = std::fs::metadata(file).ok();
-                    let file_size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
-                    let modified = metadata.as_ref().and_then(|m| m.modified().ok());
-                    let created = metadata.as_ref().and_then(|m| m.created().ok());
-                    let line_count = content.lines().count();
-
-                    // Get last git commit info
-                    let last_commit = get_last_commit(file);
-
-                    // Calculate relative path from vault root
-                    let relative_path = file.strip_prefix(&path)
-                        .ok()
-                        .map(|p| p.to_string_lossy().to_string());
-
-                    // Extract raw origin value from YAML (before enum normalization)
-                    let raw_origin = extract_raw_field(&content, "origin");
-
-                    documents.push(DocumentSummary {
-                        path: file.clone(),
-                        relative_path,
-                        title: props.title.clone(),
-                        health,
-                        refinement: props.refinement.value(),
-                        stub_count,
-                        audience: props.audience.to_string(),
-                        form: props.form.to_string(),
-                        origin: raw_origin,
-                        stubs: props.stubs.clone(),
-                        content: content.clone(),
-                        line_count,
-                        file_size,
-                        modified,
-                        created,
-                        author: None, // Not yet supported in L1Properties
-                        last_commit,
-                    });
+                    documents.push(summarize(&path, file, content, &props, health));
                 }
             }
 
@@ -175,8 +164,8 @@ impl DashboardCommand {
         let doc_count = documents.len();
         let skipped = files.len() - doc_count;
         progress.finish_with_message(&format!(
-            "Analyzed {} documents ({} skipped - no frontmatter)",
-            doc_count, skipped
+            "Analyzed {} documents ({} partially parsed, {} skipped - no frontmatter)",
+            doc_count, partial, skipped
         ));
 
         // Calculate folder statistics
@@ -213,7 +202,7 @@ impl DashboardCommand {
             }
         }
 
-        // Build files_by_top_folder Vec sorted by total count
+        // Build files_by_top_folder Vec sorted by total count, then name
         let mut files_by_top_folder: Vec<(String, usize, usize)> = top_folder_total
             .into_iter()
             .map(|(folder, total)| {
@@ -221,7 +210,7 @@ impl DashboardCommand {
                 (folder, total, with_fm)
             })
             .collect();
-        files_by_top_folder.sort_by(|a, b| b.1.cmp(&a.1));
+        files_by_top_folder.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
 
         let stats = VaultStats {
             total_documents: doc_count,
@@ -249,11 +238,27 @@ impl DashboardCommand {
             return Ok(());
         }
 
-        // Run interactive TUI
+        // Run interactive TUI, refreshed live from watch events
         let mut app = App::new();
         app.vault_stats = Some(stats);
         app.documents = documents;
         app.visible_columns = get_dashboard_columns();
+        let vault = path.clone();
+        app.live = Some(LiveUpdates {
+            root: path.clone(),
+            events: watch.subscribe(),
+            summarize: Box::new(move |file: &Path| {
+                let content = read_file(file).ok()?;
+                let props = if doc_doctor_domain::is_canvas(file) {
+                    switchboard.analyze_canvas(&content).ok()?.analysis.properties
+                } else {
+                    switchboard.parse_document_recovering(&content).ok()?.properties
+                };
+                let health = switchboard.calc_health(props.refinement.value(), &props.stubs);
+                Some(summarize(&vault, file, content, &props, health))
+            }),
+        });
+        std::thread::spawn(move || run_watch(watch, Duration::from_millis(LIVE_DEBOUNCE_MS)));
 
         run_tui(app)?;
 
@@ -298,7 +303,7 @@ impl DashboardCommand {
     }
 
     /// Run the vault dashboard (used by subcommand)
-    fn run_vault(&self, path: &str, no_interactive: bool, verbose: bool) -> Result<()> {
+    fn run_vault(&self, path: &str, no_interactive: bool, no_index: bool, verbose: bool) -> Result<()> {
         let resolved_path = resolve_path(path);
 
         if verbose {
@@ -313,6 +318,7 @@ impl DashboardCommand {
             subcommand: None,
             path: resolved_path.to_string_lossy().to_string(),
             no_interactive,
+            no_index,
         };
 
         // Use the main run method (it will skip subcommand matching since subcommand is None)
@@ -320,6 +326,44 @@ impl DashboardCommand {
     }
 }
 
+/// Summarize a document for the dashboard
+fn summarize(
+    vault: &Path,
+    file: &Path,
+    content: String,
+    props: &L1Properties,
+    health: f64,
+) -> DocumentSummary {
+    // Get file metadata
+    let metadata = std::fs::metadata(file).ok();
+    let file_size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+    let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+    let created = metadata.as_ref().and_then(|m| m.created().ok());
+
+    DocumentSummary {
+        path: file.to_path_buf(),
+        // Calculate relative path from vault root
+        relative_path: file.strip_prefix(vault).ok().map(|p| p.to_string_lossy().to_string()),
+        title: props.title.clone(),
+        health,
+        refinement: props.refinement.value(),
+        stub_count: props.stubs.len(),
+        audience: props.audience.to_string(),
+        form: props.form.to_string(),
+        // Extract raw origin value from YAML (before enum normalization)
+        origin: extract_raw_field(&content, "origin"),
+        stubs: props.stubs.clone(),
+        line_count: content.lines().count(),
+        content,
+        file_size,
+        modified,
+        created,
+        author: None, // Not yet supported in L1Properties
+        // Get last git commit info
+        last_commit: get_last_commit(file),
+    }
+}
+
 /// Get the last git commit info for a file
 fn get_last_commit(file: &Path) -> Option<String> {
     // Get the directory containing the file to run git from there
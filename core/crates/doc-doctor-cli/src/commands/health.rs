@@ -5,9 +5,10 @@
 use anyhow::Result;
 use clap::Args;
 
-use doc_doctor_domain::{calculate_health_with_config, calculate_stub_penalty_with_config, Stub};
+use doc_doctor_application::Switchboard;
+use doc_doctor_domain::{calculate_stub_penalty_with_config, Stub};
 
-use crate::config::get_config;
+use crate::commands::create_switchboard;
 use crate::output::{format_output, HealthOutput, OutputFormat};
 use crate::tui::widgets::tables::format_health_output;
 
@@ -24,7 +25,8 @@ pub struct HealthCommand {
 
 impl HealthCommand {
     pub fn run(&self, format: OutputFormat, verbose: bool) -> Result<()> {
-        let config = get_config();
+        let switchboard = create_switchboard();
+        let config = switchboard.config();
 
         if verbose {
             eprintln!(
@@ -54,7 +56,7 @@ impl HealthCommand {
         let stub_penalty = calculate_stub_penalty_with_config(&stubs, &config.stub_penalties);
 
         // Calculate health using config
-        let health = calculate_health_with_config(self.refinement, &stubs, config);
+        let health = switchboard.calc_health(self.refinement, &stubs);
 
         match format {
             OutputFormat::Human => {
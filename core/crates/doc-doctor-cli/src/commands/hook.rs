@@ -0,0 +1,233 @@
+//! Hook Command
+//!
+//! Git pre-commit hook that checks the staged version of each changed
+//! markdown document, not the working tree:
+//!
+//! ```bash
+//! ddoc hook install --touch-modified   # writes .git/hooks/pre-commit
+//! ddoc hook pre-commit                 # what the hook runs
+//! ```
+//!
+//! A commit is refused when frontmatter does not validate, a public
+//! document's refinement drops below its audience gate, or stubs become
+//! malformed.
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Subcommand};
+
+use doc_doctor_application::{
+    commit_findings, schema_findings, Finding, FindingLevel, Switchboard,
+};
+use doc_doctor_domain::ValidateDocument;
+use doc_doctor_fs::GitIntegration;
+
+use crate::commands::{
+    create_registry, create_switchboard, create_validate_use_case, create_walker, write_file,
+    CliSwitchboard,
+};
+use crate::config::{config_handle, should_ignore_path};
+use crate::output::{format_findings, OutputFormat};
+
+/// Marks hooks written by `ddoc hook install`, which may be replaced
+const HOOK_MARKER: &str = "# Installed by doc-doctor";
+
+#[derive(Args)]
+pub struct HookCommand {
+    #[command(subcommand)]
+    pub subcommand: HookSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum HookSubcommand {
+    /// Install a git pre-commit hook running `ddoc hook pre-commit`
+    Install {
+        /// Replace an existing pre-commit hook
+        #[arg(long)]
+        force: bool,
+
+        /// Strict validation in the hook
+        #[arg(short, long)]
+        strict: bool,
+
+        /// Update `modified` in the hook
+        #[arg(long)]
+        touch_modified: bool,
+    },
+
+    /// Check the staged markdown documents (run by the installed hook)
+    PreCommit {
+        /// Strict mode - reject unknown fields and YAML hazards
+        #[arg(short, long)]
+        strict: bool,
+
+        /// Set `modified` in documents that pass, and stage the change
+        #[arg(long)]
+        touch_modified: bool,
+    },
+}
+
+impl HookCommand {
+    pub fn run(&self, format: OutputFormat, verbose: bool) -> Result<()> {
+        let git = GitIntegration::new();
+        if !git.is_available() {
+            bail!("Not in a git repository (or git is not installed)");
+        }
+
+        match &self.subcommand {
+            HookSubcommand::Install {
+                force,
+                strict,
+                touch_modified,
+            } => install(&git, *force, *strict, *touch_modified),
+            HookSubcommand::PreCommit {
+                strict,
+                touch_modified,
+            } => pre_commit(&git, *strict, *touch_modified, format, verbose),
+        }
+    }
+}
+
+/// Write the pre-commit hook script
+fn install(git: &GitIntegration, force: bool, strict: bool, touch_modified: bool) -> Result<()> {
+    let hooks = git.hooks_dir()?;
+    let hook = hooks.join("pre-commit");
+
+    if let Ok(existing) = std::fs::read_to_string(&hook) {
+        if !existing.contains(HOOK_MARKER) && !force {
+            bail!(
+                "A pre-commit hook already exists at '{}'; use --force to replace it",
+                hook.display()
+            );
+        }
+    }
+
+    let exe = std::env::current_exe().context("Failed to locate the ddoc executable")?;
+    let mut command = format!("exec \"{}\" hook pre-commit", exe.display());
+    if strict {
+        command.push_str(" --strict");
+    }
+    if touch_modified {
+        command.push_str(" --touch-modified");
+    }
+    let script = format!("#!/bin/sh\n{}\n{}\n", HOOK_MARKER, command);
+
+    std::fs::create_dir_all(&hooks)
+        .with_context(|| format!("Failed to create '{}'", hooks.display()))?;
+    std::fs::write(&hook, script)
+        .with_context(|| format!("Failed to write '{}'", hook.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    println!("Installed pre-commit hook: {}", hook.display());
+    Ok(())
+}
+
+/// Check the staged documents, refusing the commit on any error
+fn pre_commit(
+    git: &GitIntegration,
+    strict: bool,
+    touch_modified: bool,
+    format: OutputFormat,
+    verbose: bool,
+) -> Result<()> {
+    let root = git.repo_root().map(Path::to_path_buf).unwrap_or_default();
+    let registry = create_registry();
+    let switchboard = create_switchboard();
+    let use_case = create_validate_use_case();
+    let config = switchboard.config();
+    let walker = create_walker();
+
+    let paths: Vec<String> = git
+        .staged_files()?
+        .into_iter()
+        .filter(|path| path.ends_with(".md") && !should_ignore_path(Path::new(path)))
+        .filter(|path| !walker.is_ignored(&root, &root.join(path)))
+        .collect();
+
+    let mut checked = Vec::new();
+    let mut findings = Vec::new();
+    for path in &paths {
+        let staged = git.staged_content(path)?;
+        // Notes without frontmatter are not J-Editorial documents
+        if registry.detect(&staged).is_none() {
+            continue;
+        }
+        if verbose {
+            eprintln!("Checking staged {}", path);
+        }
+
+        let mut found: Vec<Finding> = match use_case.validate(&staged, strict) {
+            Ok(result) => schema_findings(path, &result)
+                .into_iter()
+                .filter(|f| f.level == FindingLevel::Error)
+                .collect(),
+            Err(e) => vec![Finding::new(path, "schema-error", FindingLevel::Error, e.to_string())],
+        };
+        if let Ok(analysis) = switchboard.analyze_document(&staged) {
+            // Skipped stubs are left to `malformed-stub`, which lets stubs
+            // broken before this commit pass
+            let stubs: Vec<_> = analysis
+                .diagnostics
+                .iter()
+                .filter(|d| d.is_skipped() && d.field.starts_with("stubs"))
+                .filter_map(|d| d.position)
+                .collect();
+            found.retain(|f| f.position.is_none_or(|position| !stubs.contains(&position)));
+
+            let head = git
+                .head_content(path)?
+                .and_then(|content| switchboard.analyze_document(&content).ok());
+            found.extend(commit_findings(path, &staged, &analysis, head.as_ref(), &config));
+        }
+
+        if found.is_empty() && touch_modified {
+            touch(git, &switchboard, &root.join(path), path, &staged)?;
+        }
+        findings.extend(found);
+        checked.push(path.clone());
+    }
+
+    if let Some(report) = format_findings(&findings, &checked, format) {
+        println!("{}", report);
+    } else {
+        for finding in &findings {
+            let location = match finding.position {
+                Some(p) => format!("{}:{}:{}", finding.path, p.line, p.column),
+                None => finding.path.clone(),
+            };
+            eprintln!("{}: [{}] {}", location, finding.rule, finding.message);
+        }
+    }
+
+    if !findings.is_empty() {
+        eprintln!(
+            "doc-doctor: commit refused, {} problem(s) in staged documents \
+             (fix them, or skip the hook with `git commit --no-verify`)",
+            findings.len()
+        );
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Set `modified` in the staged document, and in the working tree when it
+/// has no unstaged changes
+fn touch(
+    git: &GitIntegration,
+    switchboard: &CliSwitchboard,
+    file: &Path,
+    path: &str,
+    staged: &str,
+) -> Result<()> {
+    let touched = switchboard.touch_modified(staged, config_handle().now())?;
+    git.stage_content(path, &touched)?;
+    if std::fs::read_to_string(file).is_ok_and(|content| content == staged) {
+        write_file(file, &touched)?;
+    }
+    Ok(())
+}
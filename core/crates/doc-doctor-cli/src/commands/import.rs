@@ -0,0 +1,119 @@
+//! Import Command
+//!
+//! Migrate metadata kept in the document body into frontmatter.
+//!
+//! # Subcommands
+//!
+//! - `tasks` - Move open Obsidian Tasks checkboxes into frontmatter stubs
+//! - `annotations` - Turn labeled comments and highlights
+//!   (`%% verify: ... %%`) into frontmatter stubs
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use doc_doctor_application::Switchboard;
+
+use crate::commands::{create_switchboard, read_file, walk_pattern, write_file};
+use crate::output::{format_output, OutputFormat, StubOutput, StubImportFileOutput, StubImportOutput};
+
+#[derive(Args)]
+pub struct ImportCommand {
+    #[command(subcommand)]
+    pub command: ImportSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum ImportSubcommand {
+    /// Move open task checkboxes into frontmatter stubs
+    Tasks(ImportArgs),
+
+    /// Turn labeled comments and highlights into frontmatter stubs
+    Annotations(ImportArgs),
+}
+
+#[derive(Args)]
+pub struct ImportArgs {
+    /// File pattern (glob)
+    pub pattern: String,
+
+    /// Delete imported tasks and comments from the body (highlights keep their text)
+    #[arg(long)]
+    pub remove: bool,
+
+    /// Don't actually modify files, just show what would be imported
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl ImportCommand {
+    pub fn run(&self, format: OutputFormat, verbose: bool) -> Result<()> {
+        match &self.command {
+            ImportSubcommand::Tasks(cmd) => run_import(cmd, Source::Tasks, format, verbose),
+            ImportSubcommand::Annotations(cmd) => {
+                run_import(cmd, Source::Annotations, format, verbose)
+            }
+        }
+    }
+}
+
+/// Where stubs are imported from
+#[derive(Clone, Copy)]
+enum Source {
+    Tasks,
+    Annotations,
+}
+
+fn run_import(cmd: &ImportArgs, source: Source, format: OutputFormat, verbose: bool) -> Result<()> {
+    let switchboard = create_switchboard();
+    let paths = walk_pattern(&cmd.pattern)?;
+
+    if paths.is_empty() {
+        println!("No files match pattern: {}", cmd.pattern);
+        return Ok(());
+    }
+
+    let mut files = Vec::new();
+    for path in paths {
+        if verbose {
+            eprintln!("Importing stubs: {}", path.display());
+        }
+
+        let content = read_file(&path)?;
+        let result = match source {
+            Source::Tasks => switchboard.import_tasks(&content, cmd.remove),
+            Source::Annotations => switchboard.import_annotations(&content, cmd.remove),
+        };
+        let result = result
+            .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+        if result.imported.is_empty() && result.skipped == 0 {
+            continue;
+        }
+
+        if !cmd.dry_run && result.updated_content != content {
+            write_file(&path, &result.updated_content)?;
+        }
+        files.push(StubImportFileOutput {
+            path: path.display().to_string(),
+            skipped: result.skipped,
+            imported: result
+                .imported
+                .iter()
+                .map(|s| StubOutput {
+                    stub_type: s.stub_type.as_str().to_string(),
+                    description: s.description.clone(),
+                    stub_form: s.stub_form.to_string(),
+                    priority: s.priority.to_string(),
+                    is_blocking: s.is_blocking(),
+                })
+                .collect(),
+        });
+    }
+
+    let output = StubImportOutput {
+        dry_run: cmd.dry_run,
+        total_imported: files.iter().map(|f| f.imported.len()).sum(),
+        files,
+    };
+    println!("{}", format_output(&output, format)?);
+    Ok(())
+}
@@ -3,45 +3,83 @@
 //! Each command is implemented as a separate module.
 //! All commands use the Application Switchboard for consistent behavior.
 
+pub mod annotations;
 pub mod batch;
+pub mod check;
 pub mod config;
 pub mod dashboard;
 pub mod dimensions;
 pub mod health;
+pub mod hook;
+pub mod import;
 pub mod parse;
+pub mod query;
+pub mod report;
 pub mod schema;
 pub mod stubs;
 pub mod test;
 pub mod usefulness;
 pub mod validate;
+pub mod watch;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use doc_doctor_application::{
-    AnalyzeDocumentUseCase, ApplicationSwitchboard, ValidateDocumentUseCase,
+    split_pattern, AnalyzeDocumentUseCase, ApplicationSwitchboard, BatchProcessUseCase,
+    IndexRefresh, InlineMetadataParser, ParserRegistry, Switchboard, ValidateDocumentUseCase,
+    VaultIndex, VaultWalker, Walk,
 };
-use doc_doctor_domain::{DocumentParser, EmbeddedSchemaProvider, SchemaProvider};
+use doc_doctor_domain::{
+    AnalysisError, AnalyzeDocument, DocumentAnalysis, DocumentParser, DocumentRepository,
+    EmbeddedSchemaProvider, SchemaProvider,
+};
+use doc_doctor_canvas::JsonCanvasParser;
+use doc_doctor_fs::{fs_walker, FileRepository, JsonIndexStore, SidecarRepository};
+use doc_doctor_markdown::MarkdownParser;
+use doc_doctor_parser_json::JsonParser;
+use doc_doctor_parser_toml::TomlParser;
 use doc_doctor_parser_yaml::YamlParser;
 
+use crate::config::{config_handle, config_provider};
+
 /// Type alias for the concrete switchboard used by CLI
-pub type CliSwitchboard = ApplicationSwitchboard<YamlParser, YamlParser, EmbeddedSchemaProvider>;
+pub type CliSwitchboard =
+    ApplicationSwitchboard<ParserRegistry, ParserRegistry, EmbeddedSchemaProvider>;
 
 /// Create the application switchboard
 ///
 /// This is the central routing mechanism for all Doc-Doctor operations.
 /// CLI commands should use this switchboard instead of directly accessing
-/// parsers or use cases.
+/// parsers or use cases. It shares the global configuration handle and can
+/// reload it from the layered config files.
 pub fn create_switchboard() -> Arc<CliSwitchboard> {
-    let parser = Arc::new(YamlParser::new());
+    let parser = Arc::new(create_registry());
     let writer = Arc::clone(&parser);
     let schema_provider = Arc::new(EmbeddedSchemaProvider);
-    Arc::new(ApplicationSwitchboard::new(parser, writer, schema_provider))
+    Arc::new(
+        ApplicationSwitchboard::new(parser, writer, schema_provider)
+            .with_config(config_handle())
+            .with_config_provider(Arc::new(config_provider()))
+            .with_body_parser(Arc::new(MarkdownParser::new()))
+            .with_canvas_parser(Arc::new(JsonCanvasParser::new())),
+    )
+}
+
+/// Create the frontmatter parser registry
+///
+/// YAML (`---`) is the default; TOML (`+++`) and JSON (`{`) documents are
+/// detected from their opening delimiter.
+pub fn create_registry() -> ParserRegistry {
+    ParserRegistry::new()
+        .register(YamlParser::new())
+        .register(TomlParser::new())
+        .register(JsonParser::new())
 }
 
 /// Create the default parser
 pub fn create_parser() -> Arc<dyn DocumentParser> {
-    Arc::new(YamlParser::new())
+    Arc::new(create_registry())
 }
 
 /// Create the default schema provider
@@ -49,9 +87,99 @@ pub fn create_schema_provider() -> Arc<dyn SchemaProvider> {
     Arc::new(EmbeddedSchemaProvider)
 }
 
+/// Create the document repository
+///
+/// Documents are read and written on the local file system. Files that
+/// cannot carry frontmatter (Org, AsciiDoc, plain text, PDF) keep their
+/// metadata in a `<file>.dd.yaml` sidecar or the folder's `.dd.yaml`.
+pub fn create_repository() -> SidecarRepository<FileRepository> {
+    SidecarRepository::new(FileRepository::new())
+}
+
+/// Create the vault walker
+///
+/// Walks honor the `walk` settings: include and exclude globs, ignore
+/// files such as `.gitignore`, templates folders, hidden folders and links.
+pub fn create_walker() -> VaultWalker {
+    fs_walker(&config_handle().get().walk)
+}
+
+/// Files matching a glob pattern, as walked by [`create_walker`]
+pub fn walk_pattern(pattern: &str) -> anyhow::Result<Vec<PathBuf>> {
+    Ok(pattern_walk(&create_walker(), pattern)?.collect())
+}
+
+/// Lazy walk of the files matching a glob pattern
+///
+/// A pattern without glob characters names a path, which must exist.
+pub fn pattern_walk(walker: &VaultWalker, pattern: &str) -> anyhow::Result<Walk> {
+    if let (path, None) = split_pattern(pattern) {
+        if !path.exists() {
+            anyhow::bail!("No such file or directory: {}", pattern);
+        }
+    }
+    walker
+        .walk_pattern(pattern)
+        .map_err(|e| anyhow::anyhow!("Invalid pattern: {}", e))
+}
+
+/// Analyses of the documents at `files` in the vault at `root`
+///
+/// With `use_index` the vault index is reused and stored again; otherwise
+/// every document is analyzed.
+pub fn index_vault(root: &Path, files: &[PathBuf], use_index: bool) -> (VaultIndex, IndexRefresh) {
+    let switchboard = create_switchboard();
+    let repository = create_repository();
+    if use_index {
+        let store = JsonIndexStore::for_vault(root);
+        VaultIndex::refresh_stored(&store, &*switchboard, &repository, root, files, u64::MAX)
+    } else {
+        let mut index = VaultIndex::new();
+        let refresh = index.refresh(&*switchboard, &repository, root, files, u64::MAX);
+        (index, refresh)
+    }
+}
+
 /// Create the analyze use case
+///
+/// Dataview inline fields and Tasks checkboxes are read as well when the
+/// vault configuration enables them.
 pub fn create_analyze_use_case() -> AnalyzeDocumentUseCase {
-    AnalyzeDocumentUseCase::new(create_parser())
+    let parser = InlineMetadataParser::new(
+        create_parser(),
+        Arc::new(MarkdownParser::new()),
+        config_handle(),
+    );
+    AnalyzeDocumentUseCase::new(Arc::new(parser)).with_config(config_handle())
+}
+
+/// Create the batch use case, reading only the first `max_file_size`
+/// bytes of larger documents
+///
+/// Documents are analyzed like [`create_analyze_use_case`] does, canvases
+/// through the switchboard.
+pub fn create_batch_use_case(max_file_size: u64) -> BatchProcessUseCase {
+    let parser = InlineMetadataParser::new(
+        create_parser(),
+        Arc::new(MarkdownParser::new()),
+        config_handle(),
+    );
+    BatchProcessUseCase::new(Arc::new(parser), Arc::new(create_repository()))
+        .with_config(config_handle())
+        .with_max_file_size(max_file_size)
+        .with_canvas_analyzer(Arc::new(CanvasAnalyzer(create_switchboard())))
+}
+
+/// Canvas analysis for use cases that take an analyzer
+struct CanvasAnalyzer(Arc<CliSwitchboard>);
+
+impl AnalyzeDocument for CanvasAnalyzer {
+    fn analyze(&self, content: &str) -> Result<DocumentAnalysis, AnalysisError> {
+        self.0
+            .analyze_canvas(content)
+            .map(|canvas| canvas.analysis)
+            .map_err(|e| AnalysisError::new(e.to_string()))
+    }
 }
 
 /// Create the validate use case
@@ -61,12 +189,14 @@ pub fn create_validate_use_case() -> ValidateDocumentUseCase {
 
 /// Read file content with error handling
 pub fn read_file(path: &Path) -> anyhow::Result<String> {
-    std::fs::read_to_string(path)
-        .map_err(|e| anyhow::anyhow!("Failed to read '{}': {}", path.display(), e))
+    create_repository()
+        .read(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read '{}': {}", path.display(), e.message))
 }
 
 /// Write file content with error handling
 pub fn write_file(path: &Path, content: &str) -> anyhow::Result<()> {
-    std::fs::write(path, content)
-        .map_err(|e| anyhow::anyhow!("Failed to write '{}': {}", path.display(), e))
+    create_repository()
+        .write(path, content)
+        .map_err(|e| anyhow::anyhow!("Failed to write '{}': {}", path.display(), e.message))
 }
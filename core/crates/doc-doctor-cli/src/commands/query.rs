@@ -0,0 +1,60 @@
+//! Query Command
+//!
+//! Run a vault query over the index of a vault, e.g.
+//!
+//! ```bash
+//! ddoc query "audience = public and health < 0.8 order by health" vault
+//! ddoc query "any(stubs, form = blocking) select path, title, blocking"
+//! ```
+
+use anyhow::{anyhow, Result};
+use clap::Args;
+
+use doc_doctor_application::{Query, Switchboard};
+
+use crate::commands::watch::vault_documents;
+use crate::commands::{create_switchboard, index_vault};
+use crate::config::resolve_path;
+use crate::output::{format_output, OutputFormat, QueryOutput};
+
+#[derive(Args)]
+pub struct QueryCommand {
+    /// Query, e.g. "health < 0.8 and any(stubs, form = blocking) order by health"
+    pub query: String,
+
+    /// Path to the vault (can be a path alias from config)
+    #[arg(default_value = ".")]
+    pub path: String,
+
+    /// Analyze every file instead of reusing the vault index
+    #[arg(long)]
+    pub no_index: bool,
+}
+
+impl QueryCommand {
+    pub fn run(&self, format: OutputFormat, verbose: bool) -> Result<()> {
+        let query = Query::parse(&self.query).map_err(|e| anyhow!("Invalid query: {}", e))?;
+
+        let root = resolve_path(&self.path);
+        let files = vault_documents(&root)?;
+        let (index, refresh) = index_vault(&root, &files, !self.no_index);
+
+        if verbose {
+            eprintln!(
+                "Index: {} reused, {} analyzed, {} removed",
+                refresh.reused + refresh.rehashed,
+                refresh.analyzed,
+                refresh.removed
+            );
+        }
+        if let Some(error) = &refresh.store_error {
+            eprintln!("Warning: could not store the index: {}", error);
+        }
+
+        let config = create_switchboard().config();
+        let rows = index.query(&root, &files, &query, &config.stub_types);
+        let output = QueryOutput::new(&self.query, &query, rows);
+        println!("{}", format_output(&output, format)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+//! Report Command
+//!
+//! Vault health reports for editorial reviews: a self-contained HTML page,
+//! a Markdown note to keep in the vault, or CSV tables.
+//!
+//! ```bash
+//! ddoc report vault --export html -o report.html
+//! ddoc report vault --export markdown -o vault/Reports/2024-W10.md
+//! ddoc report vault --export csv --sections stubs,stale -o reports/
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, ValueEnum};
+
+use doc_doctor_application::{ReportOptions, ReportSection, Switchboard, VaultReport};
+
+use crate::commands::watch::vault_documents;
+use crate::commands::{create_switchboard, index_vault};
+use crate::config::{config_handle, deterministic, resolve_path};
+use crate::output::{format_output, OutputFormat};
+
+/// Export format for `--export`
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ReportFormat {
+    /// Self-contained HTML page
+    Html,
+    /// Markdown note with frontmatter
+    Markdown,
+    /// One CSV table per section
+    Csv,
+}
+
+#[derive(Args)]
+pub struct ReportCommand {
+    /// Path to the vault (can be a path alias from config)
+    #[arg(default_value = ".")]
+    pub path: String,
+
+    /// Export instead of printing the report
+    #[arg(long, value_enum)]
+    pub export: Option<ReportFormat>,
+
+    /// Write the export to a file; a directory for CSV
+    #[arg(short, long, requires = "export")]
+    pub output: Option<PathBuf>,
+
+    /// Sections to include (summary, health, gates, stubs, stale, attention)
+    #[arg(long, value_delimiter = ',')]
+    pub sections: Vec<ReportSection>,
+
+    /// Most documents listed as stale or needing attention
+    #[arg(long, default_value = "10")]
+    pub limit: usize,
+
+    /// Analyze every file instead of reusing the vault index
+    #[arg(long)]
+    pub no_index: bool,
+}
+
+impl ReportCommand {
+    pub fn run(&self, format: OutputFormat, verbose: bool) -> Result<()> {
+        let root = resolve_path(&self.path);
+        let files = vault_documents(&root)?;
+        let (index, refresh) = index_vault(&root, &files, !self.no_index);
+
+        if verbose {
+            eprintln!(
+                "Index: {} reused, {} analyzed, {} removed",
+                refresh.reused + refresh.rehashed,
+                refresh.analyzed,
+                refresh.removed
+            );
+        }
+        if let Some(error) = &refresh.store_error {
+            eprintln!("Warning: could not store the index: {}", error);
+        }
+
+        let mut options = ReportOptions {
+            limit: self.limit,
+            file_times: !deterministic(),
+            ..Default::default()
+        };
+        if !self.sections.is_empty() {
+            options.sections = self.sections.clone();
+        }
+        let config = create_switchboard().config();
+        let now = config_handle().now();
+        let documents = index.documents(&root, &files);
+        let report = VaultReport::build(vault_name(&root), &documents, &config, now, &options);
+
+        let rendered = match self.export {
+            Some(ReportFormat::Html) => report.html(),
+            Some(ReportFormat::Markdown) => report.markdown(),
+            Some(ReportFormat::Csv) => return self.write_csv(&report, verbose),
+            None => format_output(&report, format)?,
+        };
+
+        match &self.output {
+            Some(output) => std::fs::write(output, rendered)
+                .with_context(|| format!("Failed to write '{}'", output.display()))?,
+            None => println!("{}", rendered),
+        }
+        Ok(())
+    }
+
+    /// Write `<section>.csv` files into the output directory, or a single
+    /// section to stdout
+    fn write_csv(&self, report: &VaultReport, verbose: bool) -> Result<()> {
+        let sections = report.sections();
+        let Some(dir) = &self.output else {
+            let [section] = sections[..] else {
+                bail!("CSV export of several sections needs --output DIR (or a single --sections)");
+            };
+            print!("{}", report.csv(section).unwrap_or_default());
+            return Ok(());
+        };
+
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create '{}'", dir.display()))?;
+        for section in sections {
+            let path = dir.join(format!("{}.csv", section));
+            std::fs::write(&path, report.csv(section).unwrap_or_default())
+                .with_context(|| format!("Failed to write '{}'", path.display()))?;
+            if verbose {
+                eprintln!("Wrote {}", path.display());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Name of the vault folder, for report titles
+fn vault_name(root: &Path) -> String {
+    let absolute = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    absolute
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| root.display().to_string())
+}
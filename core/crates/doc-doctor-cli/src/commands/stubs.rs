@@ -8,17 +8,24 @@
 //! - `add` - Add a new stub to document frontmatter
 //! - `resolve` - Remove a resolved stub
 //! - `update` - Update stub properties
+//! - `sync` - Create frontmatter stubs from `> [!stub]` callouts and
+//!   `{{type: ...}}` markers in the body
+//! - `suggest` - Propose stubs for gaps detected in the body
 
 use anyhow::Result;
 use clap::{Args, Subcommand};
 use std::path::PathBuf;
 
-use doc_doctor_application::{NewStub, StubFilter, StubUpdates, Switchboard};
+use doc_doctor_application::{
+    DetectionRule, DetectorOptions, NewStub, StubFilter, StubUpdates, Switchboard,
+};
+use doc_doctor_domain::{is_canvas, Stub};
 
-use crate::commands::{create_switchboard, read_file, write_file};
+use crate::commands::{create_switchboard, read_file, walk_pattern, write_file};
 use crate::output::{
     format_output, AnchorInfo, AnchorsOutput, OutputFormat, StubAddOutput, StubAnchorInfo,
-    StubLinkOutput, StubOutput, StubResolveOutput, StubUpdateOutput, StubsOutput,
+    StubLinkOutput, StubOutput, StubResolveOutput, StubSuggestFileOutput, StubSuggestOutput,
+    StubSuggestionOutput, StubSyncFileOutput, StubSyncOutput, StubUpdateOutput, StubsOutput,
 };
 
 #[derive(Args)]
@@ -43,6 +50,12 @@ pub enum StubsSubcommand {
 
     /// Find anchors and link to stubs
     Anchors(AnchorsCommand),
+
+    /// Sync stub markers in the body with frontmatter stubs
+    Sync(SyncCommand),
+
+    /// Propose stubs for gaps detected in the body
+    Suggest(SuggestCommand),
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -171,6 +184,47 @@ pub struct AnchorsCommand {
     pub dry_run: bool,
 }
 
+// ═══════════════════════════════════════════════════════════════════════════
+//                              SYNC COMMAND
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Args)]
+pub struct SyncCommand {
+    /// File pattern (glob)
+    pub pattern: String,
+
+    /// Don't actually modify files, just show what would change
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Don't modify files; fail if any marker has no matching stub
+    #[arg(long, conflicts_with = "dry_run")]
+    pub check: bool,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+//                              SUGGEST COMMAND
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Args)]
+pub struct SuggestCommand {
+    /// File pattern (glob)
+    pub pattern: String,
+
+    /// Only run these rules (todo-marker, citation-needed, empty-section,
+    /// unlinked-claim, long-section, open-question)
+    #[arg(long = "rule")]
+    pub rules: Vec<DetectionRule>,
+
+    /// Words above which a section is suggested for splitting
+    #[arg(long, default_value_t = DetectorOptions::default().long_section_words)]
+    pub max_section_words: usize,
+
+    /// Add the suggestions to frontmatter and anchor them in the body
+    #[arg(long)]
+    pub apply: bool,
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 //                          COMMAND EXECUTION
 // ═══════════════════════════════════════════════════════════════════════════
@@ -183,6 +237,8 @@ impl StubsCommand {
             StubsSubcommand::Resolve(cmd) => run_resolve(cmd, format, verbose),
             StubsSubcommand::Update(cmd) => run_update(cmd, format, verbose),
             StubsSubcommand::Anchors(cmd) => run_anchors(cmd, format, verbose),
+            StubsSubcommand::Sync(cmd) => run_sync(cmd, format, verbose),
+            StubsSubcommand::Suggest(cmd) => run_suggest(cmd, format, verbose),
         }
     }
 }
@@ -201,9 +257,21 @@ fn run_list(cmd: &ListCommand, format: OutputFormat, verbose: bool) -> Result<()
         priority: None,
     });
 
-    let stubs = switchboard
-        .list_stubs(&content, filter)
-        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    // Canvases list the stubs of their sidecar and cards, as `analyze` does
+    let stubs = if is_canvas(&cmd.path) {
+        let canvas = switchboard
+            .analyze_canvas(&content)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let mut stubs = canvas.analysis.properties.stubs;
+        if let Some(f) = &filter {
+            stubs.retain(|s| f.matches(s));
+        }
+        stubs
+    } else {
+        switchboard
+            .list_stubs(&content, filter)
+            .map_err(|e| anyhow::anyhow!("{}", e))?
+    };
 
     // Apply form filter (not in StubFilter currently)
     let filtered_stubs: Vec<_> = if let Some(form_filter) = &cmd.form_filter {
@@ -421,3 +489,128 @@ fn run_anchors(cmd: &AnchorsCommand, format: OutputFormat, verbose: bool) -> Res
 
     Ok(())
 }
+
+fn run_sync(cmd: &SyncCommand, format: OutputFormat, verbose: bool) -> Result<()> {
+    let switchboard = create_switchboard();
+    let paths = walk_pattern(&cmd.pattern)?;
+
+    if paths.is_empty() {
+        println!("No files match pattern: {}", cmd.pattern);
+        return Ok(());
+    }
+
+    let write = !cmd.dry_run && !cmd.check;
+    let mut files = Vec::new();
+    for path in paths {
+        if verbose {
+            eprintln!("Syncing stub markers: {}", path.display());
+        }
+
+        let content = read_file(&path)?;
+        let result = switchboard
+            .sync_stub_markers(&content)
+            .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+        if write && result.updated_content != content {
+            write_file(&path, &result.updated_content)?;
+        }
+
+        // Before a sync, markers without stubs are what it would create
+        let status = if write { result.status } else { switchboard.stub_sync_status(&content)? };
+        files.push(StubSyncFileOutput {
+            path: path.display().to_string(),
+            created: stub_outputs(&result.created),
+            updated: stub_outputs(&result.updated),
+            missing_in_frontmatter: stub_outputs(&status.missing_in_frontmatter),
+            missing_in_body: stub_outputs(&status.missing_in_body),
+        });
+    }
+
+    let output = StubSyncOutput {
+        dry_run: !write,
+        total_created: files.iter().map(|f| f.created.len()).sum(),
+        total_updated: files.iter().map(|f| f.updated.len()).sum(),
+        files,
+    };
+    println!("{}", format_output(&output, format)?);
+
+    if cmd.check && output.total_created + output.total_updated > 0 {
+        anyhow::bail!(
+            "{} stub marker(s) out of sync with frontmatter",
+            output.total_created + output.total_updated
+        );
+    }
+    Ok(())
+}
+
+fn run_suggest(cmd: &SuggestCommand, format: OutputFormat, verbose: bool) -> Result<()> {
+    let switchboard = create_switchboard();
+    let paths = walk_pattern(&cmd.pattern)?;
+
+    if paths.is_empty() {
+        println!("No files match pattern: {}", cmd.pattern);
+        return Ok(());
+    }
+
+    let options = DetectorOptions {
+        rules: if cmd.rules.is_empty() { DetectionRule::ALL.to_vec() } else { cmd.rules.clone() },
+        long_section_words: cmd.max_section_words,
+    };
+    let mut files = Vec::new();
+    for path in paths {
+        if verbose {
+            eprintln!("Detecting stubs: {}", path.display());
+        }
+
+        let content = read_file(&path)?;
+        let suggestions = switchboard
+            .suggest_stubs(&content, &options)
+            .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+        if suggestions.is_empty() {
+            continue;
+        }
+
+        if cmd.apply {
+            let result = switchboard
+                .apply_stub_suggestions(&content, &suggestions)
+                .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+            write_file(&path, &result.updated_content)?;
+        }
+        files.push(StubSuggestFileOutput {
+            path: path.display().to_string(),
+            suggestions: suggestions
+                .iter()
+                .map(|s| StubSuggestionOutput {
+                    rule: s.rule.id().to_string(),
+                    stub_type: s.stub.stub_type.as_str().to_string(),
+                    description: s.stub.description.clone(),
+                    stub_form: s.stub.stub_form.to_string(),
+                    anchor: s.stub.anchor.clone(),
+                    line: s.position.line,
+                    column: s.position.column,
+                    excerpt: s.excerpt.clone(),
+                })
+                .collect(),
+        });
+    }
+
+    let output = StubSuggestOutput {
+        applied: cmd.apply,
+        total: files.iter().map(|f| f.suggestions.len()).sum(),
+        files,
+    };
+    println!("{}", format_output(&output, format)?);
+    Ok(())
+}
+
+fn stub_outputs(stubs: &[Stub]) -> Vec<StubOutput> {
+    stubs
+        .iter()
+        .map(|s| StubOutput {
+            stub_type: s.stub_type.as_str().to_string(),
+            description: s.description.clone(),
+            stub_form: s.stub_form.to_string(),
+            priority: s.priority.to_string(),
+            is_blocking: s.is_blocking(),
+        })
+        .collect()
+}
@@ -5,9 +5,10 @@
 use anyhow::Result;
 use clap::Args;
 
-use doc_doctor_domain::{calculate_usefulness_with_config, Audience};
+use doc_doctor_application::Switchboard;
+use doc_doctor_domain::Audience;
 
-use crate::config::get_config;
+use crate::commands::create_switchboard;
 use crate::output::{format_output, OutputFormat, UsefulnessOutput};
 use crate::tui::widgets::tables::format_usefulness_output;
 
@@ -24,7 +25,8 @@ pub struct UsefulnessCommand {
 
 impl UsefulnessCommand {
     pub fn run(&self, format: OutputFormat, verbose: bool) -> Result<()> {
-        let config = get_config();
+        let switchboard = create_switchboard();
+        let config = switchboard.config();
 
         if verbose {
             eprintln!(
@@ -52,7 +54,7 @@ impl UsefulnessCommand {
             .map_err(|_| anyhow::anyhow!("Invalid audience: {}", self.audience))?;
 
         // Calculate usefulness using config
-        let result = calculate_usefulness_with_config(self.refinement, audience, config);
+        let result = switchboard.calc_usefulness(self.refinement, audience);
 
         match format {
             OutputFormat::Human => {
@@ -1,47 +1,77 @@
 //! Validate Command
 //!
-//! Validate frontmatter against J-Editorial schema.
+//! Validate frontmatter against J-Editorial schema, and optionally report
+//! blocking stubs and audience gate failures. With `--format sarif`,
+//! `junit` or `github` the findings of all files are printed as one CI
+//! report, e.g.
+//!
+//! ```bash
+//! ddoc validate "docs/**/*.md" --blocking-stubs --gates -f github
+//! ```
 
 use anyhow::Result;
 use clap::Args;
-use glob::glob;
 
-use doc_doctor_domain::ValidateDocument;
+use doc_doctor_application::{
+    blocking_stub_findings, gate_finding, schema_findings, Finding, FindingLevel, Switchboard,
+};
+use doc_doctor_domain::{
+    apply_fixes, SchemaError, SchemaWarning, SourcePosition, ValidateDocument,
+};
 
-use crate::commands::{create_validate_use_case, read_file};
-use crate::output::{format_output, OutputFormat, ValidationOutput};
+use crate::commands::{
+    create_parser, create_switchboard, create_validate_use_case, read_file, walk_pattern,
+    write_file, CliSwitchboard,
+};
+use crate::output::{format_findings, format_output, OutputFormat, ValidationOutput};
 
 #[derive(Args)]
 pub struct ValidateCommand {
     /// File pattern (glob)
     pub pattern: String,
 
-    /// Strict mode - reject unknown fields
+    /// Strict mode - reject unknown fields and YAML hazards
     #[arg(short, long)]
     pub strict: bool,
+
+    /// Apply automatic fixes for YAML hazards before validating
+    #[arg(long)]
+    pub fix: bool,
+
+    /// Also fail on blocking stubs
+    #[arg(long)]
+    pub blocking_stubs: bool,
+
+    /// Also fail when refinement is below the gate of the document's audience
+    #[arg(long)]
+    pub gates: bool,
 }
 
 impl ValidateCommand {
     pub fn run(&self, format: OutputFormat, verbose: bool) -> Result<()> {
         let use_case = create_validate_use_case();
-        let paths: Vec<_> = glob(&self.pattern)
-            .map_err(|e| anyhow::anyhow!("Invalid pattern: {}", e))?
-            .filter_map(|p| p.ok())
-            .collect();
+        let parser = create_parser();
+        let switchboard = (self.blocking_stubs || self.gates).then(create_switchboard);
+        let paths = walk_pattern(&self.pattern)?;
 
         if paths.is_empty() {
-            println!("No files match pattern: {}", self.pattern);
-            return Ok(());
+            if !format.is_ci() {
+                println!("No files match pattern: {}", self.pattern);
+                return Ok(());
+            }
+            eprintln!("No files match pattern: {}", self.pattern);
         }
 
         let mut all_valid = true;
+        let mut checked = Vec::new();
+        let mut findings = Vec::new();
 
         for path in paths {
             if verbose {
                 eprintln!("Validating: {}", path.display());
             }
 
-            let content = match read_file(&path) {
+            let mut content = match read_file(&path) {
                 Ok(c) => c,
                 Err(e) => {
                     eprintln!("Error reading {}: {}", path.display(), e);
@@ -50,35 +80,119 @@ impl ValidateCommand {
                 }
             };
 
+            if self.fix {
+                let (fixed, applied) = apply_fixes(&content, &parser.lint(&content));
+                if applied > 0 {
+                    write_file(&path, &fixed)?;
+                    eprintln!("Fixed {} issue(s) in {}", applied, path.display());
+                    content = fixed;
+                }
+            }
+
+            let document = path.display().to_string();
             match use_case.validate(&content, self.strict) {
                 Ok(result) => {
+                    let editorial = switchboard
+                        .as_deref()
+                        .map(|sb| self.editorial_findings(sb, &document, &content))
+                        .unwrap_or_default();
+                    let is_valid = result.is_valid
+                        && editorial.iter().all(|f| f.level != FindingLevel::Error);
+                    if !is_valid {
+                        all_valid = false;
+                    }
+
+                    if format.is_ci() {
+                        findings.extend(schema_findings(&document, &result));
+                        findings.extend(editorial);
+                        checked.push(document);
+                        continue;
+                    }
+
+                    let mut errors: Vec<String> =
+                        result.errors.iter().map(describe_error).collect();
+                    errors.extend(editorial.iter().map(|f| located(&f.message, f.position)));
                     let output = ValidationOutput {
-                        path: path.display().to_string(),
-                        is_valid: result.is_valid,
-                        error_count: result.errors.len(),
+                        path: document,
+                        is_valid,
+                        error_count: errors.len(),
                         warning_count: result.warnings.len(),
-                        errors: result.errors.iter().map(|e| e.message.clone()).collect(),
-                        warnings: result.warnings.iter().map(|w| w.message.clone()).collect(),
+                        errors,
+                        warnings: result.warnings.iter().map(describe_warning).collect(),
                     };
 
-                    if !result.is_valid {
-                        all_valid = false;
-                    }
-
                     println!("{}", format_output(&output, format)?);
                     println!();
                 }
                 Err(e) => {
                     eprintln!("Validation error for {}: {}", path.display(), e);
                     all_valid = false;
+                    if format.is_ci() {
+                        let message = e.to_string();
+                        let finding =
+                            Finding::new(&document, "schema-error", FindingLevel::Error, message);
+                        findings.push(finding);
+                        checked.push(document);
+                    }
                 }
             }
         }
 
+        if let Some(report) = format_findings(&findings, &checked, format) {
+            println!("{}", report);
+        }
+
         if !all_valid {
             std::process::exit(1);
         }
 
         Ok(())
     }
+
+    /// Blocking stubs and gate failures, as requested by the flags
+    fn editorial_findings(
+        &self,
+        switchboard: &CliSwitchboard,
+        path: &str,
+        content: &str,
+    ) -> Vec<Finding> {
+        let Ok(analysis) = switchboard.analyze_document(content) else {
+            return Vec::new();
+        };
+
+        let mut findings = Vec::new();
+        if self.blocking_stubs {
+            let block_ids = switchboard
+                .parse_body(content)
+                .map(|body| body.block_ids)
+                .unwrap_or_default();
+            let stubs = &analysis.properties.stubs;
+            findings.extend(blocking_stub_findings(path, content, stubs, &block_ids));
+        }
+        if self.gates {
+            let config = switchboard.config();
+            findings.extend(gate_finding(path, content, &analysis.properties, &config));
+        }
+        findings
+    }
+}
+
+fn describe_error(error: &SchemaError) -> String {
+    located(&error.message, error.position)
+}
+
+fn describe_warning(warning: &SchemaWarning) -> String {
+    let message = located(&warning.message, warning.position);
+    match &warning.suggestion {
+        Some(suggestion) => format!("{} ({})", message, suggestion),
+        None => message,
+    }
+}
+
+/// Prefix a message with its `line:column`, when known
+fn located(message: &str, position: Option<SourcePosition>) -> String {
+    match position {
+        Some(pos) => format!("{}:{}: {}", pos.line, pos.column, message),
+        None => message.to_string(),
+    }
 }
@@ -0,0 +1,189 @@
+//! Watch Command
+//!
+//! Watch a vault and re-analyze documents as they change, streaming one
+//! JSON event per line (NDJSON) to stdout:
+//!
+//! - `document_changed` / `document_removed`
+//! - `health_gate_crossed` when health passes or drops below the gate of
+//!   the document's audience
+//! - `stub_added` / `stub_resolved`
+//! - `anchor_orphaned` when a stub's block anchor disappears from the body
+//!
+//! Other tools subscribe by reading the stream; the dashboard consumes the
+//! same events to refresh live.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use doc_doctor_application::{IndexRefresh, VaultEvent, VaultWalker, VaultWatch};
+use doc_doctor_domain::is_canvas;
+use doc_doctor_fs::{JsonIndexStore, FOLDER_METADATA_FILE, SIDECAR_SUFFIX};
+
+use crate::commands::{create_repository, create_switchboard, create_walker, index_vault};
+use crate::config::{resolve_path, should_ignore_path};
+use crate::output::OutputFormat;
+
+#[derive(Args)]
+pub struct WatchCommand {
+    /// Path to the vault (can be a path alias from config)
+    #[arg(default_value = ".")]
+    pub path: String,
+
+    /// Milliseconds to wait for a burst of changes to settle
+    #[arg(long, value_name = "MS", default_value = "300")]
+    pub debounce: u64,
+
+    /// Analyze every file at startup instead of reusing the vault index
+    #[arg(long)]
+    pub no_index: bool,
+}
+
+impl WatchCommand {
+    pub fn run(&self, _format: OutputFormat, verbose: bool) -> Result<()> {
+        let root = resolve_path(&self.path);
+        let files = vault_documents(&root)?;
+        let (watch, _) = open_watch(&root, &files, !self.no_index);
+
+        if verbose {
+            eprintln!("Watching {} documents in {}", files.len(), root.display());
+        }
+
+        let events = watch.subscribe();
+        std::thread::spawn(move || {
+            let mut stdout = std::io::stdout().lock();
+            for event in events {
+                if writeln!(stdout, "{}", event_line(&event)).and_then(|_| stdout.flush()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        run_watch(watch, Duration::from_millis(self.debounce))
+    }
+}
+
+/// An event as an NDJSON line, stamped with the time it was emitted
+fn event_line(event: &VaultEvent) -> String {
+    let mut value = serde_json::to_value(event).unwrap_or_default();
+    value["time"] = chrono::Utc::now().to_rfc3339().into();
+    value.to_string()
+}
+
+/// Markdown files, canvases and sidecar-backed documents in a vault,
+/// without ignored paths
+pub fn vault_documents(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = doc_doctor_fs::vault_documents(&create_walker(), root)?;
+    files.retain(|f| !should_ignore_path(f));
+    Ok(files)
+}
+
+/// Bring the index of the vault at `root` up to date and watch from there
+///
+/// With `use_index` the stored index is reused and kept up to date.
+pub fn open_watch(root: &Path, files: &[PathBuf], use_index: bool) -> (VaultWatch, IndexRefresh) {
+    let (index, refresh) = index_vault(root, files, use_index);
+    let watch = VaultWatch::new(root, index);
+    if use_index {
+        (watch.with_store(Box::new(JsonIndexStore::for_vault(root))), refresh)
+    } else {
+        (watch, refresh)
+    }
+}
+
+/// Feed file system changes under the watch root into `watch` until the
+/// watcher fails
+///
+/// Changes are collected until none arrived for `debounce`, so an editor's
+/// burst of writes is analyzed once.
+pub fn run_watch(mut watch: VaultWatch, debounce: Duration) -> Result<()> {
+    let switchboard = create_switchboard();
+    let repository = create_repository();
+    let walker = create_walker();
+    let root = watch.root().to_path_buf();
+
+    let (sender, receiver) = channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .with_context(|| format!("Failed to watch '{}'", root.display()))?;
+    // Events carry absolute paths; keep keys relative to the vault
+    let absolute = root.canonicalize().unwrap_or_else(|_| root.clone());
+
+    loop {
+        let first = receiver.recv()?;
+        let mut changed = Vec::new();
+        let mut pending = Some(first);
+        let deadline = || Instant::now() + debounce;
+        let mut until = deadline();
+
+        while let Some(event) = pending.take() {
+            let event = event?;
+            if !matches!(event.kind, EventKind::Access(_)) {
+                for path in &event.paths {
+                    let relative = path.strip_prefix(&absolute).unwrap_or(path);
+                    changed.extend(documents_for(&watch, &walker, &root, relative));
+                }
+                until = deadline();
+            }
+            match receiver.recv_timeout(until.saturating_duration_since(Instant::now())) {
+                Ok(event) => pending = Some(event),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => anyhow::bail!("File watcher stopped"),
+            }
+        }
+
+        changed.sort();
+        changed.dedup();
+        if !changed.is_empty() {
+            watch.update(&*switchboard, &repository, &changed);
+        }
+    }
+}
+
+/// Documents affected by a change to `relative`, a path within the vault
+fn documents_for(
+    watch: &VaultWatch,
+    walker: &VaultWalker,
+    root: &Path,
+    relative: &Path,
+) -> Vec<PathBuf> {
+    let path = root.join(relative);
+    if relative.starts_with(".doc-doctor")
+        || should_ignore_path(&path)
+        || walker.is_ignored(root, &path)
+    {
+        return Vec::new();
+    }
+
+    let name = relative.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let indexed = |path: &Path| watch.index().get(root, path).is_some();
+
+    // Folder metadata covers every indexed document beside it
+    if name == FOLDER_METADATA_FILE {
+        let folder = relative.parent().unwrap_or(Path::new(""));
+        return watch
+            .index()
+            .entries
+            .keys()
+            .map(PathBuf::from)
+            .filter(|key| key.parent() == Some(folder))
+            .map(|key| root.join(key))
+            .collect();
+    }
+    if let Some(document) = name.strip_suffix(SIDECAR_SUFFIX) {
+        return vec![path.with_file_name(document)];
+    }
+
+    let markdown = path.extension().is_some_and(|e| e == "md");
+    if markdown || is_canvas(&path) || indexed(&path) {
+        vec![path]
+    } else {
+        Vec::new()
+    }
+}
@@ -4,18 +4,28 @@
 
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::{OnceLock, RwLock};
+use std::sync::{Arc, OnceLock, RwLock};
 
+use doc_doctor_application::ConfigHandle;
 use doc_doctor_config_yaml::{
-    config_sources, load_layered_config, user_config_path, FileConfigProvider,
+    config_sources, layered_provider, user_config_path, FileConfigProvider, LayeredYamlProvider,
 };
-use doc_doctor_domain::{CalculationConfig, ConfigProvider};
+use doc_doctor_domain::{CalculationConfig, ConfigProvider, Timestamp};
 use serde::{Deserialize, Serialize};
 
 use crate::tui::app::Column;
 
-/// Global calculation configuration instance
-static CONFIG: OnceLock<CalculationConfig> = OnceLock::new();
+/// Global calculation configuration handle, shared with every switchboard
+static CONFIG: OnceLock<ConfigHandle> = OnceLock::new();
+
+/// Profile selected with `--profile`
+static PROFILE: OnceLock<Option<String>> = OnceLock::new();
+
+/// Time selected with `--as-of`
+static AS_OF: OnceLock<Option<Timestamp>> = OnceLock::new();
+
+/// Whether `--deterministic` was given
+static DETERMINISTIC: OnceLock<bool> = OnceLock::new();
 
 /// Global CLI configuration instance (mutable for saving)
 static CLI_CONFIG: RwLock<Option<CliConfig>> = RwLock::new(None);
@@ -200,25 +210,97 @@ pub fn resolve_path(path: &str) -> PathBuf {
     get_cli_config().resolve_path(path)
 }
 
-/// Load and return the global configuration
+/// Select the profile for this run
+///
+/// Must be called before the configuration is first loaded; later calls
+/// are ignored.
+pub fn set_profile(profile: Option<String>) {
+    let _ = PROFILE.set(profile);
+}
+
+/// Get the profile selected with `--profile`, if any
+pub fn selected_profile() -> Option<String> {
+    PROFILE.get().cloned().flatten()
+}
+
+/// Calculate as of a fixed time for this run
 ///
-/// Configuration is loaded once and cached. Subsequent calls return the cached config.
+/// Dates without an offset are read in the vault timezone. Must be called
+/// before the configuration is first loaded; later calls are ignored.
+pub fn set_as_of(as_of: Option<Timestamp>, deterministic: bool) {
+    let _ = AS_OF.set(as_of);
+    let _ = DETERMINISTIC.set(deterministic);
+}
+
+/// Whether output must be reproducible: besides the fixed `--as-of` time,
+/// nothing may depend on the checkout, such as file modification times
+pub fn deterministic() -> bool {
+    DETERMINISTIC.get().copied().unwrap_or(false)
+}
+
+/// Build the layered config provider, honouring `--profile`
+pub fn config_provider() -> LayeredYamlProvider {
+    layered_provider().with_profile(selected_profile())
+}
+
+/// Get the global configuration handle
+///
+/// Configuration is loaded once; the handle is injected into every switchboard
+/// and use case so they all calculate with the same values.
 ///
 /// Loading order (later overrides earlier):
 /// 1. Built-in defaults
 /// 2. User config: `~/.config/doc-doctor/config.yaml`
-/// 3. Project config: `.doc-doctor.yaml` in current directory
-pub fn get_config() -> &'static CalculationConfig {
-    CONFIG.get_or_init(|| {
-        match load_layered_config() {
-            Ok(config) => config,
-            Err(e) => {
-                eprintln!("Warning: Failed to load config: {}", e);
+/// 3. Profile: `--profile`, else the `profile:` key of the project or user config
+/// 4. Project config: `.doc-doctor.yaml` in current directory
+///
+/// With `--as-of` every calculation is made at that time. Configuration
+/// that cannot be loaded falls back to the defaults with a warning, unless
+/// [`require_config`] loaded it first.
+pub fn config_handle() -> ConfigHandle {
+    CONFIG
+        .get_or_init(|| {
+            load_handle().unwrap_or_else(|e| {
+                eprintln!("Warning: {}", e);
                 eprintln!("Using default configuration");
-                CalculationConfig::default()
-            }
-        }
-    })
+                with_as_of(ConfigHandle::defaults())
+            })
+        })
+        .clone()
+}
+
+/// Load the configuration now, failing when it cannot be loaded
+///
+/// Used when a profile is requested explicitly, so that a typo in
+/// `--profile` is not silently replaced by the defaults.
+pub fn require_config() -> anyhow::Result<()> {
+    if CONFIG.get().is_none() {
+        let _ = CONFIG.set(load_handle()?);
+    }
+    Ok(())
+}
+
+/// Configuration handle of the layered config files
+fn load_handle() -> anyhow::Result<ConfigHandle> {
+    let config = config_provider()
+        .load()
+        .map_err(|e| anyhow::anyhow!("Failed to load config: {}", e))?;
+    let handle =
+        ConfigHandle::new(config).map_err(|e| anyhow::anyhow!("Invalid configuration: {}", e))?;
+    Ok(with_as_of(handle))
+}
+
+/// `handle` calculating at the `--as-of` time, if one was given
+fn with_as_of(handle: ConfigHandle) -> ConfigHandle {
+    if let Some(as_of) = AS_OF.get().cloned().flatten() {
+        handle.set_as_of(Some(as_of.resolve(handle.get().vault.tz())));
+    }
+    handle
+}
+
+/// Snapshot of the global configuration
+pub fn get_config() -> Arc<CalculationConfig> {
+    config_handle().get()
 }
 
 /// Print configuration sources for verbose output
@@ -228,6 +310,11 @@ pub fn print_config_sources() {
         let status = if exists { "loaded" } else { "not found" };
         eprintln!("  {} [{}]", source, status);
     }
+    match config_provider().active_profile() {
+        Ok(Some(profile)) => eprintln!("  profile: {}", profile),
+        Ok(None) => {}
+        Err(e) => eprintln!("  profile: <error: {}>", e),
+    }
 }
 
 /// Initialize user configuration file with defaults
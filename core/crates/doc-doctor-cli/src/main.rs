@@ -7,12 +7,24 @@
 //! ```bash
 //! ddoc parse document.md
 //! ddoc validate "docs/**/*.md" --strict
+//! ddoc validate "docs/**/*.md" --blocking-stubs --gates -f sarif > doc-doctor.sarif
 //! ddoc dimensions document.md
 //! ddoc batch "vault/**/*.md" --dimensions
+//! ddoc batch "archive/**/*.md" --stream --checkpoint batch.json >> results.ndjson
+//! ddoc watch vault > events.ndjson
+//! ddoc query "health < 0.8 and any(stubs, form = blocking) order by health" vault
+//! ddoc report vault --export html -o report.html
+//! ddoc check vault --update-baseline
+//! ddoc report vault --export markdown --as-of 2024-06-01 --deterministic
+//! ddoc hook install --touch-modified
 //! ddoc health --refinement 0.75
 //! ddoc usefulness --refinement 0.8 --audience internal
+//! ddoc import tasks "vault/**/*.md" --remove
+//! ddoc annotations "vault/**/*.md" --export markdown -o annotations.md
 //! ddoc config --show
 //! ddoc config --init
+//! ddoc config profiles --diff academic zettelkasten
+//! ddoc --profile academic validate "docs/**/*.md"
 //! ```
 
 mod commands;
@@ -22,12 +34,15 @@ pub mod tui;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use doc_doctor_domain::Timestamp;
 
 use commands::{
-    batch::BatchCommand, config::ConfigCommand, dashboard::DashboardCommand,
-    dimensions::DimensionsCommand, health::HealthCommand, parse::ParseCommand,
-    schema::SchemaCommand, stubs::StubsCommand, test::TestCommand,
-    usefulness::UsefulnessCommand, validate::ValidateCommand,
+    annotations::AnnotationsCommand, batch::BatchCommand, check::CheckCommand,
+    config::ConfigCommand, dashboard::DashboardCommand, dimensions::DimensionsCommand,
+    health::HealthCommand, hook::HookCommand, import::ImportCommand, parse::ParseCommand,
+    query::QueryCommand, report::ReportCommand, schema::SchemaCommand, stubs::StubsCommand,
+    test::TestCommand, usefulness::UsefulnessCommand, validate::ValidateCommand,
+    watch::WatchCommand,
 };
 use output::OutputFormat;
 
@@ -47,6 +62,19 @@ struct Cli {
     /// Verbose output
     #[arg(short, long, global = true)]
     verbose: bool,
+
+    /// Configuration profile (overrides the `profile:` key in config files)
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// Calculate freshness and reports as of this date or time instead of now
+    #[arg(long, global = true, value_name = "TIME")]
+    as_of: Option<Timestamp>,
+
+    /// Reproducible output for golden files: needs --as-of, and ignores file
+    /// modification times
+    #[arg(long, global = true, requires = "as_of")]
+    deterministic: bool,
 }
 
 #[derive(Subcommand)]
@@ -75,18 +103,44 @@ enum Commands {
     /// Export JSON schema definitions
     Schema(SchemaCommand),
 
+    /// Migrate body metadata (task checkboxes, annotations) into frontmatter
+    Import(ImportCommand),
+
+    /// List or export comments and highlights
+    Annotations(AnnotationsCommand),
+
     /// Show or initialize configuration
     Config(ConfigCommand),
 
     /// Interactive vault health dashboard
     Dashboard(DashboardCommand),
 
+    /// Watch a vault and stream change events as NDJSON
+    Watch(WatchCommand),
+
+    /// Query documents and stubs in a vault
+    Query(QueryCommand),
+
+    /// Generate a vault health report (HTML, Markdown, CSV)
+    Report(ReportCommand),
+
+    /// Enforce quality thresholds, failing only on regressions from the baseline
+    Check(CheckCommand),
+
+    /// Git pre-commit hook checking staged documents
+    Hook(HookCommand),
+
     /// Interactive test runner for document operations
     Test(TestCommand),
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    config::set_profile(cli.profile.clone());
+    config::set_as_of(cli.as_of.clone(), cli.deterministic);
+    if cli.profile.is_some() {
+        config::require_config()?;
+    }
 
     match cli.command {
         Commands::Parse(cmd) => cmd.run(cli.format, cli.verbose),
@@ -97,8 +151,15 @@ fn main() -> Result<()> {
         Commands::Usefulness(cmd) => cmd.run(cli.format, cli.verbose),
         Commands::Batch(cmd) => cmd.run(cli.format, cli.verbose),
         Commands::Schema(cmd) => cmd.run(cli.format, cli.verbose),
+        Commands::Import(cmd) => cmd.run(cli.format, cli.verbose),
+        Commands::Annotations(cmd) => cmd.run(cli.format, cli.verbose),
         Commands::Config(cmd) => cmd.run(cli.format, cli.verbose),
         Commands::Dashboard(cmd) => cmd.run(cli.format, cli.verbose),
+        Commands::Watch(cmd) => cmd.run(cli.format, cli.verbose),
+        Commands::Query(cmd) => cmd.run(cli.format, cli.verbose),
+        Commands::Report(cmd) => cmd.run(cli.format, cli.verbose),
+        Commands::Check(cmd) => cmd.run(cli.format, cli.verbose),
+        Commands::Hook(cmd) => cmd.run(cli.format, cli.verbose),
         Commands::Test(cmd) => cmd.run(cli.format, cli.verbose),
     }
 }
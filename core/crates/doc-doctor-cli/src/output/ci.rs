@@ -0,0 +1,216 @@
+//! CI Output Formats
+//!
+//! Findings as SARIF 2.1.0 (code scanning), JUnit XML (test reports) and
+//! GitHub Actions workflow commands (inline annotations on pull requests).
+
+use doc_doctor_application::{Finding, FindingLevel, FINDING_RULES};
+
+use crate::output::OutputFormat;
+
+const TOOL_NAME: &str = "doc-doctor";
+
+/// Render findings in a CI format
+///
+/// `files` are every document checked, so JUnit can report the ones that
+/// passed. Returns `None` for formats that are not CI formats.
+pub fn format_findings(
+    findings: &[Finding],
+    files: &[String],
+    format: OutputFormat,
+) -> Option<String> {
+    match format {
+        OutputFormat::Sarif => Some(sarif(findings)),
+        OutputFormat::Junit => Some(junit(findings, files)),
+        OutputFormat::Github => Some(github(findings)),
+        OutputFormat::Human | OutputFormat::Json | OutputFormat::Yaml => None,
+    }
+}
+
+/// Paths as repository-relative URIs, with forward slashes
+fn uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.strip_prefix("./").unwrap_or(&path).to_string()
+}
+
+fn sarif(findings: &[Finding]) -> String {
+    let rules: Vec<serde_json::Value> = FINDING_RULES
+        .iter()
+        .map(|(id, description)| {
+            serde_json::json!({
+                "id": id,
+                "shortDescription": { "text": description },
+            })
+        })
+        .collect();
+
+    let results: Vec<serde_json::Value> = findings
+        .iter()
+        .map(|finding| {
+            let mut location = serde_json::json!({
+                "artifactLocation": { "uri": uri(&finding.path) },
+            });
+            if let Some(position) = finding.position {
+                location["region"] = serde_json::json!({
+                    "startLine": position.line.max(1),
+                    "startColumn": position.column.max(1),
+                });
+            }
+            serde_json::json!({
+                "ruleId": finding.rule,
+                "level": finding.level.as_str(),
+                "message": { "text": finding.message },
+                "locations": [{ "physicalLocation": location }],
+            })
+        })
+        .collect();
+
+    let log = serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    });
+    serde_json::to_string_pretty(&log).unwrap_or_default()
+}
+
+/// One test case per document; errors fail it, warnings go to its output
+fn junit(findings: &[Finding], files: &[String]) -> String {
+    let mut files: Vec<&str> = files.iter().map(String::as_str).collect();
+    for finding in findings {
+        if !files.contains(&finding.path.as_str()) {
+            files.push(&finding.path);
+        }
+    }
+
+    let describe = |finding: &Finding| {
+        let location = match finding.position {
+            Some(p) => format!("{}:{}:{}", uri(&finding.path), p.line, p.column),
+            None => uri(&finding.path),
+        };
+        format!("{}: [{}] {}", location, finding.rule, finding.message)
+    };
+
+    let mut cases = String::new();
+    let mut failures = 0;
+    for file in &files {
+        let of_file: Vec<&Finding> = findings.iter().filter(|f| f.path == *file).collect();
+        let (errors, others): (Vec<&Finding>, Vec<&Finding>) =
+            of_file.iter().partition(|f| f.level == FindingLevel::Error);
+
+        cases.push_str(&format!(
+            "    <testcase classname=\"{}\" name=\"{}\" file=\"{}\">\n",
+            TOOL_NAME,
+            escape_xml(&uri(file)),
+            escape_xml(&uri(file))
+        ));
+        if !errors.is_empty() {
+            failures += 1;
+            let text: Vec<String> = errors.iter().map(|f| describe(f)).collect();
+            cases.push_str(&format!(
+                "      <failure type=\"{}\" message=\"{} problem(s)\">{}</failure>\n",
+                escape_xml(&errors[0].rule),
+                errors.len(),
+                escape_xml(&text.join("\n"))
+            ));
+        }
+        if !others.is_empty() {
+            let text: Vec<String> = others.iter().map(|f| describe(f)).collect();
+            cases.push_str(&format!(
+                "      <system-out>{}</system-out>\n",
+                escape_xml(&text.join("\n"))
+            ));
+        }
+        cases.push_str("    </testcase>\n");
+    }
+
+    let counts = format!("tests=\"{}\" failures=\"{}\"", files.len(), failures);
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <testsuites name=\"{name}\" {counts}>\n  \
+         <testsuite name=\"{name}\" {counts} errors=\"0\" skipped=\"0\">\n\
+         {cases}  </testsuite>\n</testsuites>",
+        name = TOOL_NAME,
+        counts = counts,
+        cases = cases
+    )
+}
+
+/// `::error file=...,line=...,col=...,title=...::message`, one per line
+fn github(findings: &[Finding]) -> String {
+    findings
+        .iter()
+        .map(|finding| {
+            let command = match finding.level {
+                FindingLevel::Error => "error",
+                FindingLevel::Warning => "warning",
+                FindingLevel::Note => "notice",
+            };
+            let mut properties = vec![format!("file={}", escape_property(&uri(&finding.path)))];
+            if let Some(position) = finding.position {
+                properties.push(format!("line={}", position.line));
+                properties.push(format!("col={}", position.column));
+            }
+            let title = format!("{} {}", TOOL_NAME, finding.rule);
+            properties.push(format!("title={}", escape_property(&title)));
+            format!("::{} {}::{}", command, properties.join(","), escape_data(&finding.message))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_domain::SourcePosition;
+
+    #[test]
+    fn test_ci_formats() {
+        let findings = vec![
+            Finding::new("./docs/a.md", "blocking-stub", FindingLevel::Error, "Fix: 50%, maybe")
+                .with_position(Some(SourcePosition::new(5, 3, 0))),
+            Finding::new("docs/b.md", "schema-warning", FindingLevel::Warning, "Unknown field <x>"),
+        ];
+        let files: Vec<String> =
+            ["./docs/a.md", "docs/b.md", "docs/c.md"].map(String::from).to_vec();
+
+        let sarif = format_findings(&findings, &files, OutputFormat::Sarif).unwrap();
+        let sarif: serde_json::Value = serde_json::from_str(&sarif).unwrap();
+        let location = &sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "docs/a.md");
+        assert_eq!(location["region"]["startLine"], 5);
+
+        let junit = format_findings(&findings, &files, OutputFormat::Junit).unwrap();
+        assert!(junit.contains("tests=\"3\" failures=\"1\""));
+        assert!(junit.contains("Unknown field &lt;x&gt;"));
+
+        let github = format_findings(&findings, &files, OutputFormat::Github).unwrap();
+        assert_eq!(
+            github.lines().next().unwrap(),
+            "::error file=docs/a.md,line=5,col=3,title=doc-doctor blocking-stub::Fix: 50%25, maybe"
+        );
+        assert!(format_findings(&findings, &files, OutputFormat::Json).is_none());
+    }
+}
@@ -1,9 +1,14 @@
 //! Output Formatting
 //!
-//! Supports multiple output formats: human-readable, JSON, YAML.
+//! Supports multiple output formats: human-readable, JSON, YAML, and the CI
+//! formats SARIF, JUnit XML and GitHub annotations for validation findings.
+
+mod ci;
 
 use serde::Serialize;
 
+use doc_doctor_application::{BaselineEntry, CheckFailure, Query, QueryRow, VaultReport};
+
 /// Output format options
 #[derive(Clone, Copy, Default, clap::ValueEnum)]
 pub enum OutputFormat {
@@ -14,6 +19,29 @@ pub enum OutputFormat {
     Json,
     /// YAML output
     Yaml,
+    /// SARIF 2.1.0, for code scanning (validate and check)
+    Sarif,
+    /// JUnit XML test report (validate and check)
+    Junit,
+    /// GitHub Actions workflow commands, for inline annotations (validate and check)
+    Github,
+}
+
+impl OutputFormat {
+    /// Whether this is a CI format for findings
+    pub fn is_ci(self) -> bool {
+        matches!(self, Self::Sarif | Self::Junit | Self::Github)
+    }
+}
+
+pub use ci::format_findings;
+
+/// Error for a CI format requested from a command that has no findings
+pub fn unsupported_format(format: OutputFormat) -> anyhow::Error {
+    let name = clap::ValueEnum::to_possible_value(&format)
+        .map(|v| v.get_name().to_string())
+        .unwrap_or_default();
+    anyhow::anyhow!("--format {} is only supported by `ddoc validate` and `ddoc check`", name)
 }
 
 /// Format output based on the selected format
@@ -29,6 +57,7 @@ pub fn format_output<T: Serialize + HumanReadable>(
         OutputFormat::Yaml => {
             serde_yaml::to_string(data).map_err(|e| anyhow::anyhow!("YAML error: {}", e))
         }
+        format => Err(unsupported_format(format)),
     }
 }
 
@@ -37,23 +66,59 @@ pub trait HumanReadable {
     fn to_human(&self) -> String;
 }
 
-/// Simple string wrapper for basic messages
+/// Quality gate result
 #[derive(Serialize)]
-pub struct Message {
-    pub message: String,
+#[serde(rename_all = "camelCase")]
+pub struct CheckOutput {
+    pub baseline: String,
+    pub checked: usize,
+    pub passed: bool,
+    pub regressions: Vec<CheckFailure>,
+    pub known: Vec<CheckFailure>,
+    pub improved: Vec<BaselineEntry>,
 }
 
-impl HumanReadable for Message {
+impl HumanReadable for CheckOutput {
     fn to_human(&self) -> String {
-        self.message.clone()
-    }
-}
+        let mut lines = vec![format!(
+            "Checked {} document(s) against {}",
+            self.checked, self.baseline
+        )];
+
+        if !self.regressions.is_empty() {
+            lines.push(format!("\nRegressions ({}):", self.regressions.len()));
+            for finding in self.regressions.iter().flat_map(|f| &f.findings) {
+                let location = match finding.position {
+                    Some(p) => format!("{}:{}:{}", finding.path, p.line, p.column),
+                    None => finding.path.clone(),
+                };
+                lines.push(format!("  {} [{}] {}", location, finding.rule, finding.message));
+            }
+        }
 
-impl Message {
-    pub fn new(msg: impl Into<String>) -> Self {
-        Self {
-            message: msg.into(),
+        if !self.known.is_empty() {
+            lines.push(format!("\nKnown failures: {} (in the baseline)", self.known.len()));
+        }
+
+        if !self.improved.is_empty() {
+            lines.push(format!(
+                "\nImproved ({}), run `ddoc check --update-baseline` to ratchet:",
+                self.improved.len()
+            ));
+            for entry in &self.improved {
+                let current = match entry.current {
+                    Some(value) => format!("{}", value),
+                    None => "fixed".to_string(),
+                };
+                lines.push(format!(
+                    "  {} [{}] {} -> {}",
+                    entry.path, entry.rule, entry.baseline, current
+                ));
+            }
         }
+
+        lines.push(format!("\nResult: {}", if self.passed { "PASS" } else { "FAIL" }));
+        lines.join("\n")
     }
 }
 
@@ -106,7 +171,7 @@ pub struct DimensionsOutput {
 impl HumanReadable for DimensionsOutput {
     fn to_human(&self) -> String {
         let useful_str = if self.is_useful { "Yes" } else { "No" };
-        vec![
+        [
             format!("File: {}", self.path),
             format!("Health: {:.4}", self.health),
             format!("Usefulness Margin: {:.4}", self.usefulness_margin),
@@ -129,7 +194,7 @@ pub struct HealthOutput {
 
 impl HumanReadable for HealthOutput {
     fn to_human(&self) -> String {
-        vec![
+        [
             format!("Health: {:.4}", self.health),
             format!("Refinement: {:.2}", self.refinement),
             format!("Stub Count: {}", self.stub_count),
@@ -153,7 +218,7 @@ pub struct UsefulnessOutput {
 impl HumanReadable for UsefulnessOutput {
     fn to_human(&self) -> String {
         let useful_str = if self.is_useful { "Yes" } else { "No" };
-        vec![
+        [
             format!("Margin: {:.4}", self.margin),
             format!("Is Useful: {}", useful_str),
             format!("Refinement: {:.2}", self.refinement),
@@ -208,6 +273,7 @@ pub struct BatchOutput {
     pub total: usize,
     pub succeeded: usize,
     pub failed: usize,
+    pub partial: usize,
     pub average_health: Option<f64>,
     pub results: Vec<BatchDocumentOutput>,
 }
@@ -217,8 +283,32 @@ pub struct BatchOutput {
 pub struct BatchDocumentOutput {
     pub path: String,
     pub success: bool,
+    pub partial: bool,
     pub health: Option<f64>,
     pub error: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// A line of `ddoc batch --stream` output
+#[derive(Serialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+pub enum BatchRecord {
+    Document(BatchDocumentOutput),
+    Summary(BatchSummaryOutput),
+}
+
+/// Aggregates of a streamed batch, including resumed runs
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSummaryOutput {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub partial: usize,
+    pub average_health: Option<f64>,
 }
 
 impl HumanReadable for BatchOutput {
@@ -229,6 +319,10 @@ impl HumanReadable for BatchOutput {
             format!("Failed: {}", self.failed),
         ];
 
+        if self.partial > 0 {
+            lines.push(format!("Partial: {}", self.partial));
+        }
+
         if let Some(avg) = self.average_health {
             lines.push(format!("Average Health: {:.4}", avg));
         }
@@ -246,6 +340,25 @@ impl HumanReadable for BatchOutput {
             }
         }
 
+        if self.partial > 0 {
+            lines.push("\nPartially parsed documents:".to_string());
+            for doc in self.results.iter().filter(|d| d.partial) {
+                lines.push(format!("  - {}", doc.path));
+                for diagnostic in &doc.diagnostics {
+                    lines.push(format!("      {}", diagnostic));
+                }
+            }
+        }
+
+        if self.results.iter().any(|d| !d.warnings.is_empty()) {
+            lines.push("\nWarnings:".to_string());
+            for doc in self.results.iter().filter(|d| !d.warnings.is_empty()) {
+                for warning in &doc.warnings {
+                    lines.push(format!("  - {}: {}", doc.path, warning));
+                }
+            }
+        }
+
         lines.join("\n")
     }
 }
@@ -445,3 +558,304 @@ impl HumanReadable for AnchorsOutput {
         lines.join("\n")
     }
 }
+
+/// Result of importing task checkboxes or annotations as stubs
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StubImportOutput {
+    pub dry_run: bool,
+    pub total_imported: usize,
+    pub files: Vec<StubImportFileOutput>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StubImportFileOutput {
+    pub path: String,
+    pub skipped: usize,
+    pub imported: Vec<StubOutput>,
+}
+
+impl HumanReadable for StubImportOutput {
+    fn to_human(&self) -> String {
+        let verb = if self.dry_run { "Would import" } else { "Imported" };
+        let mut lines = vec![format!(
+            "{} {} stub(s) from {} file(s)",
+            verb,
+            self.total_imported,
+            self.files.len()
+        )];
+
+        for file in &self.files {
+            lines.push(format!("\n{} ({} already stubs)", file.path, file.skipped));
+            for stub in &file.imported {
+                lines.push(format!("  + [{}] {} ({})", stub.stub_type, stub.description, stub.priority));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Result of syncing stub markers with frontmatter
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StubSyncOutput {
+    pub dry_run: bool,
+    pub total_created: usize,
+    pub total_updated: usize,
+    pub files: Vec<StubSyncFileOutput>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StubSyncFileOutput {
+    pub path: String,
+    pub created: Vec<StubOutput>,
+    pub updated: Vec<StubOutput>,
+    pub missing_in_frontmatter: Vec<StubOutput>,
+    pub missing_in_body: Vec<StubOutput>,
+}
+
+impl HumanReadable for StubSyncOutput {
+    fn to_human(&self) -> String {
+        let (created, updated) = if self.dry_run {
+            ("Would create", "would update")
+        } else {
+            ("Created", "updated")
+        };
+        let mut lines = vec![format!(
+            "{} {} and {} {} stub(s) across {} file(s)",
+            created,
+            self.total_created,
+            updated,
+            self.total_updated,
+            self.files.len()
+        )];
+
+        for file in &self.files {
+            if file.created.is_empty()
+                && file.updated.is_empty()
+                && file.missing_in_frontmatter.is_empty()
+                && file.missing_in_body.is_empty()
+            {
+                continue;
+            }
+            lines.push(format!("\n{}", file.path));
+            for stub in &file.created {
+                lines.push(format!("  + [{}] {}", stub.stub_type, stub.description));
+            }
+            for stub in &file.updated {
+                lines.push(format!("  ~ [{}] {}", stub.stub_type, stub.description));
+            }
+            for stub in &file.missing_in_body {
+                lines.push(format!("  ? [{}] {} (not in body)", stub.stub_type, stub.description));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Stubs proposed by the detectors
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StubSuggestOutput {
+    pub applied: bool,
+    pub total: usize,
+    pub files: Vec<StubSuggestFileOutput>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StubSuggestFileOutput {
+    pub path: String,
+    pub suggestions: Vec<StubSuggestionOutput>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StubSuggestionOutput {
+    pub rule: String,
+    pub stub_type: String,
+    pub description: String,
+    pub stub_form: String,
+    pub anchor: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub excerpt: String,
+}
+
+impl HumanReadable for StubSuggestOutput {
+    fn to_human(&self) -> String {
+        let verb = if self.applied { "Added" } else { "Suggested" };
+        let mut lines = vec![format!(
+            "{} {} stub(s) in {} file(s)",
+            verb,
+            self.total,
+            self.files.len()
+        )];
+
+        for file in &self.files {
+            lines.push(format!("\n{}", file.path));
+            for s in &file.suggestions {
+                lines.push(format!(
+                    "  {}:{} [{}] {} ({}, {})",
+                    s.line, s.column, s.stub_type, s.description, s.stub_form, s.rule
+                ));
+                lines.push(format!("      {}", s.excerpt));
+            }
+        }
+        if !self.applied && self.total > 0 {
+            lines.push("\nRun with --apply to add them to frontmatter".to_string());
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Documents matched by a vault query
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryOutput {
+    pub query: String,
+    pub total: usize,
+    pub columns: Vec<String>,
+    /// One object per document, keyed by column
+    pub rows: Vec<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl QueryOutput {
+    pub fn new(text: &str, query: &Query, rows: Vec<QueryRow>) -> Self {
+        let columns: Vec<String> = query.columns().iter().map(|c| c.to_string()).collect();
+        let rows = rows
+            .into_iter()
+            .map(|row| {
+                let values = row.values.iter().map(|v| serde_json::to_value(v).unwrap_or_default());
+                columns.iter().cloned().zip(values).collect()
+            })
+            .collect::<Vec<_>>();
+        Self {
+            query: text.to_string(),
+            total: rows.len(),
+            columns,
+            rows,
+        }
+    }
+}
+
+impl HumanReadable for QueryOutput {
+    fn to_human(&self) -> String {
+        if self.rows.is_empty() {
+            return "No matching documents".to_string();
+        }
+
+        let cell = |value: &serde_json::Value| match value {
+            serde_json::Value::Null => String::new(),
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Number(n) => match n.as_f64() {
+                Some(n) if n.fract() != 0.0 => format!("{:.2}", n),
+                _ => n.to_string(),
+            },
+            serde_json::Value::Array(items) => {
+                items.iter().filter_map(|i| i.as_str()).collect::<Vec<_>>().join(", ")
+            }
+            other => other.to_string(),
+        };
+        let header: Vec<String> = self.columns.iter().map(|c| c.to_uppercase()).collect();
+        let mut rows = vec![header];
+        rows.extend(self.rows.iter().map(|row| {
+            self.columns.iter().map(|c| row.get(c).map(cell).unwrap_or_default()).collect()
+        }));
+        let mut lines = aligned_table(&rows);
+        lines.push(format!("\n{} document(s)", self.total));
+        lines.join("\n")
+    }
+}
+
+impl HumanReadable for VaultReport {
+    fn to_human(&self) -> String {
+        let mut lines = vec![format!(
+            "Vault Health Report: {} ({})",
+            self.vault,
+            self.generated.format("%Y-%m-%d %H:%M UTC")
+        )];
+        for section in self.sections() {
+            lines.push(format!("\n{}", section.title()));
+            let mut rows = self.table(section).unwrap_or_default();
+            if let Some(header) = rows.first_mut() {
+                *header = header.iter().map(|c| c.to_uppercase()).collect();
+            }
+            if rows.len() > 1 {
+                lines.extend(aligned_table(&rows).into_iter().map(|l| format!("  {}", l)));
+            } else {
+                lines.push("  (none)".to_string());
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// Rows with their cells padded to the widest cell of each column
+fn aligned_table(rows: &[Vec<String>]) -> Vec<String> {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| rows.iter().filter_map(|r| r.get(i)).map(|c| c.chars().count()).max().unwrap_or(0))
+        .collect();
+    rows.iter()
+        .map(|row| {
+            let padded: Vec<String> =
+                row.iter().zip(&widths).map(|(v, w)| format!("{:<w$}", v, w = w)).collect();
+            padded.join("  ").trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Annotations across documents
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationsOutput {
+    pub total: usize,
+    pub files: Vec<AnnotationFileOutput>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationFileOutput {
+    pub path: String,
+    pub annotations: Vec<AnnotationOutput>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationOutput {
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub text: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl HumanReadable for AnnotationsOutput {
+    fn to_human(&self) -> String {
+        let mut lines = vec![format!("Total Annotations: {}", self.total)];
+
+        for file in &self.files {
+            lines.push(format!("\n{}", file.path));
+            for annotation in &file.annotations {
+                let label = annotation
+                    .label
+                    .as_ref()
+                    .map(|l| format!("[{}] ", l))
+                    .unwrap_or_default();
+                lines.push(format!(
+                    "  {}:{} {} {}{}",
+                    annotation.line, annotation.column, annotation.kind, label, annotation.text
+                ));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
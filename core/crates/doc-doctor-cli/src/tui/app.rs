@@ -2,11 +2,15 @@
 //!
 //! Manages the state and event handling for the interactive TUI.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
 use std::time::{Duration, Instant};
 
+use doc_doctor_application::{Query, QueryDocument, QueryValue, VaultEvent};
 use doc_doctor_domain::{L1Properties, StateDimensions, Stub};
 
+use crate::config::config_handle;
+
 /// Application mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum AppMode {
@@ -323,6 +327,29 @@ impl DocumentSummary {
     }
 }
 
+/// Summaries answer the fields they carry; others, like tags, are empty
+impl QueryDocument for DocumentSummary {
+    fn path(&self) -> &str {
+        self.display_path()
+    }
+
+    fn field(&self, name: &str) -> QueryValue {
+        match name {
+            "title" => self.title.clone().map_or(QueryValue::Null, QueryValue::Text),
+            "audience" => QueryValue::Text(self.audience.clone()),
+            "form" => QueryValue::Text(self.form.clone()),
+            "origin" => QueryValue::Text(self.origin.clone()),
+            "refinement" => QueryValue::Number(self.refinement),
+            "health" => QueryValue::Number(self.health),
+            _ => QueryValue::Null,
+        }
+    }
+
+    fn stubs(&self) -> &[Stub] {
+        &self.stubs
+    }
+}
+
 /// Vault statistics for dashboard
 #[derive(Debug, Clone, Default)]
 pub struct VaultStats {
@@ -393,7 +420,7 @@ impl BatchState {
 }
 
 /// AI processing state for thought streams
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct AiState {
     pub is_processing: bool,
     pub current_task: Option<String>,
@@ -402,18 +429,6 @@ pub struct AiState {
     pub start_time: Option<Instant>,
 }
 
-impl Default for AiState {
-    fn default() -> Self {
-        Self {
-            is_processing: false,
-            current_task: None,
-            thoughts: Vec::new(),
-            progress: None,
-            start_time: None,
-        }
-    }
-}
-
 /// A single thought/step in the AI processing stream
 #[derive(Debug, Clone)]
 pub struct ThoughtItem {
@@ -572,7 +587,7 @@ impl TestState {
         if let Ok(entries) = std::fs::read_dir(dir) {
             for entry in entries.filter_map(Result::ok) {
                 let path = entry.path();
-                if path.extension().map_or(false, |e| e == "md") {
+                if path.extension().is_some_and(|e| e == "md") {
                     self.test_files.push(path);
                 }
             }
@@ -796,8 +811,8 @@ impl TestState {
     }
 
     /// Update file content in memory (not saved to disk)
-    pub fn set_content(&mut self, path: &PathBuf, content: String) {
-        self.file_content.insert(path.clone(), content);
+    pub fn set_content(&mut self, path: &Path, content: String) {
+        self.file_content.insert(path.to_path_buf(), content);
     }
 
     /// Reset file to original content
@@ -954,6 +969,25 @@ impl TestState {
     }
 }
 
+/// Summarizes a changed document; `None` drops it from the dashboard
+pub type Summarize = Box<dyn Fn(&Path) -> Option<DocumentSummary>>;
+
+/// Live updates from a vault watch
+pub struct LiveUpdates {
+    /// Vault root the event paths are relative to
+    pub root: PathBuf,
+    /// Events of the watch
+    pub events: Receiver<VaultEvent>,
+    /// Summary of a changed document
+    pub summarize: Summarize,
+}
+
+impl std::fmt::Debug for LiveUpdates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LiveUpdates").field("root", &self.root).finish_non_exhaustive()
+    }
+}
+
 /// Main application state
 #[derive(Debug)]
 pub struct App {
@@ -998,6 +1032,9 @@ pub struct App {
 
     // Status message
     pub status_message: Option<(String, Instant)>,
+
+    // Live refresh from file changes
+    pub live: Option<LiveUpdates>,
 }
 
 impl Default for App {
@@ -1026,6 +1063,7 @@ impl Default for App {
             tick_count: 0,
             last_tick: Instant::now(),
             status_message: None,
+            live: None,
         }
     }
 }
@@ -1051,6 +1089,99 @@ impl App {
         self.status_message = Some((message.into(), Instant::now()));
     }
 
+    /// Apply pending watch events to the document list and statistics
+    ///
+    /// Returns whether anything changed. The selected document stays
+    /// selected when it is still listed.
+    pub fn apply_live_updates(&mut self) -> bool {
+        let Some(live) = self.live.take() else {
+            return false;
+        };
+        let events: Vec<VaultEvent> = live.events.try_iter().collect();
+        if events.is_empty() {
+            self.live = Some(live);
+            return false;
+        }
+
+        let selected = self.get_selected_document().map(|d| d.path.clone());
+        for event in &events {
+            let relative = event.path();
+            let position = self
+                .documents
+                .iter()
+                .position(|d| d.relative_path.as_deref() == Some(relative));
+            match event {
+                VaultEvent::DocumentChanged { .. } => {
+                    match ((live.summarize)(&live.root.join(relative)), position) {
+                        (Some(summary), Some(i)) => self.documents[i] = summary,
+                        (Some(summary), None) => self.documents.push(summary),
+                        (None, Some(i)) => {
+                            self.documents.remove(i);
+                        }
+                        (None, None) => {}
+                    }
+                }
+                VaultEvent::DocumentRemoved { .. } => {
+                    if let Some(i) = position {
+                        self.documents.remove(i);
+                    }
+                }
+                VaultEvent::HealthGateCrossed { audience, passed, .. } => {
+                    let verb = if *passed { "passed" } else { "fell below" };
+                    self.set_status(format!("{}: {} the {} gate", relative, verb, audience));
+                }
+                VaultEvent::StubAdded { stub_type, .. } => {
+                    self.set_status(format!("{}: stub added ({})", relative, stub_type));
+                }
+                VaultEvent::StubResolved { stub_type, .. } => {
+                    self.set_status(format!("{}: stub resolved ({})", relative, stub_type));
+                }
+                VaultEvent::AnchorOrphaned { anchor, .. } => {
+                    self.set_status(format!("{}: anchor {} orphaned", relative, anchor));
+                }
+            }
+        }
+        self.live = Some(live);
+
+        self.refresh_stats();
+        self.sort_documents();
+        if let Some(path) = selected {
+            let visible = (0..self.visible_document_count())
+                .find(|&i| self.get_visible_document(i).is_some_and(|d| d.path == path));
+            if let Some(i) = visible {
+                self.selected_document = i;
+                self.ensure_selection_visible();
+            }
+        }
+        true
+    }
+
+    /// Recalculate document statistics after the document list changed
+    fn refresh_stats(&mut self) {
+        let Some(stats) = self.vault_stats.as_mut() else {
+            return;
+        };
+        let count = self.documents.len();
+        let average = |value: fn(&DocumentSummary) -> f64| {
+            if count > 0 {
+                self.documents.iter().map(value).sum::<f64>() / count as f64
+            } else {
+                0.0
+            }
+        };
+        stats.average_health = average(|d| d.health);
+        stats.average_refinement = average(|d| d.refinement);
+        stats.total_documents = count;
+        stats.files_with_frontmatter = count;
+        stats.total_stubs = self.documents.iter().map(|d| d.stub_count).sum();
+        stats.blocking_stubs = self
+            .documents
+            .iter()
+            .flat_map(|d| &d.stubs)
+            .filter(|s| s.is_blocking())
+            .count();
+    }
+
     pub fn next_document(&mut self) {
         let count = self.visible_document_count();
         if count > 0 {
@@ -1123,9 +1254,20 @@ impl App {
     }
 
     /// Apply filter to documents
+    ///
+    /// Filter text that reads as a vault query, e.g. `health < 0.5 and
+    /// any(stubs, form = blocking)`, is evaluated as one; anything else
+    /// matches titles, paths and audiences.
     pub fn apply_filter(&mut self) {
         if self.filter_text.is_empty() {
             self.filtered_indices.clear();
+        } else if let Some(query) = self.filter_query() {
+            let config = config_handle().get();
+            self.filtered_indices = self.documents.iter()
+                .enumerate()
+                .filter(|(_, doc)| query.matches(*doc, &config.stub_types))
+                .map(|(i, _)| i)
+                .collect();
         } else {
             let filter_lower = self.filter_text.to_lowercase();
             self.filtered_indices = self.documents.iter()
@@ -1144,6 +1286,16 @@ impl App {
         self.list_offset = 0;
     }
 
+    /// The filter text as a query, unless it is a plain search word
+    fn filter_query(&self) -> Option<Query> {
+        let text = self.filter_text.trim();
+        let plain = !text.contains(|c: char| c.is_whitespace() || "=!<>~(".contains(c));
+        if plain {
+            return None;
+        }
+        Query::parse(text).ok().filter(Query::has_filter)
+    }
+
     /// Cycle to next sort field
     pub fn cycle_sort(&mut self) {
         let fields = SortField::all();
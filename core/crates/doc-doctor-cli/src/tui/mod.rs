@@ -29,7 +29,7 @@ pub mod theme;
 pub mod widgets;
 
 // Re-exports
-pub use app::{App, AppMode, BatchState, DashboardView, Event, ThoughtStatus};
+pub use app::{App, AppMode, BatchState, DashboardView, Event, LiveUpdates, ThoughtStatus};
 pub use terminal::{init, install_panic_hook, poll_event, restore, Tui};
 pub use theme::{colors, console_styles, health_color, health_style, styles};
 pub use widgets::{icons, ConsoleProgress, MultiProgress};
@@ -48,6 +48,7 @@ pub fn run_tui(mut app: App) -> Result<()> {
     let tick_rate = Duration::from_millis(100);
 
     while !app.should_quit {
+        app.apply_live_updates();
         terminal.draw(|frame| render(frame, &app))?;
 
         if let Some(event) = poll_event(tick_rate)? {
@@ -337,16 +338,14 @@ fn handle_event(app: &mut App, event: Event) -> Result<()> {
                     }
                 }
                 // Tests view
-                (KeyCode::Char('t'), _) => {
-                    if app.mode == AppMode::Dashboard {
-                        // Load test files from config
-                        if let Some(test_dir) = crate::config::get_test_dir() {
-                            app.test_state.load_test_files(&test_dir);
-                            app.mode = AppMode::Tests;
-                            app.set_status(format!("Loaded {} test files", app.test_state.test_files.len()));
-                        } else {
-                            app.set_status("No test_dir configured");
-                        }
+                (KeyCode::Char('t'), _) if app.mode == AppMode::Dashboard => {
+                    // Load test files from config
+                    if let Some(test_dir) = crate::config::get_test_dir() {
+                        app.test_state.load_test_files(&test_dir);
+                        app.mode = AppMode::Tests;
+                        app.set_status(format!("Loaded {} test files", app.test_state.test_files.len()));
+                    } else {
+                        app.set_status("No test_dir configured");
                     }
                 }
                 _ => {}
@@ -442,17 +441,13 @@ fn handle_sort_menu_input(app: &mut App, event: Event) -> Result<()> {
                 app.sort_documents();
             }
             // Toggle direction with up/down
-            KeyCode::Up | KeyCode::Char('k') => {
-                if !app.sort_ascending {
-                    app.sort_ascending = true;
-                    app.sort_documents();
-                }
+            KeyCode::Up | KeyCode::Char('k') if !app.sort_ascending => {
+                app.sort_ascending = true;
+                app.sort_documents();
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if app.sort_ascending {
-                    app.sort_ascending = false;
-                    app.sort_documents();
-                }
+            KeyCode::Down | KeyCode::Char('j') if app.sort_ascending => {
+                app.sort_ascending = false;
+                app.sort_documents();
             }
             _ => {}
         }
@@ -757,10 +752,8 @@ fn run_test_command(app: &mut App) {
 
 /// Execute an in-memory modify command
 fn execute_modify_command(app: &mut App, file: &std::path::PathBuf, command: &str) -> Result<String, String> {
-    use doc_doctor_application::{ApplicationSwitchboard, NewStub, Switchboard};
-    use doc_doctor_domain::EmbeddedSchemaProvider;
-    use doc_doctor_parser_yaml::YamlParser;
-    use std::sync::Arc;
+    use crate::commands::create_switchboard;
+    use doc_doctor_application::{NewStub, Switchboard};
 
     let content = app.test_state.get_content(file)
         .ok_or("File content not loaded")?
@@ -777,12 +770,7 @@ fn execute_modify_command(app: &mut App, file: &std::path::PathBuf, command: &st
     match action {
         "add_stub" => {
             let stub_type = parts.get(2).unwrap_or(&"expand");
-
-            // Create switchboard
-            let parser = Arc::new(YamlParser::new());
-            let writer = Arc::clone(&parser);
-            let schema_provider = Arc::new(EmbeddedSchemaProvider);
-            let switchboard = ApplicationSwitchboard::new(parser, writer, schema_provider);
+            let switchboard = create_switchboard();
 
             let new_stub = NewStub {
                 stub_type: stub_type.to_string(),
@@ -805,10 +793,7 @@ fn execute_modify_command(app: &mut App, file: &std::path::PathBuf, command: &st
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(0);
 
-            let parser = Arc::new(YamlParser::new());
-            let writer = Arc::clone(&parser);
-            let schema_provider = Arc::new(EmbeddedSchemaProvider);
-            let switchboard = ApplicationSwitchboard::new(parser, writer, schema_provider);
+            let switchboard = create_switchboard();
 
             match switchboard.resolve_stub(&content, index) {
                 Ok(result) => {
@@ -828,10 +813,8 @@ fn execute_modify_command(app: &mut App, file: &std::path::PathBuf, command: &st
 
 /// Execute an anchor command (add/remove anchor in content, link/unlink to stub)
 fn execute_anchor_command(app: &mut App, file: &std::path::PathBuf, command: &str) -> Result<String, String> {
-    use doc_doctor_application::{ApplicationSwitchboard, Switchboard};
-    use doc_doctor_domain::EmbeddedSchemaProvider;
-    use doc_doctor_parser_yaml::YamlParser;
-    use std::sync::Arc;
+    use crate::commands::create_switchboard;
+    use doc_doctor_application::Switchboard;
 
     let parts: Vec<&str> = command.split(':').collect();
     let action = parts.get(1).unwrap_or(&"");
@@ -885,12 +868,7 @@ fn execute_anchor_command(app: &mut App, file: &std::path::PathBuf, command: &st
             // Get anchor at selected line
             let anchor_id = app.test_state.get_anchor_at_line(file, selected_line)
                 .ok_or(format!("No anchor found at line {}. Add an anchor first.", selected_line))?;
-
-            // Create switchboard to link anchor to stub
-            let parser = Arc::new(YamlParser::new());
-            let writer = Arc::clone(&parser);
-            let schema_provider = Arc::new(EmbeddedSchemaProvider);
-            let switchboard = ApplicationSwitchboard::new(parser, writer, schema_provider);
+            let switchboard = create_switchboard();
 
             match switchboard.link_stub_anchor(&content, stub_index, &anchor_id) {
                 Ok(result) => {
@@ -908,12 +886,7 @@ fn execute_anchor_command(app: &mut App, file: &std::path::PathBuf, command: &st
             // Get anchor at selected line
             let anchor_id = app.test_state.get_anchor_at_line(file, selected_line)
                 .ok_or(format!("No anchor found at line {}", selected_line))?;
-
-            // Create switchboard to unlink anchor from stub
-            let parser = Arc::new(YamlParser::new());
-            let writer = Arc::clone(&parser);
-            let schema_provider = Arc::new(EmbeddedSchemaProvider);
-            let switchboard = ApplicationSwitchboard::new(parser, writer, schema_provider);
+            let switchboard = create_switchboard();
 
             match switchboard.unlink_stub_anchor(&content, stub_index, &anchor_id) {
                 Ok(result) => {
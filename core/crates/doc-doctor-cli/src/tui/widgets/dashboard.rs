@@ -335,10 +335,6 @@ fn render_compact_summary(frame: &mut Frame, app: &App, area: Rect) {
     if let Some(stats) = &app.vault_stats {
         let health_color = health_color(stats.average_health);
 
-        // Calculate documents with/without frontmatter from filter info
-        let total_files = app.documents.len() + (app.documents.len() as f64 * 0.3) as usize; // Estimate
-        let with_frontmatter = stats.total_documents;
-
         let line1 = Line::from(vec![
             Span::styled("  Documents: ", styles::label()),
             Span::styled(format!("{}", stats.total_documents), Style::default().fg(colors::PRIMARY).add_modifier(Modifier::BOLD)),
@@ -607,7 +603,7 @@ fn render_form_breakdown(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(list, chunks[0]);
 
     // Right: Form lifecycle info
-    let mut right_items: Vec<ListItem> = vec![
+    let right_items: Vec<ListItem> = vec![
         ListItem::new(Line::from(Span::styled("  Form Lifecycle", styles::subtitle()))),
         ListItem::new(Line::from(Span::raw(""))),
         ListItem::new(Line::from(vec![
@@ -680,7 +676,7 @@ fn calculate_stub_type_distribution(app: &App) -> Vec<(String, usize)> {
     }
 
     let mut sorted: Vec<_> = counts.into_iter().collect();
-    sorted.sort_by(|a, b| b.1.cmp(&a.1));
+    sorted.sort_by_key(|entry| std::cmp::Reverse(entry.1));
     sorted
 }
 
@@ -1082,7 +1078,10 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
             Span::styled("  Search: ", styles::highlight()),
             Span::styled(&app.filter_text, styles::value()),
             Span::styled("█", Style::default().fg(colors::PRIMARY)),
-            Span::styled("  (Enter to apply, Esc to cancel)", styles::subtitle()),
+            Span::styled(
+                "  (text or query, e.g. health < 0.5; Enter to apply, Esc to cancel)",
+                styles::subtitle(),
+            ),
         ])
     } else if app.mode == AppMode::SortMenu {
         Line::from(vec![
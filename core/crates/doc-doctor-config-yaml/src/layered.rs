@@ -0,0 +1,250 @@
+//! Layered YAML Configuration
+//!
+//! Merges config files key by key, so a layer that only sets
+//! `audience_gates.public` leaves every other setting from earlier layers
+//! in place.
+//!
+//! Layers (later overrides earlier):
+//! 1. Built-in defaults
+//! 2. User config
+//! 3. Profile (explicit, else selected by the project or user config)
+//! 4. Project config
+
+use std::path::PathBuf;
+
+use serde_yaml::Value;
+
+use doc_doctor_domain::{CalculationConfig, ConfigError, ConfigProvider};
+
+use crate::profiles::{read_value, selected_profile, strip_meta, ProfileSource, ProfileStore};
+
+/// Layered configuration provider with profile support
+///
+/// Re-reads its files on every `load`, so it can be attached to the
+/// application switchboard for runtime reloads.
+#[derive(Debug, Clone)]
+pub struct LayeredYamlProvider {
+    user_path: Option<PathBuf>,
+    project_path: PathBuf,
+    profile: Option<String>,
+    profiles: ProfileStore,
+}
+
+impl LayeredYamlProvider {
+    /// Create a provider for the given user and project config files
+    pub fn new(user_path: Option<PathBuf>, project_path: PathBuf) -> Self {
+        Self {
+            user_path,
+            project_path,
+            profile: None,
+            profiles: ProfileStore::new(),
+        }
+    }
+
+    /// Force a profile, overriding any `profile:` key in the config files
+    pub fn with_profile(mut self, profile: Option<String>) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Use a custom profile store
+    pub fn with_profiles(mut self, profiles: ProfileStore) -> Self {
+        self.profiles = profiles;
+        self
+    }
+
+    /// Get the profile store
+    pub fn profiles(&self) -> &ProfileStore {
+        &self.profiles
+    }
+
+    /// Resolve the active profile name
+    ///
+    /// An explicit profile wins, then the project config's `profile:` key,
+    /// then the user config's.
+    pub fn active_profile(&self) -> Result<Option<String>, ConfigError> {
+        let (user, project) = self.read_layers()?;
+        Ok(self.resolve_profile(user.as_ref(), project.as_ref()))
+    }
+
+    fn read_layers(&self) -> Result<(Option<Value>, Option<Value>), ConfigError> {
+        let user = match &self.user_path {
+            Some(path) if path.exists() => Some(read_value(path)?),
+            _ => None,
+        };
+        let project = if self.project_path.exists() {
+            Some(read_value(&self.project_path)?)
+        } else {
+            None
+        };
+        Ok((user, project))
+    }
+
+    fn resolve_profile(&self, user: Option<&Value>, project: Option<&Value>) -> Option<String> {
+        self.profile
+            .clone()
+            .or_else(|| project.and_then(selected_profile))
+            .or_else(|| user.and_then(selected_profile))
+    }
+
+    fn profile_source(&self) -> Option<(String, ProfileSource)> {
+        let profile = self.active_profile().ok().flatten()?;
+        let (_, source) = self.profiles.value(&profile).ok()?;
+        Some((profile, source))
+    }
+}
+
+impl ConfigProvider for LayeredYamlProvider {
+    fn load(&self) -> Result<CalculationConfig, ConfigError> {
+        let (user, project) = self.read_layers()?;
+        let profile = self.resolve_profile(user.as_ref(), project.as_ref());
+
+        let mut merged = serde_yaml::to_value(CalculationConfig::default())
+            .map_err(|e| ConfigError::new(format!("Failed to serialize defaults: {}", e)))?;
+
+        if let Some(user) = user {
+            deep_merge(&mut merged, strip_meta(user));
+        }
+        if let Some(name) = &profile {
+            let (layer, _) = self.profiles.value(name)?;
+            deep_merge(&mut merged, layer);
+        }
+        if let Some(project) = project {
+            deep_merge(&mut merged, strip_meta(project));
+        }
+
+        value_to_config(merged).map_err(|e| e.with_path(self.source()))
+    }
+
+    fn save(&self, _config: &CalculationConfig) -> Result<(), ConfigError> {
+        Err(ConfigError::new("Cannot save to a layered config provider; save to a specific layer"))
+    }
+
+    fn exists(&self) -> bool {
+        self.user_path.as_ref().is_some_and(|p| p.exists()) || self.project_path.exists()
+    }
+
+    fn source(&self) -> String {
+        let mut sources = vec!["built-in defaults".to_string()];
+        if let Some(user) = self.user_path.as_ref().filter(|p| p.exists()) {
+            sources.push(user.display().to_string());
+        }
+        if let Some((name, source)) = self.profile_source() {
+            sources.push(format!("profile '{}' ({})", name, source));
+        }
+        if self.project_path.exists() {
+            sources.push(self.project_path.display().to_string());
+        }
+        sources.join(" + ")
+    }
+}
+
+/// Recursively merge `overlay` into `base`
+///
+/// Mappings are merged key by key; any other value replaces the base value.
+pub(crate) fn deep_merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Mapping(base_map), Value::Mapping(overlay_map)) => {
+            for (key, value) in overlay_map {
+                match base_map.get_mut(&key) {
+                    Some(existing) => deep_merge(existing, value),
+                    None => {
+                        base_map.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Deserialize and validate a merged configuration value
+pub(crate) fn value_to_config(value: Value) -> Result<CalculationConfig, ConfigError> {
+    let config: CalculationConfig = serde_yaml::from_value(value)
+        .map_err(|e| ConfigError::new(format!("Failed to parse YAML: {}", e)))?;
+
+    config
+        .validate()
+        .map_err(|e| ConfigError::new(format!("Invalid configuration: {}", e)))?;
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn provider(dir: &TempDir, user: Option<&str>, project: Option<&str>) -> LayeredYamlProvider {
+        let user_path = dir.path().join("user.yaml");
+        let project_path = dir.path().join(".doc-doctor.yaml");
+        if let Some(content) = user {
+            fs::write(&user_path, content).unwrap();
+        }
+        if let Some(content) = project {
+            fs::write(&project_path, content).unwrap();
+        }
+        LayeredYamlProvider::new(Some(user_path), project_path)
+            .with_profiles(ProfileStore::bundled_only())
+    }
+
+    #[test]
+    fn test_partial_layers_merge_per_key() {
+        let dir = TempDir::new().unwrap();
+        let provider = provider(
+            &dir,
+            Some("audience_gates:\n  personal: 0.4\n"),
+            Some("audience_gates:\n  public: 0.95\n"),
+        );
+
+        let config = provider.load().unwrap();
+        assert_eq!(config.audience_gates.personal, 0.4);
+        assert_eq!(config.audience_gates.public, 0.95);
+        assert_eq!(config.audience_gates.internal, 0.70);
+    }
+
+    #[test]
+    fn test_project_selects_profile() {
+        let dir = TempDir::new().unwrap();
+        let provider = provider(
+            &dir,
+            Some("profile: zettelkasten\n"),
+            Some("profile: academic\nform_cadences:\n  transient: 7\n"),
+        );
+
+        assert_eq!(provider.active_profile().unwrap().as_deref(), Some("academic"));
+        let config = provider.load().unwrap();
+        // From the profile
+        assert!(config.policies.require_title);
+        // Project config overrides the profile
+        assert_eq!(config.form_cadences.transient, 7);
+        assert!(provider.source().contains("profile 'academic'"));
+    }
+
+    #[test]
+    fn test_explicit_profile_wins() {
+        let dir = TempDir::new().unwrap();
+        let provider = provider(&dir, None, Some("profile: academic\n"))
+            .with_profile(Some("product-docs".to_string()));
+
+        let config = provider.load().unwrap();
+        assert_eq!(config.policies.max_blocking_stubs, Some(2));
+        assert!(!config.policies.require_stub_description);
+    }
+
+    #[test]
+    fn test_unknown_profile_is_an_error() {
+        let dir = TempDir::new().unwrap();
+        let provider = provider(&dir, None, Some("profile: missing\n"));
+        assert!(provider.load().is_err());
+    }
+
+    #[test]
+    fn test_invalid_merged_config() {
+        let dir = TempDir::new().unwrap();
+        let provider = provider(&dir, None, Some("health:\n  refinement_weight: 0.9\n"));
+        let err = provider.load().unwrap_err();
+        assert!(err.message.contains("Invalid configuration"));
+    }
+}
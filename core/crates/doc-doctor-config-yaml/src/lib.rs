@@ -7,8 +7,15 @@
 //! Configuration is loaded in layers (later overrides earlier):
 //! 1. Built-in defaults
 //! 2. User config: `~/.config/doc-doctor/config.yaml`
-//! 3. Project config: `.doc-doctor.yaml` in working directory
-//! 4. CLI arguments (highest priority)
+//! 3. Profile: selected by `--profile` or a `profile:` key in either config
+//! 4. Project config: `.doc-doctor.yaml` in working directory
+//!
+//! Layers are merged key by key, so a file only needs the settings it changes.
+//!
+//! # Profiles
+//!
+//! Profiles are named presets (see [`ProfileStore`]). Bundled profiles ship
+//! with doc-doctor; user profiles live in `~/.config/doc-doctor/profiles/`.
 //!
 //! # Usage
 //!
@@ -24,24 +31,52 @@
 //! ```
 
 mod file_provider;
+mod layered;
 mod paths;
+mod profiles;
 
 pub use file_provider::FileConfigProvider;
-pub use paths::{find_project_config, project_config_path, project_config_path_in, user_config_dir, user_config_path};
-
-use doc_doctor_domain::{
-    CalculationConfig, ConfigError, DefaultConfigProvider, LayeredConfigProvider,
+pub use layered::LayeredYamlProvider;
+pub use paths::{
+    find_project_config, project_config_path, project_config_path_in, user_config_dir,
+    user_config_path, user_profiles_dir,
 };
+pub use profiles::{ProfileInfo, ProfileSource, ProfileStore};
+
+use doc_doctor_domain::{CalculationConfig, ConfigError};
 
 // Re-export domain types for convenience
 pub use doc_doctor_domain::{ConfigProvider, CalculationConfig as Config};
 
+/// Build the standard layered configuration provider
+///
+/// Layers (later overrides earlier):
+/// 1. Built-in defaults
+/// 2. User config (~/.config/doc-doctor/config.yaml)
+/// 3. Profile named by a `profile:` key (project config wins over user config)
+/// 4. Project config (.doc-doctor.yaml in current directory)
+///
+/// The provider re-reads its files on every `load`, so it can be attached to
+/// the application switchboard for runtime reloads.
+pub fn layered_provider() -> LayeredYamlProvider {
+    LayeredYamlProvider::new(user_config_path(), project_config_path())
+}
+
+/// Build the layered configuration provider for a custom project root
+///
+/// # Arguments
+/// * `project_root` - Directory to look for `.doc-doctor.yaml`
+pub fn layered_provider_with_root(project_root: impl AsRef<std::path::Path>) -> LayeredYamlProvider {
+    LayeredYamlProvider::new(user_config_path(), project_config_path_in(project_root))
+}
+
 /// Load configuration with standard layering
 ///
 /// Loads config in order (later overrides earlier):
 /// 1. Built-in defaults
 /// 2. User config (~/.config/doc-doctor/config.yaml)
-/// 3. Project config (.doc-doctor.yaml in current directory)
+/// 3. Selected profile, if any
+/// 4. Project config (.doc-doctor.yaml in current directory)
 ///
 /// # Returns
 /// Merged configuration from all layers
@@ -49,19 +84,7 @@ pub use doc_doctor_domain::{ConfigProvider, CalculationConfig as Config};
 /// # Errors
 /// Returns error if any existing config file fails to parse
 pub fn load_layered_config() -> Result<CalculationConfig, ConfigError> {
-    let mut provider = LayeredConfigProvider::new()
-        .add_layer(Box::new(DefaultConfigProvider));
-
-    // Add user config if it exists
-    if let Some(user_path) = user_config_path() {
-        provider = provider.add_layer(Box::new(FileConfigProvider::new(user_path)));
-    }
-
-    // Add project config if it exists
-    let project_path = project_config_path();
-    provider = provider.add_layer(Box::new(FileConfigProvider::new(project_path)));
-
-    provider.load_merged()
+    layered_provider().load()
 }
 
 /// Load configuration with custom project root
@@ -74,19 +97,7 @@ pub fn load_layered_config() -> Result<CalculationConfig, ConfigError> {
 pub fn load_layered_config_with_root(
     project_root: impl AsRef<std::path::Path>,
 ) -> Result<CalculationConfig, ConfigError> {
-    let mut provider = LayeredConfigProvider::new()
-        .add_layer(Box::new(DefaultConfigProvider));
-
-    // Add user config if it exists
-    if let Some(user_path) = user_config_path() {
-        provider = provider.add_layer(Box::new(FileConfigProvider::new(user_path)));
-    }
-
-    // Add project config
-    let project_path = project_root.as_ref().join(".doc-doctor.yaml");
-    provider = provider.add_layer(Box::new(FileConfigProvider::new(project_path)));
-
-    provider.load_merged()
+    layered_provider_with_root(project_root).load()
 }
 
 /// Get information about loaded config sources
@@ -13,6 +13,9 @@ pub const PROJECT_CONFIG_NAME: &str = ".doc-doctor.yaml";
 /// Application directory name under config home
 pub const APP_DIR_NAME: &str = "doc-doctor";
 
+/// User profiles directory name under the app config directory
+pub const PROFILES_DIR_NAME: &str = "profiles";
+
 /// Get the user configuration file path
 ///
 /// Returns the path to `~/.config/doc-doctor/config.yaml` on Unix
@@ -45,6 +48,18 @@ pub fn user_config_dir() -> Option<PathBuf> {
     dirs::config_dir().map(|dir| dir.join(APP_DIR_NAME))
 }
 
+/// Get the user profiles directory
+///
+/// Returns the path to `~/.config/doc-doctor/profiles/` on Unix
+/// or the equivalent on other platforms. Each `<name>.yaml` file in it
+/// defines a profile.
+///
+/// # Returns
+/// `Some(path)` if the config directory can be determined, `None` otherwise
+pub fn user_profiles_dir() -> Option<PathBuf> {
+    user_config_dir().map(|dir| dir.join(PROFILES_DIR_NAME))
+}
+
 /// Get the project configuration file path
 ///
 /// Returns the path to `.doc-doctor.yaml` in the current working directory.
@@ -111,10 +126,7 @@ pub fn find_project_config(start: impl AsRef<std::path::Path>) -> Option<PathBuf
             return Some(config_path);
         }
 
-        match current.parent() {
-            Some(parent) => current = parent,
-            None => return None,
-        }
+        current = current.parent()?;
     }
 }
 
@@ -142,6 +154,13 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_user_profiles_dir() {
+        if let Some(d) = user_profiles_dir() {
+            assert!(d.ends_with("doc-doctor/profiles"));
+        }
+    }
+
     #[test]
     fn test_project_config_path() {
         let path = project_config_path();
@@ -0,0 +1,307 @@
+//! Configuration Profiles
+//!
+//! Named presets that set gates, penalties, cadences, stub types and
+//! policies together. Profiles are partial YAML configs layered on top of
+//! the built-in defaults.
+//!
+//! Two sources are searched:
+//! 1. User profiles: `~/.config/doc-doctor/profiles/<name>.yaml`
+//! 2. Bundled profiles shipped with doc-doctor
+//!
+//! A user profile shadows a bundled profile with the same name.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_yaml::Value;
+
+use doc_doctor_domain::{CalculationConfig, ConfigError};
+
+use crate::layered::{deep_merge, value_to_config};
+use crate::paths::user_profiles_dir;
+
+/// Key naming the profile a config file selects
+pub const PROFILE_KEY: &str = "profile";
+
+/// Key holding a profile's one-line description
+pub const DESCRIPTION_KEY: &str = "description";
+
+/// Profiles shipped with doc-doctor
+const BUNDLED_PROFILES: &[(&str, &str)] = &[
+    ("default", include_str!("../profiles/default.yaml")),
+    ("academic", include_str!("../profiles/academic.yaml")),
+    ("product-docs", include_str!("../profiles/product-docs.yaml")),
+    ("zettelkasten", include_str!("../profiles/zettelkasten.yaml")),
+];
+
+/// Where a profile was found
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "path", rename_all = "snake_case")]
+pub enum ProfileSource {
+    /// Shipped with doc-doctor
+    Bundled,
+    /// Defined in the user profiles directory
+    User(PathBuf),
+}
+
+impl std::fmt::Display for ProfileSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProfileSource::Bundled => write!(f, "bundled"),
+            ProfileSource::User(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Summary of an available profile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProfileInfo {
+    /// Profile name
+    pub name: String,
+
+    /// One-line description from the profile file
+    pub description: Option<String>,
+
+    /// Where the profile was found
+    pub source: ProfileSource,
+}
+
+/// Lookup of bundled and user-defined profiles
+///
+/// # Example
+///
+/// ```no_run
+/// use doc_doctor_config_yaml::ProfileStore;
+///
+/// let store = ProfileStore::new();
+/// for profile in store.list() {
+///     println!("{} ({})", profile.name, profile.source);
+/// }
+/// let academic = store.load("academic").unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProfileStore {
+    user_dir: Option<PathBuf>,
+}
+
+impl ProfileStore {
+    /// Create a store using the standard user profiles directory
+    pub fn new() -> Self {
+        Self {
+            user_dir: user_profiles_dir(),
+        }
+    }
+
+    /// Create a store using a custom user profiles directory
+    pub fn with_user_dir(dir: impl AsRef<Path>) -> Self {
+        Self {
+            user_dir: Some(dir.as_ref().to_path_buf()),
+        }
+    }
+
+    /// Create a store that only knows the bundled profiles
+    pub fn bundled_only() -> Self {
+        Self { user_dir: None }
+    }
+
+    /// List all available profiles, sorted by name
+    ///
+    /// User profiles shadow bundled profiles with the same name. User files
+    /// that fail to parse are listed without a description.
+    pub fn list(&self) -> Vec<ProfileInfo> {
+        let mut profiles: Vec<ProfileInfo> = BUNDLED_PROFILES
+            .iter()
+            .map(|(name, content)| ProfileInfo {
+                name: name.to_string(),
+                description: serde_yaml::from_str(content).ok().and_then(|v| description_of(&v)),
+                source: ProfileSource::Bundled,
+            })
+            .collect();
+
+        for path in self.user_profile_files() {
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()).map(str::to_string) else {
+                continue;
+            };
+            let description = read_value(&path).ok().and_then(|v| description_of(&v));
+
+            profiles.retain(|p| p.name != name);
+            profiles.push(ProfileInfo {
+                name,
+                description,
+                source: ProfileSource::User(path),
+            });
+        }
+
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        profiles
+    }
+
+    /// Check whether a profile exists
+    pub fn exists(&self, name: &str) -> bool {
+        self.list().iter().any(|p| p.name == name)
+    }
+
+    /// Get the raw profile layer
+    ///
+    /// The `profile` and `description` keys are removed so the value can be
+    /// merged directly onto a configuration.
+    pub fn value(&self, name: &str) -> Result<(Value, ProfileSource), ConfigError> {
+        if let Some(dir) = &self.user_dir {
+            let path = dir.join(format!("{}.yaml", name));
+            if path.is_file() {
+                let value = read_value(&path)?;
+                return Ok((strip_meta(value), ProfileSource::User(path)));
+            }
+        }
+
+        let (_, content) = BUNDLED_PROFILES
+            .iter()
+            .find(|(bundled, _)| *bundled == name)
+            .ok_or_else(|| self.unknown_profile(name))?;
+
+        let value: Value = serde_yaml::from_str(content).map_err(|e| {
+            ConfigError::new(format!("Failed to parse bundled profile '{}': {}", name, e))
+        })?;
+
+        Ok((strip_meta(value), ProfileSource::Bundled))
+    }
+
+    /// Load a profile as a complete configuration (defaults + profile)
+    pub fn load(&self, name: &str) -> Result<CalculationConfig, ConfigError> {
+        let (layer, source) = self.value(name)?;
+        let mut base = serde_yaml::to_value(CalculationConfig::default())
+            .map_err(|e| ConfigError::new(format!("Failed to serialize defaults: {}", e)))?;
+        deep_merge(&mut base, layer);
+
+        value_to_config(base).map_err(|e| e.with_path(format!("profile '{}' ({})", name, source)))
+    }
+
+    fn unknown_profile(&self, name: &str) -> ConfigError {
+        let available: Vec<String> = self.list().into_iter().map(|p| p.name).collect();
+        ConfigError::new(format!(
+            "Unknown profile '{}'. Available profiles: {}",
+            name,
+            available.join(", ")
+        ))
+    }
+
+    fn user_profile_files(&self) -> Vec<PathBuf> {
+        let Some(dir) = &self.user_dir else {
+            return Vec::new();
+        };
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "yaml"))
+            .collect();
+        files.sort();
+        files
+    }
+}
+
+impl Default for ProfileStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Read a YAML file into a value, treating an empty file as an empty mapping
+pub(crate) fn read_value(path: &Path) -> Result<Value, ConfigError> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        ConfigError::new(format!("Failed to read config file: {}", e))
+            .with_path(path.display().to_string())
+    })?;
+
+    let value: Value = serde_yaml::from_str(&content).map_err(|e| {
+        ConfigError::new(format!("Failed to parse YAML: {}", e)).with_path(path.display().to_string())
+    })?;
+
+    Ok(match value {
+        Value::Null => Value::Mapping(Default::default()),
+        other => other,
+    })
+}
+
+/// Get the profile a config layer selects, if any
+pub(crate) fn selected_profile(value: &Value) -> Option<String> {
+    value.get(PROFILE_KEY).and_then(|v| v.as_str()).map(str::to_string)
+}
+
+/// Remove profile metadata keys from a config layer
+pub(crate) fn strip_meta(mut value: Value) -> Value {
+    if let Value::Mapping(map) = &mut value {
+        map.remove(PROFILE_KEY);
+        map.remove(DESCRIPTION_KEY);
+    }
+    value
+}
+
+fn description_of(value: &Value) -> Option<String> {
+    value.get(DESCRIPTION_KEY).and_then(|v| v.as_str()).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bundled_profiles_are_valid() {
+        let store = ProfileStore::bundled_only();
+        for (name, _) in BUNDLED_PROFILES {
+            let config = store.load(name).unwrap();
+            assert!(config.validate().is_ok(), "profile '{}' is invalid", name);
+        }
+    }
+
+    #[test]
+    fn test_default_profile_matches_defaults() {
+        let config = ProfileStore::bundled_only().load("default").unwrap();
+        assert!(config.diff(&CalculationConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_profile_keeps_unset_defaults() {
+        let config = ProfileStore::bundled_only().load("product-docs").unwrap();
+        assert_eq!(config.form_cadences.transient, 3);
+        // Not set by the profile
+        assert_eq!(config.health.refinement_weight, 0.7);
+        assert_eq!(config.policies.max_blocking_stubs, Some(2));
+    }
+
+    #[test]
+    fn test_user_profile_shadows_bundled() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("academic.yaml"),
+            "description: My academic\naudience_gates:\n  public: 0.99\n",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("team.yaml"), "description: Team\n").unwrap();
+
+        let store = ProfileStore::with_user_dir(temp_dir.path());
+        let profiles = store.list();
+
+        let academic = profiles.iter().find(|p| p.name == "academic").unwrap();
+        assert_eq!(academic.description.as_deref(), Some("My academic"));
+        assert!(matches!(academic.source, ProfileSource::User(_)));
+        assert!(profiles.iter().any(|p| p.name == "team"));
+        assert_eq!(profiles.iter().filter(|p| p.name == "academic").count(), 1);
+
+        let config = store.load("academic").unwrap();
+        assert_eq!(config.audience_gates.public, 0.99);
+        assert_eq!(config.audience_gates.personal, 0.5);
+    }
+
+    #[test]
+    fn test_unknown_profile() {
+        let err = ProfileStore::bundled_only().load("nope").unwrap_err();
+        assert!(err.message.contains("Unknown profile 'nope'"));
+        assert!(err.message.contains("academic"));
+    }
+}
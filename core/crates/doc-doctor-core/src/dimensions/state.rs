@@ -144,9 +144,11 @@ mod tests {
 
     #[test]
     fn test_state_dimensions() {
-        let mut props = L1Properties::default();
-        props.refinement = Refinement::new_unchecked(0.8);
-        props.audience = Audience::Internal;
+        let props = L1Properties {
+            refinement: Refinement::new_unchecked(0.8),
+            audience: Audience::Internal,
+            ..Default::default()
+        };
 
         let dims = StateDimensions::calculate(&props);
         assert!(dims.health > 0.8);
@@ -29,6 +29,8 @@
 //! let health = dimensions::calculate_health(doc.refinement.value(), &doc.stubs);
 //! ```
 
+#![allow(clippy::result_large_err)]
+
 pub mod error;
 pub mod parser;
 pub mod types;
@@ -1,8 +0,0 @@
-//! Strict YAML validation utilities
-//!
-//! Additional validation beyond standard YAML parsing.
-
-// TODO: Add strict validation for:
-// - Quoted wikilinks
-// - Block anchor format
-// - Stub syntax validation
@@ -12,4 +12,4 @@ mod sync;
 pub use stub_type::{Stub, StubType, Priority};
 pub use stub_form::StubForm;
 pub use vector::{VectorPhysics, StubContext, calculate_stub_penalty};
-pub use sync::SyncStatus;
+pub use sync::{find_anchors, SyncStatus};
@@ -12,7 +12,7 @@ use crate::stubs::Stub;
 ///
 /// These properties are stored in YAML frontmatter and are
 /// context-independent (portable across systems).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct L1Properties {
     // === Foundational Properties ===
     /// Unique identifier for the document
@@ -61,24 +61,6 @@ pub struct L1Properties {
     pub stubs: Vec<Stub>,
 }
 
-impl Default for L1Properties {
-    fn default() -> Self {
-        Self {
-            uid: None,
-            title: None,
-            created: None,
-            modified: None,
-            tags: Vec::new(),
-            aliases: Vec::new(),
-            refinement: Refinement::default(),
-            origin: Origin::default(),
-            form: Form::default(),
-            audience: Audience::default(),
-            stubs: Vec::new(),
-        }
-    }
-}
-
 impl L1Properties {
     /// Create new L1Properties with only the title set
     pub fn with_title(title: impl Into<String>) -> Self {
@@ -213,8 +195,8 @@ mod tests {
 
     #[test]
     fn test_origin_display() {
-        assert_eq!(Origin::Human.display_name(), "Human-authored");
-        assert_eq!(Origin::Ai.display_name(), "AI-generated");
+        assert_eq!(Origin::Question.display_name(), "Question");
+        assert_eq!(Origin::Experimental.display_name(), "Experimental");
     }
 
     #[test]
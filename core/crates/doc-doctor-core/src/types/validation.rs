@@ -64,10 +64,12 @@ mod tests {
 
     #[test]
     fn test_validates_high_refinement_with_stubs() {
-        let mut props = L1Properties::default();
-        props.refinement = Refinement::new_unchecked(0.95);
-        props.title = Some("Test".to_string());
-        props.stubs = vec![Stub::compact("link", "Citation needed")];
+        let props = L1Properties {
+            refinement: Refinement::new_unchecked(0.95),
+            title: Some("Test".to_string()),
+            stubs: vec![Stub::compact("link", "Citation needed")],
+            ..Default::default()
+        };
 
         let warnings = validate_l1_properties(&props).unwrap();
         assert!(warnings.iter().any(|w| w.message.contains("stubs remain")));
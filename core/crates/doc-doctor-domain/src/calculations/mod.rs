@@ -14,14 +14,18 @@ pub use state::{
     calculate_usefulness,
     calculate_usefulness_with_config,
     calculate_freshness,
+    calculate_freshness_with_config,
     calculate_trust,
+    calculate_trust_with_config,
     StateDimensions,
     Usefulness,
 };
 
 pub use trajectory::{
     calculate_potential_energy,
+    calculate_potential_energy_with_config,
     calculate_friction,
+    calculate_friction_with_config,
     calculate_magnitude,
     forecast_completion,
     StubContext,
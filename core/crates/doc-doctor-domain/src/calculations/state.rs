@@ -121,7 +121,7 @@ pub fn calculate_usefulness_with_config(
     audience: Audience,
     config: &CalculationConfig,
 ) -> Usefulness {
-    let gate = get_audience_gate(&audience, &config.audience_gates);
+    let gate = config.audience_gates.for_audience(audience);
     let margin = refinement - gate;
 
     Usefulness {
@@ -133,19 +133,6 @@ pub fn calculate_usefulness_with_config(
     }
 }
 
-/// Get gate threshold for a specific audience from config
-fn get_audience_gate(
-    audience: &Audience,
-    config: &crate::config::AudienceGatesConfig,
-) -> f64 {
-    match audience {
-        Audience::Personal => config.personal,
-        Audience::Internal => config.internal,
-        Audience::Trusted => config.trusted,
-        Audience::Public => config.public,
-    }
-}
-
 /// Calculate document freshness based on time since last modification
 ///
 /// Formula: freshness = e^(-ln(2) × Δt / τ_form)
@@ -263,9 +250,9 @@ impl StateDimensions {
             calculate_usefulness_with_config(props.refinement.value(), props.audience, config);
         let trust_level = calculate_trust_with_config(props.origin, config);
 
-        let freshness = match props.modified {
-            Some(modified) => calculate_freshness_with_config(modified, props.form, now, config),
-            None => 1.0, // No modification date, assume fresh
+        let freshness = match props.last_touched(config.vault.tz()) {
+            Some(touched) => calculate_freshness_with_config(touched, props.form, now, config),
+            None => 1.0, // No dates, assume fresh
         };
 
         Self {
@@ -4,6 +4,7 @@
 //! These are pure functions that calculate potential energy, friction, and magnitude.
 
 use serde::{Deserialize, Serialize};
+use crate::config::VectorPhysicsConfig;
 use crate::entities::Stub;
 
 /// Context for vector physics calculations
@@ -79,8 +80,17 @@ impl VectorPhysics {
     /// # Returns
     /// Vector physics with PE, friction, velocity, and magnitude
     pub fn calculate(stub: &Stub, context: &StubContext) -> Self {
-        let potential_energy = calculate_potential_energy(stub);
-        let friction_coefficient = calculate_friction(stub, context);
+        Self::calculate_with_config(stub, context, &VectorPhysicsConfig::default())
+    }
+
+    /// Calculate vector physics with custom configuration
+    pub fn calculate_with_config(
+        stub: &Stub,
+        context: &StubContext,
+        config: &VectorPhysicsConfig,
+    ) -> Self {
+        let potential_energy = calculate_potential_energy_with_config(stub, config);
+        let friction_coefficient = calculate_friction_with_config(stub, context, config);
         let editorial_velocity = context.editorial_velocity.unwrap_or(0.0);
         let magnitude = calculate_magnitude(potential_energy, friction_coefficient);
 
@@ -101,9 +111,14 @@ impl VectorPhysics {
 /// # Returns
 /// Potential energy value (typically 0.0-1.0)
 pub fn calculate_potential_energy(stub: &Stub) -> f64 {
+    calculate_potential_energy_with_config(stub, &VectorPhysicsConfig::default())
+}
+
+/// Calculate potential energy with custom defaults for unset impact/complexity
+pub fn calculate_potential_energy_with_config(stub: &Stub, config: &VectorPhysicsConfig) -> f64 {
     let urgency = stub.effective_urgency();
-    let impact = stub.effective_impact();
-    let complexity = stub.effective_complexity();
+    let impact = stub.impact.unwrap_or(config.default_impact);
+    let complexity = stub.complexity.unwrap_or(config.default_complexity);
 
     urgency * impact * complexity
 }
@@ -119,9 +134,18 @@ pub fn calculate_potential_energy(stub: &Stub) -> f64 {
 /// # Returns
 /// Friction coefficient between 0.0 and 1.0
 pub fn calculate_friction(stub: &Stub, context: &StubContext) -> f64 {
+    calculate_friction_with_config(stub, context, &VectorPhysicsConfig::default())
+}
+
+/// Calculate friction coefficient with custom friction factors
+pub fn calculate_friction_with_config(
+    stub: &Stub,
+    context: &StubContext,
+    config: &VectorPhysicsConfig,
+) -> f64 {
     // Controversy from participants or context
     let controversy = if stub.participants.len() >= 2 || context.has_controversy {
-        0.3
+        config.controversy_friction
     } else {
         0.0
     };
@@ -130,10 +154,14 @@ pub fn calculate_friction(stub: &Stub, context: &StubContext) -> f64 {
     let dependencies = (stub.dependencies.len() as f64 * 0.1).min(0.5);
 
     // Blocking stubs have additional friction
-    let blocker = if stub.is_blocking() { 0.2 } else { 0.0 };
+    let blocker = if stub.is_blocking() { config.blocking_friction } else { 0.0 };
 
     // External dependencies add friction
-    let external = if context.has_external_dependencies { 0.2 } else { 0.0 };
+    let external = if context.has_external_dependencies {
+        config.external_dep_friction
+    } else {
+        0.0
+    };
 
     (controversy + dependencies + blocker + external).min(1.0)
 }
@@ -207,13 +235,22 @@ impl TrajectoryDimensions {
     /// # Returns
     /// Aggregate trajectory dimensions
     pub fn calculate(stubs: &[Stub], context: &StubContext) -> Self {
+        Self::calculate_with_config(stubs, context, &VectorPhysicsConfig::default())
+    }
+
+    /// Calculate aggregate trajectory dimensions with custom configuration
+    pub fn calculate_with_config(
+        stubs: &[Stub],
+        context: &StubContext,
+        config: &VectorPhysicsConfig,
+    ) -> Self {
         if stubs.is_empty() {
             return Self::default();
         }
 
         let physics: Vec<VectorPhysics> = stubs
             .iter()
-            .map(|s| VectorPhysics::calculate(s, context))
+            .map(|s| VectorPhysics::calculate_with_config(s, context, config))
             .collect();
 
         let total_pe: f64 = physics.iter().map(|p| p.potential_energy).sum();
@@ -289,6 +326,21 @@ mod tests {
         assert!(friction >= 0.2);
     }
 
+    #[test]
+    fn test_friction_with_config() {
+        let mut stub = Stub::compact("fix", "test");
+        stub.stub_form = StubForm::Blocking;
+        let ctx = StubContext::default().with_external_deps(true);
+        let config = VectorPhysicsConfig {
+            blocking_friction: 0.4,
+            external_dep_friction: 0.1,
+            ..Default::default()
+        };
+
+        let friction = calculate_friction_with_config(&stub, &ctx, &config);
+        assert!((friction - 0.5).abs() < 0.001);
+    }
+
     #[test]
     fn test_friction_with_dependencies() {
         let mut stub = Stub::compact("link", "test");
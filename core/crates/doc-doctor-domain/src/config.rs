@@ -8,11 +8,17 @@
 //! Configuration is loaded in layers (later overrides earlier):
 //! 1. Built-in defaults (this module)
 //! 2. User config: `~/.config/doc-doctor/config.yaml`
-//! 3. Project config: `.doc-doctor.yaml` in working directory
-//! 4. CLI arguments (highest priority)
+//! 3. Named profile (bundled or `~/.config/doc-doctor/profiles/<name>.yaml`)
+//! 4. Project config: `.doc-doctor.yaml` in working directory
+//! 5. CLI arguments (highest priority)
 
+use std::collections::BTreeMap;
+
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 
+use crate::entities::{Audience, StubType, VectorFamily};
+
 /// Complete calculation configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -37,6 +43,21 @@ pub struct CalculationConfig {
 
     /// Vector physics defaults
     pub vector_physics: VectorPhysicsConfig,
+
+    /// Stub type vocabulary
+    pub stub_types: StubTypesConfig,
+
+    /// Document policies enforced during validation
+    pub policies: PoliciesConfig,
+
+    /// Vault-wide settings
+    pub vault: VaultConfig,
+
+    /// Quality gate thresholds for `ddoc check`
+    pub checks: ChecksConfig,
+
+    /// Which files a vault walk visits
+    pub walk: WalkConfig,
 }
 
 impl Default for CalculationConfig {
@@ -49,6 +70,11 @@ impl Default for CalculationConfig {
             trust_factors: TrustFactorsConfig::default(),
             form_cadences: FormCadencesConfig::default(),
             vector_physics: VectorPhysicsConfig::default(),
+            stub_types: StubTypesConfig::default(),
+            policies: PoliciesConfig::default(),
+            vault: VaultConfig::default(),
+            checks: ChecksConfig::default(),
+            walk: WalkConfig::default(),
         }
     }
 }
@@ -95,6 +121,36 @@ impl CalculationConfig {
         self.validate_range("stub_penalties.blocking", self.stub_penalties.blocking)?;
         self.validate_range("stub_penalties.structural", self.stub_penalties.structural)?;
 
+        if self.stub_types.custom.keys().any(|name| name.trim().is_empty()) {
+            return Err(ConfigValidationError::InvalidValue {
+                field: "stub_types.custom".to_string(),
+                message: "Custom stub type names must not be empty".to_string(),
+            });
+        }
+
+        self.validate_range("checks.min_health", self.checks.thresholds.min_health)?;
+        if self.checks.paths.iter().any(|entry| entry.glob.trim().is_empty()) {
+            return Err(ConfigValidationError::InvalidValue {
+                field: "checks.paths".to_string(),
+                message: "Path globs must not be empty".to_string(),
+            });
+        }
+
+        let globs = self.walk.include.iter().chain(&self.walk.exclude);
+        if globs.chain(&self.walk.ignore_files).any(|glob| glob.trim().is_empty()) {
+            return Err(ConfigValidationError::InvalidValue {
+                field: "walk".to_string(),
+                message: "Globs and ignore file names must not be empty".to_string(),
+            });
+        }
+
+        if self.vault.timezone.parse::<Tz>().is_err() {
+            return Err(ConfigValidationError::InvalidValue {
+                field: "vault.timezone".to_string(),
+                message: format!("Unknown IANA timezone '{}'", self.vault.timezone),
+            });
+        }
+
         Ok(())
     }
 
@@ -122,6 +178,74 @@ impl CalculationConfig {
             trust_factors: other.trust_factors.clone(),
             form_cadences: other.form_cadences.clone(),
             vector_physics: other.vector_physics.clone(),
+            stub_types: other.stub_types.clone(),
+            policies: other.policies.clone(),
+            vault: other.vault.clone(),
+            checks: other.checks.clone(),
+            walk: other.walk.clone(),
+        }
+    }
+
+    /// List the settings that differ between two configurations
+    ///
+    /// Paths use dotted notation (e.g. `audience_gates.public`); values are
+    /// rendered as YAML scalars.
+    pub fn diff(&self, other: &CalculationConfig) -> Vec<ConfigDifference> {
+        let mut left = BTreeMap::new();
+        let mut right = BTreeMap::new();
+        if let Ok(value) = serde_yaml::to_value(self) {
+            flatten_value("", &value, &mut left);
+        }
+        if let Ok(value) = serde_yaml::to_value(other) {
+            flatten_value("", &value, &mut right);
+        }
+
+        let mut paths: Vec<&String> = left.keys().chain(right.keys()).collect();
+        paths.sort();
+        paths.dedup();
+
+        paths
+            .into_iter()
+            .filter(|path| left.get(*path) != right.get(*path))
+            .map(|path| ConfigDifference {
+                path: path.clone(),
+                left: left.get(path).cloned(),
+                right: right.get(path).cloned(),
+            })
+            .collect()
+    }
+}
+
+/// A single setting that differs between two configurations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigDifference {
+    /// Dotted path of the setting
+    pub path: String,
+
+    /// Value in the first configuration (None if unset)
+    pub left: Option<String>,
+
+    /// Value in the second configuration (None if unset)
+    pub right: Option<String>,
+}
+
+/// Flatten a YAML value into dotted paths with scalar renderings
+fn flatten_value(prefix: &str, value: &serde_yaml::Value, out: &mut BTreeMap<String, String>) {
+    match value {
+        serde_yaml::Value::Mapping(map) => {
+            for (key, child) in map {
+                let key = match key {
+                    serde_yaml::Value::String(s) => s.clone(),
+                    other => serde_yaml::to_string(other).unwrap_or_default().trim().to_string(),
+                };
+                let path = if prefix.is_empty() { key } else { format!("{}.{}", prefix, key) };
+                flatten_value(&path, child, out);
+            }
+        }
+        serde_yaml::Value::Null => {}
+        other => {
+            let rendered = serde_yaml::to_string(other).unwrap_or_default();
+            out.insert(prefix.to_string(), rendered.trim().to_string());
         }
     }
 }
@@ -188,6 +312,16 @@ impl AudienceGatesConfig {
             _ => None,
         }
     }
+
+    /// Get the gate threshold for an audience
+    pub fn for_audience(&self, audience: Audience) -> f64 {
+        match audience {
+            Audience::Personal => self.personal,
+            Audience::Internal => self.internal,
+            Audience::Trusted => self.trusted,
+            Audience::Public => self.public,
+        }
+    }
 }
 
 /// Stub penalty values by form
@@ -358,7 +492,7 @@ pub struct VectorPhysicsConfig {
     /// Friction factor for controversy (default: 0.3)
     pub controversy_friction: f64,
 
-    /// Friction factor for blocking stubs (default: 0.1)
+    /// Friction factor for blocking stubs (default: 0.2)
     pub blocking_friction: f64,
 }
 
@@ -370,7 +504,232 @@ impl Default for VectorPhysicsConfig {
             default_complexity: 0.5,
             external_dep_friction: 0.2,
             controversy_friction: 0.3,
-            blocking_friction: 0.1,
+            blocking_friction: 0.2,
+        }
+    }
+}
+
+/// Stub type vocabulary
+///
+/// Extends the built-in J-Editorial stub types with team-specific ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StubTypesConfig {
+    /// Additional stub types and the vector family they belong to
+    pub custom: BTreeMap<String, VectorFamily>,
+
+    /// Accept stub types outside the built-in and custom vocabulary (default: true)
+    pub allow_unknown: bool,
+}
+
+impl Default for StubTypesConfig {
+    fn default() -> Self {
+        Self {
+            custom: BTreeMap::new(),
+            allow_unknown: true,
+        }
+    }
+}
+
+impl StubTypesConfig {
+    /// Whether a stub type is built-in or declared as custom
+    pub fn is_known(&self, stub_type: &StubType) -> bool {
+        stub_type.is_builtin() || self.custom_family(stub_type).is_some()
+    }
+
+    /// Vector family for a stub type, honouring custom declarations
+    pub fn family(&self, stub_type: &StubType) -> VectorFamily {
+        self.custom_family(stub_type)
+            .unwrap_or_else(|| stub_type.vector_family())
+    }
+
+    fn custom_family(&self, stub_type: &StubType) -> Option<VectorFamily> {
+        self.custom
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(stub_type.as_str()))
+            .map(|(_, family)| *family)
+    }
+}
+
+/// Document policies
+///
+/// Escalate common frontmatter issues from warnings to validation errors.
+/// Defaults keep every policy relaxed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PoliciesConfig {
+    /// Documents must have a title (default: false)
+    pub require_title: bool,
+
+    /// Stubs must have a non-empty description (default: false)
+    pub require_stub_description: bool,
+
+    /// Maximum number of blocking stubs per document (default: unlimited)
+    pub max_blocking_stubs: Option<usize>,
+}
+
+/// Vault-wide settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VaultConfig {
+    /// IANA timezone for frontmatter dates without an offset (default: UTC)
+    pub timezone: String,
+
+    /// Read Dataview inline fields (`refinement:: 0.7`) as properties
+    /// frontmatter leaves unset (default: false)
+    pub inline_fields: bool,
+
+    /// Read open Obsidian Tasks checkboxes as stubs (default: false)
+    pub task_stubs: bool,
+
+    /// Read `> [!stub]` callouts and `{{type: ...}}` markers as stubs
+    /// (default: true)
+    pub stub_markers: bool,
+}
+
+impl Default for VaultConfig {
+    fn default() -> Self {
+        Self {
+            timezone: "UTC".to_string(),
+            inline_fields: false,
+            task_stubs: false,
+            stub_markers: true,
+        }
+    }
+}
+
+impl VaultConfig {
+    /// The configured timezone, or UTC if it is not a known IANA name
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(Tz::UTC)
+    }
+}
+
+/// Quality gate thresholds
+///
+/// Vault-wide thresholds, overridden for documents matching the globs in
+/// `paths` (later entries win):
+///
+/// ```yaml
+/// checks:
+///   min_health: 0.5
+///   paths:
+///     - glob: "drafts/**"
+///       min_health: 0.0
+///       min_usefulness_margin: -1.0
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChecksConfig {
+    /// Thresholds for every document
+    #[serde(flatten)]
+    pub thresholds: CheckThresholds,
+
+    /// Overrides by path glob, relative to the vault root
+    pub paths: Vec<PathChecks>,
+}
+
+/// Thresholds a document must meet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckThresholds {
+    /// Minimum health (default: 0.0)
+    pub min_health: f64,
+
+    /// Allow blocking stubs in `audience: public` documents (default: false)
+    pub allow_public_blocking_stubs: bool,
+
+    /// Minimum usefulness margin, refinement minus the audience gate
+    /// (default: 0.0)
+    pub min_usefulness_margin: f64,
+}
+
+impl Default for CheckThresholds {
+    fn default() -> Self {
+        Self {
+            min_health: 0.0,
+            allow_public_blocking_stubs: false,
+            min_usefulness_margin: 0.0,
+        }
+    }
+}
+
+/// Threshold overrides for documents matching a glob
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PathChecks {
+    /// Path glob, e.g. `drafts/**`
+    pub glob: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_health: Option<f64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_public_blocking_stubs: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_usefulness_margin: Option<f64>,
+}
+
+impl PathChecks {
+    /// Apply the overrides set in this entry
+    pub fn apply(&self, thresholds: &mut CheckThresholds) {
+        if let Some(min_health) = self.min_health {
+            thresholds.min_health = min_health;
+        }
+        if let Some(allow) = self.allow_public_blocking_stubs {
+            thresholds.allow_public_blocking_stubs = allow;
+        }
+        if let Some(margin) = self.min_usefulness_margin {
+            thresholds.min_usefulness_margin = margin;
+        }
+    }
+}
+
+/// Which files a vault walk visits
+///
+/// Globs are relative to the vault root. Ignore files use gitignore syntax
+/// and apply to the folder they are in:
+///
+/// ```yaml
+/// walk:
+///   exclude: ["archive/**"]
+///   ignore_files: [".gitignore", ".ddignore"]
+///   templates: ["Templates"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WalkConfig {
+    /// Only files matching one of these globs (default: every file)
+    pub include: Vec<String>,
+
+    /// Skip files and folders matching these globs
+    pub exclude: Vec<String>,
+
+    /// Ignore files read in every folder (default: `.gitignore`, `.ddignore`)
+    pub ignore_files: Vec<String>,
+
+    /// Template folders to skip, in addition to the one set in Obsidian's
+    /// Templates plugin
+    pub templates: Vec<String>,
+
+    /// Walk hidden folders such as `.obsidian/`, `.trash/` and `.git/`
+    /// (default: false)
+    pub hidden: bool,
+
+    /// Follow symbolic links; links that lead back into the walk are
+    /// visited once (default: false)
+    pub follow_symlinks: bool,
+}
+
+impl Default for WalkConfig {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            ignore_files: vec![".gitignore".to_string(), ".ddignore".to_string()],
+            templates: Vec::new(),
+            hidden: false,
+            follow_symlinks: false,
         }
     }
 }
@@ -384,6 +743,9 @@ pub enum ConfigValidationError {
     /// Values not in expected order
     InvalidOrder { field: String, message: String },
 
+    /// Value not acceptable for the field
+    InvalidValue { field: String, message: String },
+
     /// Value outside valid range
     OutOfRange {
         field: String,
@@ -402,6 +764,9 @@ impl std::fmt::Display for ConfigValidationError {
             Self::InvalidOrder { field, message } => {
                 write!(f, "Invalid order in '{}': {}", field, message)
             }
+            Self::InvalidValue { field, message } => {
+                write!(f, "Invalid value in '{}': {}", field, message)
+            }
             Self::OutOfRange {
                 field,
                 value,
@@ -475,6 +840,60 @@ mod tests {
         assert_eq!(gates.get("unknown"), None);
     }
 
+    #[test]
+    fn test_diff_lists_changed_settings() {
+        let base = CalculationConfig::default();
+        let mut other = CalculationConfig::default();
+        other.audience_gates.public = 0.95;
+        other.stub_types.custom.insert("replicate".to_string(), VectorFamily::Computation);
+
+        let diff = base.diff(&other);
+        assert_eq!(diff.len(), 2);
+        assert_eq!(diff[0].path, "audience_gates.public");
+        assert_eq!(diff[0].left.as_deref(), Some("0.9"));
+        assert_eq!(diff[0].right.as_deref(), Some("0.95"));
+        assert_eq!(diff[1].path, "stub_types.custom.replicate");
+        assert_eq!(diff[1].left, None);
+        assert!(base.diff(&base).is_empty());
+    }
+
+    #[test]
+    fn test_stub_types_custom_family() {
+        let mut stub_types = StubTypesConfig::default();
+        stub_types.custom.insert("replicate".to_string(), VectorFamily::Computation);
+
+        assert!(stub_types.is_known(&StubType::new("Replicate")));
+        assert!(stub_types.is_known(&StubType::new("link")));
+        assert!(!stub_types.is_known(&StubType::new("mystery")));
+        assert_eq!(stub_types.family(&StubType::new("replicate")), VectorFamily::Computation);
+    }
+
+    #[test]
+    fn test_checks_path_overrides() {
+        let yaml = "checks:\n  min_health: 0.5\n  paths:\n    \
+                    - glob: \"drafts/**\"\n      min_health: 0.1\n";
+        let config: CalculationConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.checks.thresholds.min_health, 0.5);
+        assert!(!config.checks.thresholds.allow_public_blocking_stubs);
+
+        let mut thresholds = config.checks.thresholds.clone();
+        config.checks.paths[0].apply(&mut thresholds);
+        assert_eq!(thresholds.min_health, 0.1);
+        assert_eq!(thresholds.min_usefulness_margin, 0.0);
+    }
+
+    #[test]
+    fn test_walk_config() {
+        let config: CalculationConfig = serde_yaml::from_str("walk:\n  hidden: true\n").unwrap();
+        assert!(config.walk.hidden);
+        assert_eq!(config.walk.ignore_files, [".gitignore", ".ddignore"]);
+        assert!(config.validate().is_ok());
+
+        let mut config = CalculationConfig::default();
+        config.walk.exclude.push(" ".to_string());
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_stub_penalties_get() {
         let penalties = StubPenaltiesConfig::default();
@@ -0,0 +1,275 @@
+//! Value Coercion Audit
+//!
+//! Several frontmatter values are leniently deserialized: an unknown stub
+//! `priority`, `stub_form` or `stub_origin` falls back to its default, and an
+//! unknown document `origin` becomes `human`. This module finds those values
+//! in raw frontmatter so callers can report them instead of silently
+//! changing scores.
+
+use serde_yaml::Value;
+
+use super::{Audience, Form, Origin, Priority, StubForm, StubOrigin};
+
+/// A frontmatter value that is replaced by a default when parsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coercion {
+    /// Field path (e.g. `origin`, `stubs[1].stub_form`)
+    pub path: String,
+    /// Value as written in the frontmatter
+    pub value: String,
+    /// Value used instead
+    pub applied: String,
+    /// Closest valid value, if one is similar enough
+    pub suggestion: Option<String>,
+}
+
+impl Coercion {
+    /// Human-readable description of the coercion
+    pub fn message(&self) -> String {
+        format!(
+            "Unknown value '{}' for '{}'; using '{}' instead",
+            self.value, self.path, self.applied
+        )
+    }
+
+    /// "Did you mean" hint, if a close match exists
+    pub fn hint(&self) -> Option<String> {
+        self.suggestion.as_ref().map(|s| format!("Did you mean '{}'?", s))
+    }
+}
+
+/// Valid spellings and default for an enumerated field
+struct Vocabulary {
+    valid: Vec<String>,
+    default: String,
+    accepts: fn(&str) -> bool,
+}
+
+impl Vocabulary {
+    fn audience() -> Self {
+        Self {
+            valid: Audience::all().iter().map(|a| a.to_string()).collect(),
+            default: Audience::default().to_string(),
+            accepts: |s| Audience::all().iter().any(|a| a.to_string() == s),
+        }
+    }
+
+    fn form() -> Self {
+        Self {
+            valid: Form::all().iter().map(|f| f.to_string()).collect(),
+            default: Form::default().to_string(),
+            accepts: |s| Form::all().iter().any(|f| f.to_string() == s),
+        }
+    }
+
+    fn origin() -> Self {
+        Self {
+            valid: Origin::all().iter().map(|o| o.to_string()).collect(),
+            default: Origin::default().to_string(),
+            accepts: |s| {
+                let lower = s.to_lowercase();
+                lower == "ai-assisted" || Origin::all().iter().any(|o| o.to_string() == lower)
+            },
+        }
+    }
+
+    fn stub_form() -> Self {
+        Self {
+            valid: StubForm::all().iter().map(|f| f.to_string()).collect(),
+            default: StubForm::default().to_string(),
+            accepts: |s| s.parse::<StubForm>().is_ok(),
+        }
+    }
+
+    fn priority() -> Self {
+        Self {
+            valid: ["low", "medium", "high", "critical"].map(String::from).to_vec(),
+            default: Priority::default().to_string(),
+            accepts: |s| s.parse::<Priority>().is_ok(),
+        }
+    }
+
+    fn stub_origin() -> Self {
+        Self {
+            valid: [
+                "author_identified",
+                "peer_surfaced",
+                "qa_detected",
+                "user_reported",
+                "system_generated",
+                "external_cited",
+            ]
+            .map(String::from)
+            .to_vec(),
+            default: StubOrigin::default().to_string(),
+            accepts: |s| s.parse::<StubOrigin>().is_ok(),
+        }
+    }
+
+    fn check(&self, path: String, value: &Value) -> Option<Coercion> {
+        let written = match value {
+            Value::String(s) => {
+                if (self.accepts)(s) {
+                    return None;
+                }
+                s.clone()
+            }
+            Value::Null => return None,
+            other => serde_yaml::to_string(other).unwrap_or_default().trim().to_string(),
+        };
+
+        let candidates: Vec<&str> = self.valid.iter().map(String::as_str).collect();
+        Some(Coercion {
+            path,
+            suggestion: did_you_mean(&written, &candidates),
+            value: written,
+            applied: self.default.clone(),
+        })
+    }
+}
+
+/// Find every enumerated value that parsing would replace with a default
+///
+/// # Arguments
+/// * `frontmatter` - Raw frontmatter as a YAML value
+pub fn find_coercions(frontmatter: &Value) -> Vec<Coercion> {
+    let mut coercions = Vec::new();
+    let Value::Mapping(map) = frontmatter else {
+        return coercions;
+    };
+
+    let top_level: [(&str, Vocabulary); 3] = [
+        ("audience", Vocabulary::audience()),
+        ("form", Vocabulary::form()),
+        ("origin", Vocabulary::origin()),
+    ];
+    for (key, vocabulary) in &top_level {
+        if let Some(value) = map.get(*key) {
+            coercions.extend(vocabulary.check(key.to_string(), value));
+        }
+    }
+
+    if let Some(Value::Sequence(stubs)) = map.get("stubs") {
+        for (i, stub) in stubs.iter().enumerate() {
+            let Value::Mapping(fields) = stub else {
+                continue;
+            };
+
+            // Compact stubs with options nest their fields under the type key
+            let nested = fields.values().filter_map(|v| v.as_mapping());
+            for fields in std::iter::once(fields).chain(nested) {
+                check_stub_fields(i, fields, &mut coercions);
+            }
+        }
+    }
+
+    coercions
+}
+
+fn check_stub_fields(index: usize, fields: &serde_yaml::Mapping, out: &mut Vec<Coercion>) {
+    let checks: [(&str, Vocabulary); 4] = [
+        ("stub_form", Vocabulary::stub_form()),
+        ("priority", Vocabulary::priority()),
+        ("stub_origin", Vocabulary::stub_origin()),
+        ("origin", Vocabulary::stub_origin()),
+    ];
+
+    for (key, vocabulary) in &checks {
+        if let Some(value) = fields.get(*key) {
+            out.extend(vocabulary.check(format!("stubs[{}].{}", index, key), value));
+        }
+    }
+}
+
+/// Suggest the closest candidate to a mistyped value
+///
+/// Returns a candidate only when it is within a small edit distance
+/// (a third of the value's length, at least 1, at most 3).
+pub fn did_you_mean(value: &str, candidates: &[&str]) -> Option<String> {
+    let value = value.to_lowercase();
+    let max_distance = (value.chars().count() / 3).clamp(1, 3);
+
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(&value, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// Levenshtein distance, counting an adjacent swap as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coercions(yaml: &str) -> Vec<Coercion> {
+        find_coercions(&serde_yaml::from_str(yaml).unwrap())
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        assert_eq!(did_you_mean("pubilc", &["personal", "public"]).as_deref(), Some("public"));
+        assert_eq!(did_you_mean("blcoking", &["blocking", "transient"]).as_deref(), Some("blocking"));
+        assert_eq!(did_you_mean("zzz", &["low", "high"]), None);
+    }
+
+    #[test]
+    fn test_find_document_coercions() {
+        let found = coercions("audience: pubilc\norigin: humna\nform: stable\n");
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].path, "audience");
+        assert_eq!(found[0].suggestion.as_deref(), Some("public"));
+        assert_eq!(found[1].path, "origin");
+        assert_eq!(found[1].applied, "human");
+    }
+
+    #[test]
+    fn test_find_stub_coercions() {
+        let found = coercions(
+            "stubs:\n  - type: link\n    stub_form: blcoking\n    priority: hgih\n  - verify:\n      stub_origin: peer\n      priority: urgent\n",
+        );
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].path, "stubs[0].stub_form");
+        assert_eq!(found[0].applied, "transient");
+        assert_eq!(found[0].hint().as_deref(), Some("Did you mean 'blocking'?"));
+        assert_eq!(found[1].suggestion.as_deref(), Some("high"));
+        assert_eq!(found[2].path, "stubs[1].priority");
+        assert_eq!(found[2].suggestion, None);
+    }
+
+    #[test]
+    fn test_valid_values_are_not_reported() {
+        let found = coercions(
+            "audience: public\norigin: AI-Assisted\nstubs:\n  - type: link\n    stub_form: blocking\n    stub_origin: qa\n",
+        );
+        assert!(found.is_empty());
+    }
+}
@@ -4,15 +4,16 @@
 //! These properties are context-independent (portable across systems).
 
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 
-use super::{Audience, Form, Origin, Refinement, Stub};
+use super::{Audience, Form, Origin, Refinement, Stub, Timestamp};
 
 /// L1 Intrinsic Properties - Core document metadata
 ///
 /// These properties are stored in YAML frontmatter and are
 /// context-independent (portable across systems).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct L1Properties {
     // === Foundational Properties ===
     /// Unique identifier for the document
@@ -25,11 +26,11 @@ pub struct L1Properties {
 
     /// Creation timestamp
     #[serde(default)]
-    pub created: Option<DateTime<Utc>>,
+    pub created: Option<Timestamp>,
 
     /// Last modification timestamp
     #[serde(default)]
-    pub modified: Option<DateTime<Utc>>,
+    pub modified: Option<Timestamp>,
 
     /// Tags/categories
     #[serde(default)]
@@ -61,24 +62,6 @@ pub struct L1Properties {
     pub stubs: Vec<Stub>,
 }
 
-impl Default for L1Properties {
-    fn default() -> Self {
-        Self {
-            uid: None,
-            title: None,
-            created: None,
-            modified: None,
-            tags: Vec::new(),
-            aliases: Vec::new(),
-            refinement: Refinement::default(),
-            origin: Origin::default(),
-            form: Form::default(),
-            audience: Audience::default(),
-            stubs: Vec::new(),
-        }
-    }
-}
-
 impl L1Properties {
     /// Create a new empty L1Properties
     pub fn new() -> Self {
@@ -93,6 +76,18 @@ impl L1Properties {
         }
     }
 
+    /// When the document was last touched, reading local dates in `tz`
+    ///
+    /// The later of `modified` and `created`, so a date-only `modified` is
+    /// refined by a same-day `created` that records the time.
+    pub fn last_touched(&self, tz: Tz) -> Option<DateTime<Utc>> {
+        [&self.modified, &self.created]
+            .into_iter()
+            .flatten()
+            .map(|ts| ts.resolve(tz))
+            .max()
+    }
+
     /// Get the number of stubs
     pub fn stub_count(&self) -> usize {
         self.stubs.len()
@@ -201,4 +196,16 @@ mod tests {
         assert_eq!(blocking_stubs.len(), 1);
         assert_eq!(blocking_stubs[0].description, "critical");
     }
+
+    #[test]
+    fn test_last_touched() {
+        let props: L1Properties =
+            serde_yaml::from_str("created: 2024-03-05 14:20\nmodified: 2024-03-05\n").unwrap();
+
+        // The same-day creation time is more precise than the date-only modification
+        let touched = props.last_touched(chrono_tz::Europe::Berlin).unwrap();
+        assert_eq!(touched.to_rfc3339(), "2024-03-05T13:20:00+00:00");
+        assert!(L1Properties::new().last_touched(Tz::UTC).is_none());
+    }
+
 }
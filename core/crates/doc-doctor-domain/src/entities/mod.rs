@@ -4,15 +4,19 @@
 //! that represent document metadata without external dependencies.
 
 mod audience;
+mod coercion;
 mod document;
 mod form;
 mod origin;
 mod refinement;
 mod stub;
+mod timestamp;
 
 pub use audience::Audience;
+pub use coercion::{did_you_mean, find_coercions, Coercion};
 pub use document::L1Properties;
 pub use form::Form;
 pub use origin::Origin;
 pub use refinement::Refinement;
 pub use stub::{Priority, Stub, StubForm, StubOrigin, StubType, VectorFamily};
+pub use timestamp::{Timestamp, TimestampError, TimestampPrecision};
@@ -12,6 +12,7 @@
 
 use serde::{Deserialize, Deserializer, Serialize};
 use std::str::FromStr;
+use crate::entities::Timestamp;
 use crate::errors::{DomainError, DomainResult};
 
 // ============================================================================
@@ -187,7 +188,7 @@ impl std::fmt::Display for Priority {
 /// Vector family classification
 ///
 /// Categorizes stubs by the type of work required to resolve them.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum VectorFamily {
     /// Finding existing information
@@ -196,7 +197,8 @@ pub enum VectorFamily {
     Computation,
     /// Combining perspectives
     Synthesis,
-    /// Generating new content
+    /// Generating new content (default for unknown types)
+    #[default]
     Creation,
     /// Architectural changes
     Structural,
@@ -215,12 +217,6 @@ impl VectorFamily {
     }
 }
 
-impl Default for VectorFamily {
-    fn default() -> Self {
-        VectorFamily::Creation
-    }
-}
-
 // ============================================================================
 // StubType
 // ============================================================================
@@ -240,6 +236,11 @@ impl StubType {
         &self.0
     }
 
+    /// Whether this is one of the J-Editorial built-in stub types
+    pub fn is_builtin(&self) -> bool {
+        KNOWN_STUB_TYPES.contains(&self.0.to_lowercase().as_str())
+    }
+
     /// Get the vector family for this stub type
     pub fn vector_family(&self) -> VectorFamily {
         match self.0.to_lowercase().as_str() {
@@ -402,6 +403,10 @@ pub struct Stub {
     /// Complexity factor (0.0-1.0)
     #[serde(default)]
     pub complexity: Option<f64>,
+
+    /// Due date (e.g. from an Obsidian Tasks `📅` field)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<Timestamp>,
 }
 
 /// All known stub types from J-Editorial framework
@@ -467,35 +472,38 @@ impl<'de> Deserialize<'de> for Stub {
                         }
                         serde_yaml::Value::Mapping(obj) => {
                             // Compact with object: `- verify: { description: "...", ... }`
-                            let description = obj.get(&serde_yaml::Value::String("description".to_string()))
+                            let description = obj.get(serde_yaml::Value::String("description".to_string()))
                                 .and_then(|v| v.as_str())
                                 .unwrap_or("")
                                 .to_string();
 
-                            let stub_form = obj.get(&serde_yaml::Value::String("stub_form".to_string()))
+                            let stub_form = obj.get(serde_yaml::Value::String("stub_form".to_string()))
                                 .and_then(|v| v.as_str())
                                 .and_then(|s| StubForm::from_str(s).ok())
                                 .unwrap_or_default();
 
-                            let priority = obj.get(&serde_yaml::Value::String("priority".to_string()))
+                            let priority = obj.get(serde_yaml::Value::String("priority".to_string()))
                                 .and_then(|v| v.as_str())
                                 .and_then(|s| Priority::from_str(s).ok())
                                 .unwrap_or_default();
 
-                            let origin = obj.get(&serde_yaml::Value::String("stub_origin".to_string()))
-                                .or_else(|| obj.get(&serde_yaml::Value::String("origin".to_string())))
+                            let origin = obj.get(serde_yaml::Value::String("stub_origin".to_string()))
+                                .or_else(|| obj.get(serde_yaml::Value::String("origin".to_string())))
                                 .and_then(|v| v.as_str())
                                 .and_then(|s| StubOrigin::from_str(s).ok())
                                 .unwrap_or_default();
 
-                            let inline_anchors = parse_string_or_vec(&obj, "inline_anchor")
-                                .or_else(|| parse_string_or_vec(&obj, "inline_anchors"))
+                            let inline_anchors = parse_string_or_vec(obj, "inline_anchor")
+                                .or_else(|| parse_string_or_vec(obj, "inline_anchors"))
                                 .unwrap_or_default();
 
-                            let anchor = obj.get(&serde_yaml::Value::String("anchor".to_string()))
+                            let anchor = obj.get(serde_yaml::Value::String("anchor".to_string()))
                                 .and_then(|v| v.as_str())
                                 .map(String::from);
 
+                            let due = obj.get(serde_yaml::Value::String("due".to_string()))
+                                .and_then(parse_due);
+
                             return Ok(Stub {
                                 stub_type,
                                 description,
@@ -511,6 +519,7 @@ impl<'de> Deserialize<'de> for Stub {
                                 urgency: None,
                                 impact: None,
                                 complexity: None,
+                                due,
                             });
                         }
                         _ => {
@@ -568,6 +577,10 @@ impl<'de> Deserialize<'de> for Stub {
                     .and_then(|(_, v)| v.as_str())
                     .map(String::from);
 
+                let due = entries.iter()
+                    .find(|(k, _)| k == "due")
+                    .and_then(|(_, v)| parse_due(v));
+
                 let inline_anchors = entries.iter()
                     .find(|(k, _)| k == "inline_anchors" || k == "inline_anchor")
                     .map(|(_, v)| {
@@ -596,6 +609,7 @@ impl<'de> Deserialize<'de> for Stub {
                     urgency: None,
                     impact: None,
                     complexity: None,
+                    due,
                 })
             }
         }
@@ -604,15 +618,20 @@ impl<'de> Deserialize<'de> for Stub {
     }
 }
 
+/// Helper to parse a due date; unreadable dates are dropped
+fn parse_due(value: &serde_yaml::Value) -> Option<Timestamp> {
+    serde_yaml::from_value(value.clone()).ok()
+}
+
 /// Helper to parse a field that can be a string or a list of strings
 fn parse_string_or_vec(map: &serde_yaml::Mapping, key: &str) -> Option<Vec<String>> {
-    let value = map.get(&serde_yaml::Value::String(key.to_string()))?;
+    let value = map.get(serde_yaml::Value::String(key.to_string()))?;
     if let Some(s) = value.as_str() {
         Some(vec![s.to_string()])
-    } else if let Some(seq) = value.as_sequence() {
-        Some(seq.iter().filter_map(|v| v.as_str().map(String::from)).collect())
     } else {
-        None
+        value
+            .as_sequence()
+            .map(|seq| seq.iter().filter_map(|v| v.as_str().map(String::from)).collect())
     }
 }
 
@@ -634,6 +653,7 @@ impl Stub {
             urgency: None,
             impact: None,
             complexity: None,
+            due: None,
         }
     }
 
@@ -845,4 +865,15 @@ priority: high
         assert_eq!(stubs[2].stub_type.as_str(), "expand");
         assert_eq!(stubs[2].priority, Priority::Medium);
     }
+
+    #[test]
+    fn test_due_date_round_trip() {
+        let yaml = "type: verify\ndescription: Figure 3\ndue: 2024-05-01\n";
+        let stub: Stub = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(stub.due.as_ref().map(|d| d.to_string()).as_deref(), Some("2024-05-01"));
+
+        let written = serde_yaml::to_string(&stub).unwrap();
+        assert!(written.contains("due: 2024-05-01"));
+        assert!(!serde_yaml::to_string(&Stub::compact("link", "x")).unwrap().contains("due"));
+    }
 }
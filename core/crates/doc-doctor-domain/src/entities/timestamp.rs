@@ -0,0 +1,407 @@
+//! Frontmatter Timestamps
+//!
+//! `created` and `modified` values as people and templates write them:
+//! RFC 3339, date-only, naive local times and common templater formats.
+//! The original text is kept, so documents round-trip in their own style.
+
+use std::fmt;
+
+use chrono::{
+    DateTime, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// How much of the time of day a timestamp records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TimestampPrecision {
+    /// Date only (`2024-03-05`)
+    Day,
+    /// Hours and minutes (`2024-03-05 14:20`)
+    Minute,
+    /// Seconds or finer (`2024-03-05T14:20:00Z`)
+    Second,
+}
+
+/// Date-time formats with an explicit UTC offset
+const OFFSET_FORMATS: &[(&str, TimestampPrecision)] = &[
+    ("%Y-%m-%dT%H:%M:%S%.f%:z", TimestampPrecision::Second),
+    ("%Y-%m-%d %H:%M:%S%.f%:z", TimestampPrecision::Second),
+    ("%Y-%m-%dT%H:%M%:z", TimestampPrecision::Minute),
+    ("%Y-%m-%d %H:%M%:z", TimestampPrecision::Minute),
+    ("%Y-%m-%dT%H:%M:%S%.f%z", TimestampPrecision::Second),
+    ("%Y-%m-%d %H:%M:%S%.f %z", TimestampPrecision::Second),
+    ("%Y-%m-%d %H:%M %z", TimestampPrecision::Minute),
+];
+
+/// Local date-time formats, read in the vault timezone
+const LOCAL_FORMATS: &[(&str, TimestampPrecision)] = &[
+    ("%Y-%m-%dT%H:%M:%S%.f", TimestampPrecision::Second),
+    ("%Y-%m-%d %H:%M:%S%.f", TimestampPrecision::Second),
+    ("%Y-%m-%dT%H:%M", TimestampPrecision::Minute),
+    ("%Y-%m-%d %H:%M", TimestampPrecision::Minute),
+    ("%Y-%m-%d %I:%M %p", TimestampPrecision::Minute),
+    ("%Y-%m-%d %I:%M:%S %p", TimestampPrecision::Second),
+    ("%Y/%m/%d %H:%M", TimestampPrecision::Minute),
+    ("%Y/%m/%d %H:%M:%S", TimestampPrecision::Second),
+    ("%Y%m%d%H%M%S", TimestampPrecision::Second),
+    ("%Y%m%d%H%M", TimestampPrecision::Minute),
+];
+
+/// Date-only formats, read as local midnight in the vault timezone
+const DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",
+    "%Y/%m/%d",
+    "%Y.%m.%d",
+    "%Y%m%d",
+    "%B %d, %Y",
+    "%b %d, %Y",
+    "%A, %B %d, %Y",
+    "%d %B %Y",
+    "%d %b %Y",
+];
+
+/// Format used for timestamps created from an instant
+const RFC3339_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%:z";
+
+/// A frontmatter date or date-time
+///
+/// Values without an offset are local to the vault and are resolved with
+/// [`resolve`](Self::resolve). Serializing writes the original text back.
+///
+/// # Example
+///
+/// ```
+/// use doc_doctor_domain::{Timestamp, TimestampPrecision};
+///
+/// let ts: Timestamp = "2024-03-05 14:20".parse().unwrap();
+/// assert_eq!(ts.precision(), TimestampPrecision::Minute);
+/// assert_eq!(ts.resolve(chrono_tz::Europe::Berlin).to_rfc3339(), "2024-03-05T13:20:00+00:00");
+/// assert_eq!(ts.to_string(), "2024-03-05 14:20");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timestamp {
+    local: NaiveDateTime,
+    offset: Option<FixedOffset>,
+    precision: TimestampPrecision,
+    format: &'static str,
+    original: String,
+    numeric: bool,
+}
+
+/// Error for text that matches no supported date format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimestampError {
+    /// The rejected text
+    pub value: String,
+}
+
+impl fmt::Display for TimestampError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unrecognized date '{}' (expected e.g. 2024-03-05, 2024-03-05 14:20 or RFC 3339)",
+            self.value
+        )
+    }
+}
+
+impl std::error::Error for TimestampError {}
+
+impl Timestamp {
+    /// Parse a timestamp in any supported format
+    pub fn parse(text: &str) -> Result<Self, TimestampError> {
+        let trimmed = text.trim();
+        let original = text.to_string();
+        // "Z" is the UTC offset in RFC 3339
+        let normalized = match trimmed.strip_suffix(['Z', 'z']) {
+            Some(rest) if rest.contains(':') => format!("{}+00:00", rest),
+            _ => trimmed.to_string(),
+        };
+
+        for &(format, precision) in OFFSET_FORMATS {
+            if let Ok(dt) = DateTime::parse_from_str(&normalized, format) {
+                return Ok(Self {
+                    local: dt.naive_local(),
+                    offset: Some(*dt.offset()),
+                    precision,
+                    format,
+                    original,
+                    numeric: false,
+                });
+            }
+        }
+
+        for &(format, precision) in LOCAL_FORMATS {
+            if let Ok(local) = NaiveDateTime::parse_from_str(trimmed, format) {
+                return Ok(Self::local(local, precision, format, original));
+            }
+        }
+
+        for &format in DATE_FORMATS {
+            if let Ok(date) = NaiveDate::parse_from_str(trimmed, format) {
+                let local = date.and_time(NaiveTime::MIN);
+                return Ok(Self::local(local, TimestampPrecision::Day, format, original));
+            }
+        }
+
+        Err(TimestampError { value: original })
+    }
+
+    /// A timestamp for an integer value
+    ///
+    /// Digits are read as a compact date (`20240305`, `202403051420`) when
+    /// they form one, otherwise as Unix seconds.
+    pub fn from_integer(value: i64) -> Result<Self, TimestampError> {
+        if let Ok(mut ts) = Self::parse(&value.to_string()) {
+            ts.numeric = true;
+            return Ok(ts);
+        }
+
+        let dt = DateTime::from_timestamp(value, 0).ok_or_else(|| TimestampError {
+            value: value.to_string(),
+        })?;
+        Ok(Self {
+            local: dt.naive_utc(),
+            offset: Some(FixedOffset::east_opt(0).expect("zero offset is valid")),
+            precision: TimestampPrecision::Second,
+            format: "%s",
+            original: value.to_string(),
+            numeric: true,
+        })
+    }
+
+    fn local(local: NaiveDateTime, precision: TimestampPrecision, format: &'static str, original: String) -> Self {
+        Self {
+            local,
+            offset: None,
+            precision,
+            format,
+            original,
+            numeric: false,
+        }
+    }
+
+    /// How much of the time of day is recorded
+    pub fn precision(&self) -> TimestampPrecision {
+        self.precision
+    }
+
+    /// Whether the value carries its own UTC offset
+    pub fn has_offset(&self) -> bool {
+        self.offset.is_some()
+    }
+
+    /// The text as written in the document
+    pub fn original(&self) -> &str {
+        &self.original
+    }
+
+    /// The instant, reading local values in `tz`
+    ///
+    /// Date-only values are local midnight. Times skipped by a daylight
+    /// saving change resolve to the first valid time after them.
+    pub fn resolve(&self, tz: Tz) -> DateTime<Utc> {
+        if let Some(offset) = self.offset {
+            return offset
+                .from_local_datetime(&self.local)
+                .single()
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|| Utc.from_utc_datetime(&self.local));
+        }
+
+        let mut local = self.local;
+        loop {
+            match tz.from_local_datetime(&local) {
+                LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => return dt.with_timezone(&Utc),
+                LocalResult::None => local += chrono::Duration::minutes(15),
+            }
+        }
+    }
+
+    /// The instant, reading local values as UTC
+    pub fn to_utc(&self) -> DateTime<Utc> {
+        self.resolve(Tz::UTC)
+    }
+
+    /// A new timestamp for `instant`, written in this timestamp's style
+    ///
+    /// Local values are written in `tz`; values with an offset keep it.
+    pub fn restyled(&self, instant: DateTime<Utc>, tz: Tz) -> Self {
+        let (local, original) = match self.offset {
+            Some(offset) => {
+                let dt = instant.with_timezone(&offset);
+                (dt.naive_local(), dt.format(self.format).to_string())
+            }
+            None => {
+                let dt = instant.with_timezone(&tz);
+                (dt.naive_local(), dt.format(self.format).to_string())
+            }
+        };
+        let original = match self.original.trim_end().chars().last() {
+            Some('Z' | 'z') if original.ends_with("+00:00") => {
+                format!("{}Z", original.trim_end_matches("+00:00"))
+            }
+            _ => original,
+        };
+
+        Self {
+            local: match self.precision {
+                TimestampPrecision::Day => local.date().and_time(NaiveTime::MIN),
+                _ => local,
+            },
+            offset: self.offset,
+            precision: self.precision,
+            format: self.format,
+            original,
+            numeric: self.numeric,
+        }
+    }
+}
+
+impl From<DateTime<Utc>> for Timestamp {
+    fn from(instant: DateTime<Utc>) -> Self {
+        Self {
+            local: instant.naive_utc(),
+            offset: Some(FixedOffset::east_opt(0).expect("zero offset is valid")),
+            precision: TimestampPrecision::Second,
+            format: RFC3339_FORMAT,
+            original: instant.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            numeric: false,
+        }
+    }
+}
+
+impl std::str::FromStr for Timestamp {
+    type Err = TimestampError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.original)
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.original.parse::<i64>() {
+            Ok(number) if self.numeric => serializer.serialize_i64(number),
+            _ => serializer.serialize_str(&self.original),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TimestampVisitor;
+
+        impl Visitor<'_> for TimestampVisitor {
+            type Value = Timestamp;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a date or date-time")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Timestamp, E> {
+                Timestamp::parse(v).map_err(E::custom)
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Timestamp, E> {
+                Timestamp::from_integer(v).map_err(E::custom)
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Timestamp, E> {
+                let v = i64::try_from(v).map_err(|_| E::custom(format!("date out of range: {}", v)))?;
+                self.visit_i64(v)
+            }
+        }
+
+        deserializer.deserialize_any(TimestampVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::America::New_York;
+
+    fn utc(text: &str) -> String {
+        Timestamp::parse(text).unwrap().to_utc().to_rfc3339()
+    }
+
+    #[test]
+    fn test_parse_formats() {
+        assert_eq!(utc("2024-03-05T14:20:00Z"), "2024-03-05T14:20:00+00:00");
+        assert_eq!(utc("2024-03-05T14:20:00.250+02:00"), "2024-03-05T12:20:00.250+00:00");
+        assert_eq!(utc("2024-03-05 14:20 +0100"), "2024-03-05T13:20:00+00:00");
+        assert_eq!(utc("2024-03-05 14:20"), "2024-03-05T14:20:00+00:00");
+        assert_eq!(utc("2024-03-05 02:20 PM"), "2024-03-05T14:20:00+00:00");
+        assert_eq!(utc("202403051420"), "2024-03-05T14:20:00+00:00");
+        assert_eq!(utc("2024-03-05"), "2024-03-05T00:00:00+00:00");
+        assert_eq!(utc("March 5, 2024"), "2024-03-05T00:00:00+00:00");
+        assert_eq!(utc("Tuesday, March 5, 2024"), "2024-03-05T00:00:00+00:00");
+
+        assert!(Timestamp::parse("last tuesday").is_err());
+        assert!(Timestamp::parse("2024-13-05").is_err());
+    }
+
+    #[test]
+    fn test_precision() {
+        let precision = |text| Timestamp::parse(text).unwrap().precision();
+        assert_eq!(precision("2024-03-05"), TimestampPrecision::Day);
+        assert_eq!(precision("2024-03-05 14:20"), TimestampPrecision::Minute);
+        assert_eq!(precision("2024-03-05T14:20:00Z"), TimestampPrecision::Second);
+    }
+
+    #[test]
+    fn test_vault_timezone() {
+        let local = Timestamp::parse("2024-07-01 09:00").unwrap();
+        assert_eq!(local.resolve(New_York).to_rfc3339(), "2024-07-01T13:00:00+00:00");
+
+        // Values with an offset ignore the vault timezone
+        let fixed = Timestamp::parse("2024-07-01T09:00:00+02:00").unwrap();
+        assert_eq!(fixed.resolve(New_York), fixed.to_utc());
+
+        // 02:30 does not exist on the spring-forward day
+        let gap = Timestamp::parse("2024-03-10 02:30").unwrap();
+        assert_eq!(gap.resolve(New_York).to_rfc3339(), "2024-03-10T07:00:00+00:00");
+    }
+
+    #[test]
+    fn test_round_trip_keeps_style() {
+        for text in ["2024-03-05", "2024-03-05 14:20", "2024-03-05T14:20:00Z"] {
+            let yaml = format!("date: {}\n", text);
+            let value: std::collections::BTreeMap<String, Timestamp> = serde_yaml::from_str(&yaml).unwrap();
+            assert_eq!(serde_yaml::to_string(&value).unwrap(), yaml);
+        }
+
+        let numeric: Timestamp = serde_yaml::from_str("20240305").unwrap();
+        assert_eq!(numeric.precision(), TimestampPrecision::Day);
+        assert_eq!(serde_yaml::to_string(&numeric).unwrap(), "20240305\n");
+    }
+
+    #[test]
+    fn test_restyled() {
+        let instant = Utc.with_ymd_and_hms(2024, 7, 1, 13, 0, 0).unwrap();
+
+        let date = Timestamp::parse("2024-03-05").unwrap();
+        assert_eq!(date.restyled(instant, New_York).original(), "2024-07-01");
+
+        let local = Timestamp::parse("2024-03-05 14:20").unwrap();
+        assert_eq!(local.restyled(instant, New_York).original(), "2024-07-01 09:00");
+
+        let zulu = Timestamp::parse("2024-03-05T14:20:00Z").unwrap();
+        assert_eq!(zulu.restyled(instant, New_York).original(), "2024-07-01T13:00:00Z");
+    }
+
+    #[test]
+    fn test_unix_seconds() {
+        let ts = Timestamp::from_integer(1_709_648_400).unwrap();
+        assert_eq!(ts.to_utc().to_rfc3339(), "2024-03-05T14:20:00+00:00");
+    }
+}
@@ -49,33 +49,43 @@ pub mod ports;
 
 // Re-export commonly used types for convenience
 pub use calculations::{
-    calculate_friction, calculate_health, calculate_health_with_config, calculate_magnitude,
-    calculate_potential_energy, calculate_stub_penalty, calculate_stub_penalty_with_config,
-    calculate_usefulness, calculate_usefulness_with_config, forecast_completion, StateDimensions,
-    StubContext, TrajectoryDimensions, Usefulness, VectorPhysics,
+    calculate_freshness_with_config, calculate_friction, calculate_friction_with_config,
+    calculate_health, calculate_health_with_config, calculate_magnitude,
+    calculate_potential_energy, calculate_potential_energy_with_config, calculate_stub_penalty,
+    calculate_stub_penalty_with_config, calculate_trust_with_config, calculate_usefulness,
+    calculate_usefulness_with_config, forecast_completion, StateDimensions, StubContext,
+    TrajectoryDimensions, Usefulness, VectorPhysics,
 };
 
 pub use entities::{
-    Audience, Form, L1Properties, Origin, Priority, Refinement, Stub, StubForm, StubOrigin,
-    StubType, VectorFamily,
+    did_you_mean, find_coercions, Audience, Coercion, Form, L1Properties, Origin, Priority,
+    Refinement, Stub, StubForm, StubOrigin, StubType, Timestamp, TimestampError,
+    TimestampPrecision, VectorFamily,
 };
 
 pub use config::{
-    AudienceGatesConfig, CalculationConfig, ConfigValidationError, FormCadencesConfig,
-    HealthConfig, StubPenaltiesConfig, TrustFactorsConfig, VectorPhysicsConfig,
+    AudienceGatesConfig, CalculationConfig, CheckThresholds, ChecksConfig, ConfigDifference,
+    ConfigValidationError, FormCadencesConfig, HealthConfig, PathChecks, PoliciesConfig,
+    StubPenaltiesConfig, StubTypesConfig, TrustFactorsConfig, VaultConfig, VectorPhysicsConfig,
+    WalkConfig,
 };
 
 pub use errors::{DomainError, DomainResult, ValidationWarning};
 
 pub use ports::inbound::{
     AnalysisError, AnalyzeDocument, BatchDocumentResult, BatchError, BatchProcess, BatchResult,
-    CalculateDimensions, DefaultCalculator, DocumentAnalysis, SchemaError, SchemaWarning,
-    ValidateDocument, ValidationError, ValidationResult,
+    BatchSummary, CalculateDimensions, DefaultCalculator, DocumentAnalysis, SchemaError,
+    SchemaWarning, ValidateDocument, ValidationError, ValidationResult,
 };
 
 pub use ports::outbound::{
-    Action, ActionType, ConfigError, ConfigProvider, DefaultConfigProvider, DocumentMetadata,
-    DocumentParser, DocumentRepository, DocumentWriter, EmbeddedSchemaProvider, LayeredConfigProvider,
-    MetadataSpan, NoOpRuleEngine, ParseError, RepositoryError, RepositoryErrorKind, RuleContext,
-    RuleEngine, RuleError, RuleResult, SchemaProvider, SerializeError, SourcePosition,
+    apply_fixes, is_canvas, strip_bom, Action, ActionType, Annotation, AnnotationKind, BlockId,
+    BodyParser, Canvas, CanvasEdge, CanvasNode, CanvasNodeKind, CanvasParser, CodeKind, CodeSpan,
+    ConfigError, ConfigProvider, DefaultConfigProvider, DocumentMetadata, DocumentParser,
+    DocumentRepository, DocumentWriter, EmbeddedSchemaProvider, Footnote, FootnoteKind, Heading,
+    InlineField, LayeredConfigProvider, LineEnding, LintFinding, LintRule, MarkdownBody,
+    MetadataSpan, NoOpRuleEngine, ParseDiagnostic, ParseDiagnosticKind, ParseError,
+    RecoveredParse, RepositoryError, RepositoryErrorKind, RuleContext, RuleEngine, RuleError,
+    RuleResult, SchemaProvider, Section, SerializeError, SourcePosition, StubMarker,
+    StubMarkerSyntax, TaskItem, TaskStatus, TextEdit, TextStyle, WikiLink, BOM, CANVAS_EXTENSION,
 };
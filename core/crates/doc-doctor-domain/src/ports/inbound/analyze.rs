@@ -5,6 +5,7 @@
 use crate::calculations::StateDimensions;
 use crate::entities::L1Properties;
 use crate::errors::ValidationWarning;
+use crate::ports::outbound::ParseDiagnostic;
 
 /// Document analysis result
 #[derive(Debug, Clone)]
@@ -15,6 +16,10 @@ pub struct DocumentAnalysis {
     pub dimensions: StateDimensions,
     /// Validation warnings (non-fatal issues)
     pub warnings: Vec<ValidationWarning>,
+    /// Entries skipped or coerced while parsing
+    pub diagnostics: Vec<ParseDiagnostic>,
+    /// True when some entries were skipped, so the analysis covers only part of the document
+    pub partial: bool,
 }
 
 impl DocumentAnalysis {
@@ -24,6 +29,8 @@ impl DocumentAnalysis {
             properties,
             dimensions,
             warnings: Vec::new(),
+            diagnostics: Vec::new(),
+            partial: false,
         }
     }
 
@@ -33,6 +40,13 @@ impl DocumentAnalysis {
         self
     }
 
+    /// Add parse diagnostics, marking the analysis partial if entries were skipped
+    pub fn with_diagnostics(mut self, diagnostics: Vec<ParseDiagnostic>) -> Self {
+        self.partial = diagnostics.iter().any(ParseDiagnostic::is_skipped);
+        self.diagnostics = diagnostics;
+        self
+    }
+
     /// Check if the document is healthy (health >= 0.7)
     pub fn is_healthy(&self) -> bool {
         self.dimensions.health >= 0.7
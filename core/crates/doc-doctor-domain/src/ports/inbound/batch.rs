@@ -4,6 +4,8 @@
 
 use crate::calculations::StateDimensions;
 use crate::entities::L1Properties;
+use crate::ports::outbound::ParseDiagnostic;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 /// Single document result in batch processing
@@ -17,6 +19,11 @@ pub struct BatchDocumentResult {
     pub dimensions: Option<StateDimensions>,
     /// Error message (if failed)
     pub error: Option<String>,
+    /// Entries skipped or coerced while parsing
+    pub diagnostics: Vec<ParseDiagnostic>,
+    /// Problems reading the document that did not stop processing
+    /// (e.g. a large file read only in part)
+    pub warnings: Vec<String>,
 }
 
 impl BatchDocumentResult {
@@ -27,6 +34,8 @@ impl BatchDocumentResult {
             properties: Some(properties),
             dimensions: Some(dimensions),
             error: None,
+            diagnostics: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
@@ -37,13 +46,32 @@ impl BatchDocumentResult {
             properties: None,
             dimensions: None,
             error: Some(error.into()),
+            diagnostics: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
+    /// Add parse diagnostics
+    pub fn with_diagnostics(mut self, diagnostics: Vec<ParseDiagnostic>) -> Self {
+        self.diagnostics = diagnostics;
+        self
+    }
+
+    /// Add a read warning
+    pub fn with_warning(mut self, warning: impl Into<String>) -> Self {
+        self.warnings.push(warning.into());
+        self
+    }
+
     /// Check if this result is successful
     pub fn is_success(&self) -> bool {
         self.error.is_none()
     }
+
+    /// Check if this result succeeded with some entries skipped
+    pub fn is_partial(&self) -> bool {
+        self.is_success() && self.diagnostics.iter().any(ParseDiagnostic::is_skipped)
+    }
 }
 
 /// Batch processing result
@@ -57,6 +85,8 @@ pub struct BatchResult {
     pub succeeded: usize,
     /// Failed documents
     pub failed: usize,
+    /// Successful documents with skipped entries (counted in `succeeded`)
+    pub partial: usize,
 }
 
 impl BatchResult {
@@ -65,12 +95,14 @@ impl BatchResult {
         let total = documents.len();
         let succeeded = documents.iter().filter(|d| d.is_success()).count();
         let failed = total - succeeded;
+        let partial = documents.iter().filter(|d| d.is_partial()).count();
 
         Self {
             documents,
             total,
             succeeded,
             failed,
+            partial,
         }
     }
 
@@ -100,6 +132,51 @@ impl BatchResult {
     }
 }
 
+/// Batch aggregates, computed incrementally as results arrive
+///
+/// Unlike [`BatchResult`] it keeps no documents, so a streaming batch
+/// uses constant memory.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSummary {
+    /// Documents processed
+    pub total: usize,
+    /// Successful documents
+    pub succeeded: usize,
+    /// Failed documents
+    pub failed: usize,
+    /// Successful documents with skipped entries (counted in `succeeded`)
+    pub partial: usize,
+    /// Sum of the health of documents with dimensions
+    pub health_sum: f64,
+    /// Number of documents with dimensions
+    pub health_count: usize,
+}
+
+impl BatchSummary {
+    /// Count a result
+    pub fn add(&mut self, result: &BatchDocumentResult) {
+        self.total += 1;
+        if result.is_success() {
+            self.succeeded += 1;
+        } else {
+            self.failed += 1;
+        }
+        if result.is_partial() {
+            self.partial += 1;
+        }
+        if let Some(dimensions) = &result.dimensions {
+            self.health_sum += dimensions.health;
+            self.health_count += 1;
+        }
+    }
+
+    /// Average health of the documents with dimensions
+    pub fn average_health(&self) -> Option<f64> {
+        (self.health_count > 0).then(|| self.health_sum / self.health_count as f64)
+    }
+}
+
 /// Batch processing error
 #[derive(Debug, Clone)]
 pub struct BatchError {
@@ -9,6 +9,6 @@ mod calculate;
 mod validate;
 
 pub use analyze::{AnalysisError, AnalyzeDocument, DocumentAnalysis};
-pub use batch::{BatchDocumentResult, BatchError, BatchProcess, BatchResult};
+pub use batch::{BatchDocumentResult, BatchError, BatchProcess, BatchResult, BatchSummary};
 pub use calculate::{CalculateDimensions, DefaultCalculator};
 pub use validate::{SchemaError, SchemaWarning, ValidateDocument, ValidationError, ValidationResult};
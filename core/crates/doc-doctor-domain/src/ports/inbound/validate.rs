@@ -45,6 +45,8 @@ pub struct SchemaWarning {
     pub message: String,
     /// JSON path
     pub path: Option<String>,
+    /// Position in source document
+    pub position: Option<SourcePosition>,
     /// Suggested fix
     pub suggestion: Option<String>,
 }
@@ -55,6 +57,7 @@ impl SchemaWarning {
         Self {
             message: message.into(),
             path: None,
+            position: None,
             suggestion: None,
         }
     }
@@ -65,6 +68,12 @@ impl SchemaWarning {
         self
     }
 
+    /// Add position
+    pub fn with_position(mut self, position: SourcePosition) -> Self {
+        self.position = Some(position);
+        self
+    }
+
     /// Add suggestion
     pub fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
         self.suggestion = Some(suggestion.into());
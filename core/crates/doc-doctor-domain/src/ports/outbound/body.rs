@@ -0,0 +1,393 @@
+//! Markdown Body Port
+//!
+//! Format-agnostic model of a document body: the heading tree, block IDs,
+//! links, footnotes, code, Dataview inline fields, task checkboxes and
+//! annotations.
+//! Anchors, coverage, link graphs and section-level features read the body
+//! through this model instead of scanning raw text.
+
+use super::parser::SourcePosition;
+use crate::entities::Priority;
+
+/// An ATX heading (`## Title`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heading {
+    /// Heading level (1-6)
+    pub level: u8,
+    /// Heading text without markers or a trailing block ID
+    pub text: String,
+    /// Position of the first `#`
+    pub position: SourcePosition,
+}
+
+/// A heading and everything up to the next heading of the same or higher level
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    /// The section's heading
+    pub heading: Heading,
+    /// Byte offset where the section ends
+    pub end_offset: usize,
+    /// Nested sections
+    pub children: Vec<Section>,
+}
+
+impl Section {
+    /// Whether the section contains a byte offset
+    pub fn contains(&self, offset: usize) -> bool {
+        offset >= self.heading.position.offset && offset < self.end_offset
+    }
+}
+
+/// A block identifier (`Paragraph text ^block-id`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockId {
+    /// Identifier without the caret
+    pub id: String,
+    /// Position of the caret
+    pub position: SourcePosition,
+}
+
+/// A wikilink (`[[Note#Heading|Alias]]`) or embed (`![[Note]]`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct WikiLink {
+    /// Linked note; empty for links within the same document
+    pub target: String,
+    /// Heading fragment (`#Heading`)
+    pub heading: Option<String>,
+    /// Block fragment (`#^block-id`), without the caret
+    pub block: Option<String>,
+    /// Display text (`|Alias`)
+    pub alias: Option<String>,
+    /// Position of the opening brackets (or `!` for embeds)
+    pub position: SourcePosition,
+}
+
+/// Footnote reference or definition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FootnoteKind {
+    /// `[^label]` in text
+    Reference,
+    /// `[^label]: text` at the start of a line
+    Definition,
+}
+
+/// A footnote reference or definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct Footnote {
+    /// Label without brackets or caret
+    pub label: String,
+    /// Reference or definition
+    pub kind: FootnoteKind,
+    /// Position of the opening bracket
+    pub position: SourcePosition,
+}
+
+/// Kind of literal content
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeKind {
+    /// `` `code` `` within a line
+    Inline,
+    /// A fenced code block
+    Fenced,
+    /// `$math$` or a `$$` block
+    Math,
+}
+
+/// Literal content, where markdown syntax has no meaning
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeSpan {
+    /// Inline code, fenced block or math
+    pub kind: CodeKind,
+    /// Fence info string (e.g. `rust`), for fenced blocks
+    pub language: Option<String>,
+    /// Position of the opening delimiter
+    pub start: SourcePosition,
+    /// Byte offset just past the closing delimiter
+    pub end_offset: usize,
+}
+
+impl CodeSpan {
+    /// Whether the span contains a byte offset
+    pub fn contains(&self, offset: usize) -> bool {
+        offset >= self.start.offset && offset < self.end_offset
+    }
+}
+
+/// A Dataview inline field (`key:: value`, `[key:: value]` or `(key:: value)`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct InlineField {
+    /// Field name as written
+    pub key: String,
+    /// Raw value, trimmed
+    pub value: String,
+    /// Position of the key
+    pub position: SourcePosition,
+}
+
+/// Checkbox state of a task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskStatus {
+    /// `- [ ]`
+    Open,
+    /// `- [/]`
+    InProgress,
+    /// `- [x]`
+    Done,
+    /// `- [-]`
+    Cancelled,
+}
+
+impl TaskStatus {
+    /// Status for a checkbox character; unknown characters count as open
+    pub fn from_marker(marker: char) -> Self {
+        match marker {
+            'x' | 'X' => Self::Done,
+            '-' => Self::Cancelled,
+            '/' => Self::InProgress,
+            _ => Self::Open,
+        }
+    }
+
+    /// Whether the task still needs doing
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Open | Self::InProgress)
+    }
+}
+
+/// A task checkbox, with Obsidian Tasks metadata split out
+///
+/// `- [ ] verify figure 3 #cite ⏫ 📅 2024-05-01 ^fig3` has the text
+/// `verify figure 3`, tag `cite`, high priority, a due date and block ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskItem {
+    /// Checkbox state
+    pub status: TaskStatus,
+    /// Task text without tags, emoji fields or a block ID
+    pub text: String,
+    /// Tags without `#` (e.g. `cite`, `stub/verify`)
+    pub tags: Vec<String>,
+    /// Emoji markers other than dates and priorities (e.g. `🔗`)
+    pub markers: Vec<String>,
+    /// Priority from `🔺` `⏫` `🔼` `🔽` `⏬`
+    pub priority: Option<Priority>,
+    /// Due date after `📅`, as written
+    pub due: Option<String>,
+    /// Trailing block ID, without the caret
+    pub block_id: Option<String>,
+    /// Position of the list marker
+    pub position: SourcePosition,
+    /// Byte offset just past the line break
+    pub end_offset: usize,
+}
+
+/// Kind of annotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationKind {
+    /// Obsidian comment (`%% text %%`)
+    Comment,
+    /// HTML comment (`<!-- text -->`)
+    HtmlComment,
+    /// Highlight (`==text==`)
+    Highlight,
+}
+
+impl AnnotationKind {
+    /// Name used in exports (`comment`, `html_comment`, `highlight`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Comment => "comment",
+            Self::HtmlComment => "html_comment",
+            Self::Highlight => "highlight",
+        }
+    }
+}
+
+impl std::fmt::Display for AnnotationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A comment or highlight
+///
+/// A leading `label:` names the annotation, as in Enhanced Annotations:
+/// `%% verify: check the date %%` has the label `verify` and the text
+/// `check the date`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    /// Comment or highlight
+    pub kind: AnnotationKind,
+    /// Label before the first `:`, if it is a single word
+    pub label: Option<String>,
+    /// Text without delimiters or label, trimmed
+    pub text: String,
+    /// Position of the opening delimiter
+    pub position: SourcePosition,
+    /// Byte offset just past the closing delimiter
+    pub end_offset: usize,
+}
+
+/// Syntax of a stub declared in the body
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StubMarkerSyntax {
+    /// Callout (`> [!stub] expand: add examples`)
+    Callout,
+    /// Double braces (`{{expand: add examples}}`)
+    Braces,
+}
+
+impl StubMarkerSyntax {
+    /// Name used in reports (`callout`, `braces`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Callout => "callout",
+            Self::Braces => "braces",
+        }
+    }
+}
+
+impl std::fmt::Display for StubMarkerSyntax {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A stub declared in the body
+///
+/// `> [!stub] expand: add deployment examples` and
+/// `{{expand: add deployment examples}}` both have the label `expand` and
+/// the text `add deployment examples`. A callout whose title is only the
+/// label takes its text from the callout body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StubMarker {
+    /// Callout or braces
+    pub syntax: StubMarkerSyntax,
+    /// Label before the first `:`, normally a stub type
+    pub label: String,
+    /// Text without the label or block ID, trimmed
+    pub text: String,
+    /// Block ID on the marker, or on its own line right after a callout
+    pub block_id: Option<String>,
+    /// Position of the callout's first `>` or the opening braces
+    pub position: SourcePosition,
+    /// Byte offset just past the closing braces or the callout's last line
+    pub end_offset: usize,
+}
+
+/// Structure of a document body
+///
+/// All positions are relative to the whole document, frontmatter included.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarkdownBody {
+    /// Top-level sections, each with its nested sections
+    pub sections: Vec<Section>,
+    /// Block IDs outside code and math
+    pub block_ids: Vec<BlockId>,
+    /// Wikilinks, excluding embeds
+    pub wikilinks: Vec<WikiLink>,
+    /// Embeds (`![[...]]`)
+    pub embeds: Vec<WikiLink>,
+    /// Footnote references and definitions
+    pub footnotes: Vec<Footnote>,
+    /// Inline code, fenced blocks and math
+    pub code: Vec<CodeSpan>,
+    /// Dataview inline fields outside code
+    pub inline_fields: Vec<InlineField>,
+    /// Task checkboxes outside code
+    pub tasks: Vec<TaskItem>,
+    /// Comments and highlights outside code
+    pub annotations: Vec<Annotation>,
+    /// Stub callouts and brace markers outside code
+    pub stub_markers: Vec<StubMarker>,
+}
+
+impl MarkdownBody {
+    /// All headings in document order
+    pub fn headings(&self) -> Vec<&Heading> {
+        fn collect<'a>(sections: &'a [Section], out: &mut Vec<&'a Heading>) {
+            for section in sections {
+                out.push(&section.heading);
+                collect(&section.children, out);
+            }
+        }
+
+        let mut headings = Vec::new();
+        collect(&self.sections, &mut headings);
+        headings
+    }
+
+    /// Headings enclosing a byte offset, outermost first
+    pub fn section_path(&self, offset: usize) -> Vec<&Heading> {
+        let mut path = Vec::new();
+        let mut sections = &self.sections;
+        while let Some(section) = sections.iter().find(|s| s.contains(offset)) {
+            path.push(&section.heading);
+            sections = &section.children;
+        }
+        path
+    }
+
+    /// Find a block ID
+    pub fn block(&self, id: &str) -> Option<&BlockId> {
+        self.block_ids.iter().find(|b| b.id == id)
+    }
+
+    /// Whether a byte offset falls inside code or math
+    pub fn is_code(&self, offset: usize) -> bool {
+        self.code.iter().any(|c| c.contains(offset))
+    }
+}
+
+/// Markdown body parser trait
+///
+/// This is an outbound port: adapters turn the document body into a
+/// [`MarkdownBody`]. Parsing never fails; unrecognized syntax is plain text.
+pub trait BodyParser: Send + Sync {
+    /// Parse the body of a document
+    ///
+    /// # Arguments
+    /// * `content` - Raw document content
+    /// * `body_offset` - Byte offset where the body starts (after frontmatter)
+    fn parse_body(&self, content: &str, body_offset: usize) -> MarkdownBody;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heading(level: u8, text: &str, offset: usize) -> Heading {
+        Heading {
+            level,
+            text: text.to_string(),
+            position: SourcePosition::new(1, 1, offset),
+        }
+    }
+
+    #[test]
+    fn test_section_path() {
+        let body = MarkdownBody {
+            sections: vec![Section {
+                heading: heading(1, "Top", 0),
+                end_offset: 100,
+                children: vec![
+                    Section {
+                        heading: heading(2, "First", 10),
+                        end_offset: 50,
+                        children: vec![],
+                    },
+                    Section {
+                        heading: heading(2, "Second", 50),
+                        end_offset: 100,
+                        children: vec![],
+                    },
+                ],
+            }],
+            ..Default::default()
+        };
+
+        let path: Vec<_> = body.section_path(60).iter().map(|h| h.text.as_str()).collect();
+        assert_eq!(path, vec!["Top", "Second"]);
+        assert_eq!(body.headings().len(), 3);
+        assert!(body.section_path(150).is_empty());
+    }
+}
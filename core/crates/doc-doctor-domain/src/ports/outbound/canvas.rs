@@ -0,0 +1,115 @@
+//! Canvas Port
+//!
+//! Model of an Obsidian canvas (a `.canvas` file in the JSON Canvas
+//! format): cards laid out on a board and the edges between them. Text
+//! cards are analyzed like small notes; file cards reference notes in the
+//! vault.
+
+use std::path::Path;
+
+use super::parser::ParseError;
+
+/// File extension of canvas documents
+pub const CANVAS_EXTENSION: &str = "canvas";
+
+/// Whether a path names a canvas document
+pub fn is_canvas(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(CANVAS_EXTENSION))
+}
+
+/// Content of a canvas card
+#[derive(Debug, Clone, PartialEq)]
+pub enum CanvasNodeKind {
+    /// Markdown text written on the card
+    Text {
+        /// Card text
+        text: String,
+    },
+    /// A vault file shown on the card
+    File {
+        /// Vault-relative path (e.g. `Notes/Plan.md`)
+        file: String,
+        /// Heading or block within the file (e.g. `#Goals`)
+        subpath: Option<String>,
+    },
+    /// A web page
+    Link {
+        /// Page URL
+        url: String,
+    },
+    /// A labeled frame around other cards
+    Group {
+        /// Group label
+        label: Option<String>,
+    },
+}
+
+/// A card on a canvas
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanvasNode {
+    /// Node identifier, unique within the canvas
+    pub id: String,
+    /// Card content
+    pub kind: CanvasNodeKind,
+}
+
+/// An arrow between two cards
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanvasEdge {
+    /// Edge identifier
+    pub id: String,
+    /// Node the edge starts from
+    pub from_node: String,
+    /// Node the edge points to
+    pub to_node: String,
+    /// Text on the edge
+    pub label: Option<String>,
+}
+
+/// A parsed canvas
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Canvas {
+    /// Cards, in file order
+    pub nodes: Vec<CanvasNode>,
+    /// Edges, in file order
+    pub edges: Vec<CanvasEdge>,
+}
+
+impl Canvas {
+    /// Find a node by ID
+    pub fn node(&self, id: &str) -> Option<&CanvasNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    /// Text cards as `(node id, text)`
+    pub fn text_cards(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.nodes.iter().filter_map(|n| match &n.kind {
+            CanvasNodeKind::Text { text } => Some((n.id.as_str(), text.as_str())),
+            _ => None,
+        })
+    }
+
+    /// Vault files referenced by file cards, without duplicates
+    pub fn files(&self) -> Vec<&str> {
+        let mut files: Vec<&str> = Vec::new();
+        for node in &self.nodes {
+            if let CanvasNodeKind::File { file, .. } = &node.kind {
+                if !files.contains(&file.as_str()) {
+                    files.push(file);
+                }
+            }
+        }
+        files
+    }
+}
+
+/// Port for reading canvas documents
+pub trait CanvasParser: Send + Sync {
+    /// Parse canvas content
+    ///
+    /// Content that is not a canvas (not a JSON object with a `nodes`
+    /// array) is an error.
+    fn parse_canvas(&self, content: &str) -> Result<Canvas, ParseError>;
+}
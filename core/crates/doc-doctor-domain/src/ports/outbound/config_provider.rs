@@ -140,3 +140,21 @@ impl Default for LayeredConfigProvider {
         Self::new()
     }
 }
+
+impl ConfigProvider for LayeredConfigProvider {
+    fn load(&self) -> Result<CalculationConfig, ConfigError> {
+        self.load_merged()
+    }
+
+    fn save(&self, _config: &CalculationConfig) -> Result<(), ConfigError> {
+        Err(ConfigError::new("Cannot save to a layered config provider; save to a specific layer"))
+    }
+
+    fn exists(&self) -> bool {
+        self.providers.iter().any(|p| p.exists())
+    }
+
+    fn source(&self) -> String {
+        self.loaded_sources().join(" + ")
+    }
+}
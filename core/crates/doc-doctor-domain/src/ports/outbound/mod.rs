@@ -3,14 +3,30 @@
 //! These ports define services that the domain needs from the outside world.
 //! Adapters implement these traits to provide actual functionality.
 
+mod body;
+mod canvas;
 mod config_provider;
 mod parser;
 mod repository;
 mod rules;
 mod schema;
+mod text;
 
+pub use body::{
+    Annotation, AnnotationKind, BlockId, BodyParser, CodeKind, CodeSpan, Footnote, FootnoteKind,
+    Heading, InlineField, MarkdownBody, Section, StubMarker, StubMarkerSyntax, TaskItem,
+    TaskStatus, WikiLink,
+};
+pub use canvas::{
+    is_canvas, Canvas, CanvasEdge, CanvasNode, CanvasNodeKind, CanvasParser, CANVAS_EXTENSION,
+};
 pub use config_provider::{ConfigError, ConfigProvider, DefaultConfigProvider, LayeredConfigProvider};
-pub use parser::{DocumentParser, DocumentWriter, MetadataSpan, ParseError, SerializeError, SourcePosition};
+pub use parser::{
+    apply_fixes, DocumentParser, DocumentWriter, LintFinding, LintRule, MetadataSpan,
+    ParseDiagnostic, ParseDiagnosticKind, ParseError, RecoveredParse, SerializeError,
+    SourcePosition, TextEdit,
+};
 pub use repository::{DocumentMetadata, DocumentRepository, RepositoryError, RepositoryErrorKind};
 pub use rules::{Action, ActionType, NoOpRuleEngine, RuleContext, RuleEngine, RuleError, RuleResult};
 pub use schema::{EmbeddedSchemaProvider, SchemaProvider};
+pub use text::{strip_bom, LineEnding, TextStyle, BOM};
@@ -5,10 +5,11 @@
 
 use crate::entities::L1Properties;
 use crate::errors::DomainError;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Source position in a document
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SourcePosition {
     /// Line number (1-indexed)
     pub line: usize,
@@ -23,6 +24,21 @@ impl SourcePosition {
     pub fn new(line: usize, column: usize, offset: usize) -> Self {
         Self { line, column, offset }
     }
+
+    /// Create a position from a byte offset into `content`
+    ///
+    /// Columns count characters, starting at 1.
+    pub fn from_offset(content: &str, offset: usize) -> Self {
+        let offset = offset.min(content.len());
+        let before = content.get(..offset).unwrap_or(content);
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            offset,
+        }
+    }
 }
 
 impl fmt::Display for SourcePosition {
@@ -97,6 +113,256 @@ impl fmt::Display for ParseError {
 
 impl std::error::Error for ParseError {}
 
+/// What happened to a frontmatter entry during error-recovering parsing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParseDiagnosticKind {
+    /// The entry was malformed and left out
+    Skipped,
+    /// The value was not recognized and replaced by a default
+    Coerced,
+}
+
+/// Diagnostic for a frontmatter entry that was not read as written
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParseDiagnostic {
+    /// What happened to the entry
+    pub kind: ParseDiagnosticKind,
+    /// Path of the entry (e.g. `refinement`, `stubs[2]`, `stubs[0].priority`)
+    pub field: String,
+    /// Error message
+    pub message: String,
+    /// Position of the entry in the document
+    pub position: Option<SourcePosition>,
+    /// Suggested fix (e.g. "Did you mean 'public'?")
+    pub suggestion: Option<String>,
+}
+
+impl ParseDiagnostic {
+    /// Create a diagnostic for a skipped entry
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            kind: ParseDiagnosticKind::Skipped,
+            field: field.into(),
+            message: message.into(),
+            position: None,
+            suggestion: None,
+        }
+    }
+
+    /// Create a diagnostic for a value replaced by a default
+    pub fn coerced(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            kind: ParseDiagnosticKind::Coerced,
+            ..Self::new(field, message)
+        }
+    }
+
+    /// Add position information
+    pub fn with_position(mut self, position: SourcePosition) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Add a suggested fix
+    pub fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    /// Check if the entry was left out entirely
+    pub fn is_skipped(&self) -> bool {
+        self.kind == ParseDiagnosticKind::Skipped
+    }
+}
+
+impl fmt::Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(pos) = &self.position {
+            write!(f, "[{}] ", pos)?;
+        }
+        write!(f, "{}: {}", self.field, self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " ({})", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of error-recovering parsing
+///
+/// Holds every field and stub that could be read; each entry that could
+/// not, and each value replaced by a default, becomes a diagnostic instead
+/// of failing the whole document.
+#[derive(Debug, Clone)]
+pub struct RecoveredParse {
+    /// Properties built from the valid entries
+    pub properties: L1Properties,
+    /// One diagnostic per skipped entry or coerced value
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+impl RecoveredParse {
+    /// Create a result with no skipped entries
+    pub fn complete(properties: L1Properties) -> Self {
+        Self {
+            properties,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Check if some entries were skipped
+    pub fn is_partial(&self) -> bool {
+        self.diagnostics.iter().any(ParseDiagnostic::is_skipped)
+    }
+}
+
+/// Strict-mode frontmatter lint rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintRule {
+    /// The same key appears twice in one mapping
+    DuplicateKey,
+    /// A `[[wikilink]]` value is unquoted and parses as a nested sequence
+    UnquotedWikilink,
+    /// A `^block-anchor` value is unquoted or malformed
+    BlockAnchor,
+    /// Indentation uses tab characters
+    TabIndentation,
+}
+
+impl LintRule {
+    /// Stable identifier (e.g. `duplicate-key`)
+    pub fn id(&self) -> &'static str {
+        match self {
+            LintRule::DuplicateKey => "duplicate-key",
+            LintRule::UnquotedWikilink => "unquoted-wikilink",
+            LintRule::BlockAnchor => "block-anchor",
+            LintRule::TabIndentation => "tab-indentation",
+        }
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+/// Replacement of a byte range of the document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// Byte offset of the range in the full document
+    pub offset: usize,
+    /// Length of the replaced range in bytes
+    pub length: usize,
+    /// Replacement text
+    pub replacement: String,
+}
+
+impl TextEdit {
+    /// Create a new edit
+    pub fn new(offset: usize, length: usize, replacement: impl Into<String>) -> Self {
+        Self {
+            offset,
+            length,
+            replacement: replacement.into(),
+        }
+    }
+}
+
+/// Finding from the strict frontmatter lint pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintFinding {
+    /// Rule that produced the finding
+    pub rule: LintRule,
+    /// Finding message
+    pub message: String,
+    /// Path of the affected entry, if known (e.g. `stubs[0].anchor`)
+    pub field: Option<String>,
+    /// Position in the document
+    pub position: Option<SourcePosition>,
+    /// Human-readable suggestion
+    pub suggestion: Option<String>,
+    /// Automatic fix, when one is safe
+    pub fix: Option<TextEdit>,
+}
+
+impl LintFinding {
+    /// Create a new finding
+    pub fn new(rule: LintRule, message: impl Into<String>) -> Self {
+        Self {
+            rule,
+            message: message.into(),
+            field: None,
+            position: None,
+            suggestion: None,
+            fix: None,
+        }
+    }
+
+    /// Add the affected entry path
+    pub fn with_field(mut self, field: impl Into<String>) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    /// Add position information
+    pub fn with_position(mut self, position: SourcePosition) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Add a suggestion
+    pub fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    /// Add an automatic fix
+    pub fn with_fix(mut self, fix: TextEdit) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(pos) = &self.position {
+            write!(f, "[{}] ", pos)?;
+        }
+        write!(f, "{}: {}", self.rule, self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " ({})", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Apply the automatic fixes of lint findings to a document
+///
+/// Fixes are applied back to front; a fix overlapping one already applied
+/// is skipped so the result stays well-formed. Returns the new content and
+/// the number of fixes applied.
+pub fn apply_fixes(content: &str, findings: &[LintFinding]) -> (String, usize) {
+    let mut edits: Vec<&TextEdit> = findings.iter().filter_map(|f| f.fix.as_ref()).collect();
+    edits.sort_by_key(|e| std::cmp::Reverse(e.offset));
+
+    let mut result = content.to_string();
+    let mut applied = 0;
+    let mut limit = content.len();
+
+    for edit in edits {
+        let end = edit.offset + edit.length;
+        if end > limit || !result.is_char_boundary(edit.offset) || !result.is_char_boundary(end) {
+            continue;
+        }
+        result.replace_range(edit.offset..end, &edit.replacement);
+        limit = edit.offset;
+        applied += 1;
+    }
+
+    (result, applied)
+}
+
 /// Error type for serialization operations
 #[derive(Debug, Clone)]
 pub struct SerializeError {
@@ -140,6 +406,29 @@ pub trait DocumentParser: Send + Sync {
     /// Parsed L1 properties or parse error with position
     fn parse(&self, content: &str) -> Result<L1Properties, ParseError>;
 
+    /// Parse document content, skipping malformed entries
+    ///
+    /// Valid fields and stubs are returned even when others are broken;
+    /// each skipped entry and each value replaced by a default is reported
+    /// as a diagnostic. Errors are reserved
+    /// for documents that cannot be read at all (e.g. no metadata block or
+    /// invalid syntax).
+    ///
+    /// The default implementation does not recover: it delegates to `parse`.
+    fn parse_recovering(&self, content: &str) -> Result<RecoveredParse, ParseError> {
+        self.parse(content).map(RecoveredParse::complete)
+    }
+
+    /// Check the raw metadata block for format hazards
+    ///
+    /// Runs on the text rather than the parsed value, so it also explains
+    /// documents that fail to parse (e.g. duplicate keys).
+    ///
+    /// The default implementation finds nothing.
+    fn lint(&self, _content: &str) -> Vec<LintFinding> {
+        Vec::new()
+    }
+
     /// Extract raw metadata without full parsing
     ///
     /// Useful for validation or quick inspection without
@@ -152,6 +441,14 @@ pub trait DocumentParser: Send + Sync {
     /// Metadata span if found, None otherwise
     fn extract_metadata(&self, content: &str) -> Option<MetadataSpan>;
 
+    /// Byte offset where the document body starts
+    ///
+    /// Points just past the metadata block and its closing delimiter line.
+    /// The default implementation treats the whole document as body.
+    fn body_offset(&self, _content: &str) -> usize {
+        0
+    }
+
     /// Get supported format identifier
     ///
     /// # Returns
@@ -27,6 +27,8 @@ pub enum RepositoryErrorKind {
     InvalidPath,
     /// I/O error
     IoError,
+    /// Content is not text in a supported encoding (e.g. a PDF)
+    InvalidEncoding,
     /// Other error
     Other,
 }
@@ -95,6 +97,20 @@ pub trait DocumentRepository: Send + Sync {
     /// Document content as string or error
     fn read(&self, path: &Path) -> Result<String, RepositoryError>;
 
+    /// Read the start of a document
+    ///
+    /// Used for very large files, where the metadata is all that is
+    /// needed. Adapters that can read partially stop near `max_bytes`, at
+    /// a line break; the default reads the whole document.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the document
+    /// * `max_bytes` - Approximate number of bytes to read
+    fn read_prefix(&self, path: &Path, max_bytes: u64) -> Result<String, RepositoryError> {
+        let _ = max_bytes;
+        self.read(path)
+    }
+
     /// Write document content to path
     ///
     /// # Arguments
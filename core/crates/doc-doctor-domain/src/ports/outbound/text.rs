@@ -0,0 +1,112 @@
+//! Text Style
+//!
+//! Byte-order mark and line-ending conventions of a document. Parsers skip
+//! a leading BOM when looking for metadata; writers generate new metadata
+//! with `\n` and restyle it to match the original so a Windows-authored
+//! note keeps its BOM and CRLF line endings after an update.
+
+/// UTF-8 byte-order mark, as decoded into a `char`
+pub const BOM: char = '\u{feff}';
+
+/// Strip a leading byte-order mark
+pub fn strip_bom(content: &str) -> &str {
+    content.strip_prefix(BOM).unwrap_or(content)
+}
+
+/// Line-ending convention
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n` (Unix, macOS)
+    #[default]
+    Lf,
+    /// `\r\n` (Windows)
+    CrLf,
+}
+
+impl LineEnding {
+    /// Detect the convention from the first line break
+    ///
+    /// Documents without a line break are treated as LF.
+    pub fn detect(content: &str) -> Self {
+        match content.find('\n') {
+            Some(i) if content[..i].ends_with('\r') => Self::CrLf,
+            _ => Self::Lf,
+        }
+    }
+
+    /// The line break itself
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
+
+    /// Convert every line break in `text` to this convention
+    pub fn apply(&self, text: &str) -> String {
+        let normalized = text.replace("\r\n", "\n");
+        match self {
+            Self::Lf => normalized,
+            Self::CrLf => normalized.replace('\n', "\r\n"),
+        }
+    }
+}
+
+/// Byte-order mark and line endings of a document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextStyle {
+    /// Whether the document starts with a BOM
+    pub bom: bool,
+    /// Line-ending convention
+    pub line_ending: LineEnding,
+}
+
+impl TextStyle {
+    /// Detect the style of a document
+    pub fn detect(content: &str) -> Self {
+        Self {
+            bom: content.starts_with(BOM),
+            line_ending: LineEnding::detect(content),
+        }
+    }
+
+    /// Restyle generated text to match
+    ///
+    /// Line breaks are converted and, for the start of a document, the BOM
+    /// is restored.
+    pub fn apply(&self, text: &str) -> String {
+        let text = self.line_ending.apply(strip_bom(text));
+        if self.bom {
+            format!("{}{}", BOM, text)
+        } else {
+            text
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_style() {
+        let style = TextStyle::detect("\u{feff}---\r\ntitle: A\r\n---\r\n");
+        assert!(style.bom);
+        assert_eq!(style.line_ending, LineEnding::CrLf);
+
+        assert_eq!(TextStyle::detect("---\ntitle: A\n"), TextStyle::default());
+        assert_eq!(LineEnding::detect("no line break"), LineEnding::Lf);
+    }
+
+    #[test]
+    fn test_apply_style() {
+        let style = TextStyle {
+            bom: true,
+            line_ending: LineEnding::CrLf,
+        };
+        assert_eq!(style.apply("---\na: 1\n---\n"), "\u{feff}---\r\na: 1\r\n---\r\n");
+        // Already styled text is not doubled up
+        assert_eq!(style.apply(&style.apply("a\n")), "\u{feff}a\r\n");
+        assert_eq!(LineEnding::Lf.apply("a\r\nb\n"), "a\nb\n");
+    }
+}
@@ -0,0 +1,104 @@
+//! Text Encodings
+//!
+//! Documents are UTF-8, but notes written by older Windows editors are
+//! often Latin-1. Those are decoded as Windows-1252 (a superset that also
+//! covers smart quotes and the euro sign) and written back in the same
+//! encoding, so a round trip never changes bytes the user did not edit.
+
+use encoding_rs::WINDOWS_1252;
+
+/// Encoding a document was read in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TextEncoding {
+    /// UTF-8, with or without a byte-order mark
+    Utf8,
+    /// Latin-1 / Windows-1252
+    Windows1252,
+}
+
+/// Why bytes could not be decoded as text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Undecodable {
+    /// UTF-16 text (e.g. "Unicode" in Windows Notepad)
+    Utf16,
+    /// Not text at all (e.g. a PDF or image)
+    Binary,
+}
+
+impl Undecodable {
+    /// Explanation for error messages
+    pub(crate) fn message(&self) -> &'static str {
+        match self {
+            Self::Utf16 => "File is UTF-16 text; re-save it as UTF-8",
+            Self::Binary => "File is binary, not text",
+        }
+    }
+}
+
+/// Decode file contents
+///
+/// Valid UTF-8 is taken as is. Anything else that looks like text is
+/// decoded as Windows-1252; NUL bytes or a high share of control
+/// characters mark the file as binary.
+pub(crate) fn decode(bytes: &[u8]) -> Result<(String, TextEncoding), Undecodable> {
+    if bytes.starts_with(&[0xff, 0xfe]) || bytes.starts_with(&[0xfe, 0xff]) {
+        return Err(Undecodable::Utf16);
+    }
+    if is_binary(bytes) {
+        return Err(Undecodable::Binary);
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Ok((text.to_string(), TextEncoding::Utf8)),
+        Err(_) => {
+            let (text, _) = WINDOWS_1252.decode_without_bom_handling(bytes);
+            Ok((text.into_owned(), TextEncoding::Windows1252))
+        }
+    }
+}
+
+/// Encode text for writing
+///
+/// Returns `None` when the text has characters the encoding cannot hold.
+pub(crate) fn encode(text: &str, encoding: TextEncoding) -> Option<Vec<u8>> {
+    match encoding {
+        TextEncoding::Utf8 => Some(text.as_bytes().to_vec()),
+        TextEncoding::Windows1252 => {
+            let (bytes, _, unmappable) = WINDOWS_1252.encode(text);
+            (!unmappable).then(|| bytes.into_owned())
+        }
+    }
+}
+
+/// NUL bytes, or more than one control character in ten
+fn is_binary(bytes: &[u8]) -> bool {
+    let sample = &bytes[..bytes.len().min(8192)];
+    let controls = sample
+        .iter()
+        .filter(|&&b| b < 0x20 && !matches!(b, b'\t' | b'\n' | b'\r' | 0x0c))
+        .count();
+    sample.contains(&0) || controls * 10 > sample.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_and_encode_latin1() {
+        let bytes = b"title: Caf\xe9 \x93quoted\x94\n";
+        let (text, encoding) = decode(bytes).unwrap();
+
+        assert_eq!(text, "title: Café \u{201c}quoted\u{201d}\n");
+        assert_eq!(encoding, TextEncoding::Windows1252);
+        assert_eq!(encode(&text, encoding).unwrap(), bytes);
+        assert!(encode("emoji \u{1f600}", encoding).is_none());
+    }
+
+    #[test]
+    fn test_undecodable() {
+        assert_eq!(decode(&[0x25, 0x50, 0x44, 0x46, 0x00, 0xff]), Err(Undecodable::Binary));
+        assert_eq!(decode(&[0xff, 0xfe, b'a', 0x00]), Err(Undecodable::Utf16));
+        assert_eq!(decode("naïve".as_bytes()).unwrap().1, TextEncoding::Utf8);
+    }
+}
@@ -0,0 +1,187 @@
+//! File Repository
+//!
+//! `DocumentRepository` over the local file system.
+//!
+//! Files are decoded by [`crate::encoding`]: UTF-8 as is, Latin-1 notes as
+//! Windows-1252. Writes keep a file's existing encoding.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use doc_doctor_domain::{DocumentMetadata, DocumentRepository, RepositoryError, RepositoryErrorKind};
+
+use crate::encoding::{decode, encode, TextEncoding};
+
+/// Local file system repository
+#[derive(Debug, Clone, Default)]
+pub struct FileRepository;
+
+impl FileRepository {
+    /// Create a new file repository
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Convert an I/O error into a repository error for `path`
+fn convert_error(path: &Path, err: io::Error) -> RepositoryError {
+    match err.kind() {
+        io::ErrorKind::NotFound => RepositoryError::not_found(path),
+        io::ErrorKind::PermissionDenied => RepositoryError::permission_denied(path),
+        _ => RepositoryError::new(RepositoryErrorKind::IoError, err.to_string()).with_path(path),
+    }
+}
+
+/// Decode bytes read from `path`
+fn decode_file(path: &Path, bytes: &[u8]) -> Result<(String, TextEncoding), RepositoryError> {
+    decode(bytes).map_err(|reason| {
+        RepositoryError::new(RepositoryErrorKind::InvalidEncoding, reason.message()).with_path(path)
+    })
+}
+
+/// Drop a partial last line, or an incomplete UTF-8 sequence when the
+/// prefix has no line break
+fn trim_prefix(bytes: &mut Vec<u8>) {
+    match bytes.iter().rposition(|&b| b == b'\n') {
+        Some(newline) => bytes.truncate(newline + 1),
+        None => {
+            if let Err(e) = std::str::from_utf8(bytes) {
+                if e.error_len().is_none() {
+                    bytes.truncate(e.valid_up_to());
+                }
+            }
+        }
+    }
+}
+
+fn unix_time(time: io::Result<std::time::SystemTime>) -> Option<i64> {
+    time.ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs() as i64)
+}
+
+impl DocumentRepository for FileRepository {
+    fn read(&self, path: &Path) -> Result<String, RepositoryError> {
+        let bytes = std::fs::read(path).map_err(|e| convert_error(path, e))?;
+        decode_file(path, &bytes).map(|(text, _)| text)
+    }
+
+    fn read_prefix(&self, path: &Path, max_bytes: u64) -> Result<String, RepositoryError> {
+        let file = File::open(path).map_err(|e| convert_error(path, e))?;
+        let mut bytes = Vec::new();
+        file.take(max_bytes.saturating_add(1))
+            .read_to_end(&mut bytes)
+            .map_err(|e| convert_error(path, e))?;
+
+        if bytes.len() as u64 > max_bytes {
+            bytes.truncate(max_bytes as usize);
+            trim_prefix(&mut bytes);
+        }
+        decode_file(path, &bytes).map(|(text, _)| text)
+    }
+
+    fn write(&self, path: &Path, content: &str) -> Result<(), RepositoryError> {
+        // Keep the encoding of an existing file
+        let encoding = std::fs::read(path)
+            .ok()
+            .and_then(|bytes| decode(&bytes).ok())
+            .map_or(TextEncoding::Utf8, |(_, encoding)| encoding);
+
+        let bytes = encode(content, encoding).ok_or_else(|| {
+            RepositoryError::new(
+                RepositoryErrorKind::InvalidEncoding,
+                "Content has characters that cannot be saved in this Latin-1 file",
+            )
+            .with_path(path)
+        })?;
+        std::fs::write(path, bytes).map_err(|e| convert_error(path, e))
+    }
+
+    fn list(&self, pattern: &str) -> Result<Vec<PathBuf>, RepositoryError> {
+        let paths = glob::glob(pattern)
+            .map_err(|e| RepositoryError::new(RepositoryErrorKind::InvalidPath, e.to_string()))?;
+        Ok(paths.filter_map(Result::ok).collect())
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn metadata(&self, path: &Path) -> Result<DocumentMetadata, RepositoryError> {
+        let metadata = std::fs::metadata(path).map_err(|e| convert_error(path, e))?;
+        Ok(DocumentMetadata {
+            size: metadata.len(),
+            modified: unix_time(metadata.modified()),
+            created: unix_time(metadata.created()),
+            is_directory: metadata.is_dir(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_write_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = FileRepository::new();
+        let path = dir.path().join("note.md");
+
+        repository.write(&path, "# Note\n").unwrap();
+        assert_eq!(repository.read(&path).unwrap(), "# Note\n");
+        assert!(repository.exists(&path));
+        assert_eq!(repository.metadata(&path).unwrap().size, 7);
+
+        let pattern = format!("{}/*.md", dir.path().display());
+        assert_eq!(repository.list(&pattern).unwrap(), vec![path]);
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = FileRepository::new();
+
+        let missing = repository.read(&dir.path().join("missing.md")).unwrap_err();
+        assert_eq!(missing.kind, RepositoryErrorKind::NotFound);
+
+        let binary = dir.path().join("scan.pdf");
+        std::fs::write(&binary, [0x25, 0x50, 0x44, 0x46, 0x00, 0xff, 0xfe]).unwrap();
+        let err = repository.read(&binary).unwrap_err();
+        assert_eq!(err.kind, RepositoryErrorKind::InvalidEncoding);
+    }
+
+    #[test]
+    fn test_latin1_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = FileRepository::new();
+        let path = dir.path().join("notes.md");
+        std::fs::write(&path, b"---\r\ntitle: Caf\xe9\r\n---\r\nBody\r\n").unwrap();
+
+        let content = repository.read(&path).unwrap();
+        assert_eq!(content, "---\r\ntitle: Café\r\n---\r\nBody\r\n");
+
+        repository.write(&path, &content.replace("Body", "Crème")).unwrap();
+        assert_eq!(
+            std::fs::read(&path).unwrap(),
+            b"---\r\ntitle: Caf\xe9\r\n---\r\nCr\xe8me\r\n"
+        );
+
+        let err = repository.write(&path, "emoji \u{1f600}").unwrap_err();
+        assert_eq!(err.kind, RepositoryErrorKind::InvalidEncoding);
+    }
+
+    #[test]
+    fn test_read_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = FileRepository::new();
+        let path = dir.path().join("large.md");
+        std::fs::write(&path, "---\ntitle: Large\n---\nline one\nline two\n").unwrap();
+
+        assert_eq!(repository.read_prefix(&path, 25).unwrap(), "---\ntitle: Large\n---\n");
+        assert_eq!(repository.read_prefix(&path, 1000).unwrap(), repository.read(&path).unwrap());
+    }
+}
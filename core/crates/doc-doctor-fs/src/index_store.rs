@@ -0,0 +1,91 @@
+//! Vault Index Store
+//!
+//! Keeps the vault index as JSON in `.doc-doctor/index.json` at the vault
+//! root. Writes go to a temporary file that is then renamed, so a crashed
+//! or concurrent run never leaves half an index behind.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use doc_doctor_application::{IndexStore, VaultIndex};
+use doc_doctor_domain::{RepositoryError, RepositoryErrorKind};
+
+/// Location of the index within a vault
+pub const INDEX_FILE: &str = ".doc-doctor/index.json";
+
+/// Vault index stored as a JSON file
+#[derive(Debug, Clone)]
+pub struct JsonIndexStore {
+    path: PathBuf,
+}
+
+impl JsonIndexStore {
+    /// Store the index at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Store the index of the vault at `root` in its `.doc-doctor` folder
+    pub fn for_vault(root: &Path) -> Self {
+        Self::new(root.join(INDEX_FILE))
+    }
+
+    /// Path of the index file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn error(&self, kind: RepositoryErrorKind, message: impl ToString) -> RepositoryError {
+        RepositoryError::new(kind, message.to_string()).with_path(&self.path)
+    }
+
+    fn io_error(&self, err: io::Error) -> RepositoryError {
+        self.error(RepositoryErrorKind::IoError, err)
+    }
+}
+
+impl IndexStore for JsonIndexStore {
+    fn load(&self) -> Result<Option<VaultIndex>, RepositoryError> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(self.io_error(e)),
+        };
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| self.error(RepositoryErrorKind::Other, format!("Invalid index: {}", e)))
+    }
+
+    fn save(&self, index: &VaultIndex) -> Result<(), RepositoryError> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| self.io_error(e))?;
+        }
+        let json = serde_json::to_string(index)
+            .map_err(|e| self.error(RepositoryErrorKind::Other, e))?;
+        let temp = self.path.with_extension(format!("json.{}.tmp", std::process::id()));
+        std::fs::write(&temp, json).map_err(|e| self.io_error(e))?;
+        std::fs::rename(&temp, &self.path).map_err(|e| self.io_error(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = JsonIndexStore::for_vault(dir.path());
+        assert!(store.load().unwrap().is_none());
+
+        let mut index = VaultIndex::new();
+        index.config_hash = "abc".to_string();
+        store.save(&index).unwrap();
+        assert!(store.path().ends_with(".doc-doctor/index.json"));
+        assert_eq!(store.load().unwrap().unwrap().config_hash, "abc");
+
+        std::fs::write(store.path(), "{").unwrap();
+        assert!(store.load().is_err());
+        assert!(VaultIndex::load(&store).entries.is_empty());
+    }
+}
@@ -0,0 +1,61 @@
+//! File System Adapter
+//!
+//! Implements the `DocumentRepository` port from the domain layer on top
+//! of `std::fs`, plus a sidecar decorator for files that cannot carry
+//! frontmatter (Org, AsciiDoc, plain text, PDF).
+//!
+//! Text is read as UTF-8 or, for older Windows-authored notes, Latin-1;
+//! writes keep the file's encoding.
+//!
+//! # Sidecar Metadata
+//!
+//! `SidecarRepository` keeps `L1Properties` next to the document instead of
+//! inside it, in one of two places:
+//!
+//! - `<file>.dd.yaml` — a sidecar for a single document
+//! - `.dd.yaml` — a per-folder file mapping file names to properties
+//!
+//! Reads return a synthesized markdown-style document (`---` frontmatter
+//! followed by the file's text), so parsers, stubs and health work
+//! unchanged. Writes split the frontmatter back into the sidecar.
+//!
+//! # Vault Index
+//!
+//! `JsonIndexStore` implements the application's `IndexStore` port, keeping
+//! the incremental vault index in `.doc-doctor/index.json`.
+//!
+//! # Vault Walk
+//!
+//! `FsLister` implements the application's `DirectoryLister` port, and
+//! `vault_documents` lists the documents of a vault walk, sidecar-backed
+//! files included.
+//!
+//! # Git
+//!
+//! `GitIntegration` runs git for commits, history and diffs of documents,
+//! and reads and writes staged blobs for pre-commit checks.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::path::Path;
+//! use doc_doctor_domain::DocumentRepository;
+//! use doc_doctor_fs::{FileRepository, SidecarRepository};
+//!
+//! let repository = SidecarRepository::new(FileRepository::new());
+//! let content = repository.read(Path::new("notes/meeting.org")).unwrap();
+//! assert!(content.starts_with("---\n"));
+//! ```
+
+mod encoding;
+mod file_repository;
+mod git;
+mod index_store;
+mod sidecar;
+mod walk;
+
+pub use file_repository::FileRepository;
+pub use git::{CommitInfo, CommitResult, DiffResult, GitError, GitIntegration, SnapshotResult};
+pub use index_store::{JsonIndexStore, INDEX_FILE};
+pub use sidecar::{sidecar_path, SidecarRepository, FOLDER_METADATA_FILE, SIDECAR_SUFFIX};
+pub use walk::{fs_walker, vault_documents, FsLister};
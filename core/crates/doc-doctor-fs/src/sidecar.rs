@@ -0,0 +1,415 @@
+//! Sidecar Metadata
+//!
+//! A `DocumentRepository` decorator that stores frontmatter outside the
+//! document, for formats that cannot hold it.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use serde_yaml::{Mapping, Value};
+
+use doc_doctor_domain::{DocumentMetadata, DocumentRepository, RepositoryError, RepositoryErrorKind};
+
+/// Suffix of a per-document sidecar (`report.pdf` → `report.pdf.dd.yaml`)
+pub const SIDECAR_SUFFIX: &str = ".dd.yaml";
+
+/// Per-folder metadata file, mapping file names to properties
+pub const FOLDER_METADATA_FILE: &str = ".dd.yaml";
+
+/// Extensions of documents that carry their own frontmatter
+const FRONTMATTER_EXTENSIONS: &[&str] = &["md", "markdown", "mdx"];
+
+/// Path of the sidecar file for a document
+pub fn sidecar_path(path: &Path) -> PathBuf {
+    let mut sidecar = path.as_os_str().to_owned();
+    sidecar.push(SIDECAR_SUFFIX);
+    PathBuf::from(sidecar)
+}
+
+/// Where a document's metadata lives
+enum Source {
+    /// `<file>.dd.yaml`
+    Sidecar(PathBuf),
+    /// An entry in the folder's `.dd.yaml`
+    Folder { file: PathBuf, key: String },
+}
+
+/// Repository decorator for sidecar metadata
+///
+/// A document's metadata comes from, in order: its `<file>.dd.yaml`, its
+/// entry in the folder's `.dd.yaml`, or its own frontmatter. Reads of
+/// sidecar-backed documents return `---` frontmatter followed by the file's
+/// text (empty for binary files); writes store the frontmatter back in the
+/// sidecar and only touch the file itself when its text changed.
+///
+/// Files that cannot carry frontmatter and have no metadata yet get a new
+/// `<file>.dd.yaml` on their first write. Sidecar files themselves are
+/// hidden from `list`.
+#[derive(Debug, Clone, Default)]
+pub struct SidecarRepository<R> {
+    inner: R,
+}
+
+impl<R: DocumentRepository> SidecarRepository<R> {
+    /// Wrap a repository
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// The wrapped repository
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Whether a document's metadata is stored outside it
+    pub fn has_sidecar(&self, path: &Path) -> bool {
+        matches!(self.source(path), Ok(Some(_)))
+    }
+
+    /// Documents under `dir` whose metadata lives in sidecar or folder files
+    pub fn documents(&self, dir: &Path) -> Result<Vec<PathBuf>, RepositoryError> {
+        let pattern = dir.join("**").join(format!("*{}", SIDECAR_SUFFIX));
+        self.documents_of(self.inner.list(&pattern.to_string_lossy())?)
+    }
+
+    /// Documents among `files` whose metadata lives in sidecar or folder
+    /// files that are among them too, e.g. the files of a vault walk
+    pub fn documents_among(&self, files: &[PathBuf]) -> Result<Vec<PathBuf>, RepositoryError> {
+        let metadata_files = files.iter().filter(|f| file_name(f).ends_with(SIDECAR_SUFFIX));
+        let mut documents = self.documents_of(metadata_files.cloned().collect())?;
+        let files: HashSet<&PathBuf> = files.iter().collect();
+        documents.retain(|d| files.contains(d));
+        Ok(documents)
+    }
+
+    /// Documents described by sidecar and folder metadata files
+    fn documents_of(&self, metadata_files: Vec<PathBuf>) -> Result<Vec<PathBuf>, RepositoryError> {
+        let mut documents = Vec::new();
+
+        for metadata_file in metadata_files {
+            let Some(folder) = metadata_file.parent() else {
+                continue;
+            };
+            let name = file_name(&metadata_file);
+
+            if name == FOLDER_METADATA_FILE {
+                let entries = self.read_folder(&metadata_file)?;
+                documents.extend(
+                    entries
+                        .keys()
+                        .filter_map(Value::as_str)
+                        .map(|key| folder.join(key)),
+                );
+            } else if let Some(document) = name.strip_suffix(SIDECAR_SUFFIX) {
+                documents.push(folder.join(document));
+            }
+        }
+
+        documents.retain(|d| self.inner.exists(d));
+        documents.sort();
+        documents.dedup();
+        Ok(documents)
+    }
+
+    fn source(&self, path: &Path) -> Result<Option<Source>, RepositoryError> {
+        let sidecar = sidecar_path(path);
+        if self.inner.exists(&sidecar) {
+            return Ok(Some(Source::Sidecar(sidecar)));
+        }
+
+        let file = folder_file(path);
+        if self.inner.exists(&file) {
+            let key = file_name(path);
+            if self.read_folder(&file)?.contains_key(key.as_str()) {
+                return Ok(Some(Source::Folder { file, key }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn read_folder(&self, file: &Path) -> Result<Mapping, RepositoryError> {
+        let content = self.inner.read(file)?;
+        if content.trim().is_empty() {
+            return Ok(Mapping::new());
+        }
+        serde_yaml::from_str(&content).map_err(|e| invalid_metadata(file, e))
+    }
+
+    fn read_metadata(&self, source: &Source) -> Result<String, RepositoryError> {
+        match source {
+            Source::Sidecar(sidecar) => self.inner.read(sidecar),
+            Source::Folder { file, key } => {
+                let entry = self.read_folder(file)?.remove(key.as_str()).unwrap_or(Value::Null);
+                match entry {
+                    Value::Null => Ok(String::new()),
+                    entry => serde_yaml::to_string(&entry).map_err(|e| invalid_metadata(file, e)),
+                }
+            }
+        }
+    }
+
+    fn write_metadata(&self, source: &Source, yaml: &str) -> Result<(), RepositoryError> {
+        match source {
+            Source::Sidecar(sidecar) => self.inner.write(sidecar, yaml),
+            Source::Folder { file, key } => {
+                let entry: Value = serde_yaml::from_str(yaml).map_err(|e| invalid_metadata(file, e))?;
+                let mut entries = self.read_folder(file)?;
+                entries.insert(Value::String(key.clone()), entry);
+                let content = serde_yaml::to_string(&entries).map_err(|e| invalid_metadata(file, e))?;
+                self.inner.write(file, &content)
+            }
+        }
+    }
+
+    /// The document's text (up to `limit` bytes), or `None` for binary files
+    fn read_body(&self, path: &Path, limit: Option<u64>) -> Result<Option<String>, RepositoryError> {
+        let text = match limit {
+            Some(max_bytes) => self.inner.read_prefix(path, max_bytes),
+            None => self.inner.read(path),
+        };
+        match text {
+            Ok(body) => Ok(Some(body)),
+            Err(e) if e.kind == RepositoryErrorKind::InvalidEncoding => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write the document's text if it changed; binary files are never rewritten
+    fn write_body(&self, path: &Path, body: &str) -> Result<(), RepositoryError> {
+        match self.read_body(path, None) {
+            Ok(Some(current)) if current == body => Ok(()),
+            Ok(None) => Ok(()),
+            Ok(Some(_)) => self.inner.write(path, body),
+            Err(e) if e.kind == RepositoryErrorKind::NotFound => self.inner.write(path, body),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Read a document, or its first `limit` bytes
+    ///
+    /// The limit applies to the file itself; sidecar metadata is always
+    /// read whole.
+    fn read_limited(&self, path: &Path, limit: Option<u64>) -> Result<String, RepositoryError> {
+        if is_metadata_file(path) {
+            return self.inner.read(path);
+        }
+
+        match self.source(path)? {
+            Some(source) => {
+                let yaml = self.read_metadata(&source)?;
+                let body = self.read_body(path, limit)?.unwrap_or_default();
+                Ok(synthesize(&yaml, &body))
+            }
+            None if !carries_frontmatter(path) => {
+                Ok(self.read_body(path, limit)?.unwrap_or_default())
+            }
+            None => match limit {
+                Some(max_bytes) => self.inner.read_prefix(path, max_bytes),
+                None => self.inner.read(path),
+            },
+        }
+    }
+}
+
+impl<R: DocumentRepository> DocumentRepository for SidecarRepository<R> {
+    fn read(&self, path: &Path) -> Result<String, RepositoryError> {
+        self.read_limited(path, None)
+    }
+
+    fn read_prefix(&self, path: &Path, max_bytes: u64) -> Result<String, RepositoryError> {
+        self.read_limited(path, Some(max_bytes))
+    }
+
+    fn write(&self, path: &Path, content: &str) -> Result<(), RepositoryError> {
+        if is_metadata_file(path) {
+            return self.inner.write(path, content);
+        }
+
+        let source = match self.source(path)? {
+            Some(source) => source,
+            None if carries_frontmatter(path) => return self.inner.write(path, content),
+            None => Source::Sidecar(sidecar_path(path)),
+        };
+
+        match split_frontmatter(content) {
+            Some((yaml, body)) => {
+                self.write_metadata(&source, yaml)?;
+                self.write_body(path, body)
+            }
+            None => self.write_body(path, content),
+        }
+    }
+
+    fn list(&self, pattern: &str) -> Result<Vec<PathBuf>, RepositoryError> {
+        let mut paths = self.inner.list(pattern)?;
+        paths.retain(|p| !is_metadata_file(p));
+        Ok(paths)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.inner.exists(path)
+    }
+
+    /// The modification time covers the document's sidecar or folder file
+    fn metadata(&self, path: &Path) -> Result<DocumentMetadata, RepositoryError> {
+        let mut metadata = self.inner.metadata(path)?;
+        for file in [sidecar_path(path), folder_file(path)] {
+            if let Ok(source) = self.inner.metadata(&file) {
+                metadata.modified = metadata.modified.max(source.modified);
+            }
+        }
+        Ok(metadata)
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn folder_file(path: &Path) -> PathBuf {
+    path.parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(FOLDER_METADATA_FILE)
+}
+
+fn is_metadata_file(path: &Path) -> bool {
+    file_name(path).ends_with(SIDECAR_SUFFIX)
+}
+
+fn carries_frontmatter(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| FRONTMATTER_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+fn invalid_metadata(path: &Path, err: serde_yaml::Error) -> RepositoryError {
+    RepositoryError::new(RepositoryErrorKind::Other, format!("Invalid metadata file: {}", err))
+        .with_path(path)
+}
+
+/// Join sidecar YAML and a body into a frontmatter document
+fn synthesize(yaml: &str, body: &str) -> String {
+    let newline = if yaml.is_empty() || yaml.ends_with('\n') { "" } else { "\n" };
+    format!("---\n{}{}---\n{}", yaml, newline, body)
+}
+
+/// Split a `---` frontmatter document into its YAML and body
+fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileRepository;
+
+    fn repository() -> (tempfile::TempDir, SidecarRepository<FileRepository>) {
+        (tempfile::tempdir().unwrap(), SidecarRepository::new(FileRepository::new()))
+    }
+
+    #[test]
+    fn test_read_and_write_sidecar() {
+        let (dir, repository) = repository();
+        let doc = dir.path().join("meeting.org");
+        std::fs::write(&doc, "* Agenda\n").unwrap();
+        std::fs::write(sidecar_path(&doc), "title: Meeting\nrefinement: 0.4\n").unwrap();
+
+        let content = repository.read(&doc).unwrap();
+        assert_eq!(content, "---\ntitle: Meeting\nrefinement: 0.4\n---\n* Agenda\n");
+
+        repository
+            .write(&doc, "---\ntitle: Meeting\nrefinement: 0.6\n---\n* Agenda\n")
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(sidecar_path(&doc)).unwrap(),
+            "title: Meeting\nrefinement: 0.6\n"
+        );
+        assert_eq!(std::fs::read_to_string(&doc).unwrap(), "* Agenda\n");
+    }
+
+    #[test]
+    fn test_binary_document() {
+        let (dir, repository) = repository();
+        let doc = dir.path().join("scan.pdf");
+        let bytes = [0x25, 0x50, 0x44, 0x46, 0x00, 0xff, 0xfe];
+        std::fs::write(&doc, bytes).unwrap();
+
+        // No metadata yet: the first write creates the sidecar
+        assert_eq!(repository.read(&doc).unwrap(), "");
+        repository.write(&doc, "---\ntitle: Scan\n---\n").unwrap();
+
+        assert!(repository.has_sidecar(&doc));
+        assert_eq!(repository.read(&doc).unwrap(), "---\ntitle: Scan\n---\n");
+        assert_eq!(std::fs::read(&doc).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_folder_metadata() {
+        let (dir, repository) = repository();
+        let doc = dir.path().join("guide.adoc");
+        std::fs::write(&doc, "= Guide\n").unwrap();
+        std::fs::write(
+            dir.path().join(FOLDER_METADATA_FILE),
+            "guide.adoc:\n  title: Guide\nother.txt:\n  title: Other\n",
+        )
+        .unwrap();
+
+        assert_eq!(repository.read(&doc).unwrap(), "---\ntitle: Guide\n---\n= Guide\n");
+
+        repository.write(&doc, "---\ntitle: Renamed\n---\n= Guide\n").unwrap();
+        let folder: Mapping =
+            serde_yaml::from_str(&std::fs::read_to_string(dir.path().join(FOLDER_METADATA_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(folder["guide.adoc"]["title"], Value::from("Renamed"));
+        assert_eq!(folder["other.txt"]["title"], Value::from("Other"));
+        assert!(!sidecar_path(&doc).exists());
+    }
+
+    #[test]
+    fn test_markdown_passes_through() {
+        let (dir, repository) = repository();
+        let doc = dir.path().join("note.md");
+
+        repository.write(&doc, "---\ntitle: Note\n---\n# Note\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&doc).unwrap(), "---\ntitle: Note\n---\n# Note\n");
+        assert!(!repository.has_sidecar(&doc));
+    }
+
+    #[test]
+    fn test_list_and_documents() {
+        let (dir, repository) = repository();
+        for name in ["a.txt", "b.org", "c.md"] {
+            std::fs::write(dir.path().join(name), "text\n").unwrap();
+        }
+        std::fs::write(sidecar_path(&dir.path().join("a.txt")), "title: A\n").unwrap();
+        std::fs::write(dir.path().join(FOLDER_METADATA_FILE), "b.org:\n  title: B\n").unwrap();
+
+        let listed = repository.list(&format!("{}/*", dir.path().display())).unwrap();
+        assert_eq!(listed.len(), 3);
+        assert!(listed.iter().all(|p| !is_metadata_file(p)));
+
+        let documents = repository.documents(dir.path()).unwrap();
+        assert_eq!(documents, vec![dir.path().join("a.txt"), dir.path().join("b.org")]);
+    }
+
+    #[test]
+    fn test_split_frontmatter() {
+        assert_eq!(split_frontmatter("---\na: 1\n---\nbody"), Some(("a: 1\n", "body")));
+        assert_eq!(split_frontmatter("---\n---\n"), Some(("", "")));
+        assert_eq!(split_frontmatter("# No frontmatter"), None);
+    }
+}
@@ -0,0 +1,138 @@
+//! Vault Walk
+//!
+//! `FsLister` implements the application's `DirectoryLister` port with
+//! `std::fs`, so a `VaultWalker` can walk vaults on disk.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use doc_doctor_application::{is_document_path, DirEntry, DirectoryLister, VaultWalker};
+use doc_doctor_domain::{RepositoryError, RepositoryErrorKind, WalkConfig};
+
+use crate::{FileRepository, SidecarRepository};
+
+/// Directory listing on the local file system
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FsLister;
+
+impl DirectoryLister for FsLister {
+    fn entries(&self, dir: &Path) -> Result<Vec<DirEntry>, RepositoryError> {
+        let listed = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+        let entries = fs::read_dir(listed).map_err(|e| {
+            RepositoryError::new(RepositoryErrorKind::IoError, e.to_string()).with_path(dir)
+        })?;
+        // Broken links and entries that vanished meanwhile are left out
+        Ok(entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| self.entry(&entry.path()))
+            .collect())
+    }
+
+    fn entry(&self, path: &Path) -> Option<DirEntry> {
+        let is_symlink = fs::symlink_metadata(path).ok()?.file_type().is_symlink();
+        // `.`, `..` and `a/..` have no name of their own
+        let name = match path.file_name() {
+            Some(name) => name.to_os_string(),
+            None => fs::canonicalize(path).ok()?.file_name().unwrap_or_default().to_os_string(),
+        };
+        Some(DirEntry {
+            name: name.to_string_lossy().into_owned(),
+            is_dir: fs::metadata(path).ok()?.is_dir(),
+            is_symlink,
+        })
+    }
+
+    fn read_to_string(&self, path: &Path) -> Option<String> {
+        fs::read_to_string(path).ok()
+    }
+
+    fn canonical(&self, path: &Path) -> Option<PathBuf> {
+        fs::canonicalize(path).ok()
+    }
+}
+
+/// Walker of vaults on disk
+pub fn fs_walker(config: &WalkConfig) -> VaultWalker {
+    VaultWalker::new(Arc::new(FsLister), config)
+}
+
+/// Documents of a walk of the vault at `root`: markdown files, canvases
+/// and files whose metadata lives in sidecars
+pub fn vault_documents(walker: &VaultWalker, root: &Path) -> Result<Vec<PathBuf>, RepositoryError> {
+    let files: Vec<PathBuf> = walker.walk(root)?.collect();
+    let repository = SidecarRepository::new(FileRepository::new());
+    let mut documents = repository.documents_among(&files)?;
+    documents.extend(files.into_iter().filter(|f| is_document_path(f)));
+    documents.sort();
+    documents.dedup();
+    Ok(documents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_documents() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for (path, content) in [
+            (".gitignore", "build/\n"),
+            (".obsidian/app.json", "{}"),
+            ("a.md", "# A"),
+            ("board.canvas", "{}"),
+            ("build/out.md", "# Out"),
+            ("report.pdf", "%PDF"),
+            ("report.pdf.dd.yaml", "title: Report\n"),
+            ("image.png", ""),
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(root, root.join("loop")).unwrap();
+
+        let documents = vault_documents(&fs_walker(&WalkConfig::default()), root).unwrap();
+        let names: Vec<&str> = documents
+            .iter()
+            .map(|d| d.strip_prefix(root).unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, ["a.md", "board.canvas", "report.pdf"]);
+
+        let config = WalkConfig {
+            follow_symlinks: true,
+            ..Default::default()
+        };
+        assert_eq!(vault_documents(&fs_walker(&config), root).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_walk_pattern_relative_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("a.md"), "# A").unwrap();
+        fs::write(root.join("sub/b.md"), "# B").unwrap();
+
+        let parent = root.join("sub/..");
+        let entry = FsLister.entry(&parent).unwrap();
+        assert!(entry.is_dir);
+        assert_eq!(entry.name, root.file_name().unwrap().to_string_lossy());
+        assert!(FsLister.entry(Path::new(".")).unwrap().is_dir);
+
+        let walker = fs_walker(&WalkConfig::default());
+        let mut names: Vec<PathBuf> = walker
+            .walk_pattern(parent.to_str().unwrap())
+            .unwrap()
+            .map(|p| p.strip_prefix(&parent).unwrap().to_path_buf())
+            .collect();
+        names.sort();
+        assert_eq!(names, [PathBuf::from("a.md"), PathBuf::from("sub/b.md")]);
+
+        let current = root.join("sub/.");
+        let walk = walker.walk_pattern(current.to_str().unwrap()).unwrap();
+        assert_eq!(walk.collect::<Vec<_>>(), [current.join("b.md")]);
+    }
+}
@@ -0,0 +1,173 @@
+//! Annotations
+//!
+//! Obsidian comments (`%% %%`), HTML comments (`<!-- -->`) and highlights
+//! (`==text==`). Comments may span lines, so the body is scanned as a whole
+//! once code and math are known; highlights stay within a line. Indices are
+//! byte offsets into the document.
+
+use doc_doctor_domain::{Annotation, AnnotationKind, SourcePosition};
+
+/// Find annotations in `content` from byte `from`, skipping `literals`
+///
+/// `literals` are the start and end of code and math spans. An unclosed
+/// comment runs to the end of the document, as Obsidian renders it.
+pub(crate) fn scan(content: &str, from: usize, literals: &[(usize, usize)]) -> Vec<Annotation> {
+    let bytes = content.as_bytes();
+    let mut literals = literals.to_vec();
+    literals.sort_unstable();
+    let mut literal = 0;
+    let mut lines = LineTracker::default();
+    let mut found = Vec::new();
+    let mut i = from;
+
+    while i < bytes.len() {
+        while literal < literals.len() && literals[literal].1 <= i {
+            literal += 1;
+        }
+        if literal < literals.len() && literals[literal].0 <= i {
+            i = literals[literal].1;
+            continue;
+        }
+
+        let rest = &bytes[i..];
+        let delimiters = if rest.starts_with(b"%%") {
+            Some((AnnotationKind::Comment, "%%", "%%"))
+        } else if rest.starts_with(b"<!--") {
+            Some((AnnotationKind::HtmlComment, "<!--", "-->"))
+        } else if rest.starts_with(b"==") {
+            Some((AnnotationKind::Highlight, "==", "=="))
+        } else {
+            None
+        };
+
+        let Some((kind, open, close)) = delimiters else {
+            // Escapes only matter before a delimiter; skip the escaped byte
+            i += if bytes[i] == b'\\' { 2 } else { 1 };
+            continue;
+        };
+
+        let inner_start = i + open.len();
+        let (inner_end, end) = match kind {
+            AnnotationKind::Highlight => match highlight_close(content, i) {
+                Some(close_at) => (close_at, close_at + close.len()),
+                None => {
+                    // "===" and longer runs are rules or setext underlines
+                    i += bytes[i..].iter().take_while(|&&b| b == b'=').count();
+                    continue;
+                }
+            },
+            _ => match content[inner_start..].find(close) {
+                Some(offset) => (inner_start + offset, inner_start + offset + close.len()),
+                None => (content.len(), content.len()),
+            },
+        };
+
+        let (label, text) = split_label(content[inner_start..inner_end].trim());
+        found.push(Annotation {
+            kind,
+            label: label.map(str::to_string),
+            text: text.to_string(),
+            position: lines.position(content, i),
+            end_offset: end,
+        });
+        i = end;
+    }
+
+    found
+}
+
+/// Index of the `==` closing a highlight that opens at `start`
+fn highlight_close(content: &str, start: usize) -> Option<usize> {
+    let inner_start = start + 2;
+    let line_end = content[inner_start..]
+        .find('\n')
+        .map_or(content.len(), |e| inner_start + e);
+    let line = &content[inner_start..line_end];
+    if line.starts_with(['=', ' ', '\t']) {
+        return None;
+    }
+    let close = line.find("==")?;
+    let inner = &line[..close];
+    (!inner.trim().is_empty() && !inner.ends_with([' ', '\t'])).then_some(inner_start + close)
+}
+
+/// Split a leading `label:` off annotation text
+///
+/// The label is a single word; `https://...` and `10:30` are plain text.
+pub(crate) fn split_label(text: &str) -> (Option<&str>, &str) {
+    let Some((label, rest)) = text.split_once(':') else {
+        return (None, text);
+    };
+    let valid = label.chars().next().is_some_and(char::is_alphabetic)
+        && label
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/'))
+        && !rest.starts_with("//");
+    if valid {
+        (Some(label), rest.trim())
+    } else {
+        (None, text)
+    }
+}
+
+/// Line and column for increasing offsets without rescanning the document
+#[derive(Default)]
+struct LineTracker {
+    offset: usize,
+    line: usize,
+    line_start: usize,
+}
+
+impl LineTracker {
+    fn position(&mut self, content: &str, offset: usize) -> SourcePosition {
+        let skipped = &content[self.offset..offset];
+        if let Some(last) = skipped.rfind('\n') {
+            self.line += skipped.matches('\n').count();
+            self.line_start = self.offset + last + 1;
+        }
+        self.offset = offset;
+        let column = content[self.line_start..offset].chars().count() + 1;
+        SourcePosition::new(self.line + 1, column, offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(annotations: &[Annotation]) -> Vec<(AnnotationKind, Option<&str>, &str)> {
+        annotations
+            .iter()
+            .map(|a| (a.kind, a.label.as_deref(), a.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_comments_and_highlights() {
+        let content = "Intro ==key claim== and %% verify: check the date %%\n<!--\nnote:\nspans lines\n-->\n";
+        let found = scan(content, 0, &[]);
+
+        assert_eq!(
+            summary(&found),
+            vec![
+                (AnnotationKind::Highlight, None, "key claim"),
+                (AnnotationKind::Comment, Some("verify"), "check the date"),
+                (AnnotationKind::HtmlComment, Some("note"), "spans lines"),
+            ]
+        );
+        assert_eq!(found[1].position, SourcePosition::new(1, 25, 24));
+        assert_eq!(found[2].position, SourcePosition::new(2, 1, 53));
+        assert_eq!(&content[found[2].end_offset - 3..found[2].end_offset], "-->");
+    }
+
+    #[test]
+    fn test_skipped_syntax() {
+        let content = "`%% code %%` a == b\n===\nSee https://x.y and \\==not==\n%% open";
+        let found = scan(content, 0, &[(0, 12)]);
+
+        assert_eq!(summary(&found), vec![(AnnotationKind::Comment, None, "open")]);
+        assert_eq!(found[0].end_offset, content.len());
+        assert_eq!(split_label("https://x.y"), (None, "https://x.y"));
+        assert_eq!(split_label("10:30 meeting"), (None, "10:30 meeting"));
+    }
+}
@@ -0,0 +1,232 @@
+//! Dataview and Tasks syntax
+//!
+//! Inline fields (`key:: value`, `[key:: value]`, `(key:: value)`) and
+//! task checkboxes with Obsidian Tasks emoji metadata
+//! (`- [ ] text #tag ⏫ 📅 2024-05-01 ^id`). Indices are byte offsets into
+//! the line.
+
+use doc_doctor_domain::{Priority, TaskStatus};
+
+/// An inline field found in a line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Field<'a> {
+    pub(crate) start: usize,
+    pub(crate) key: &'a str,
+    pub(crate) value: &'a str,
+}
+
+/// A task checkbox, before positions are attached
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Task {
+    pub(crate) status: TaskStatus,
+    pub(crate) text: String,
+    pub(crate) tags: Vec<String>,
+    pub(crate) markers: Vec<String>,
+    pub(crate) priority: Option<Priority>,
+    pub(crate) due: Option<String>,
+    pub(crate) block_id: Option<String>,
+}
+
+/// Tasks-plugin date signifiers; only the due date is kept
+const DUE: &str = "📅";
+const OTHER_DATES: &[&str] = &["⏳", "🛫", "➕", "✅", "❌"];
+const RECURRENCE: &str = "🔁";
+
+/// Inline fields in `line`, skipping those that start inside `literals`
+///
+/// A whole-line field may follow a list marker; headings never hold one.
+pub(crate) fn fields<'a>(line: &'a str, literals: &[(usize, usize)], heading: bool) -> Vec<Field<'a>> {
+    let in_literal = |i: usize| literals.iter().any(|&(start, end)| i >= start && i < end);
+    let mut found = Vec::new();
+
+    let body_start = line.len() - strip_list_marker(line.trim_start()).len();
+    if !heading && !line[body_start..].starts_with(['[', '(']) {
+        if let Some((key, value)) = split_field(&line[body_start..]) {
+            if !in_literal(body_start) {
+                found.push(Field { start: body_start, key, value });
+            }
+            return found;
+        }
+    }
+
+    for (open, close) in [('[', ']'), ('(', ')')] {
+        let mut from = 0;
+        while let Some(i) = line[from..].find(open).map(|i| i + from) {
+            from = i + 1;
+            let doubled = line[i + 1..].starts_with(open) || line[..i].ends_with(open);
+            if doubled || in_literal(i) {
+                continue;
+            }
+            let Some(end) = line[i + 1..].find(close).map(|e| e + i + 1) else {
+                break;
+            };
+            if let Some((key, value)) = split_field(&line[i + 1..end]) {
+                found.push(Field { start: i + 1, key, value });
+                from = end + 1;
+            }
+        }
+    }
+
+    found.sort_by_key(|f| f.start);
+    found
+}
+
+/// Split `key:: value`; keys are words, spaces, `-`, `_` and `/`
+fn split_field(text: &str) -> Option<(&str, &str)> {
+    let (key, value) = text.split_once("::")?;
+    let valid = !key.trim().is_empty()
+        && !key.starts_with(char::is_whitespace)
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_' | '/'));
+    let value = value.trim();
+    (valid && !value.is_empty()).then_some((key.trim(), value))
+}
+
+/// Remove a leading `-`, `*`, `+` or `1.` list marker
+fn strip_list_marker(trimmed: &str) -> &str {
+    if let Some(rest) = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+        .or_else(|| trimmed.strip_prefix("+ "))
+    {
+        return rest;
+    }
+    let digits = trimmed.bytes().take_while(u8::is_ascii_digit).count();
+    match trimmed[digits..].strip_prefix(". ").or_else(|| trimmed[digits..].strip_prefix(") ")) {
+        Some(rest) if digits > 0 => rest,
+        _ => trimmed,
+    }
+}
+
+/// Parse a task checkbox line (leading indentation already removed)
+pub(crate) fn task(trimmed: &str) -> Option<Task> {
+    let rest = strip_list_marker(trimmed);
+    if rest.len() == trimmed.len() {
+        return None;
+    }
+    let rest = rest.strip_prefix('[')?;
+    let marker = rest.chars().next()?;
+    let text = rest[marker.len_utf8()..].strip_prefix(']')?;
+    if !(text.is_empty() || text.starts_with(' ')) {
+        return None;
+    }
+
+    let mut task = Task {
+        status: TaskStatus::from_marker(marker),
+        text: String::new(),
+        tags: Vec::new(),
+        markers: Vec::new(),
+        priority: None,
+        due: None,
+        block_id: None,
+    };
+    let mut words: Vec<&str> = Vec::new();
+    let mut tokens = text.split_whitespace().peekable();
+
+    while let Some(token) = tokens.next() {
+        if let Some(priority) = priority(token) {
+            task.priority = Some(priority);
+        } else if let Some(signifier) = std::iter::once(DUE)
+            .chain(OTHER_DATES.iter().copied())
+            .find(|s| token.starts_with(s))
+        {
+            // The date may be attached ("📅2024-05-01") or the next token
+            let attached = &token[signifier.len()..];
+            let date = if attached.is_empty() { tokens.next() } else { Some(attached) };
+            if signifier == DUE {
+                task.due = date.map(str::to_string);
+            }
+        } else if token.starts_with(RECURRENCE) {
+            // "🔁 every week" runs to the next signifier, tag or block ID
+            while tokens.peek().is_some_and(|t| !is_metadata(t)) {
+                tokens.next();
+            }
+        } else if let Some(tag) = tag(token) {
+            task.tags.push(tag.to_string());
+        } else if let Some(id) = token.strip_prefix('^').filter(|_| tokens.peek().is_none()) {
+            task.block_id = Some(id.to_string());
+        } else if is_emoji(token) {
+            task.markers.push(token.to_string());
+        } else {
+            words.push(token);
+        }
+    }
+
+    task.text = words.join(" ");
+    Some(task)
+}
+
+fn priority(token: &str) -> Option<Priority> {
+    match token {
+        "🔺" => Some(Priority::Critical),
+        "⏫" => Some(Priority::High),
+        "🔼" => Some(Priority::Medium),
+        "🔽" | "⏬" => Some(Priority::Low),
+        _ => None,
+    }
+}
+
+/// `#tag` without the hash; `#` alone or `##` are not tags
+fn tag(token: &str) -> Option<&str> {
+    let tag = token.strip_prefix('#')?;
+    let valid = tag
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && tag.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/'));
+    valid.then_some(tag)
+}
+
+/// A token that starts with a pictographic symbol
+fn is_emoji(token: &str) -> bool {
+    token
+        .chars()
+        .next()
+        .is_some_and(|c| !c.is_alphanumeric() && u32::from(c) >= 0x2190)
+}
+
+fn is_metadata(token: &str) -> bool {
+    is_emoji(token) || tag(token).is_some() || token.starts_with('^')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_and_bracketed_fields() {
+        let line_field = fields("- refinement:: 0.7", &[], false);
+        assert_eq!(line_field, vec![Field { start: 2, key: "refinement", value: "0.7" }]);
+
+        let bracketed = fields("Text [audience:: public] and (form:: draft) `[no:: field]`", &[(44, 59)], false);
+        let keys: Vec<_> = bracketed.iter().map(|f| (f.key, f.value)).collect();
+        assert_eq!(keys, vec![("audience", "public"), ("form", "draft")]);
+
+        assert!(fields("## Title:: not a field", &[], true).is_empty());
+        assert!(fields("See [[Note::x]]", &[], false).is_empty());
+    }
+
+    #[test]
+    fn test_task_metadata() {
+        let task = task("- [ ] verify figure 3 #cite ⏫ 📅 2024-05-01 ✅ 2024-05-02 🔁 every week ^fig3").unwrap();
+        assert_eq!(task.status, TaskStatus::Open);
+        assert_eq!(task.text, "verify figure 3");
+        assert_eq!(task.tags, vec!["cite"]);
+        assert_eq!(task.priority, Some(Priority::High));
+        assert_eq!(task.due.as_deref(), Some("2024-05-01"));
+        assert_eq!(task.block_id.as_deref(), Some("fig3"));
+    }
+
+    #[test]
+    fn test_task_markers_and_status() {
+        let task = task("* [x] 🔗 add source for #1 claim 📅2024-06-01").unwrap();
+        assert_eq!(task.status, TaskStatus::Done);
+        assert_eq!(task.markers, vec!["🔗"]);
+        assert_eq!(task.text, "add source for #1 claim");
+        assert_eq!(task.due.as_deref(), Some("2024-06-01"));
+
+        assert!(self::task("- plain item").is_none());
+        assert!(self::task("- [link](url)").is_none());
+    }
+}
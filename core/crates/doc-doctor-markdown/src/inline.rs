@@ -0,0 +1,202 @@
+//! Inline scanning
+//!
+//! Finds code spans, math, wikilinks and footnote references within a
+//! single line. Indices are byte offsets into the line.
+
+/// An inline element
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Inline<'a> {
+    /// `` `code` ``
+    Code { start: usize, end: usize },
+    /// `$math$` or `$$math$$`
+    Math { start: usize, end: usize },
+    /// `[[inner]]` or `![[inner]]`
+    Link { start: usize, inner: &'a str, embed: bool },
+    /// `[^label]`
+    FootnoteRef { start: usize, label: &'a str },
+}
+
+impl Inline<'_> {
+    /// Start and end of a code or math element
+    pub(crate) fn literal_range(&self) -> Option<(usize, usize)> {
+        match *self {
+            Inline::Code { start, end } | Inline::Math { start, end } => Some((start, end)),
+            _ => None,
+        }
+    }
+}
+
+/// Scan `line` from byte index `from`
+///
+/// Backslash escapes are honoured. Nothing inside code or math is
+/// reported, so `` `[[x]]` `` and `$x^2$` yield only the literal.
+pub(crate) fn scan(line: &str, from: usize) -> Vec<Inline<'_>> {
+    let bytes = line.as_bytes();
+    let mut items = Vec::new();
+    let mut i = from;
+
+    while i < bytes.len() {
+        let rest = &line[i..];
+        match bytes[i] {
+            b'\\' => i += 1 + rest[1..].chars().next().map_or(0, char::len_utf8),
+            b'`' => {
+                let width = run_length(bytes, i, b'`');
+                match find_closing_run(bytes, i + width, b'`', width) {
+                    Some(close) => {
+                        items.push(Inline::Code { start: i, end: close + width });
+                        i = close + width;
+                    }
+                    None => i += width,
+                }
+            }
+            b'$' if rest.starts_with("$$") => match rest[2..].find("$$") {
+                Some(close) => {
+                    let end = i + 2 + close + 2;
+                    items.push(Inline::Math { start: i, end });
+                    i = end;
+                }
+                None => i += 2,
+            },
+            b'$' => match find_math_close(line, i) {
+                Some(close) => {
+                    items.push(Inline::Math { start: i, end: close + 1 });
+                    i = close + 1;
+                }
+                None => i += 1,
+            },
+            b'!' if rest.starts_with("![[") => match link(line, i + 1) {
+                Some((inner, end)) => {
+                    items.push(Inline::Link { start: i, inner, embed: true });
+                    i = end;
+                }
+                None => i += 3,
+            },
+            b'[' if rest.starts_with("[[") => match link(line, i) {
+                Some((inner, end)) => {
+                    items.push(Inline::Link { start: i, inner, embed: false });
+                    i = end;
+                }
+                None => i += 2,
+            },
+            b'[' if rest.starts_with("[^") => match rest.find(']') {
+                Some(close) if is_label(&rest[2..close]) => {
+                    items.push(Inline::FootnoteRef { start: i, label: &rest[2..close] });
+                    i += close + 1;
+                }
+                _ => i += 2,
+            },
+            _ => i += rest.chars().next().map_or(1, char::len_utf8),
+        }
+    }
+
+    items
+}
+
+fn run_length(bytes: &[u8], start: usize, marker: u8) -> usize {
+    bytes[start..].iter().take_while(|&&b| b == marker).count()
+}
+
+/// Find a run of exactly `width` markers at or after `from`
+fn find_closing_run(bytes: &[u8], from: usize, marker: u8, width: usize) -> Option<usize> {
+    let mut i = from;
+    while i < bytes.len() {
+        if bytes[i] == marker {
+            let run = run_length(bytes, i, marker);
+            if run == width {
+                return Some(i);
+            }
+            i += run;
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
+
+/// Closing `$` of inline math opened at `open`
+///
+/// Follows the pandoc rule so prices are not math: the opening `$` must be
+/// followed by a non-space, and the closing one preceded by a non-space
+/// and not followed by a digit.
+fn find_math_close(line: &str, open: usize) -> Option<usize> {
+    let after = line[open + 1..].chars().next()?;
+    if after.is_whitespace() || after == '$' {
+        return None;
+    }
+
+    let bytes = line.as_bytes();
+    let mut i = open + 2;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'$' => {
+                let before_space = line[..i].ends_with(char::is_whitespace);
+                let digit_after = bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+                if !before_space && !digit_after {
+                    return Some(i);
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Inner text and end index of the `[[...]]` starting at `open`
+fn link(line: &str, open: usize) -> Option<(&str, usize)> {
+    let close = line[open + 2..].find("]]")? + open + 2;
+    let inner = &line[open + 2..close];
+    (!inner.trim().is_empty()).then_some((inner, close + 2))
+}
+
+fn is_label(label: &str) -> bool {
+    !label.is_empty() && !label.contains(char::is_whitespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literals_hide_syntax() {
+        let items = scan("`[[not]]` and $x^2$ but [[Yes]]", 0);
+        assert_eq!(
+            items,
+            vec![
+                Inline::Code { start: 0, end: 9 },
+                Inline::Math { start: 14, end: 19 },
+                Inline::Link { start: 24, inner: "Yes", embed: false },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prices_are_not_math() {
+        assert!(scan("costs $5 or $10", 0).is_empty());
+    }
+
+    #[test]
+    fn test_embeds_and_footnotes() {
+        let items = scan("![[image.png]] text[^1] \\[[escaped]]", 0);
+        assert_eq!(
+            items,
+            vec![
+                Inline::Link { start: 0, inner: "image.png", embed: true },
+                Inline::FootnoteRef { start: 19, label: "1" },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multibyte_text() {
+        let items = scan("📅 café \\é [[Link]]", 0);
+        assert_eq!(items, vec![Inline::Link { start: 15, inner: "Link", embed: false }]);
+    }
+
+    #[test]
+    fn test_double_backtick_code() {
+        let items = scan("``a ` b`` after", 0);
+        assert_eq!(items, vec![Inline::Code { start: 0, end: 9 }]);
+    }
+}
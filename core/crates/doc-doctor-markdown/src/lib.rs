@@ -0,0 +1,40 @@
+//! Markdown Body Parser
+//!
+//! Implements the `BodyParser` trait from the domain layer for Obsidian
+//! flavoured markdown.
+//!
+//! # Features
+//!
+//! - ATX headings, nested into a section tree
+//! - Block IDs (`text ^block-id`)
+//! - Wikilinks (`[[Note#Heading|Alias]]`) and embeds (`![[Note]]`)
+//! - Footnote references and definitions
+//! - Dataview inline fields (`key:: value`) and task checkboxes with
+//!   Obsidian Tasks metadata (`- [ ] text ⏫ 📅 2024-05-01`)
+//! - Annotations: `%% comments %%`, `<!-- comments -->` and `==highlights==`,
+//!   with an optional `label:`
+//! - Stub markers: `> [!stub] type: text` callouts and `{{type: text}}`
+//! - Inline code, fenced code blocks and math, whose contents are never
+//!   mistaken for the syntax above
+//!
+//! # Example
+//!
+//! ```rust
+//! use doc_doctor_domain::BodyParser;
+//! use doc_doctor_markdown::MarkdownParser;
+//!
+//! let content = "# Notes\n\nSee [[Other]] ^intro\n\n```\nnot ^an-anchor\n```\n";
+//! let body = MarkdownParser::new().parse_body(content, 0);
+//!
+//! assert_eq!(body.headings()[0].text, "Notes");
+//! assert_eq!(body.block_ids.len(), 1);
+//! assert_eq!(body.wikilinks[0].target, "Other");
+//! ```
+
+mod annotations;
+mod dataview;
+mod inline;
+mod markers;
+mod parser;
+
+pub use parser::MarkdownParser;
@@ -0,0 +1,269 @@
+//! Stub Markers
+//!
+//! Stubs declared in the body: `> [!stub] type: text` callouts and
+//! `{{type: text}}` markers. Callouts are whole blockquotes, so the body is
+//! scanned by line once code, math and block IDs are known. Indices are
+//! byte offsets into the document.
+
+use doc_doctor_domain::{BlockId, SourcePosition, StubMarker, StubMarkerSyntax};
+
+use crate::annotations::split_label;
+
+/// A line of the document, without its line break
+#[derive(Clone, Copy)]
+struct Line {
+    number: usize,
+    start: usize,
+    end: usize,
+    next: usize,
+}
+
+/// Find stub markers in `content` from byte `from`
+///
+/// `literals` are the start and end of code and math spans; markers
+/// starting inside them are skipped.
+pub(crate) fn scan(
+    content: &str,
+    from: usize,
+    literals: &[(usize, usize)],
+    block_ids: &[BlockId],
+) -> Vec<StubMarker> {
+    let lines = lines(content, from);
+    let in_literal = |offset: usize| literals.iter().any(|&(s, e)| s <= offset && offset < e);
+    let mut found = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        if in_literal(line.start) {
+            i += 1;
+            continue;
+        }
+
+        if let Some(title) = callout_title(content, line) {
+            let mut last = i;
+            while last + 1 < lines.len()
+                && !in_literal(lines[last + 1].start)
+                && quote_text(content, lines[last + 1]).is_some()
+            {
+                last += 1;
+            }
+            if let Some(marker) = callout(content, &lines[i..=last], title, block_ids) {
+                found.push(marker);
+            }
+            i = last + 1;
+            continue;
+        }
+
+        found.extend(braces(content, line, &in_literal, block_ids));
+        i += 1;
+    }
+
+    found
+}
+
+/// Build a callout marker from its lines; `title` is where its title starts
+fn callout(
+    content: &str,
+    lines: &[Line],
+    title: usize,
+    block_ids: &[BlockId],
+) -> Option<StubMarker> {
+    let first = lines[0];
+    let last = lines[lines.len() - 1];
+    let title = content[title..text_end(first, block_ids)].trim();
+
+    let (label, mut text) = match split_label(title) {
+        (Some(label), text) => (label, text.to_string()),
+        (None, label) if is_word(label) => (label, String::new()),
+        _ => return None,
+    };
+    // A bare label takes its text from the callout body
+    if text.is_empty() {
+        let body: Vec<&str> = lines[1..]
+            .iter()
+            .filter_map(|&line| {
+                let start = quote_text(content, line)?;
+                Some(content[start..text_end(line, block_ids)].trim())
+            })
+            .filter(|t| !t.is_empty())
+            .collect();
+        text = body.join(" ");
+    }
+
+    let within = block_ids
+        .iter()
+        .find(|b| first.start <= b.position.offset && b.position.offset < last.end);
+    let after = || {
+        let rest = &content[last.next..];
+        let at = last.next + indent(rest);
+        // The ID must stand alone, not end the next paragraph
+        let line = content[at..].lines().next().unwrap_or("").trim_end();
+        block_ids
+            .iter()
+            .find(|b| b.position.offset == at && line.len() == b.id.len() + 1)
+    };
+
+    Some(StubMarker {
+        syntax: StubMarkerSyntax::Callout,
+        label: label.to_string(),
+        text,
+        block_id: within.or_else(after).map(|b| b.id.clone()),
+        position: position(content, first, first.start + indent(&content[first.start..])),
+        end_offset: last.next,
+    })
+}
+
+/// `{{label: text}}` markers on a line
+fn braces(
+    content: &str,
+    line: Line,
+    in_literal: &dyn Fn(usize) -> bool,
+    block_ids: &[BlockId],
+) -> Vec<StubMarker> {
+    let text = &content[line.start..line.end];
+    let block_id = block_ids
+        .iter()
+        .find(|b| line.start <= b.position.offset && b.position.offset < line.end);
+    let mut found = Vec::new();
+    let mut from = 0;
+
+    while let Some(open) = text[from..].find("{{").map(|o| from + o) {
+        let Some(close) = text[open + 2..].find("}}").map(|c| open + 2 + c) else {
+            break;
+        };
+        from = close + 2;
+        if in_literal(line.start + open) {
+            continue;
+        }
+        if let (Some(label), text) = split_label(text[open + 2..close].trim()) {
+            if !text.is_empty() {
+                found.push(StubMarker {
+                    syntax: StubMarkerSyntax::Braces,
+                    label: label.to_string(),
+                    text: text.to_string(),
+                    block_id: block_id.map(|b| b.id.clone()),
+                    position: position(content, line, line.start + open),
+                    end_offset: line.start + close + 2,
+                });
+            }
+        }
+    }
+
+    found
+}
+
+/// Offset of the title of a `> [!stub]` callout opening on `line`
+fn callout_title(content: &str, line: Line) -> Option<usize> {
+    let start = quote_text(content, line)?;
+    let rest = &content[start..line.end];
+    let tag = rest.get(..7)?;
+    if !tag.eq_ignore_ascii_case("[!stub]") {
+        return None;
+    }
+    let fold = rest[7..].starts_with(['+', '-']) as usize;
+    Some(start + 7 + fold)
+}
+
+/// Offset of a quoted line's text, after its `>` markers
+fn quote_text(content: &str, line: Line) -> Option<usize> {
+    let text = &content[line.start..line.end];
+    let mut at = indent(text);
+    if !text[at..].starts_with('>') {
+        return None;
+    }
+    while text[at..].starts_with('>') {
+        at += 1;
+        at += text[at..].starts_with(' ') as usize;
+    }
+    Some(line.start + at)
+}
+
+/// End of a line's text, before a trailing block ID
+fn text_end(line: Line, block_ids: &[BlockId]) -> usize {
+    block_ids
+        .iter()
+        .map(|b| b.position.offset)
+        .find(|&offset| line.start <= offset && offset < line.end)
+        .unwrap_or(line.end)
+}
+
+/// Whether `text` is a single label-like word
+fn is_word(text: &str) -> bool {
+    text.chars().next().is_some_and(char::is_alphabetic)
+        && text.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/'))
+}
+
+fn indent(text: &str) -> usize {
+    text.len() - text.trim_start().len()
+}
+
+fn position(content: &str, line: Line, offset: usize) -> SourcePosition {
+    let column = content[line.start..offset].chars().count() + 1;
+    SourcePosition::new(line.number, column, offset)
+}
+
+/// Lines of `content` from byte `from`
+fn lines(content: &str, from: usize) -> Vec<Line> {
+    let mut number = content[..from].matches('\n').count() + 1;
+    let mut start = from;
+    let mut lines = Vec::new();
+    while start < content.len() {
+        let next = content[start..].find('\n').map_or(content.len(), |e| start + e + 1);
+        let end = content[start..next].trim_end_matches(['\n', '\r']).len() + start;
+        lines.push(Line {
+            number,
+            start,
+            end,
+            next,
+        });
+        number += 1;
+        start = next;
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(id: &str, content: &str) -> BlockId {
+        let offset = content.find(&format!("^{}", id)).unwrap();
+        BlockId {
+            id: id.to_string(),
+            position: SourcePosition::new(1, 1, offset),
+        }
+    }
+
+    #[test]
+    fn test_callouts() {
+        let content = "> [!stub] expand: add deployment examples ^deploy\n> more\n\n\
+            > [!STUB]- verify\n> Check the numbers\n> against Q3\n\n^q3\n\n> [!note] other\n";
+        let ids = [block("deploy", content), block("q3", content)];
+        let found = scan(content, 0, &[], &ids);
+
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].label, "expand");
+        assert_eq!(found[0].text, "add deployment examples");
+        assert_eq!(found[0].block_id.as_deref(), Some("deploy"));
+        assert_eq!(found[0].end_offset, content.find("\n\n").unwrap() + 1);
+        assert_eq!(found[1].label, "verify");
+        assert_eq!(found[1].text, "Check the numbers against Q3");
+        assert_eq!(found[1].block_id.as_deref(), Some("q3"));
+        assert_eq!(found[1].position.line, 4);
+    }
+
+    #[test]
+    fn test_braces() {
+        let content = "Intro {{expand: add examples}} and `{{cite: code}}` {{date}} ^intro\n";
+        let code = content.find('`').unwrap();
+        let found = scan(content, 0, &[(code, code + 16)], &[block("intro", content)]);
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].syntax, StubMarkerSyntax::Braces);
+        assert_eq!((found[0].label.as_str(), found[0].text.as_str()), ("expand", "add examples"));
+        assert_eq!(found[0].block_id.as_deref(), Some("intro"));
+        assert_eq!(found[0].position, SourcePosition::new(1, 7, 6));
+        assert_eq!(&content[found[0].end_offset - 2..found[0].end_offset], "}}");
+    }
+}
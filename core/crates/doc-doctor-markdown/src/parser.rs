@@ -0,0 +1,523 @@
+//! Markdown Parser Implementation
+//!
+//! Implements the `BodyParser` trait with a line scanner: block structure
+//! (fences, math blocks, headings, footnote definitions) is recognized per
+//! line, and everything else is handed to the inline scanner. Annotations
+//! are found last, since comments may span lines but never start in code.
+
+use doc_doctor_domain::{
+    strip_bom, BlockId, BodyParser, CodeKind, CodeSpan, Footnote, FootnoteKind, Heading,
+    InlineField, MarkdownBody, Section, SourcePosition, TaskItem, WikiLink,
+};
+
+use crate::annotations;
+use crate::dataview;
+use crate::inline::{self, Inline};
+use crate::markers;
+
+/// Markdown body parser
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownParser;
+
+impl MarkdownParser {
+    /// Create a new markdown parser
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl BodyParser for MarkdownParser {
+    fn parse_body(&self, content: &str, body_offset: usize) -> MarkdownBody {
+        // A document without frontmatter may still open with a BOM
+        let bom = content.len() - strip_bom(content).len();
+        let body_offset = body_offset.clamp(bom, content.len());
+        let mut scanner = Scanner {
+            content,
+            body_offset,
+            body: MarkdownBody::default(),
+            headings: Vec::new(),
+            block: None,
+        };
+
+        let first_line = content[..body_offset].matches('\n').count() + 1;
+        let mut offset = body_offset;
+        for (line_no, line) in (first_line..).zip(content[body_offset..].split_inclusive('\n')) {
+            let end = offset + line.len();
+            scanner.line(line.trim_end_matches(['\n', '\r']), line_no, offset, end);
+            offset += line.len();
+        }
+        scanner.finish()
+    }
+}
+
+/// An open fenced code or math block
+struct OpenBlock {
+    kind: CodeKind,
+    marker: u8,
+    width: usize,
+    language: Option<String>,
+    start: SourcePosition,
+}
+
+struct Scanner<'a> {
+    content: &'a str,
+    body_offset: usize,
+    body: MarkdownBody,
+    headings: Vec<Heading>,
+    block: Option<OpenBlock>,
+}
+
+impl Scanner<'_> {
+    fn position(&self, line_no: usize, line_start: usize, offset: usize) -> SourcePosition {
+        let column = self.content[line_start..offset].chars().count() + 1;
+        SourcePosition::new(line_no, column, offset)
+    }
+
+    fn line(&mut self, text: &str, line_no: usize, line_start: usize, line_end: usize) {
+        let trimmed = text.trim_start();
+        let indent = text.len() - trimmed.len();
+
+        if let Some(block) = &self.block {
+            if closes(block, trimmed) {
+                let block = self.block.take().expect("block is open");
+                self.body.code.push(CodeSpan {
+                    kind: block.kind,
+                    language: block.language,
+                    start: block.start,
+                    end_offset: line_start + text.len(),
+                });
+            }
+            return;
+        }
+
+        let position = self.position(line_no, line_start, line_start + indent);
+        if let Some(block) = open_block(trimmed, position) {
+            self.block = Some(block);
+            return;
+        }
+
+        let mut from = 0;
+        let mut is_heading = false;
+        if indent <= 3 {
+            if let Some((level, title)) = heading(trimmed) {
+                is_heading = true;
+                self.headings.push(Heading {
+                    level,
+                    text: title.to_string(),
+                    position,
+                });
+            } else if let Some((label, end)) = footnote_definition(trimmed) {
+                self.body.footnotes.push(Footnote {
+                    label: label.to_string(),
+                    kind: FootnoteKind::Definition,
+                    position,
+                });
+                from = indent + end;
+            }
+        }
+
+        let items = inline::scan(text, from);
+        self.block_id(text, &items, line_no, line_start);
+        self.dataview(text, &items, is_heading, line_no, (line_start, line_end));
+        for item in items {
+            self.inline(item, line_no, line_start);
+        }
+    }
+
+    /// Record a trailing `^block-id`, unless it sits inside code or math
+    fn block_id(&mut self, text: &str, items: &[Inline<'_>], line_no: usize, line_start: usize) {
+        let trimmed = text.trim_end();
+        let Some(caret) = trimmed.rfind('^') else {
+            return;
+        };
+        let id = &trimmed[caret + 1..];
+        let standalone = trimmed[..caret].is_empty() || trimmed[..caret].ends_with(char::is_whitespace);
+        let valid = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        let literal = items
+            .iter()
+            .filter_map(Inline::literal_range)
+            .any(|(start, end)| caret >= start && caret < end);
+
+        if standalone && valid && !literal {
+            let position = self.position(line_no, line_start, line_start + caret);
+            self.body.block_ids.push(BlockId {
+                id: id.to_string(),
+                position,
+            });
+        }
+    }
+
+    /// Record inline fields and a task checkbox
+    fn dataview(
+        &mut self,
+        text: &str,
+        items: &[Inline<'_>],
+        is_heading: bool,
+        line_no: usize,
+        (line_start, line_end): (usize, usize),
+    ) {
+        let literals: Vec<_> = items.iter().filter_map(Inline::literal_range).collect();
+        for field in dataview::fields(text, &literals, is_heading) {
+            self.body.inline_fields.push(InlineField {
+                key: field.key.to_string(),
+                value: field.value.to_string(),
+                position: self.position(line_no, line_start, line_start + field.start),
+            });
+        }
+
+        let trimmed = text.trim_start();
+        if let Some(task) = dataview::task(trimmed) {
+            let indent = text.len() - trimmed.len();
+            self.body.tasks.push(TaskItem {
+                status: task.status,
+                text: task.text,
+                tags: task.tags,
+                markers: task.markers,
+                priority: task.priority,
+                due: task.due,
+                block_id: task.block_id,
+                position: self.position(line_no, line_start, line_start + indent),
+                end_offset: line_end,
+            });
+        }
+    }
+
+    fn inline(&mut self, item: Inline<'_>, line_no: usize, line_start: usize) {
+        match item {
+            Inline::Code { start, end } | Inline::Math { start, end } => {
+                let kind = match item {
+                    Inline::Code { .. } => CodeKind::Inline,
+                    _ => CodeKind::Math,
+                };
+                self.body.code.push(CodeSpan {
+                    kind,
+                    language: None,
+                    start: self.position(line_no, line_start, line_start + start),
+                    end_offset: line_start + end,
+                });
+            }
+            Inline::Link { start, inner, embed } => {
+                let link = wikilink(inner, self.position(line_no, line_start, line_start + start));
+                if embed {
+                    self.body.embeds.push(link);
+                } else {
+                    self.body.wikilinks.push(link);
+                }
+            }
+            Inline::FootnoteRef { start, label } => {
+                self.body.footnotes.push(Footnote {
+                    label: label.to_string(),
+                    kind: FootnoteKind::Reference,
+                    position: self.position(line_no, line_start, line_start + start),
+                });
+            }
+        }
+    }
+
+    fn finish(mut self) -> MarkdownBody {
+        // An unclosed block runs to the end of the document
+        if let Some(block) = self.block.take() {
+            self.body.code.push(CodeSpan {
+                kind: block.kind,
+                language: block.language,
+                start: block.start,
+                end_offset: self.content.len(),
+            });
+        }
+        self.body.sections = sections(&self.headings, self.content.len());
+
+        let literals: Vec<_> = self.body.code.iter().map(|c| (c.start.offset, c.end_offset)).collect();
+        self.body.annotations = annotations::scan(self.content, self.body_offset, &literals);
+        self.body.stub_markers =
+            markers::scan(self.content, self.body_offset, &literals, &self.body.block_ids);
+        self.body
+    }
+}
+
+/// Open a fenced code block or `$$` math block
+///
+/// One-line `$$...$$` is left to the inline scanner.
+fn open_block(trimmed: &str, position: SourcePosition) -> Option<OpenBlock> {
+    let bytes = trimmed.as_bytes();
+    let marker = *bytes.first()?;
+
+    if marker == b'`' || marker == b'~' {
+        let width = bytes.iter().take_while(|&&b| b == marker).count();
+        let info = trimmed[width..].trim();
+        if width < 3 || (marker == b'`' && info.contains('`')) {
+            return None;
+        }
+        return Some(OpenBlock {
+            kind: CodeKind::Fenced,
+            marker,
+            width,
+            language: info.split_whitespace().next().map(str::to_string),
+            start: position,
+        });
+    }
+
+    let math = trimmed.strip_prefix("$$")?;
+    (!math.contains("$$")).then_some(OpenBlock {
+        kind: CodeKind::Math,
+        marker: b'$',
+        width: 2,
+        language: None,
+        start: position,
+    })
+}
+
+/// Whether `trimmed` closes the open block
+fn closes(block: &OpenBlock, trimmed: &str) -> bool {
+    match block.kind {
+        CodeKind::Math => trimmed.trim_end().ends_with("$$"),
+        _ => {
+            let trimmed = trimmed.trim_end();
+            trimmed.len() >= block.width && trimmed.bytes().all(|b| b == block.marker)
+        }
+    }
+}
+
+/// Level and text of an ATX heading
+fn heading(trimmed: &str) -> Option<(u8, &str)> {
+    let level = trimmed.bytes().take_while(|&b| b == b'#').count();
+    let rest = &trimmed[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+
+    let mut text = rest.trim();
+    // Optional closing sequence: "## Title ##"
+    let without_closing = text.trim_end_matches('#');
+    if without_closing.is_empty() || without_closing.ends_with([' ', '\t']) {
+        text = without_closing.trim_end();
+    }
+    // Trailing block ID: "## Title ^id"
+    if let Some((title, id)) = text.rsplit_once(" ^") {
+        if !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            text = title.trim_end();
+        }
+    }
+    Some((level as u8, text))
+}
+
+/// Label and end index of a `[^label]:` definition
+fn footnote_definition(trimmed: &str) -> Option<(&str, usize)> {
+    let rest = trimmed.strip_prefix("[^")?;
+    let close = rest.find("]:")?;
+    let label = &rest[..close];
+    (!label.is_empty() && !label.contains(char::is_whitespace)).then_some((label, close + 4))
+}
+
+/// Split `Note#Heading|Alias` or `Note#^block` into its parts
+fn wikilink(inner: &str, position: SourcePosition) -> WikiLink {
+    let (reference, alias) = match inner.split_once('|') {
+        Some((reference, alias)) => (reference, Some(alias.trim().to_string())),
+        None => (inner, None),
+    };
+    let (target, fragment) = match reference.split_once('#') {
+        Some((target, fragment)) => (target, Some(fragment.trim())),
+        None => (reference, None),
+    };
+    let (heading, block) = match fragment {
+        Some(f) => match f.strip_prefix('^') {
+            Some(block) => (None, Some(block.to_string())),
+            None => (Some(f.to_string()), None),
+        },
+        None => (None, None),
+    };
+
+    WikiLink {
+        target: target.trim().to_string(),
+        heading,
+        block,
+        alias,
+        position,
+    }
+}
+
+/// Nest headings into sections ending at the next heading of the same or higher level
+fn sections(headings: &[Heading], end: usize) -> Vec<Section> {
+    let mut tree = Vec::new();
+    let mut i = 0;
+
+    while i < headings.len() {
+        let level = headings[i].level;
+        let next = headings[i + 1..]
+            .iter()
+            .position(|h| h.level <= level)
+            .map_or(headings.len(), |p| i + 1 + p);
+        let end_offset = headings.get(next).map_or(end, |h| h.position.offset);
+
+        tree.push(Section {
+            heading: headings[i].clone(),
+            end_offset,
+            children: sections(&headings[i + 1..next], end_offset),
+        });
+        i = next;
+    }
+
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const DOCUMENT: &str = "---
+title: Test
+---
+# Guide
+
+Intro with [[Other Note#Setup|setup]] and ![[diagram.png]] ^intro
+
+## Install ##
+
+Run `cargo ^build` first.[^1]
+
+```rust
+let x = y ^ z; // ^not-an-anchor
+```
+
+$$
+e^{i\\pi} ^math
+$$
+
+### Details ^details
+
+[^1]: Footnote with $x^2$ ^fn-block
+
+# Appendix
+";
+
+    fn parse() -> MarkdownBody {
+        MarkdownParser::new().parse_body(DOCUMENT, 20)
+    }
+
+    #[test]
+    fn test_sections() {
+        let body = parse();
+        let titles: Vec<_> = body.headings().iter().map(|h| (h.level, h.text.as_str())).collect();
+        assert_eq!(
+            titles,
+            vec![(1, "Guide"), (2, "Install"), (3, "Details"), (1, "Appendix")]
+        );
+
+        assert_eq!(body.sections.len(), 2);
+        assert_eq!(body.sections[0].children[0].children[0].heading.text, "Details");
+        assert_eq!(body.headings()[0].position.line, 4);
+
+        let footnote = body.footnotes.iter().find(|f| f.kind == FootnoteKind::Definition).unwrap();
+        let path: Vec<_> = body
+            .section_path(footnote.position.offset)
+            .iter()
+            .map(|h| h.text.as_str())
+            .collect();
+        assert_eq!(path, vec!["Guide", "Install", "Details"]);
+    }
+
+    #[test]
+    fn test_block_ids_skip_code_and_math() {
+        let body = parse();
+        let ids: Vec<_> = body.block_ids.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(ids, vec!["intro", "details", "fn-block"]);
+
+        let intro = body.block("intro").unwrap();
+        assert_eq!(intro.position.line, 6);
+        assert_eq!(&DOCUMENT[intro.position.offset..intro.position.offset + 6], "^intro");
+    }
+
+    #[test]
+    fn test_links_and_footnotes() {
+        let body = parse();
+
+        assert_eq!(body.wikilinks.len(), 1);
+        let link = &body.wikilinks[0];
+        assert_eq!(link.target, "Other Note");
+        assert_eq!(link.heading.as_deref(), Some("Setup"));
+        assert_eq!(link.alias.as_deref(), Some("setup"));
+
+        assert_eq!(body.embeds[0].target, "diagram.png");
+
+        let kinds: Vec<_> = body.footnotes.iter().map(|f| (f.label.as_str(), f.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![("1", FootnoteKind::Reference), ("1", FootnoteKind::Definition)]
+        );
+    }
+
+    #[test]
+    fn test_code_spans() {
+        let body = parse();
+        let kinds: Vec<_> = body.code.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![CodeKind::Inline, CodeKind::Fenced, CodeKind::Math, CodeKind::Math]
+        );
+        assert_eq!(body.code[1].language.as_deref(), Some("rust"));
+
+        let fence = DOCUMENT.find("```rust").unwrap();
+        assert!(body.is_code(fence + 10));
+        assert!(!body.is_code(DOCUMENT.find("^intro").unwrap()));
+    }
+
+    #[test]
+    fn test_block_links() {
+        let body = MarkdownParser::new().parse_body("See [[#^intro]] and [[Note#^para|para]]\n", 0);
+        assert_eq!(body.wikilinks[0].target, "");
+        assert_eq!(body.wikilinks[0].block.as_deref(), Some("intro"));
+        assert_eq!(body.wikilinks[1].block.as_deref(), Some("para"));
+        assert!(body.block_ids.is_empty());
+    }
+
+    #[test]
+    fn test_unclosed_fence_runs_to_end() {
+        let content = "Text\n```\ncode ^hidden\n";
+        let body = MarkdownParser::new().parse_body(content, 0);
+        assert!(body.block_ids.is_empty());
+        assert_eq!(body.code[0].end_offset, content.len());
+    }
+
+    #[test]
+    fn test_bom_and_crlf() {
+        let content = "\u{feff}# Title\r\n\r\nText ^para\r\n";
+        let body = MarkdownParser::new().parse_body(content, 0);
+        assert_eq!(body.headings()[0].text, "Title");
+        assert_eq!(body.block_ids[0].id, "para");
+    }
+
+    #[test]
+    fn test_inline_fields_and_tasks() {
+        let content = "refinement:: 0.7\n\n- [ ] verify figure 3 📅 2024-05-01\n```\n- [ ] not a task\nkey:: no\n```\n";
+        let body = MarkdownParser::new().parse_body(content, 0);
+
+        assert_eq!(body.inline_fields.len(), 1);
+        assert_eq!(body.inline_fields[0].key, "refinement");
+        assert_eq!(body.tasks.len(), 1);
+        assert_eq!(body.tasks[0].text, "verify figure 3");
+        assert_eq!(body.tasks[0].position.line, 3);
+        assert_eq!(&content[body.tasks[0].position.offset..body.tasks[0].end_offset], "- [ ] verify figure 3 📅 2024-05-01\n");
+    }
+
+    #[test]
+    fn test_annotations() {
+        let content = "---\n%% in frontmatter %%\n---\nText ==key== %% todo: expand %%\n```\n%% code %%\n```\n";
+        let body = MarkdownParser::new().parse_body(content, 29);
+
+        assert_eq!(body.annotations.len(), 2);
+        assert_eq!(body.annotations[0].text, "key");
+        assert_eq!(body.annotations[1].label.as_deref(), Some("todo"));
+        assert_eq!(body.annotations[1].position.line, 4);
+    }
+
+    #[test]
+    fn test_stub_markers() {
+        let content = "Intro {{cite: source for 40%}}\n\n> [!stub] expand\n> Add examples\n\n^examples\n\n```\n{{fix: code}}\n```\n";
+        let body = MarkdownParser::new().parse_body(content, 0);
+
+        assert_eq!(body.stub_markers.len(), 2);
+        assert_eq!(body.stub_markers[0].label, "cite");
+        assert_eq!(body.stub_markers[1].text, "Add examples");
+        assert_eq!(body.stub_markers[1].block_id.as_deref(), Some("examples"));
+    }
+}
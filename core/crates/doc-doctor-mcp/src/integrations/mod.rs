@@ -11,6 +11,7 @@ pub mod smart_connections;
 #[derive(Debug, Clone)]
 pub struct IntegrationStatus {
     pub available: bool,
+    #[allow(dead_code)]
     pub plugin_name: &'static str,
     pub reminder: Option<String>,
 }
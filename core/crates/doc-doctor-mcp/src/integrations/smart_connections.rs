@@ -3,14 +3,19 @@
 //! Provides semantic search and RAG capabilities using Smart Connections embeddings.
 //! Smart Connections stores embeddings in `.smart-env/` or `.smart-connections/` folders.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
+use doc_doctor_application::VaultWalker;
+use doc_doctor_domain::{RepositoryError, WalkConfig};
+use doc_doctor_fs::fs_walker;
+
 use super::IntegrationStatus;
 
 /// Smart Connections integration for semantic search
 pub struct SmartConnectionsIntegration {
     /// Path to the vault
+    #[allow(dead_code)]
     vault_path: Option<PathBuf>,
     /// Path to the embeddings data
     env_path: Option<PathBuf>,
@@ -18,6 +23,8 @@ pub struct SmartConnectionsIntegration {
     embeddings: HashMap<String, Vec<f32>>,
     /// Whether embeddings are loaded
     loaded: bool,
+    /// Walker of the vault's markdown files
+    walker: VaultWalker,
 }
 
 impl SmartConnectionsIntegration {
@@ -28,6 +35,7 @@ impl SmartConnectionsIntegration {
             env_path: None,
             embeddings: HashMap::new(),
             loaded: false,
+            walker: fs_walker(&WalkConfig::default()),
         }
     }
 
@@ -39,9 +47,22 @@ impl SmartConnectionsIntegration {
             env_path,
             embeddings: HashMap::new(),
             loaded: false,
+            walker: fs_walker(&WalkConfig::default()),
         }
     }
 
+    /// Walk vaults with `walker`, e.g. one honoring the configured ignores
+    pub fn with_walker(mut self, walker: VaultWalker) -> Self {
+        self.walker = walker;
+        self
+    }
+
+    /// Markdown files of the vault, without ignored paths
+    fn markdown_files(&self, vault_path: &Path) -> Result<Vec<PathBuf>, RepositoryError> {
+        let walk = self.walker.walk(vault_path)?;
+        Ok(walk.filter(|p| p.extension().is_some_and(|e| e == "md")).collect())
+    }
+
     /// Check if Smart Connections is available
     pub fn check_availability(&self) -> IntegrationStatus {
         if self.env_path.is_some() {
@@ -164,6 +185,7 @@ impl SmartConnectionsIntegration {
     }
 
     /// Find semantically related documents
+    #[allow(dead_code)]
     pub fn find_related(
         &self,
         query_embedding: &[f32],
@@ -234,8 +256,8 @@ impl SmartConnectionsIntegration {
         let mut matches = Vec::new();
 
         // Scan markdown files in vault
-        if let Ok(entries) = glob::glob(&format!("{}/**/*.md", vault_path.display())) {
-            for entry in entries.flatten() {
+        if let Ok(entries) = self.markdown_files(vault_path) {
+            for entry in entries {
                 if let Ok(content) = std::fs::read_to_string(&entry) {
                     let doc_words: std::collections::HashSet<String> = content
                         .to_lowercase()
@@ -336,8 +358,8 @@ impl SmartConnectionsIntegration {
         let mut duplicates = Vec::new();
 
         // Scan markdown files
-        if let Ok(entries) = glob::glob(&format!("{}/**/*.md", vault_path.display())) {
-            for entry in entries.flatten() {
+        if let Ok(entries) = self.markdown_files(vault_path) {
+            for entry in entries {
                 if let Ok(doc_content) = std::fs::read_to_string(&entry) {
                     for para in &paragraphs {
                         // Simple containment check
@@ -390,8 +412,8 @@ impl SmartConnectionsIntegration {
             }
         }
 
-        // Deduplicate by path and keep highest similarity
-        let mut seen: HashMap<String, DuplicateMatch> = HashMap::new();
+        // Deduplicate by path and keep highest similarity, in path order
+        let mut seen: BTreeMap<String, DuplicateMatch> = BTreeMap::new();
         for dup in duplicates {
             if let Some(existing) = seen.get(&dup.path) {
                 if dup.similarity > existing.similarity {
@@ -406,11 +428,13 @@ impl SmartConnectionsIntegration {
     }
 
     /// Check if embeddings are loaded
+    #[allow(dead_code)]
     pub fn is_loaded(&self) -> bool {
         self.loaded
     }
 
     /// Get the number of embeddings
+    #[allow(dead_code)]
     pub fn embedding_count(&self) -> usize {
         self.embeddings.len()
     }
@@ -423,6 +447,7 @@ impl Default for SmartConnectionsIntegration {
 }
 
 /// Calculate cosine similarity between two vectors
+#[allow(dead_code)]
 fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     if a.len() != b.len() || a.is_empty() {
         return 0.0;
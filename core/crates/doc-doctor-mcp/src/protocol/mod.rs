@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Deserialize)]
 pub struct JsonRpcRequest {
     /// JSON-RPC version (always "2.0")
+    #[allow(dead_code)]
     pub jsonrpc: String,
     /// Request ID
     pub id: serde_json::Value,
@@ -98,8 +99,3 @@ impl McpTool {
     }
 }
 
-/// Marker trait for MCP messages
-pub trait McpMessage {}
-
-impl McpMessage for JsonRpcRequest {}
-impl McpMessage for JsonRpcResponse {}
@@ -13,17 +13,29 @@
 
 mod handlers;
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use doc_doctor_application::{
-    ApplicationSwitchboard, NewStub, StubFilter, StubUpdates, Switchboard,
+    annotations_markdown, ApplicationSwitchboard, DetectionRule, DetectorOptions,
+    DocumentAnnotations, IndexRefresh, NewStub, ParserRegistry, Query, StubFilter, StubUpdates,
+    Switchboard, SyncStatus, VaultIndex,
 };
-use doc_doctor_domain::EmbeddedSchemaProvider;
+use doc_doctor_canvas::JsonCanvasParser;
+use doc_doctor_config_yaml::layered_provider;
+use doc_doctor_domain::{
+    Annotation, DocumentRepository, EmbeddedSchemaProvider, ParseDiagnostic, WalkConfig,
+};
+use doc_doctor_fs::{
+    fs_walker, vault_documents, FileRepository, JsonIndexStore, SidecarRepository,
+};
+use doc_doctor_markdown::MarkdownParser;
+use doc_doctor_parser_json::JsonParser;
+use doc_doctor_parser_toml::TomlParser;
 use doc_doctor_parser_yaml::YamlParser;
 
-use crate::integrations::git::GitIntegration;
+use crate::integrations::git::{check_availability, GitIntegration};
 use crate::integrations::smart_connections::SmartConnectionsIntegration;
 use crate::protocol::McpTool;
 
@@ -35,8 +47,9 @@ type ToolHandler = Box<dyn Fn(serde_json::Value) -> Result<String, String> + Sen
 /// All tools delegate to the Application Switchboard for consistent behavior
 /// across CLI, MCP, and WASM interfaces.
 pub struct ToolRegistry {
-    tools: HashMap<String, (McpTool, ToolHandler)>,
-    switchboard: Arc<ApplicationSwitchboard<YamlParser, YamlParser, EmbeddedSchemaProvider>>,
+    tools: BTreeMap<String, (McpTool, ToolHandler)>,
+    switchboard: Arc<ApplicationSwitchboard<ParserRegistry, ParserRegistry, EmbeddedSchemaProvider>>,
+    repository: Arc<SidecarRepository<FileRepository>>,
     git: Arc<GitIntegration>,
     smart_connections: Arc<std::sync::RwLock<SmartConnectionsIntegration>>,
 }
@@ -44,17 +57,32 @@ pub struct ToolRegistry {
 impl ToolRegistry {
     /// Create a new tool registry with all tools registered
     pub fn new() -> Self {
-        let parser = Arc::new(YamlParser::new());
+        let parser = Arc::new(
+            ParserRegistry::new()
+                .register(YamlParser::new())
+                .register(TomlParser::new())
+                .register(JsonParser::new()),
+        );
         let writer = Arc::clone(&parser);
         let schema_provider = Arc::new(EmbeddedSchemaProvider);
 
-        let switchboard = Arc::new(ApplicationSwitchboard::new(parser, writer, schema_provider));
+        let switchboard = Arc::new(
+            ApplicationSwitchboard::new(parser, writer, schema_provider)
+                .with_config_provider(Arc::new(layered_provider()))
+                .with_body_parser(Arc::new(MarkdownParser::new()))
+                .with_canvas_parser(Arc::new(JsonCanvasParser::new())),
+        );
+        if let Err(e) = switchboard.reload_config() {
+            tracing::warn!("Using default configuration: {}", e);
+        }
+        let repository = Arc::new(SidecarRepository::new(FileRepository::new()));
         let git = Arc::new(GitIntegration::new());
         let smart_connections = Arc::new(std::sync::RwLock::new(SmartConnectionsIntegration::new()));
 
         let mut registry = Self {
-            tools: HashMap::new(),
+            tools: BTreeMap::new(),
             switchboard,
+            repository,
             git,
             smart_connections,
         };
@@ -72,6 +100,8 @@ impl ToolRegistry {
         self.register_validate_document();
         self.register_list_stubs();
         self.register_find_stub_anchors();
+        self.register_extract_annotations();
+        self.register_export_annotations();
 
         // Stub management tools
         self.register_add_stub();
@@ -79,6 +109,10 @@ impl ToolRegistry {
         self.register_update_stub();
         self.register_link_stub_anchor();
         self.register_unlink_stub_anchor();
+        self.register_import_annotations();
+        self.register_stub_sync_status();
+        self.register_sync_stub_markers();
+        self.register_suggest_stubs();
 
         // Calculation tools
         self.register_calculate_health();
@@ -90,6 +124,11 @@ impl ToolRegistry {
         self.register_get_audience_gates();
         self.register_get_schema();
 
+        // Configuration tools
+        self.register_get_config();
+        self.register_reload_config();
+        self.register_set_as_of();
+
         // Batch tools
         self.register_batch_analyze();
 
@@ -97,6 +136,7 @@ impl ToolRegistry {
         self.register_read_document();
         self.register_scan_vault();
         self.register_find_blocking_stubs();
+        self.register_query_vault();
 
         // Git integration tools
         self.register_snapshot_before_edit();
@@ -116,7 +156,7 @@ impl ToolRegistry {
         self.tools.insert(tool.name.clone(), (tool, handler));
     }
 
-    /// List all available tools
+    /// List all available tools, in name order
     pub fn list_tools(&self) -> Vec<&McpTool> {
         self.tools.values().map(|(tool, _)| tool).collect()
     }
@@ -195,6 +235,8 @@ impl ToolRegistry {
                 "properties": analysis.properties,
                 "dimensions": analysis.dimensions,
                 "warnings": analysis.warnings,
+                "partial": analysis.partial,
+                "diagnostics": diagnostics_json(&analysis.diagnostics),
             }))
             .map_err(|e| e.to_string())
         });
@@ -254,6 +296,7 @@ impl ToolRegistry {
                     serde_json::json!({
                         "path": w.path,
                         "message": w.message,
+                        "position": w.position.as_ref().map(|p| format!("line {}, col {}", p.line, p.column)),
                         "suggestion": w.suggestion
                     })
                 })
@@ -358,6 +401,119 @@ impl ToolRegistry {
         self.register(tool, handler);
     }
 
+    fn register_extract_annotations(&mut self) {
+        let switchboard = Arc::clone(&self.switchboard);
+
+        let tool = McpTool::new(
+            "extract_annotations",
+            "Extract comments (%% %%, <!-- -->) and ==highlights== from document content, with their labels and positions",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "content": {
+                        "type": "string",
+                        "description": "Markdown document content"
+                    }
+                },
+                "required": ["content"]
+            }),
+        );
+
+        let handler: ToolHandler = Box::new(move |args| {
+            let content = args
+                .get("content")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'content'")?;
+            let body = switchboard.parse_body(content).map_err(|e| e.to_string())?;
+            serde_json::to_string_pretty(&annotations_json(&body.annotations))
+                .map_err(|e| e.to_string())
+        });
+
+        self.register(tool, handler);
+    }
+
+    fn register_export_annotations(&mut self) {
+        let switchboard = Arc::clone(&self.switchboard);
+        let repository = Arc::clone(&self.repository);
+
+        let tool = McpTool::new(
+            "export_annotations",
+            "Export the comments and highlights of every document in a directory as Markdown or JSON",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Path to the vault directory"
+                    },
+                    "pattern": {
+                        "type": "string",
+                        "description": "Glob pattern for files (default: **/*.md)",
+                        "default": "**/*.md"
+                    },
+                    "format": {
+                        "type": "string",
+                        "enum": ["markdown", "json"],
+                        "description": "Export format (default: markdown)",
+                        "default": "markdown"
+                    }
+                },
+                "required": ["path"]
+            }),
+        );
+
+        let handler: ToolHandler = Box::new(move |args| {
+            let vault_path = args
+                .get("path")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'path'")?;
+            let pattern = args
+                .get("pattern")
+                .and_then(|v| v.as_str())
+                .unwrap_or("**/*.md");
+            let format = args
+                .get("format")
+                .and_then(|v| v.as_str())
+                .unwrap_or("markdown");
+
+            let root = std::path::Path::new(vault_path);
+            let paths = walk_vault(&switchboard.config().walk, root, Some(pattern))?;
+
+            let mut documents = Vec::new();
+            for path in &paths {
+                let Ok(content) = repository.read(path) else {
+                    continue;
+                };
+                let body = switchboard.parse_body(&content).map_err(|e| e.to_string())?;
+                documents.push(DocumentAnnotations {
+                    path: path.display().to_string(),
+                    annotations: body.annotations,
+                });
+            }
+
+            match format {
+                "markdown" => Ok(annotations_markdown(&documents)),
+                "json" => {
+                    let files: Vec<_> = documents
+                        .iter()
+                        .filter(|d| !d.annotations.is_empty())
+                        .map(|d| {
+                            serde_json::json!({
+                                "path": d.path,
+                                "annotations": annotations_json(&d.annotations),
+                            })
+                        })
+                        .collect();
+                    serde_json::to_string_pretty(&serde_json::json!({ "files": files }))
+                        .map_err(|e| e.to_string())
+                }
+                other => Err(format!("Unknown format '{}': expected markdown or json", other)),
+            }
+        });
+
+        self.register(tool, handler);
+    }
+
     // =========================================================================
     // Stub Management Tools (NEW)
     // =========================================================================
@@ -664,14 +820,228 @@ impl ToolRegistry {
         self.register(tool, handler);
     }
 
+    fn register_import_annotations(&mut self) {
+        let switchboard = Arc::clone(&self.switchboard);
+
+        let tool = McpTool::new(
+            "import_annotations",
+            "Turn labeled annotations (e.g. %% verify: check the date %%) into frontmatter stubs. Returns updated document content.",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "content": {
+                        "type": "string",
+                        "description": "Markdown document content"
+                    },
+                    "remove": {
+                        "type": "boolean",
+                        "description": "Delete converted comments and unwrap converted highlights",
+                        "default": false
+                    }
+                },
+                "required": ["content"]
+            }),
+        );
+
+        let handler: ToolHandler = Box::new(move |args| {
+            let content = args
+                .get("content")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'content'")?;
+            let remove = args
+                .get("remove")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+            let result = switchboard
+                .import_annotations(content, remove)
+                .map_err(|e| e.to_string())?;
+            serde_json::to_string_pretty(&serde_json::json!({
+                "updated_content": result.updated_content,
+                "imported": result.imported,
+                "skipped": result.skipped,
+            }))
+            .map_err(|e| e.to_string())
+        });
+
+        self.register(tool, handler);
+    }
+
+    fn register_stub_sync_status(&mut self) {
+        let switchboard = Arc::clone(&self.switchboard);
+
+        let tool = McpTool::new(
+            "stub_sync_status",
+            "Compare stub markers in the body ('> [!stub] expand: ...' callouts, '{{expand: ...}}') with frontmatter stubs, in both directions.",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "content": {
+                        "type": "string",
+                        "description": "Markdown document content"
+                    }
+                },
+                "required": ["content"]
+            }),
+        );
+
+        let handler: ToolHandler = Box::new(move |args| {
+            let content = args
+                .get("content")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'content'")?;
+
+            let status = switchboard
+                .stub_sync_status(content)
+                .map_err(|e| e.to_string())?;
+            serde_json::to_string_pretty(&sync_status_json(&status)).map_err(|e| e.to_string())
+        });
+
+        self.register(tool, handler);
+    }
+
+    fn register_sync_stub_markers(&mut self) {
+        let switchboard = Arc::clone(&self.switchboard);
+
+        let tool = McpTool::new(
+            "sync_stub_markers",
+            "Create or update frontmatter stubs from stub markers in the body, with their block anchors. Returns updated document content.",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "content": {
+                        "type": "string",
+                        "description": "Markdown document content"
+                    }
+                },
+                "required": ["content"]
+            }),
+        );
+
+        let handler: ToolHandler = Box::new(move |args| {
+            let content = args
+                .get("content")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'content'")?;
+
+            let result = switchboard
+                .sync_stub_markers(content)
+                .map_err(|e| e.to_string())?;
+            serde_json::to_string_pretty(&serde_json::json!({
+                "updated_content": result.updated_content,
+                "created": result.created,
+                "updated": result.updated,
+                "status": sync_status_json(&result.status),
+            }))
+            .map_err(|e| e.to_string())
+        });
+
+        self.register(tool, handler);
+    }
+
+    fn register_suggest_stubs(&mut self) {
+        let switchboard = Arc::clone(&self.switchboard);
+
+        let tool = McpTool::new(
+            "suggest_stubs",
+            "Detect gaps in a document (TODO/FIXME/TBD, [citation needed], empty or long sections, unlinked numeric claims, open questions) and propose system-generated stubs. Nothing is written unless 'accept' lists suggestions to apply.",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "content": {
+                        "type": "string",
+                        "description": "Markdown document content"
+                    },
+                    "rules": {
+                        "type": "array",
+                        "items": {
+                            "type": "string",
+                            "enum": DetectionRule::ALL.iter().map(|r| r.id()).collect::<Vec<_>>()
+                        },
+                        "description": "Rules to run (default: all)"
+                    },
+                    "long_section_words": {
+                        "type": "integer",
+                        "description": "Words above which a section is suggested for splitting",
+                        "default": DetectorOptions::default().long_section_words
+                    },
+                    "accept": {
+                        "type": "array",
+                        "items": { "type": "integer" },
+                        "description": "Indices of suggestions to add to frontmatter; returns updated content"
+                    }
+                },
+                "required": ["content"]
+            }),
+        );
+
+        let handler: ToolHandler = Box::new(move |args| {
+            let content = args
+                .get("content")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'content'")?;
+            let mut options = DetectorOptions::default();
+            if let Some(rules) = args.get("rules").and_then(|v| v.as_array()) {
+                options.rules = rules
+                    .iter()
+                    .map(|r| r.as_str().unwrap_or_default().parse())
+                    .collect::<Result<_, _>>()?;
+            }
+            if let Some(words) = args.get("long_section_words").and_then(|v| v.as_u64()) {
+                options.long_section_words = words as usize;
+            }
+
+            let suggestions = switchboard
+                .suggest_stubs(content, &options)
+                .map_err(|e| e.to_string())?;
+            let mut response = serde_json::json!({
+                "suggestions": suggestions
+                    .iter()
+                    .enumerate()
+                    .map(|(i, s)| serde_json::json!({
+                        "index": i,
+                        "rule": s.rule.id(),
+                        "stub": s.stub,
+                        "line": s.position.line,
+                        "column": s.position.column,
+                        "excerpt": s.excerpt,
+                    }))
+                    .collect::<Vec<_>>(),
+            });
+
+            if let Some(accept) = args.get("accept").and_then(|v| v.as_array()) {
+                let accepted = accept
+                    .iter()
+                    .map(|i| {
+                        i.as_u64()
+                            .and_then(|i| suggestions.get(i as usize))
+                            .cloned()
+                            .ok_or_else(|| format!("No suggestion at index {}", i))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let result = switchboard
+                    .apply_stub_suggestions(content, &accepted)
+                    .map_err(|e| e.to_string())?;
+                response["updated_content"] = result.updated_content.into();
+                response["applied"] = result.imported.len().into();
+            }
+
+            serde_json::to_string_pretty(&response).map_err(|e| e.to_string())
+        });
+
+        self.register(tool, handler);
+    }
+
     // =========================================================================
     // Calculation Tools
     // =========================================================================
 
     fn register_calculate_health(&mut self) {
+        let switchboard = Arc::clone(&self.switchboard);
+
         let tool = McpTool::new(
             "calculate_health",
-            "Calculate document health score from refinement and stubs. Formula: health = 0.7×refinement + 0.3×(1-stub_penalty)",
+            "Calculate document health score from refinement and stubs. Formula: health = refinement_weight×refinement + stub_weight×(1-stub_penalty), with the weights from the `health` config",
             serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -698,12 +1068,15 @@ impl ToolRegistry {
             }),
         );
 
-        let handler: ToolHandler = Box::new(move |args| handlers::calc_health(args));
+        let handler: ToolHandler =
+            Box::new(move |args| handlers::calc_health(switchboard.as_ref(), args));
 
         self.register(tool, handler);
     }
 
     fn register_calculate_usefulness(&mut self) {
+        let switchboard = Arc::clone(&self.switchboard);
+
         let tool = McpTool::new(
             "calculate_usefulness",
             "Calculate usefulness margin for a target audience. Margin = refinement - gate",
@@ -726,7 +1099,8 @@ impl ToolRegistry {
             }),
         );
 
-        let handler: ToolHandler = Box::new(|args| handlers::calc_usefulness(args));
+        let handler: ToolHandler =
+            Box::new(move |args| handlers::calc_usefulness(switchboard.as_ref(), args));
 
         self.register(tool, handler);
     }
@@ -765,6 +1139,8 @@ impl ToolRegistry {
     }
 
     fn register_calculate_vector_physics(&mut self) {
+        let switchboard = Arc::clone(&self.switchboard);
+
         let tool = McpTool::new(
             "calculate_vector_physics",
             "Calculate vector physics (potential energy, friction, magnitude) for stub prioritization",
@@ -796,7 +1172,8 @@ impl ToolRegistry {
             }),
         );
 
-        let handler: ToolHandler = Box::new(|args| handlers::calculate_vector_physics(args));
+        let handler: ToolHandler =
+            Box::new(move |args| handlers::calculate_vector_physics(switchboard.as_ref(), args));
 
         self.register(tool, handler);
     }
@@ -806,6 +1183,8 @@ impl ToolRegistry {
     // =========================================================================
 
     fn register_get_audience_gates(&mut self) {
+        let switchboard = Arc::clone(&self.switchboard);
+
         let tool = McpTool::new(
             "get_audience_gates",
             "Get the refinement gate thresholds for each audience level",
@@ -815,7 +1194,8 @@ impl ToolRegistry {
             }),
         );
 
-        let handler: ToolHandler = Box::new(|_| handlers::get_audience_gates());
+        let handler: ToolHandler =
+            Box::new(move |_| handlers::get_audience_gates(switchboard.as_ref()));
 
         self.register(tool, handler);
     }
@@ -855,6 +1235,66 @@ impl ToolRegistry {
         self.register(tool, handler);
     }
 
+    // =========================================================================
+    // Configuration Tools
+    // =========================================================================
+
+    fn register_get_config(&mut self) {
+        let switchboard = Arc::clone(&self.switchboard);
+
+        let tool = McpTool::new(
+            "get_config",
+            "Get the active calculation configuration (weights, gates, penalties, trust factors, cadences)",
+            serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }),
+        );
+
+        let handler: ToolHandler = Box::new(move |_| handlers::get_config(switchboard.as_ref()));
+
+        self.register(tool, handler);
+    }
+
+    fn register_reload_config(&mut self) {
+        let switchboard = Arc::clone(&self.switchboard);
+
+        let tool = McpTool::new(
+            "reload_config",
+            "Reload the calculation configuration from the user and project config files. All subsequent tool calls use the reloaded values",
+            serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }),
+        );
+
+        let handler: ToolHandler = Box::new(move |_| handlers::reload_config(switchboard.as_ref()));
+
+        self.register(tool, handler);
+    }
+
+    fn register_set_as_of(&mut self) {
+        let config = self.switchboard.config_handle();
+
+        let tool = McpTool::new(
+            "set_as_of",
+            "Calculate freshness as of a fixed date or time, so analyses are reproducible. All subsequent tool calls use it; omit 'as_of' to use the current time again",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "as_of": {
+                        "type": "string",
+                        "description": "Date or time, e.g. 2024-06-01 or 2024-06-01T12:00:00Z; dates without an offset are in the vault timezone"
+                    }
+                }
+            }),
+        );
+
+        let handler: ToolHandler = Box::new(move |args| handlers::set_as_of(&config, args));
+
+        self.register(tool, handler);
+    }
+
     // =========================================================================
     // Batch Tools
     // =========================================================================
@@ -949,6 +1389,7 @@ impl ToolRegistry {
 
     fn register_read_document(&mut self) {
         let switchboard = Arc::clone(&self.switchboard);
+        let repository = Arc::clone(&self.repository);
 
         let tool = McpTool::new(
             "read_document",
@@ -981,8 +1422,9 @@ impl ToolRegistry {
                 .unwrap_or(true);
 
             // Read file
-            let content = std::fs::read_to_string(path)
-                .map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+            let content = repository
+                .read(std::path::Path::new(path))
+                .map_err(|e| format!("Failed to read '{}': {}", path, e.message))?;
 
             if analyze {
                 let analysis = switchboard
@@ -1011,10 +1453,11 @@ impl ToolRegistry {
 
     fn register_scan_vault(&mut self) {
         let switchboard = Arc::clone(&self.switchboard);
+        let repository = Arc::clone(&self.repository);
 
         let tool = McpTool::new(
             "scan_vault",
-            "Scan a directory for markdown files and analyze their health. Returns summary statistics.",
+            "Scan a directory for markdown files and canvases and analyze their health. Returns summary statistics and backlink counts.",
             serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -1024,13 +1467,17 @@ impl ToolRegistry {
                     },
                     "pattern": {
                         "type": "string",
-                        "description": "Glob pattern for files (default: **/*.md)",
-                        "default": "**/*.md"
+                        "description": "Glob pattern for files within the vault (default: every document the walk settings do not ignore)"
                     },
                     "include_content": {
                         "type": "boolean",
                         "description": "Include document content in results (default: false)",
                         "default": false
+                    },
+                    "use_index": {
+                        "type": "boolean",
+                        "description": "Reuse analyses of unchanged files from the vault index in .doc-doctor/index.json (default: true)",
+                        "default": true
                     }
                 },
                 "required": ["path"]
@@ -1042,74 +1489,76 @@ impl ToolRegistry {
                 .get("path")
                 .and_then(|v| v.as_str())
                 .ok_or("Missing 'path'")?;
-            let pattern = args
-                .get("pattern")
-                .and_then(|v| v.as_str())
-                .unwrap_or("**/*.md");
+            let pattern = args.get("pattern").and_then(|v| v.as_str());
             let include_content = args
                 .get("include_content")
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false);
+            let use_index = args.get("use_index").and_then(|v| v.as_bool()).unwrap_or(true);
 
-            // Use glob to find files
-            let full_pattern = format!("{}/{}", vault_path, pattern);
-            let paths: Vec<_> = glob::glob(&full_pattern)
-                .map_err(|e| format!("Invalid glob pattern: {}", e))?
-                .filter_map(Result::ok)
-                .collect();
-
+            let root = std::path::Path::new(vault_path);
+            let paths = walk_vault(&switchboard.config().walk, root, pattern)?;
+            let (index, refresh) =
+                index_vault(&*switchboard, &*repository, root, &paths, use_index);
+            let graph = index.link_graph(root, &paths);
             let mut results = Vec::new();
             let mut total_health = 0.0;
             let mut total_stubs = 0;
             let mut blocking_count = 0;
             let mut success_count = 0;
+            let mut partial_count = 0;
 
             for path in &paths {
-                match std::fs::read_to_string(path) {
-                    Ok(content) => {
-                        match switchboard.analyze_document(&content) {
-                            Ok(analysis) => {
-                                total_health += analysis.dimensions.health;
-                                total_stubs += analysis.properties.stubs.len();
-                                blocking_count += analysis
-                                    .properties
-                                    .stubs
-                                    .iter()
-                                    .filter(|s| s.is_blocking())
-                                    .count();
-                                success_count += 1;
-
-                                let mut result = serde_json::json!({
-                                    "path": path.display().to_string(),
-                                    "success": true,
-                                    "health": analysis.dimensions.health,
-                                    "refinement": analysis.properties.refinement.value(),
-                                    "stub_count": analysis.properties.stubs.len(),
-                                    "blocking_stubs": analysis.properties.stubs.iter().filter(|s| s.is_blocking()).count(),
-                                });
-
-                                if include_content {
-                                    result["content"] = serde_json::Value::String(content);
-                                }
-
-                                results.push(result);
-                            }
-                            Err(e) => {
-                                results.push(serde_json::json!({
-                                    "path": path.display().to_string(),
-                                    "success": false,
-                                    "error": e.to_string(),
-                                }));
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        results.push(serde_json::json!({
-                            "path": path.display().to_string(),
-                            "success": false,
-                            "error": format!("Failed to read file: {}", e),
-                        }));
-                    }
+                let Some(entry) = index.get(root, path) else {
+                    let error = refresh.failure(path).unwrap_or("Not indexed");
+                    results.push(serde_json::json!({
+                        "path": path.display().to_string(),
+                        "success": false,
+                        "error": error,
+                    }));
+                    continue;
+                };
+
+                let stubs = &entry.properties.stubs;
+                let blocking = stubs.iter().filter(|s| s.is_blocking()).count();
+                total_health += entry.dimensions.health;
+                total_stubs += stubs.len();
+                blocking_count += blocking;
+                success_count += 1;
+                if entry.partial {
+                    partial_count += 1;
+                }
+
+                // Links are vault-relative, so the graph is too
+                let relative = VaultIndex::key(root, path);
+                let mut result = serde_json::json!({
+                    "path": path.display().to_string(),
+                    "relative_path": relative,
+                    "canvas": entry.canvas,
+                    "success": true,
+                    "partial": entry.partial,
+                    "health": entry.dimensions.health,
+                    "refinement": entry.properties.refinement.value(),
+                    "stub_count": stubs.len(),
+                    "blocking_stubs": blocking,
+                });
+
+                if entry.partial {
+                    result["diagnostics"] = diagnostics_json(&entry.diagnostics);
+                }
+
+                if include_content {
+                    let content = repository.read(path).unwrap_or_default();
+                    result["content"] = serde_json::Value::String(content);
+                }
+
+                results.push(result);
+            }
+
+            for result in &mut results {
+                if let Some(relative) = result["relative_path"].as_str() {
+                    let backlinks = graph.backlinks(relative).len();
+                    result["backlinks"] = backlinks.into();
                 }
             }
 
@@ -1123,10 +1572,12 @@ impl ToolRegistry {
                 "vault_path": vault_path,
                 "total_files": paths.len(),
                 "analyzed": success_count,
+                "partial": partial_count,
                 "failed": paths.len() - success_count,
                 "average_health": avg_health,
                 "total_stubs": total_stubs,
                 "blocking_stubs": blocking_count,
+                "index": index_json(&refresh, use_index),
                 "results": results,
             }))
             .map_err(|e| e.to_string())
@@ -1137,6 +1588,7 @@ impl ToolRegistry {
 
     fn register_find_blocking_stubs(&mut self) {
         let switchboard = Arc::clone(&self.switchboard);
+        let repository = Arc::clone(&self.repository);
 
         let tool = McpTool::new(
             "find_blocking_stubs",
@@ -1150,8 +1602,12 @@ impl ToolRegistry {
                     },
                     "pattern": {
                         "type": "string",
-                        "description": "Glob pattern for files (default: **/*.md)",
-                        "default": "**/*.md"
+                        "description": "Glob pattern for files within the vault (default: every document the walk settings do not ignore)"
+                    },
+                    "use_index": {
+                        "type": "boolean",
+                        "description": "Reuse analyses of unchanged files from the vault index (default: true)",
+                        "default": true
                     }
                 },
                 "required": ["path"]
@@ -1163,45 +1619,40 @@ impl ToolRegistry {
                 .get("path")
                 .and_then(|v| v.as_str())
                 .ok_or("Missing 'path'")?;
-            let pattern = args
-                .get("pattern")
-                .and_then(|v| v.as_str())
-                .unwrap_or("**/*.md");
-
-            // Use glob to find files
-            let full_pattern = format!("{}/{}", vault_path, pattern);
-            let paths: Vec<_> = glob::glob(&full_pattern)
-                .map_err(|e| format!("Invalid glob pattern: {}", e))?
-                .filter_map(Result::ok)
-                .collect();
+            let pattern = args.get("pattern").and_then(|v| v.as_str());
+            let use_index = args.get("use_index").and_then(|v| v.as_bool()).unwrap_or(true);
 
+            let root = std::path::Path::new(vault_path);
+            let paths = walk_vault(&switchboard.config().walk, root, pattern)?;
+            let (index, refresh) =
+                index_vault(&*switchboard, &*repository, root, &paths, use_index);
             let mut blocking_stubs = Vec::new();
             let mut documents_with_blocking = 0;
 
             for path in &paths {
-                if let Ok(content) = std::fs::read_to_string(path) {
-                    if let Ok(props) = switchboard.parse_document(&content) {
-                        let doc_blocking: Vec<_> = props
-                            .stubs
-                            .iter()
-                            .enumerate()
-                            .filter(|(_, s)| s.is_blocking())
-                            .map(|(idx, s)| {
-                                serde_json::json!({
-                                    "document": path.display().to_string(),
-                                    "stub_index": idx,
-                                    "type": s.stub_type.as_str(),
-                                    "description": s.description,
-                                    "priority": s.priority.to_string(),
-                                })
-                            })
-                            .collect();
+                let Some(entry) = index.get(root, path) else {
+                    continue;
+                };
+                let doc_blocking: Vec<_> = entry
+                    .properties
+                    .stubs
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, s)| s.is_blocking())
+                    .map(|(idx, s)| {
+                        serde_json::json!({
+                            "document": path.display().to_string(),
+                            "stub_index": idx,
+                            "type": s.stub_type.as_str(),
+                            "description": s.description,
+                            "priority": s.priority.to_string(),
+                        })
+                    })
+                    .collect();
 
-                        if !doc_blocking.is_empty() {
-                            documents_with_blocking += 1;
-                            blocking_stubs.extend(doc_blocking);
-                        }
-                    }
+                if !doc_blocking.is_empty() {
+                    documents_with_blocking += 1;
+                    blocking_stubs.extend(doc_blocking);
                 }
             }
 
@@ -1211,6 +1662,78 @@ impl ToolRegistry {
                 "documents_with_blocking": documents_with_blocking,
                 "total_blocking_stubs": blocking_stubs.len(),
                 "blocking_stubs": blocking_stubs,
+                "index": index_json(&refresh, use_index),
+            }))
+            .map_err(|e| e.to_string())
+        });
+
+        self.register(tool, handler);
+    }
+
+    fn register_query_vault(&mut self) {
+        let switchboard = Arc::clone(&self.switchboard);
+        let repository = Arc::clone(&self.repository);
+
+        let tool = McpTool::new(
+            "query_vault",
+            "Query documents and stubs in a vault, e.g. 'audience = public and health < 0.8 and any(stubs, form = blocking and family = retrieval) order by health select path, title, health limit 20'. Returns the matching documents with the selected fields.",
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Path to the vault directory"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "Conditions on document fields (path, title, audience, form, origin, refinement, health, usefulness, trust, freshness, tags, stub_count, blocking, partial, canvas, links, ...) combined with and/or/not; any/all/none/count(stubs, ...) test stub fields (type, family, form, priority, origin, description, anchor, ...); optional 'order by', 'select' and 'limit' clauses"
+                    },
+                    "use_index": {
+                        "type": "boolean",
+                        "description": "Reuse analyses of unchanged files from the vault index (default: true)",
+                        "default": true
+                    }
+                },
+                "required": ["path", "query"]
+            }),
+        );
+
+        let handler: ToolHandler = Box::new(move |args| {
+            let vault_path = args
+                .get("path")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'path'")?;
+            let text = args
+                .get("query")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing 'query'")?;
+            let use_index = args.get("use_index").and_then(|v| v.as_bool()).unwrap_or(true);
+            let query = Query::parse(text).map_err(|e| format!("Invalid query: {}", e))?;
+
+            let root = std::path::Path::new(vault_path);
+            let paths = walk_vault(&switchboard.config().walk, root, None)?;
+
+            let (index, refresh) =
+                index_vault(&*switchboard, &*repository, root, &paths, use_index);
+            let rows = index.query(root, &paths, &query, &switchboard.config().stub_types);
+            let columns = query.columns();
+            let documents: Vec<serde_json::Value> = rows
+                .iter()
+                .map(|row| {
+                    let fields = columns.iter().zip(&row.values).map(|(column, value)| {
+                        (column.to_string(), serde_json::to_value(value).unwrap_or_default())
+                    });
+                    serde_json::Value::Object(fields.collect())
+                })
+                .collect();
+
+            serde_json::to_string_pretty(&serde_json::json!({
+                "vault_path": vault_path,
+                "query": text,
+                "total": documents.len(),
+                "columns": columns,
+                "documents": documents,
+                "index": index_json(&refresh, use_index),
             }))
             .map_err(|e| e.to_string())
         });
@@ -1251,7 +1774,7 @@ impl ToolRegistry {
                 .ok_or("Missing 'path'")?;
             let message = args.get("message").and_then(|v| v.as_str());
 
-            let status = git.check_availability();
+            let status = check_availability(&git);
             if !status.available {
                 return serde_json::to_string_pretty(&serde_json::json!({
                     "success": false,
@@ -1327,7 +1850,7 @@ impl ToolRegistry {
                 .ok_or("Missing 'stub_description'")?;
             let additional = args.get("additional_message").and_then(|v| v.as_str());
 
-            let status = git.check_availability();
+            let status = check_availability(&git);
             if !status.available {
                 return serde_json::to_string_pretty(&serde_json::json!({
                     "success": false,
@@ -1391,7 +1914,7 @@ impl ToolRegistry {
                 .and_then(|v| v.as_u64())
                 .unwrap_or(10) as usize;
 
-            let status = git.check_availability();
+            let status = check_availability(&git);
             if !status.available {
                 return serde_json::to_string_pretty(&serde_json::json!({
                     "commits": [],
@@ -1477,7 +2000,7 @@ impl ToolRegistry {
                 .and_then(|v| v.as_str())
                 .unwrap_or("HEAD");
 
-            let status = git.check_availability();
+            let status = check_availability(&git);
             if !status.available {
                 return serde_json::to_string_pretty(&serde_json::json!({
                     "diff": "",
@@ -1517,6 +2040,7 @@ impl ToolRegistry {
 
     fn register_find_related_documents(&mut self) {
         let sc = Arc::clone(&self.smart_connections);
+        let switchboard = Arc::clone(&self.switchboard);
 
         let tool = McpTool::new(
             "find_related_documents",
@@ -1569,7 +2093,8 @@ impl ToolRegistry {
 
             // Try to get or create SC integration for this vault
             let mut sc_guard = sc.write().map_err(|e| e.to_string())?;
-            *sc_guard = SmartConnectionsIntegration::for_vault(&vault);
+            let walker = fs_walker(&switchboard.config().walk);
+            *sc_guard = SmartConnectionsIntegration::for_vault(&vault).with_walker(walker);
 
             let status = sc_guard.check_availability();
             let has_embeddings = status.available;
@@ -1610,6 +2135,7 @@ impl ToolRegistry {
 
     fn register_suggest_links(&mut self) {
         let sc = Arc::clone(&self.smart_connections);
+        let switchboard = Arc::clone(&self.switchboard);
 
         let tool = McpTool::new(
             "suggest_links",
@@ -1667,7 +2193,8 @@ impl ToolRegistry {
             let vault = PathBuf::from(vault_path);
 
             let mut sc_guard = sc.write().map_err(|e| e.to_string())?;
-            *sc_guard = SmartConnectionsIntegration::for_vault(&vault);
+            let walker = fs_walker(&switchboard.config().walk);
+            *sc_guard = SmartConnectionsIntegration::for_vault(&vault).with_walker(walker);
 
             let status = sc_guard.check_availability();
 
@@ -1698,6 +2225,7 @@ impl ToolRegistry {
 
     fn register_detect_duplicates(&mut self) {
         let sc = Arc::clone(&self.smart_connections);
+        let switchboard = Arc::clone(&self.switchboard);
 
         let tool = McpTool::new(
             "detect_duplicates",
@@ -1739,7 +2267,9 @@ impl ToolRegistry {
 
             let vault = PathBuf::from(vault_path);
 
-            let sc_guard = sc.read().map_err(|e| e.to_string())?;
+            let mut sc_guard = sc.write().map_err(|e| e.to_string())?;
+            let walker = fs_walker(&switchboard.config().walk);
+            *sc_guard = SmartConnectionsIntegration::for_vault(&vault).with_walker(walker);
 
             let duplicates = sc_guard.detect_duplicates(content, &vault, threshold);
 
@@ -1826,7 +2356,8 @@ impl ToolRegistry {
             };
 
             let mut sc_guard = sc.write().map_err(|e| e.to_string())?;
-            *sc_guard = SmartConnectionsIntegration::for_vault(&vault);
+            let walker = fs_walker(&switchboard.config().walk);
+            *sc_guard = SmartConnectionsIntegration::for_vault(&vault).with_walker(walker);
 
             let status = sc_guard.check_availability();
 
@@ -1881,3 +2412,95 @@ impl Default for ToolRegistry {
         Self::new()
     }
 }
+
+/// Render annotations as JSON
+fn annotations_json(annotations: &[Annotation]) -> serde_json::Value {
+    annotations
+        .iter()
+        .map(|a| {
+            serde_json::json!({
+                "kind": a.kind.as_str(),
+                "label": a.label,
+                "text": a.text,
+                "line": a.position.line,
+                "column": a.position.column,
+            })
+        })
+        .collect()
+}
+
+/// Render a stub sync status as JSON
+fn sync_status_json(status: &SyncStatus) -> serde_json::Value {
+    serde_json::json!({
+        "synced": status.synced.len(),
+        "is_synced": status.is_synced(),
+        "missing_in_frontmatter": status.missing_in_frontmatter,
+        "missing_in_body": status.missing_in_body,
+    })
+}
+
+/// Render parse diagnostics as JSON
+/// Documents of the vault at `root`, walked with the `walk` settings
+///
+/// A `pattern` within the vault, such as `notes/**/*.md`, replaces the
+/// configured includes.
+fn walk_vault(
+    config: &WalkConfig,
+    root: &std::path::Path,
+    pattern: Option<&str>,
+) -> Result<Vec<PathBuf>, String> {
+    let mut walker = fs_walker(config);
+    if let Some(pattern) = pattern {
+        walker = walker
+            .with_include(&[pattern.to_string()])
+            .map_err(|e| format!("Invalid glob pattern: {}", e.message))?;
+    }
+    vault_documents(&walker, root).map_err(|e| e.to_string())
+}
+
+/// Analyses of the documents at `paths` in a vault
+///
+/// With `use_index` the vault's stored index is refreshed and saved, so
+/// unchanged files are not parsed again; otherwise every document is
+/// analyzed into a throwaway index.
+fn index_vault(
+    switchboard: &dyn Switchboard,
+    repository: &dyn DocumentRepository,
+    root: &std::path::Path,
+    paths: &[PathBuf],
+    use_index: bool,
+) -> (VaultIndex, IndexRefresh) {
+    if use_index {
+        let store = JsonIndexStore::for_vault(root);
+        VaultIndex::refresh_stored(&store, switchboard, repository, root, paths, u64::MAX)
+    } else {
+        let mut index = VaultIndex::new();
+        let refresh = index.refresh(switchboard, repository, root, paths, u64::MAX);
+        (index, refresh)
+    }
+}
+
+/// How a vault index refresh went
+fn index_json(refresh: &IndexRefresh, use_index: bool) -> serde_json::Value {
+    serde_json::json!({
+        "enabled": use_index,
+        "reused": refresh.reused + refresh.rehashed,
+        "analyzed": refresh.analyzed,
+        "removed": refresh.removed,
+        "store_error": refresh.store_error,
+    })
+}
+
+fn diagnostics_json(diagnostics: &[ParseDiagnostic]) -> serde_json::Value {
+    diagnostics
+        .iter()
+        .map(|d| {
+            serde_json::json!({
+                "field": d.field,
+                "message": d.message,
+                "line": d.position.map(|p| p.line),
+                "column": d.position.map(|p| p.column),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,117 @@
+//! Frontmatter extraction
+//!
+//! Finds the JSON object at the start of a markdown document.
+
+use doc_doctor_domain::{strip_bom, MetadataSpan, SourcePosition};
+
+/// Information about extracted frontmatter
+#[derive(Debug, Clone)]
+pub struct JsonFrontmatter {
+    /// The JSON object, braces included
+    pub content: String,
+    /// Byte offset where the object starts (after any byte-order mark)
+    pub start_offset: usize,
+    /// Byte offset where the object ends (after the closing brace)
+    pub end_offset: usize,
+    /// Byte offset where the document body starts
+    pub body_offset: usize,
+}
+
+impl JsonFrontmatter {
+    /// Convert to domain MetadataSpan
+    pub fn to_metadata_span(&self, document: &str) -> MetadataSpan {
+        MetadataSpan {
+            content: self.content.clone(),
+            start: SourcePosition::from_offset(document, self.start_offset),
+            end: SourcePosition::from_offset(document, self.end_offset),
+        }
+    }
+}
+
+/// Extract JSON frontmatter from document content
+///
+/// The document must start with `{`, after an optional byte-order mark. The object ends at its matching
+/// closing brace (strings and escapes are honoured); one line break after
+/// it is treated as part of the frontmatter.
+///
+/// Returns `None` when the document does not start with `{` or the object
+/// is never closed. Syntax errors inside a closed object are left for the
+/// parser to report with a position.
+pub fn extract_frontmatter(content: &str) -> Option<JsonFrontmatter> {
+    let start_offset = content.len() - strip_bom(content).len();
+    if !content[start_offset..].starts_with('{') {
+        return None;
+    }
+
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in content.char_indices().skip_while(|&(i, _)| i < start_offset) {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    let end_offset = i + 1;
+                    let rest = &content[end_offset..];
+                    let newline = if rest.starts_with("\r\n") {
+                        2
+                    } else {
+                        usize::from(rest.starts_with('\n'))
+                    };
+                    return Some(JsonFrontmatter {
+                        content: content[start_offset..end_offset].to_string(),
+                        start_offset,
+                        end_offset,
+                        body_offset: end_offset + newline,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_frontmatter() {
+        let content = "{\n  \"title\": \"Braces } in \\\"strings\\\"\",\n  \"nested\": {}\n}\n# Body\n";
+        let span = extract_frontmatter(content).unwrap();
+
+        assert!(span.content.ends_with("{}\n}"));
+        assert_eq!(&content[span.body_offset..], "# Body\n");
+    }
+
+    #[test]
+    fn test_extract_after_bom() {
+        let content = "\u{feff}{\"title\": \"Test\"}\r\nBody";
+        let span = extract_frontmatter(content).unwrap();
+
+        assert_eq!(span.content, "{\"title\": \"Test\"}");
+        assert_eq!(span.start_offset, 3);
+        assert_eq!(&content[span.body_offset..], "Body");
+    }
+
+    #[test]
+    fn test_no_frontmatter() {
+        assert!(extract_frontmatter("---\ntitle: Test\n---\n").is_none());
+        assert!(extract_frontmatter("{\n  \"title\": \"Unclosed\"\n").is_none());
+    }
+}
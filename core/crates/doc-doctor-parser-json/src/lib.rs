@@ -0,0 +1,36 @@
+//! JSON Frontmatter Parser
+//!
+//! Implements the `DocumentParser` and `DocumentWriter` traits from the
+//! domain layer for JSON frontmatter: a document that opens with a JSON
+//! object, as supported by Hugo.
+//!
+//! # Features
+//!
+//! - Extracts the leading JSON object from markdown documents
+//! - Provides line/column positions for syntax and type errors
+//! - Reports values replaced by defaults as diagnostics
+//! - Format identifier: "json"
+//!
+//! # Example
+//!
+//! ```rust
+//! use doc_doctor_parser_json::JsonParser;
+//! use doc_doctor_domain::DocumentParser;
+//!
+//! let parser = JsonParser::new();
+//! let content = r#"{
+//!   "title": "My Document",
+//!   "refinement": 0.75
+//! }
+//! # Content
+//! "#;
+//!
+//! let props = parser.parse(content).unwrap();
+//! assert_eq!(props.title.as_deref(), Some("My Document"));
+//! ```
+
+mod frontmatter;
+mod parser;
+
+pub use frontmatter::{extract_frontmatter, JsonFrontmatter};
+pub use parser::JsonParser;
@@ -0,0 +1,202 @@
+//! JSON Parser Implementation
+//!
+//! Implements the `DocumentParser` and `DocumentWriter` traits for JSON frontmatter.
+
+use serde_json::Value;
+
+use doc_doctor_domain::{
+    find_coercions, strip_bom, DocumentParser, DocumentWriter, L1Properties, MetadataSpan,
+    ParseDiagnostic, ParseError, RecoveredParse, SerializeError, SourcePosition, TextStyle,
+};
+
+use crate::frontmatter::{extract_frontmatter, JsonFrontmatter};
+
+/// JSON frontmatter parser
+///
+/// Parses markdown documents that open with a JSON object.
+#[derive(Debug, Clone, Default)]
+pub struct JsonParser;
+
+impl JsonParser {
+    /// Create a new JSON parser
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Deserialize the frontmatter as `T`
+    fn parse_as<T: serde::de::DeserializeOwned>(&self, content: &str) -> Result<T, ParseError> {
+        let span = extract_frontmatter(content).ok_or_else(|| {
+            ParseError::new("No frontmatter found in document. Expected document to start with '{'")
+        })?;
+
+        serde_json::from_str(&span.content).map_err(|e| convert_error(&span, e))
+    }
+}
+
+/// Drop null values, which mark unset optional fields
+fn strip_nulls(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
+}
+
+/// Convert a serde_json error, whose line and column are document positions
+/// because the frontmatter starts the document (after any byte-order mark)
+fn convert_error(span: &JsonFrontmatter, err: serde_json::Error) -> ParseError {
+    let error = ParseError::new(err.to_string());
+    if err.line() == 0 {
+        return error;
+    }
+
+    let line_start: usize = span
+        .content
+        .split_inclusive('\n')
+        .take(err.line() - 1)
+        .map(str::len)
+        .sum();
+    let offset = span.start_offset + line_start + err.column().saturating_sub(1);
+    error.with_position(SourcePosition::new(err.line(), err.column(), offset))
+}
+
+impl DocumentParser for JsonParser {
+    fn parse(&self, content: &str) -> Result<L1Properties, ParseError> {
+        self.parse_as(content)
+    }
+
+    fn parse_recovering(&self, content: &str) -> Result<RecoveredParse, ParseError> {
+        let value: Value = self.parse_as(content)?;
+        let diagnostics = serde_yaml::to_value(&value)
+            .map(|v| find_coercions(&v))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|coercion| {
+                let diagnostic = ParseDiagnostic::coerced(&coercion.path, coercion.message());
+                match coercion.hint() {
+                    Some(hint) => diagnostic.with_suggestion(hint),
+                    None => diagnostic,
+                }
+            })
+            .collect();
+
+        Ok(RecoveredParse {
+            properties: self.parse(content)?,
+            diagnostics,
+        })
+    }
+
+    fn extract_metadata(&self, content: &str) -> Option<MetadataSpan> {
+        extract_frontmatter(content).map(|s| s.to_metadata_span(content))
+    }
+
+    fn body_offset(&self, content: &str) -> usize {
+        extract_frontmatter(content).map_or(0, |s| s.body_offset)
+    }
+
+    fn format_id(&self) -> &'static str {
+        "json"
+    }
+}
+
+impl DocumentWriter for JsonParser {
+    fn serialize_document(
+        &self,
+        original_content: &str,
+        properties: &L1Properties,
+    ) -> Result<String, SerializeError> {
+        let json = self.serialize_frontmatter(properties)?;
+
+        let style = TextStyle::detect(original_content);
+
+        match extract_frontmatter(original_content) {
+            Some(span) => Ok(format!(
+                "{}{}",
+                style.apply(&format!("{}\n", json)),
+                &original_content[span.body_offset..]
+            )),
+            None => Ok(format!(
+                "{}{}",
+                style.apply(&format!("{}\n\n", json)),
+                strip_bom(original_content)
+            )),
+        }
+    }
+
+    fn serialize_frontmatter(&self, properties: &L1Properties) -> Result<String, SerializeError> {
+        let mut value = serde_json::to_value(properties).map_err(|e| SerializeError::new(e.to_string()))?;
+        strip_nulls(&mut value);
+        serde_json::to_string_pretty(&value).map_err(|e| SerializeError::new(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_domain::Audience;
+
+    const DOCUMENT: &str = r#"{
+  "title": "Hugo Page",
+  "refinement": 0.8,
+  "audience": "trusted",
+  "stubs": [
+    { "type": "link", "description": "Citation needed", "stub_form": "blcoking" }
+  ]
+}
+# Body
+"#;
+
+    #[test]
+    fn test_parse_document() {
+        let props = JsonParser::new().parse(DOCUMENT).unwrap();
+
+        assert_eq!(props.title.as_deref(), Some("Hugo Page"));
+        assert_eq!(props.audience, Audience::Trusted);
+        assert_eq!(props.stubs.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_recovering_reports_coercions() {
+        let result = JsonParser::new().parse_recovering(DOCUMENT).unwrap();
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].suggestion.as_deref(), Some("Did you mean 'blocking'?"));
+    }
+
+    #[test]
+    fn test_error_position() {
+        let err = JsonParser::new()
+            .parse("{\n  \"title\": \"Ok\",\n  \"refinement\": \"high\"\n}\n")
+            .unwrap_err();
+        assert_eq!(err.position.unwrap().line, 3);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let parser = JsonParser::new();
+        let props = parser.parse(DOCUMENT).unwrap();
+
+        let written = parser.serialize_document(DOCUMENT, &props).unwrap();
+        assert!(written.starts_with('{'));
+        assert!(written.ends_with("}\n# Body\n"));
+        assert!(!written.contains("null"));
+
+        let reparsed = parser.parse(&written).unwrap();
+        assert_eq!(reparsed.title, props.title);
+        assert_eq!(reparsed.stubs.len(), 1);
+    }
+
+    #[test]
+    fn test_round_trip_bom_crlf() {
+        let parser = JsonParser::new();
+        let content = "\u{feff}{\r\n  \"title\": \"Windows\"\r\n}\r\nBody\r\n";
+        let props = parser.parse(content).unwrap();
+
+        let written = parser.serialize_document(content, &props).unwrap();
+        assert!(written.starts_with("\u{feff}{\r\n"));
+        assert!(written.ends_with("}\r\nBody\r\n"));
+        assert!(!written.replace("\r\n", "").contains('\n'));
+    }
+}
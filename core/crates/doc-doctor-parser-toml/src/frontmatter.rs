@@ -0,0 +1,113 @@
+//! Frontmatter extraction
+//!
+//! Extracts `+++`-delimited TOML frontmatter from markdown documents.
+
+use doc_doctor_domain::{strip_bom, MetadataSpan, SourcePosition};
+
+/// Opening and closing frontmatter delimiter
+pub const DELIMITER: &str = "+++";
+
+/// Information about extracted frontmatter
+#[derive(Debug, Clone)]
+pub struct TomlFrontmatter {
+    /// The TOML content (without delimiters)
+    pub content: String,
+    /// Byte offset where TOML content starts
+    pub start_offset: usize,
+    /// Byte offset where TOML content ends
+    pub end_offset: usize,
+    /// Byte offset where the document body starts (after the closing delimiter)
+    pub body_offset: usize,
+}
+
+impl TomlFrontmatter {
+    /// Convert to domain MetadataSpan
+    pub fn to_metadata_span(&self, document: &str) -> MetadataSpan {
+        MetadataSpan {
+            content: self.content.clone(),
+            start: SourcePosition::from_offset(document, self.start_offset),
+            end: SourcePosition::from_offset(document, self.end_offset),
+        }
+    }
+}
+
+/// Extract TOML frontmatter from document content
+///
+/// A leading byte-order mark is skipped; offsets still count its bytes.
+///
+/// # Format
+/// ```text
+/// +++
+/// title = "My Document"
+/// +++
+/// # Rest of document
+/// ```
+pub fn extract_frontmatter(content: &str) -> Option<TomlFrontmatter> {
+    let bom = content.len() - strip_bom(content).len();
+    let after_opening = content[bom..].strip_prefix(DELIMITER)?;
+    let start_offset = if after_opening.starts_with('\n') {
+        bom + DELIMITER.len() + 1
+    } else if after_opening.starts_with("\r\n") {
+        bom + DELIMITER.len() + 2
+    } else {
+        return None;
+    };
+
+    let mut offset = start_offset;
+    for line in content[start_offset..].split_inclusive('\n') {
+        if line.trim_end_matches(['\r', '\n']) == DELIMITER {
+            // The newline before the closing delimiter belongs to it
+            let end_offset = content[..offset].strip_suffix('\n').map_or(offset, |s| {
+                s.strip_suffix('\r').map_or(s.len(), str::len)
+            });
+            return Some(TomlFrontmatter {
+                content: content[start_offset..end_offset.max(start_offset)].to_string(),
+                start_offset,
+                end_offset: end_offset.max(start_offset),
+                body_offset: offset + line.len(),
+            });
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_frontmatter() {
+        let content = "+++\ntitle = \"Test\"\n+++\n# Body\n";
+        let span = extract_frontmatter(content).unwrap();
+
+        assert_eq!(span.content, "title = \"Test\"");
+        assert_eq!(span.start_offset, 4);
+        assert_eq!(&content[span.body_offset..], "# Body\n");
+    }
+
+    #[test]
+    fn test_extract_crlf_and_eof() {
+        let span = extract_frontmatter("+++\r\ntitle = \"Test\"\r\n+++").unwrap();
+        assert_eq!(span.content, "title = \"Test\"");
+
+        let empty = extract_frontmatter("+++\n+++\nBody").unwrap();
+        assert_eq!(empty.content, "");
+    }
+
+    #[test]
+    fn test_extract_after_bom() {
+        let content = "\u{feff}+++\ntitle = \"Test\"\n+++\nBody";
+        let span = extract_frontmatter(content).unwrap();
+
+        assert_eq!(span.content, "title = \"Test\"");
+        assert_eq!(&content[span.body_offset..], "Body");
+    }
+
+    #[test]
+    fn test_no_frontmatter() {
+        assert!(extract_frontmatter("---\ntitle: Test\n---\n").is_none());
+        assert!(extract_frontmatter("+++\ntitle = \"Unclosed\"\n").is_none());
+    }
+}
@@ -0,0 +1,36 @@
+//! TOML Frontmatter Parser
+//!
+//! Implements the `DocumentParser` and `DocumentWriter` traits from the
+//! domain layer for `+++`-delimited TOML frontmatter, as used by Hugo and
+//! Zola sites.
+//!
+//! # Features
+//!
+//! - Extracts TOML frontmatter from markdown documents
+//! - Native TOML datetimes are read as `created`/`modified` timestamps
+//! - Reports values replaced by defaults as diagnostics
+//! - Format identifier: "toml"
+//!
+//! # Example
+//!
+//! ```rust
+//! use doc_doctor_parser_toml::TomlParser;
+//! use doc_doctor_domain::DocumentParser;
+//!
+//! let parser = TomlParser::new();
+//! let content = r#"+++
+//! title = "My Document"
+//! refinement = 0.75
+//! +++
+//! # Content
+//! "#;
+//!
+//! let props = parser.parse(content).unwrap();
+//! assert_eq!(props.title.as_deref(), Some("My Document"));
+//! ```
+
+mod frontmatter;
+mod parser;
+
+pub use frontmatter::{extract_frontmatter, TomlFrontmatter, DELIMITER};
+pub use parser::TomlParser;
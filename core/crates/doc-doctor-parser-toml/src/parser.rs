@@ -0,0 +1,258 @@
+//! TOML Parser Implementation
+//!
+//! Implements the `DocumentParser` and `DocumentWriter` traits for TOML frontmatter.
+//!
+//! TOML is converted to a JSON value before deserializing `L1Properties`, so
+//! native datetimes become RFC 3339 strings and the domain's serde
+//! definitions apply unchanged.
+
+use serde_json::Value as JsonValue;
+use toml::Value as TomlValue;
+
+use doc_doctor_domain::{
+    find_coercions, strip_bom, DocumentParser, DocumentWriter, L1Properties, MetadataSpan,
+    ParseDiagnostic, ParseError, RecoveredParse, SerializeError, SourcePosition, TextStyle,
+};
+
+use crate::frontmatter::{extract_frontmatter, TomlFrontmatter, DELIMITER};
+
+/// TOML frontmatter parser
+///
+/// Parses markdown documents with `+++`-delimited TOML frontmatter.
+#[derive(Debug, Clone, Default)]
+pub struct TomlParser;
+
+impl TomlParser {
+    /// Create a new TOML parser
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse the frontmatter into a JSON value
+    fn parse_value(&self, content: &str) -> Result<(JsonValue, TomlFrontmatter), ParseError> {
+        let span = extract_frontmatter(content).ok_or_else(|| {
+            ParseError::new("No frontmatter found in document. Expected document to start with '+++'")
+        })?;
+
+        let table: toml::Table = span.content.parse().map_err(|e: toml::de::Error| {
+            let error = ParseError::new(e.message().to_string());
+            match e.span() {
+                Some(range) => error
+                    .with_position(SourcePosition::from_offset(content, span.start_offset + range.start))
+                    .with_snippet(span.content[range].to_string()),
+                None => error,
+            }
+        })?;
+
+        Ok((toml_to_json(TomlValue::Table(table)), span))
+    }
+}
+
+impl DocumentParser for TomlParser {
+    fn parse(&self, content: &str) -> Result<L1Properties, ParseError> {
+        let (value, _) = self.parse_value(content)?;
+        serde_json::from_value(value).map_err(|e| ParseError::new(e.to_string()))
+    }
+
+    fn parse_recovering(&self, content: &str) -> Result<RecoveredParse, ParseError> {
+        let (value, _) = self.parse_value(content)?;
+        let diagnostics = serde_yaml::to_value(&value)
+            .map(|v| find_coercions(&v))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|coercion| {
+                let diagnostic = ParseDiagnostic::coerced(&coercion.path, coercion.message());
+                match coercion.hint() {
+                    Some(hint) => diagnostic.with_suggestion(hint),
+                    None => diagnostic,
+                }
+            })
+            .collect();
+
+        let properties = serde_json::from_value(value).map_err(|e| ParseError::new(e.to_string()))?;
+        Ok(RecoveredParse {
+            properties,
+            diagnostics,
+        })
+    }
+
+    fn extract_metadata(&self, content: &str) -> Option<MetadataSpan> {
+        extract_frontmatter(content).map(|s| s.to_metadata_span(content))
+    }
+
+    fn body_offset(&self, content: &str) -> usize {
+        extract_frontmatter(content).map_or(0, |s| s.body_offset)
+    }
+
+    fn format_id(&self) -> &'static str {
+        "toml"
+    }
+}
+
+impl DocumentWriter for TomlParser {
+    fn serialize_document(
+        &self,
+        original_content: &str,
+        properties: &L1Properties,
+    ) -> Result<String, SerializeError> {
+        let toml = self.serialize_frontmatter(properties)?;
+
+        let style = TextStyle::detect(original_content);
+        let frontmatter = format!("{}\n{}{}\n", DELIMITER, toml, DELIMITER);
+
+        match extract_frontmatter(original_content) {
+            Some(span) => Ok(format!(
+                "{}{}",
+                style.apply(&frontmatter),
+                &original_content[span.body_offset..]
+            )),
+            None => Ok(format!(
+                "{}{}{}",
+                style.apply(&frontmatter),
+                style.line_ending.as_str(),
+                strip_bom(original_content)
+            )),
+        }
+    }
+
+    fn serialize_frontmatter(&self, properties: &L1Properties) -> Result<String, SerializeError> {
+        let value = serde_json::to_value(properties).map_err(|e| SerializeError::new(e.to_string()))?;
+        match json_to_toml(value) {
+            Some(TomlValue::Table(mut table)) => {
+                restore_datetimes(&mut table);
+                toml::to_string(&table).map_err(|e| SerializeError::new(e.to_string()))
+            }
+            _ => Err(SerializeError::new("Properties did not serialize to a table")),
+        }
+    }
+}
+
+/// Write dates that TOML can represent natively as datetimes again
+///
+/// Dates outside TOML's grammar (e.g. `2024-03-05 14:20`) were strings in
+/// the original document and stay strings.
+fn restore_datetimes(table: &mut toml::Table) {
+    for key in ["created", "modified"] {
+        if let Some(TomlValue::String(text)) = table.get(key) {
+            if let Ok(datetime) = text.parse::<toml::value::Datetime>() {
+                table.insert(key.to_string(), TomlValue::Datetime(datetime));
+            }
+        }
+    }
+}
+
+/// Convert a TOML value to JSON; datetimes become strings
+fn toml_to_json(value: TomlValue) -> JsonValue {
+    match value {
+        TomlValue::String(s) => JsonValue::String(s),
+        TomlValue::Integer(i) => JsonValue::from(i),
+        TomlValue::Float(f) => JsonValue::from(f),
+        TomlValue::Boolean(b) => JsonValue::Bool(b),
+        TomlValue::Datetime(dt) => JsonValue::String(dt.to_string()),
+        TomlValue::Array(items) => JsonValue::Array(items.into_iter().map(toml_to_json).collect()),
+        TomlValue::Table(table) => {
+            JsonValue::Object(table.into_iter().map(|(k, v)| (k, toml_to_json(v))).collect())
+        }
+    }
+}
+
+/// Convert a JSON value to TOML; nulls have no TOML form and are dropped
+fn json_to_toml(value: JsonValue) -> Option<TomlValue> {
+    match value {
+        JsonValue::Null => None,
+        JsonValue::Bool(b) => Some(TomlValue::Boolean(b)),
+        JsonValue::Number(n) => n
+            .as_i64()
+            .map(TomlValue::Integer)
+            .or_else(|| n.as_f64().map(TomlValue::Float)),
+        JsonValue::String(s) => Some(TomlValue::String(s)),
+        JsonValue::Array(items) => Some(TomlValue::Array(
+            items.into_iter().filter_map(json_to_toml).collect(),
+        )),
+        JsonValue::Object(map) => Some(TomlValue::Table(
+            map.into_iter()
+                .filter_map(|(k, v)| json_to_toml(v).map(|v| (k, v)))
+                .collect(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_domain::Audience;
+
+    const DOCUMENT: &str = r#"+++
+title = "Hugo Page"
+refinement = 0.8
+audience = "internal"
+created = 2024-03-01T10:00:00Z
+
+[[stubs]]
+type = "link"
+description = "Citation needed"
+priority = "hgih"
++++
+# Body
+"#;
+
+    #[test]
+    fn test_parse_document() {
+        let props = TomlParser::new().parse(DOCUMENT).unwrap();
+
+        assert_eq!(props.title.as_deref(), Some("Hugo Page"));
+        assert_eq!(props.refinement.value(), 0.8);
+        assert_eq!(props.audience, Audience::Internal);
+        assert!(props.created.is_some());
+        assert_eq!(props.stubs.len(), 1);
+        assert_eq!(props.stubs[0].stub_type.as_str(), "link");
+    }
+
+    #[test]
+    fn test_parse_recovering_reports_coercions() {
+        let result = TomlParser::new().parse_recovering(DOCUMENT).unwrap();
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].field, "stubs[0].priority");
+    }
+
+    #[test]
+    fn test_syntax_error_position() {
+        let err = TomlParser::new().parse("+++\ntitle = \"Ok\"\nrefinement = = 1\n+++\n").unwrap_err();
+        assert_eq!(err.position.unwrap().line, 3);
+    }
+
+    #[test]
+    fn test_no_frontmatter() {
+        assert!(TomlParser::new().parse("---\ntitle: Yaml\n---\n").is_err());
+        assert!(TomlParser::new().extract_metadata("# Just markdown").is_none());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let parser = TomlParser::new();
+        let props = parser.parse(DOCUMENT).unwrap();
+
+        let written = parser.serialize_document(DOCUMENT, &props).unwrap();
+        assert!(written.starts_with("+++\n"));
+        assert!(written.ends_with("+++\n# Body\n"));
+        assert!(written.contains("created = 2024-03-01T10:00:00Z\n"));
+
+        let reparsed = parser.parse(&written).unwrap();
+        assert_eq!(reparsed.title, props.title);
+        assert_eq!(reparsed.created, props.created);
+        assert_eq!(reparsed.stubs.len(), 1);
+    }
+
+    #[test]
+    fn test_round_trip_crlf() {
+        let parser = TomlParser::new();
+        let content = "+++\r\ntitle = \"Windows\"\r\n+++\r\nBody\r\n";
+        let props = parser.parse(content).unwrap();
+
+        let written = parser.serialize_document(content, &props).unwrap();
+        assert!(written.starts_with("+++\r\n"));
+        assert!(written.contains("title = \"Windows\"\r\n"));
+        assert!(written.ends_with("+++\r\nBody\r\n"));
+        assert!(!written.replace("\r\n", "").contains('\n'));
+    }
+}
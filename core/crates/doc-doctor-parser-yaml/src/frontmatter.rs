@@ -2,7 +2,7 @@
 //!
 //! Extracts YAML frontmatter from markdown documents with position tracking.
 
-use doc_doctor_domain::MetadataSpan;
+use doc_doctor_domain::{strip_bom, MetadataSpan};
 
 /// Information about extracted frontmatter
 #[derive(Debug, Clone)]
@@ -30,6 +30,20 @@ impl FrontmatterSpan {
             end: SourcePosition::new(self.end_line, 1, self.end_offset),
         }
     }
+
+    /// Byte offset where the body starts, after the closing `---` line
+    pub fn body_offset(&self, document: &str) -> usize {
+        // The YAML content ends before "\n---"
+        let after_delimiter = (self.end_offset + 4).min(document.len());
+        let rest = &document[after_delimiter..];
+        if rest.starts_with("\r\n") {
+            after_delimiter + 2
+        } else if rest.starts_with('\n') {
+            after_delimiter + 1
+        } else {
+            after_delimiter
+        }
+    }
 }
 
 /// Extract frontmatter from document content
@@ -41,6 +55,8 @@ impl FrontmatterSpan {
 /// * `Some(FrontmatterSpan)` if valid frontmatter found
 /// * `None` if no frontmatter or invalid delimiters
 ///
+/// A leading byte-order mark is skipped; offsets still count its bytes.
+///
 /// # Format
 /// ```text
 /// ---
@@ -50,19 +66,21 @@ impl FrontmatterSpan {
 /// # Rest of document
 /// ```
 pub fn extract_frontmatter(content: &str) -> Option<FrontmatterSpan> {
+    let bom = content.len() - strip_bom(content).len();
+
     // Must start with "---"
-    if !content.starts_with("---") {
+    if !content[bom..].starts_with("---") {
         return None;
     }
 
     // Find the position after opening delimiter
-    let after_opening = &content[3..];
+    let after_opening = &content[bom + 3..];
 
     // Must have newline after opening ---
     let yaml_start = if after_opening.starts_with('\n') {
-        4 // "---\n"
+        bom + 4 // "---\n"
     } else if after_opening.starts_with("\r\n") {
-        5 // "---\r\n"
+        bom + 5 // "---\r\n"
     } else {
         return None; // Invalid: no newline after opening ---
     };
@@ -136,6 +154,7 @@ mod tests {
         assert_eq!(span.content, "title: Test\nrefinement: 0.5");
         assert_eq!(span.start_offset, 4);
         assert_eq!(span.start_line, 2);
+        assert_eq!(&content[span.body_offset(content)..], "# Content");
     }
 
     #[test]
@@ -145,6 +164,17 @@ mod tests {
         let span = extract_frontmatter(content).unwrap();
         // Windows line endings include \r in the content
         assert!(span.content.contains("title: Test"));
+        assert_eq!(&content[span.body_offset(content)..], "Content");
+    }
+
+    #[test]
+    fn test_extract_after_bom() {
+        let content = "\u{feff}---\ntitle: Test\n---\nContent";
+
+        let span = extract_frontmatter(content).unwrap();
+        assert_eq!(span.content, "title: Test");
+        assert_eq!(span.start_offset, 7);
+        assert_eq!(&content[span.body_offset(content)..], "Content");
     }
 
     #[test]
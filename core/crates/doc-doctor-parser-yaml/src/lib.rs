@@ -8,6 +8,10 @@
 //! - Extracts YAML frontmatter from markdown documents
 //! - Provides accurate line/column positions for error reporting
 //! - Supports both compact (`- link: "desc"`) and structured stub syntax
+//! - Error recovery: malformed fields and stubs become diagnostics instead
+//!   of failing the whole document
+//! - Strict lint: duplicate keys, unquoted wikilinks, block anchors and tab
+//!   indentation, with automatic fixes
 //! - Format identifier: "yaml"
 //!
 //! # Example
@@ -30,10 +34,12 @@
 
 mod error;
 mod frontmatter;
+mod lint;
 mod parser;
 mod position;
+mod recovery;
 
 pub use error::{YamlParseError, YamlParseErrorKind};
-pub use frontmatter::{extract_frontmatter, FrontmatterSpan};
+pub use frontmatter::{extract_frontmatter, extract_raw_frontmatter, FrontmatterSpan};
 pub use parser::YamlParser;
 pub use position::PositionTracker;
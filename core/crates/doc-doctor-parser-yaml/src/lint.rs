@@ -0,0 +1,406 @@
+//! Strict YAML Lint
+//!
+//! Line-based checks for frontmatter hazards that serde_yaml either rejects
+//! with an unhelpful message or accepts with a surprising meaning:
+//!
+//! - Duplicate keys in one mapping
+//! - Unquoted `[[wikilinks]]`, which parse as nested sequences
+//! - Unquoted or malformed `^block-anchor` values
+//! - Tab indentation
+//!
+//! The checks work on the raw text, so they also explain documents that
+//! fail to parse.
+
+use std::collections::HashMap;
+
+use doc_doctor_domain::{LintFinding, LintRule, TextEdit};
+
+use crate::position::PositionTracker;
+
+/// Columns a tab is counted as, and replaced by when fixing indentation
+const TAB_WIDTH: usize = 2;
+
+/// Lint frontmatter YAML
+///
+/// # Arguments
+/// * `yaml` - Frontmatter content (without delimiters)
+/// * `tracker` - Position tracker for the full document
+/// * `base` - Byte offset of the frontmatter content in the document
+pub(crate) fn lint(yaml: &str, tracker: &PositionTracker, base: usize) -> Vec<LintFinding> {
+    Linter::new(tracker, base).run(yaml)
+}
+
+/// One mapping level: key indent and the keys seen so far (with their offset)
+struct Scope {
+    indent: usize,
+    keys: HashMap<String, usize>,
+}
+
+struct Linter<'t, 'a> {
+    tracker: &'t PositionTracker<'a>,
+    base: usize,
+    findings: Vec<LintFinding>,
+    scopes: Vec<Scope>,
+    /// Indent of the key owning a `|` or `>` block scalar being skipped
+    block_scalar: Option<usize>,
+    /// Current top-level key
+    top_key: Option<String>,
+    /// Indent and index of the current `stubs` item
+    stub_item: Option<(usize, usize)>,
+}
+
+impl<'t, 'a> Linter<'t, 'a> {
+    fn new(tracker: &'t PositionTracker<'a>, base: usize) -> Self {
+        Self {
+            tracker,
+            base,
+            findings: Vec::new(),
+            scopes: Vec::new(),
+            block_scalar: None,
+            top_key: None,
+            stub_item: None,
+        }
+    }
+
+    fn run(mut self, yaml: &str) -> Vec<LintFinding> {
+        let mut offset = 0;
+        for raw in yaml.split('\n') {
+            self.line(offset, raw.strip_suffix('\r').unwrap_or(raw));
+            offset += raw.len() + 1;
+        }
+        self.findings
+    }
+
+    fn line(&mut self, offset: usize, line: &str) {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            return;
+        }
+
+        let leading = &line[..line.len() - trimmed.len()];
+        let indent = leading.chars().map(|c| if c == '\t' { TAB_WIDTH } else { 1 }).sum::<usize>();
+
+        if let Some(owner) = self.block_scalar {
+            if indent > owner {
+                return;
+            }
+            self.block_scalar = None;
+        }
+
+        if leading.contains('\t') {
+            self.tab_indentation(offset, leading);
+        }
+
+        // Strip sequence markers; each one opens a new item one level deeper
+        let mut column = indent;
+        let mut rest = trimmed;
+        let mut rest_offset = offset + leading.len();
+        let mut is_item = false;
+        while rest == "-" || rest.starts_with("- ") {
+            let after = rest[1..].trim_start();
+            let skipped = rest.len() - after.len();
+            column += skipped;
+            rest_offset += skipped;
+            rest = after;
+            is_item = true;
+        }
+
+        if is_item {
+            self.open_item(indent, column);
+        } else {
+            self.close_scopes(column);
+        }
+
+        match split_key(rest) {
+            Some((key, value_start)) => {
+                let value = rest[value_start..].trim_start();
+                let value_offset = rest_offset + rest.len() - value.len();
+                let field = self.field_path(column, &key);
+
+                self.check_duplicate(&key, column, rest_offset, &field);
+                if column == 0 {
+                    self.top_key = Some(key);
+                }
+                if is_block_scalar(value) {
+                    self.block_scalar = Some(column);
+                }
+                self.check_value(strip_comment(value), value_offset, &field);
+            }
+            None if !rest.is_empty() => {
+                let field = self.top_key.clone().unwrap_or_default();
+                self.check_value(strip_comment(rest), rest_offset, &field);
+            }
+            None => {}
+        }
+    }
+
+    /// A sequence item starts a fresh mapping at its content column
+    fn open_item(&mut self, indent: usize, column: usize) {
+        self.scopes.retain(|s| s.indent < column);
+        self.scopes.push(Scope {
+            indent: column,
+            keys: HashMap::new(),
+        });
+
+        if self.top_key.as_deref() == Some("stubs") {
+            self.stub_item = match self.stub_item {
+                Some((item_indent, index)) if item_indent == indent => Some((indent, index + 1)),
+                Some(current) if indent > current.0 => Some(current),
+                _ => Some((indent, 0)),
+            };
+        }
+    }
+
+    fn close_scopes(&mut self, column: usize) {
+        self.scopes.retain(|s| s.indent <= column);
+        if column == 0 {
+            self.stub_item = None;
+        }
+    }
+
+    fn field_path(&self, column: usize, key: &str) -> String {
+        match (&self.top_key, self.stub_item) {
+            (_, _) if column == 0 => key.to_string(),
+            (Some(top), Some((_, index))) if top == "stubs" => format!("stubs[{}].{}", index, key),
+            (Some(top), _) => format!("{}.{}", top, key),
+            (None, _) => key.to_string(),
+        }
+    }
+
+    fn check_duplicate(&mut self, key: &str, column: usize, offset: usize, field: &str) {
+        if self.scopes.last().is_none_or(|s| s.indent < column) {
+            self.scopes.push(Scope {
+                indent: column,
+                keys: HashMap::new(),
+            });
+        }
+        let scope = self.scopes.last_mut().expect("scope was just ensured");
+
+        match scope.keys.get(key) {
+            Some(&first) => {
+                let first = self.tracker.frontmatter_position(first).line;
+                self.findings.push(
+                    LintFinding::new(
+                        LintRule::DuplicateKey,
+                        format!("Duplicate key '{}' (first defined on line {})", key, first),
+                    )
+                    .with_field(field)
+                    .with_position(self.tracker.frontmatter_position(offset))
+                    .with_suggestion(format!(
+                        "Remove one of the '{}' entries or merge them",
+                        key
+                    )),
+                );
+            }
+            None => {
+                scope.keys.insert(key.to_string(), offset);
+            }
+        }
+    }
+
+    fn check_value(&mut self, value: &str, offset: usize, field: &str) {
+        if value.starts_with("[[") {
+            let quoted = quote(value);
+            self.findings.push(
+                LintFinding::new(
+                    LintRule::UnquotedWikilink,
+                    format!("Unquoted wikilink {} parses as a nested list", value),
+                )
+                .with_field(field)
+                .with_position(self.tracker.frontmatter_position(offset))
+                .with_suggestion(format!("Quote it: {}", quoted))
+                .with_fix(TextEdit::new(self.base + offset, value.len(), quoted)),
+            );
+            return;
+        }
+
+        let (inner, is_quoted) = unquote(value);
+        let Some(id) = inner.strip_prefix('^') else {
+            return;
+        };
+
+        if !is_valid_anchor(id) {
+            let sanitized = sanitize_anchor(id);
+            let mut finding = LintFinding::new(
+                LintRule::BlockAnchor,
+                format!(
+                    "Block anchor '^{}' is not valid; ids may only contain letters, digits and hyphens",
+                    id
+                ),
+            )
+            .with_field(field)
+            .with_position(self.tracker.frontmatter_position(offset));
+
+            if !sanitized.is_empty() {
+                let replacement = quote(&format!("^{}", sanitized));
+                finding = finding
+                    .with_suggestion(format!("Use {}", replacement))
+                    .with_fix(TextEdit::new(self.base + offset, value.len(), replacement));
+            }
+            self.findings.push(finding);
+        } else if !is_quoted {
+            let quoted = quote(value);
+            self.findings.push(
+                LintFinding::new(LintRule::BlockAnchor, format!("Unquoted block anchor {}", value))
+                    .with_field(field)
+                    .with_position(self.tracker.frontmatter_position(offset))
+                    .with_suggestion(format!("Quote it: {}", quoted))
+                    .with_fix(TextEdit::new(self.base + offset, value.len(), quoted)),
+            );
+        }
+    }
+
+    fn tab_indentation(&mut self, offset: usize, leading: &str) {
+        let tab = leading.find('\t').unwrap_or(0);
+        let spaces = leading.replace('\t', &" ".repeat(TAB_WIDTH));
+        self.findings.push(
+            LintFinding::new(LintRule::TabIndentation, "Indentation uses a tab character")
+                .with_position(self.tracker.frontmatter_position(offset + tab))
+                .with_suggestion("Indent with spaces; YAML does not allow tabs")
+                .with_fix(TextEdit::new(self.base + offset, leading.len(), spaces)),
+        );
+    }
+}
+
+/// Split `key: value` into the key and the byte index where the value begins
+fn split_key(text: &str) -> Option<(String, usize)> {
+    if let Some(quote) = text.chars().next().filter(|c| *c == '"' || *c == '\'') {
+        let close = text[1..].find(quote)? + 1;
+        let after = &text[close + 1..];
+        let value = after.strip_prefix(':')?;
+        if !(value.is_empty() || value.starts_with(' ')) {
+            return None;
+        }
+        return Some((text[1..close].to_string(), close + 2));
+    }
+
+    if text.starts_with(['[', '{', '&', '*', '!', '|', '>']) {
+        return None;
+    }
+
+    let colon = text
+        .match_indices(':')
+        .map(|(i, _)| i)
+        .find(|i| text[i + 1..].is_empty() || text[i + 1..].starts_with(' '))?;
+    Some((text[..colon].trim_end().to_string(), colon + 1))
+}
+
+fn is_block_scalar(value: &str) -> bool {
+    let value = strip_comment(value);
+    value.starts_with('|') || value.starts_with('>')
+}
+
+/// Drop a trailing ` # comment` from an unquoted value
+fn strip_comment(value: &str) -> &str {
+    if value.starts_with(['"', '\'']) {
+        return value.trim_end();
+    }
+    match value.find(" #") {
+        Some(i) => value[..i].trim_end(),
+        None => value.trim_end(),
+    }
+}
+
+/// Get the text of a possibly quoted scalar, and whether it was quoted
+fn unquote(value: &str) -> (&str, bool) {
+    for quote in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            return (&value[1..value.len() - 1], true);
+        }
+    }
+    (value, false)
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn is_valid_anchor(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// Replace runs of invalid characters with a single hyphen
+fn sanitize_anchor(id: &str) -> String {
+    let mut sanitized = String::new();
+    for c in id.chars() {
+        if c.is_ascii_alphanumeric() {
+            sanitized.push(c);
+        } else if !sanitized.ends_with('-') {
+            sanitized.push('-');
+        }
+    }
+    sanitized.trim_matches('-').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use doc_doctor_domain::apply_fixes;
+
+    fn lint_doc(content: &str) -> Vec<LintFinding> {
+        let yaml_end = content[4..].find("\n---").unwrap() + 4;
+        let tracker = PositionTracker::new(content, 4);
+        lint(&content[4..yaml_end], &tracker, 4)
+    }
+
+    #[test]
+    fn test_duplicate_keys() {
+        let content = "---\ntitle: A\nstubs:\n  - type: link\n    priority: high\n    priority: low\n  - type: link\ntitle: B\n---\n";
+        let findings = lint_doc(content);
+
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].rule, LintRule::DuplicateKey);
+        assert_eq!(findings[0].field.as_deref(), Some("stubs[0].priority"));
+        assert_eq!(findings[0].position.unwrap().line, 6);
+        assert!(findings[1].message.contains("first defined on line 2"));
+        assert_eq!(findings[1].position.unwrap().line, 8);
+    }
+
+    #[test]
+    fn test_sibling_items_are_not_duplicates() {
+        let content = "---\nstubs:\n  - type: link\n    description: a\n  - type: expand\n    description: b\nnotes: |\n  title: x\n  title: y\n---\n";
+        assert!(lint_doc(content).is_empty());
+    }
+
+    #[test]
+    fn test_unquoted_wikilink_fix() {
+        let content = "---\nrelated: [[Other Note]]\nlinks:\n  - [[A]]\n  - \"[[B]]\"\n---\n";
+        let findings = lint_doc(content);
+
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].rule, LintRule::UnquotedWikilink);
+        assert_eq!(findings[0].position.unwrap().column, 10);
+
+        let (fixed, applied) = apply_fixes(content, &findings);
+        assert_eq!(applied, 2);
+        assert_eq!(fixed, "---\nrelated: \"[[Other Note]]\"\nlinks:\n  - \"[[A]]\"\n  - \"[[B]]\"\n---\n");
+    }
+
+    #[test]
+    fn test_block_anchors() {
+        let content = "---\nstubs:\n  - type: link\n    anchor: ^ok-1\n  - type: link\n    anchor: \"^bad id!\"\n  - type: link\n    anchor: \"^fine\"\n---\n";
+        let findings = lint_doc(content);
+
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].field.as_deref(), Some("stubs[0].anchor"));
+        assert!(findings[0].message.starts_with("Unquoted block anchor"));
+        assert_eq!(findings[1].field.as_deref(), Some("stubs[1].anchor"));
+
+        let (fixed, _) = apply_fixes(content, &findings);
+        assert!(fixed.contains("anchor: \"^ok-1\""));
+        assert!(fixed.contains("anchor: \"^bad-id\""));
+    }
+
+    #[test]
+    fn test_tab_indentation() {
+        let content = "---\nstubs:\n\t- type: link\n---\n";
+        let findings = lint_doc(content);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, LintRule::TabIndentation);
+        assert_eq!(findings[0].position.unwrap().line, 3);
+
+        let (fixed, _) = apply_fixes(content, &findings);
+        assert_eq!(fixed, "---\nstubs:\n  - type: link\n---\n");
+    }
+}
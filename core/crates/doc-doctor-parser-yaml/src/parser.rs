@@ -3,13 +3,15 @@
 //! Implements the `DocumentParser` and `DocumentWriter` traits for YAML frontmatter.
 
 use doc_doctor_domain::{
-    DocumentParser, DocumentWriter, L1Properties, MetadataSpan, ParseError, SerializeError,
-    SourcePosition,
+    strip_bom, DocumentParser, DocumentWriter, L1Properties, LintFinding, MetadataSpan,
+    ParseError, RecoveredParse, SerializeError, SourcePosition, TextStyle,
 };
 
 use crate::error::{YamlParseError, YamlParseErrorKind};
 use crate::frontmatter::{extract_frontmatter, FrontmatterSpan};
-use crate::position::PositionTracker;
+use crate::lint::lint;
+use crate::position::{without_location, PositionTracker};
+use crate::recovery::{audit, recover};
 
 /// YAML frontmatter parser
 ///
@@ -38,6 +40,7 @@ impl YamlParser {
     }
 
     /// Parse frontmatter and return detailed result
+    #[allow(clippy::result_large_err)]
     pub fn parse_detailed(&self, content: &str) -> Result<ParseResult, YamlParseError> {
         // Extract frontmatter
         let span = extract_frontmatter(content)
@@ -65,6 +68,7 @@ impl YamlParser {
     }
 
     /// Check for unknown fields in YAML
+    #[allow(clippy::result_large_err)]
     fn check_unknown_fields(
         &self,
         yaml_content: &str,
@@ -148,9 +152,15 @@ impl YamlParser {
             tracker.extract_snippet(span.start_offset + loc.index(), 30)
         });
 
+        // The position is in document coordinates; serde's own is not
+        let message = match position {
+            Some(_) => without_location(&err.to_string()),
+            None => err.to_string(),
+        };
+
         YamlParseError {
             kind: YamlParseErrorKind::YamlSyntax,
-            message: err.to_string(),
+            message,
             position,
             snippet,
             field: None,
@@ -166,10 +176,44 @@ impl DocumentParser for YamlParser {
             .map_err(|e| e.into())
     }
 
+    fn parse_recovering(&self, content: &str) -> Result<RecoveredParse, ParseError> {
+        let strict = self.parse_detailed(content);
+
+        // Only a readable frontmatter block can be recovered or audited entry by entry
+        let Some(span) = extract_frontmatter(content) else {
+            return strict
+                .map(|r| RecoveredParse::complete(r.properties))
+                .map_err(|e| e.into());
+        };
+        let tracker = PositionTracker::new(content, span.start_offset);
+
+        match strict {
+            Ok(result) => Ok(RecoveredParse {
+                properties: result.properties,
+                diagnostics: audit(&span.content, &tracker),
+            }),
+            Err(err) => recover(&span.content, &tracker).map_err(|_| err.into()),
+        }
+    }
+
+    fn lint(&self, content: &str) -> Vec<LintFinding> {
+        match extract_frontmatter(content) {
+            Some(span) => {
+                let tracker = PositionTracker::new(content, span.start_offset);
+                lint(&span.content, &tracker, span.start_offset)
+            }
+            None => Vec::new(),
+        }
+    }
+
     fn extract_metadata(&self, content: &str) -> Option<MetadataSpan> {
         extract_frontmatter(content).map(|s| s.to_metadata_span())
     }
 
+    fn body_offset(&self, content: &str) -> usize {
+        extract_frontmatter(content).map_or(0, |s| s.body_offset(content))
+    }
+
     fn format_id(&self) -> &'static str {
         "yaml"
     }
@@ -184,36 +228,21 @@ impl DocumentWriter for YamlParser {
         // Serialize frontmatter
         let yaml = self.serialize_frontmatter(properties)?;
 
-        // Extract the current frontmatter span to find where to replace
-        if let Some(span) = extract_frontmatter(original_content) {
-            // Get content after frontmatter
-            // Find the end of the closing delimiter
-            let after_frontmatter_start = span.end_offset;
-
-            // Find the closing delimiter and skip past it
-            let rest_of_content = &original_content[after_frontmatter_start..];
-            let body_start = if rest_of_content.starts_with("\n---\n") {
-                after_frontmatter_start + 5
-            } else if rest_of_content.starts_with("\n---\r\n") {
-                after_frontmatter_start + 6
-            } else if rest_of_content.starts_with("\n---") {
-                // End of file case
-                after_frontmatter_start + 4
-            } else {
-                // Just newline
-                after_frontmatter_start
-            };
-
-            let body = if body_start < original_content.len() {
-                &original_content[body_start..]
-            } else {
-                ""
-            };
-
-            Ok(format!("---\n{}\n---\n{}", yaml, body))
-        } else {
+        // New frontmatter follows the original's BOM and line endings;
+        // the body is kept byte for byte
+        let style = TextStyle::detect(original_content);
+        match extract_frontmatter(original_content) {
+            Some(span) => Ok(format!(
+                "{}{}",
+                style.apply(&format!("---\n{}\n---\n", yaml)),
+                &original_content[span.body_offset(original_content)..]
+            )),
             // No existing frontmatter - prepend new one
-            Ok(format!("---\n{}\n---\n\n{}", yaml, original_content))
+            None => Ok(format!(
+                "{}{}",
+                style.apply(&format!("---\n{}\n---\n\n", yaml)),
+                strip_bom(original_content)
+            )),
         }
     }
 
@@ -304,6 +333,13 @@ stubs:
 
         let err = parser.parse(content).unwrap_err();
         assert!(!err.message.is_empty());
+
+        // Located in the document, without serde's frontmatter-relative location
+        let content = "---\ntitle: A\naudience: public\nstubs:\n\t- bad\n---\nBody\n";
+        let err = parser.parse(content).unwrap_err();
+        assert_eq!(err.position.unwrap().line, 5);
+        assert!(err.message.starts_with("found character that cannot start any token"));
+        assert!(!err.message.contains(" at line "), "{}", err.message);
     }
 
     #[test]
@@ -315,6 +351,57 @@ stubs:
         assert!(result.warnings.iter().any(|w| w.field.as_deref() == Some("custom_field")));
     }
 
+    #[test]
+    fn test_parse_recovering_keeps_valid_stubs() {
+        let parser = YamlParser::new();
+        let content = r#"---
+title: Partly Broken
+stubs:
+  - link: "Citation needed"
+  - [not, a, stub]
+  - expand: "Add more detail"
+---
+# Content"#;
+
+        assert!(parser.parse(content).is_err());
+
+        let result = parser.parse_recovering(content).unwrap();
+        assert!(result.is_partial());
+        assert_eq!(result.properties.title.as_deref(), Some("Partly Broken"));
+        assert_eq!(result.properties.stubs.len(), 2);
+        assert_eq!(result.diagnostics[0].field, "stubs[1]");
+        // Line 5 of the document (the opening delimiter is line 1)
+        assert_eq!(result.diagnostics[0].position.unwrap().line, 5);
+    }
+
+    #[test]
+    fn test_parse_recovering_valid_document() {
+        let parser = YamlParser::new();
+        let result = parser.parse_recovering("---\ntitle: Fine\n---\n").unwrap();
+        assert!(!result.is_partial());
+    }
+
+    #[test]
+    fn test_parse_recovering_reports_coercions() {
+        let parser = YamlParser::new();
+        let content = "---\ntitle: Typo\nstubs:\n  - type: link\n    priority: hgih\n---\n";
+
+        let result = parser.parse_recovering(content).unwrap();
+        assert!(!result.is_partial());
+        assert_eq!(result.properties.stubs.len(), 1);
+
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.field, "stubs[0].priority");
+        assert_eq!(diagnostic.position.unwrap().line, 5);
+        assert_eq!(diagnostic.suggestion.as_deref(), Some("Did you mean 'high'?"));
+    }
+
+    #[test]
+    fn test_parse_recovering_invalid_syntax_fails() {
+        let parser = YamlParser::new();
+        assert!(parser.parse_recovering("---\ntitle: [invalid yaml\n---\n").is_err());
+    }
+
     #[test]
     fn test_format_id() {
         let parser = YamlParser::new();
@@ -366,4 +453,19 @@ stubs:
         assert_eq!(props.audience, Audience::Personal);
         assert!(props.stubs.is_empty());
     }
+
+    #[test]
+    fn test_serialize_preserves_bom_and_crlf() {
+        let parser = YamlParser::new();
+        let content = "\u{feff}---\r\ntitle: Windows\r\nrefinement: 0.5\r\n---\r\n# Body\r\nText\r\n";
+
+        let props = parser.parse(content).unwrap();
+        assert_eq!(props.title.as_deref(), Some("Windows"));
+
+        let output = parser.serialize_document(content, &props).unwrap();
+        assert!(output.starts_with("\u{feff}---\r\n"));
+        assert!(output.ends_with("---\r\n# Body\r\nText\r\n"));
+        assert!(!output.replace("\r\n", "").contains('\n'));
+        assert_eq!(parser.parse(&output).unwrap().title.as_deref(), Some("Windows"));
+    }
 }
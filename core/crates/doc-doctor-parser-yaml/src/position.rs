@@ -122,10 +122,55 @@ impl<'a> PositionTracker<'a> {
     }
 }
 
+/// A serde_yaml message without its ` at line N column M` location
+///
+/// serde_yaml counts from the start of the frontmatter; once an error is
+/// relocated to document coordinates its own location would contradict them.
+pub(crate) fn without_location(message: &str) -> String {
+    let mut rest = message;
+    let mut out = String::with_capacity(message.len());
+    while let Some(start) = rest.find(" at line ") {
+        let after = &rest[start + " at line ".len()..];
+        let line_len = after.len() - after.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let column = after[line_len..].strip_prefix(" column ");
+        let column_len = column.map_or(0, |c| {
+            c.len() - c.trim_start_matches(|c: char| c.is_ascii_digit()).len()
+        });
+        match column {
+            Some(_) if line_len > 0 && column_len > 0 => {
+                out.push_str(&rest[..start]);
+                rest = &after[line_len + " column ".len() + column_len..];
+            }
+            _ => {
+                out.push_str(&rest[..start + " at line ".len()]);
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_without_location() {
+        assert_eq!(
+            without_location(
+                "found character that cannot start any token at line 4 column 1, \
+                 while scanning for the next token"
+            ),
+            "found character that cannot start any token, while scanning for the next token"
+        );
+        assert_eq!(
+            without_location("invalid type: string \"x\", expected f64 at line 2 column 13"),
+            "invalid type: string \"x\", expected f64"
+        );
+        assert_eq!(without_location("title: at line one"), "title: at line one");
+    }
+
     #[test]
     fn test_position_from_offset() {
         let content = "---\ntitle: Test\nrefinement: 0.5\n---\n";
@@ -0,0 +1,336 @@
+//! Error-Recovering Parsing
+//!
+//! Rebuilds `L1Properties` entry by entry when strict deserialization fails,
+//! so one malformed stub or field does not hide the rest of the document.
+//! Values that parse but are silently replaced by defaults are reported too.
+
+use serde_yaml::{Mapping, Value};
+
+use doc_doctor_domain::{find_coercions, L1Properties, ParseDiagnostic, RecoveredParse, Stub};
+
+use crate::error::{YamlParseError, YamlParseErrorKind};
+use crate::position::{without_location, PositionTracker};
+
+const STUBS_KEY: &str = "stubs";
+
+/// Recover properties from frontmatter YAML that failed strict parsing
+///
+/// Each top-level field is checked on its own and each stub is read
+/// separately; entries that fail become diagnostics. Returns an error only
+/// when the YAML itself is unreadable or is not a mapping.
+#[allow(clippy::result_large_err)]
+pub(crate) fn recover(
+    yaml: &str,
+    tracker: &PositionTracker,
+) -> Result<RecoveredParse, YamlParseError> {
+    let value: Value = serde_yaml::from_str(yaml)
+        .map_err(|e| YamlParseError::yaml_syntax(e.to_string()))?;
+
+    let map = match value {
+        Value::Mapping(map) => map,
+        Value::Null => Mapping::new(),
+        _ => {
+            return Err(YamlParseError::new(
+                YamlParseErrorKind::TypeMismatch,
+                "Frontmatter must be a mapping of fields",
+            ))
+        }
+    };
+
+    let locator = EntryLocator::new(yaml);
+    let mut diagnostics = Vec::new();
+    let mut cleaned = Mapping::new();
+    let mut stubs = Vec::new();
+    let coercions = coercion_diagnostics(&Value::Mapping(map.clone()), &locator, tracker);
+
+    for (key, value) in map {
+        let name = match &key {
+            Value::String(s) => s.clone(),
+            other => serde_yaml::to_string(other).unwrap_or_default().trim().to_string(),
+        };
+
+        if name == STUBS_KEY {
+            stubs = recover_stubs(value, &locator, tracker, &mut diagnostics);
+            continue;
+        }
+
+        let mut single = Mapping::new();
+        single.insert(key.clone(), value.clone());
+        match serde_yaml::from_value::<L1Properties>(Value::Mapping(single)) {
+            Ok(_) => {
+                cleaned.insert(key, value);
+            }
+            Err(e) => {
+                diagnostics.push(diagnostic(&name, e, locator.field(&name), tracker));
+            }
+        }
+    }
+
+    let mut properties: L1Properties = serde_yaml::from_value(Value::Mapping(cleaned))
+        .map_err(|e| YamlParseError::yaml_syntax(e.to_string()))?;
+    properties.stubs = stubs;
+
+    Ok(RecoveredParse {
+        properties,
+        diagnostics: merge_coercions(diagnostics, coercions),
+    })
+}
+
+/// Report values in valid frontmatter that are replaced by defaults
+pub(crate) fn audit(yaml: &str, tracker: &PositionTracker) -> Vec<ParseDiagnostic> {
+    match serde_yaml::from_str::<Value>(yaml) {
+        Ok(value) => coercion_diagnostics(&value, &EntryLocator::new(yaml), tracker),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn coercion_diagnostics(
+    value: &Value,
+    locator: &EntryLocator,
+    tracker: &PositionTracker,
+) -> Vec<ParseDiagnostic> {
+    find_coercions(value)
+        .into_iter()
+        .map(|coercion| {
+            let mut diagnostic = ParseDiagnostic::coerced(&coercion.path, coercion.message());
+            if let Some(hint) = coercion.hint() {
+                diagnostic = diagnostic.with_suggestion(hint);
+            }
+            match locator.path(&coercion.path) {
+                Some(offset) => diagnostic.with_position(tracker.frontmatter_position(offset)),
+                None => diagnostic,
+            }
+        })
+        .collect()
+}
+
+/// Combine skipped entries with coercions
+///
+/// A strictly-typed field (e.g. `audience`) that was skipped keeps its
+/// skipped diagnostic and gains the coercion's suggestion; coercions inside
+/// skipped stubs are dropped.
+fn merge_coercions(
+    mut skipped: Vec<ParseDiagnostic>,
+    coercions: Vec<ParseDiagnostic>,
+) -> Vec<ParseDiagnostic> {
+    for coercion in coercions {
+        if let Some(existing) = skipped.iter_mut().find(|d| d.field == coercion.field) {
+            if existing.suggestion.is_none() {
+                existing.suggestion = coercion.suggestion;
+            }
+            continue;
+        }
+        let inside_skipped = skipped
+            .iter()
+            .any(|d| coercion.field.starts_with(&format!("{}.", d.field)));
+        if !inside_skipped {
+            skipped.push(coercion);
+        }
+    }
+    skipped
+}
+
+/// Read each stub on its own, skipping the malformed ones
+fn recover_stubs(
+    value: Value,
+    locator: &EntryLocator,
+    tracker: &PositionTracker,
+    diagnostics: &mut Vec<ParseDiagnostic>,
+) -> Vec<Stub> {
+    let items = match value {
+        Value::Sequence(items) => items,
+        Value::Null => return Vec::new(),
+        other => {
+            let err = serde_yaml::from_value::<Vec<Stub>>(other).err();
+            if let Some(e) = err {
+                diagnostics.push(diagnostic(STUBS_KEY, e, locator.field(STUBS_KEY), tracker));
+            }
+            return Vec::new();
+        }
+    };
+
+    let offsets = locator.stub_items();
+    let mut stubs = Vec::new();
+
+    for (i, item) in items.into_iter().enumerate() {
+        match serde_yaml::from_value::<Stub>(item) {
+            Ok(stub) => stubs.push(stub),
+            Err(e) => {
+                let offset = offsets.get(i).copied().or_else(|| locator.field(STUBS_KEY));
+                diagnostics.push(diagnostic(&format!("stubs[{}]", i), e, offset, tracker));
+            }
+        }
+    }
+
+    stubs
+}
+
+fn diagnostic(
+    field: &str,
+    err: serde_yaml::Error,
+    offset: Option<usize>,
+    tracker: &PositionTracker,
+) -> ParseDiagnostic {
+    match offset {
+        Some(offset) => ParseDiagnostic::new(field, without_location(&err.to_string()))
+            .with_position(tracker.frontmatter_position(offset)),
+        None => ParseDiagnostic::new(field, err.to_string()),
+    }
+}
+
+/// Finds the byte offsets of top-level keys and `stubs` items in block YAML
+struct EntryLocator<'a> {
+    yaml: &'a str,
+}
+
+impl<'a> EntryLocator<'a> {
+    fn new(yaml: &'a str) -> Self {
+        Self { yaml }
+    }
+
+    /// Lines with their byte offset
+    fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> {
+        let mut offset = 0;
+        self.yaml.split('\n').map(move |line| {
+            let start = offset;
+            offset += line.len() + 1;
+            (start, line)
+        })
+    }
+
+    /// Offset of a top-level key
+    fn field(&self, name: &str) -> Option<usize> {
+        self.lines().find_map(|(offset, line)| {
+            let key = line.split(':').next()?;
+            let key = key.trim_matches(|c| c == '"' || c == '\'');
+            (key == name && line.contains(':')).then_some(offset)
+        })
+    }
+
+    /// Offset of a field path (`origin`, `stubs[1]`, `stubs[1].priority`)
+    fn path(&self, path: &str) -> Option<usize> {
+        let Some(rest) = path.strip_prefix("stubs[") else {
+            return self.field(path);
+        };
+        let (index, field) = rest.split_once(']')?;
+        let index: usize = index.parse().ok()?;
+        let items = self.stub_items();
+        let start = *items.get(index)?;
+
+        let Some(field) = field.strip_prefix('.') else {
+            return Some(start);
+        };
+        let end = items.get(index + 1).copied().unwrap_or(self.yaml.len());
+
+        self.lines()
+            .filter(|(offset, line)| *offset + line.len() >= start && *offset < end)
+            .find_map(|(offset, line)| {
+                let trimmed = line.trim_start();
+                let entry = trimmed.trim_start_matches("- ").trim_start();
+                entry
+                    .starts_with(&format!("{}:", field))
+                    .then(|| offset + line.len() - entry.len())
+            })
+            .or(Some(start))
+    }
+
+    /// Offsets of the items of a block-style `stubs` sequence, in order
+    fn stub_items(&self) -> Vec<usize> {
+        let Some(start) = self.field(STUBS_KEY) else {
+            return Vec::new();
+        };
+
+        let mut items = Vec::new();
+        let mut item_indent = None;
+
+        for (offset, line) in self.lines().skip_while(|(o, _)| *o <= start) {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let indent = line.len() - trimmed.len();
+            let is_item = trimmed == "-" || trimmed.starts_with("- ");
+
+            // Next top-level key ends the block
+            if indent == 0 && !is_item {
+                break;
+            }
+
+            if is_item {
+                let expected = *item_indent.get_or_insert(indent);
+                if indent == expected {
+                    items.push(offset + indent);
+                }
+            }
+        }
+
+        items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recover_str(yaml: &str) -> RecoveredParse {
+        let tracker = PositionTracker::new(yaml, 0);
+        recover(yaml, &tracker).unwrap()
+    }
+
+    #[test]
+    fn test_malformed_stub_is_skipped() {
+        let yaml = "title: Doc\nstubs:\n  - link: \"ok\"\n  - [broken]\n  - type: expand\n    description: fine\n";
+        let result = recover_str(yaml);
+
+        assert_eq!(result.properties.title.as_deref(), Some("Doc"));
+        assert_eq!(result.properties.stubs.len(), 2);
+        assert_eq!(result.diagnostics.len(), 1);
+
+        let diag = &result.diagnostics[0];
+        assert_eq!(diag.field, "stubs[1]");
+        assert_eq!(diag.position.unwrap().line, 4);
+    }
+
+    #[test]
+    fn test_malformed_field_is_skipped() {
+        let yaml = "title: Doc\nrefinement: high\naudience: internal\n";
+        let result = recover_str(yaml);
+
+        assert_eq!(result.properties.audience, doc_doctor_domain::Audience::Internal);
+        assert_eq!(result.properties.refinement.value(), 0.0);
+        assert_eq!(result.diagnostics[0].field, "refinement");
+        assert_eq!(result.diagnostics[0].position.unwrap().line, 2);
+    }
+
+    #[test]
+    fn test_coercions_are_positioned() {
+        let yaml = "title: Doc\norigin: humna\nstubs:\n  - type: link\n    stub_form: blcoking\n";
+        let tracker = PositionTracker::new(yaml, 0);
+        let diagnostics = audit(yaml, &tracker);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert!(!diagnostics[0].is_skipped());
+        assert_eq!(diagnostics[0].field, "origin");
+        assert_eq!(diagnostics[0].suggestion.as_deref(), Some("Did you mean 'human'?"));
+        assert_eq!(diagnostics[1].field, "stubs[0].stub_form");
+        let position = diagnostics[1].position.unwrap();
+        assert_eq!((position.line, position.column), (5, 5));
+    }
+
+    #[test]
+    fn test_skipped_field_gains_suggestion() {
+        let result = recover_str("title: Doc\naudience: pubilc\n");
+
+        assert_eq!(result.diagnostics.len(), 1);
+        assert!(result.diagnostics[0].is_skipped());
+        assert_eq!(result.diagnostics[0].field, "audience");
+        assert_eq!(result.diagnostics[0].suggestion.as_deref(), Some("Did you mean 'public'?"));
+    }
+
+    #[test]
+    fn test_non_mapping_is_an_error() {
+        let tracker = PositionTracker::new("- a\n- b\n", 0);
+        assert!(recover("- a\n- b\n", &tracker).is_err());
+    }
+}
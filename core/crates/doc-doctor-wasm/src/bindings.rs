@@ -5,10 +5,10 @@
 use wasm_bindgen::prelude::*;
 use std::sync::Arc;
 
-use doc_doctor_application::{AnalyzeDocumentUseCase, ValidateDocumentUseCase};
+use doc_doctor_application::{ApplicationSwitchboard, Switchboard};
 use doc_doctor_domain::{
-    calculate_health, calculate_usefulness, Audience, AnalyzeDocument, DocumentParser,
-    EmbeddedSchemaProvider, SchemaProvider, Stub, StubContext, ValidateDocument, VectorPhysics,
+    Audience, CalculationConfig, DocumentParser, EmbeddedSchemaProvider, Stub, StubContext,
+    Timestamp,
 };
 use doc_doctor_parser_yaml::YamlParser;
 
@@ -21,10 +21,12 @@ use crate::types::{
 /// Main Doc-Doctor WASM interface
 ///
 /// Provides access to all document analysis functionality.
+/// All operations route through the application switchboard and use the
+/// configuration set with `setConfig` (built-in defaults until then).
 #[wasm_bindgen]
 pub struct DocDoctor {
     parser: Arc<YamlParser>,
-    schema_provider: Arc<EmbeddedSchemaProvider>,
+    switchboard: ApplicationSwitchboard<YamlParser, YamlParser, EmbeddedSchemaProvider>,
 }
 
 #[wasm_bindgen]
@@ -32,10 +34,68 @@ impl DocDoctor {
     /// Create a new DocDoctor instance
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        Self {
-            parser: Arc::new(YamlParser::new()),
-            schema_provider: Arc::new(EmbeddedSchemaProvider),
-        }
+        let parser = Arc::new(YamlParser::new());
+        let switchboard = ApplicationSwitchboard::new(
+            Arc::clone(&parser),
+            Arc::clone(&parser),
+            Arc::new(EmbeddedSchemaProvider),
+        );
+        Self { parser, switchboard }
+    }
+
+    /// Replace the calculation configuration
+    ///
+    /// # Arguments
+    /// * `config_json` - JSON object with configuration sections
+    ///   (`health`, `audience_gates`, `stub_penalties`, ...); omitted
+    ///   sections use built-in defaults
+    ///
+    /// # Returns
+    /// Error if the JSON is malformed or the configuration is invalid
+    #[wasm_bindgen(js_name = setConfig)]
+    pub fn set_config(&self, config_json: &str) -> Result<(), JsValue> {
+        let config: CalculationConfig = serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid config JSON: {}", e)))?;
+
+        self.switchboard
+            .set_config(config)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Get the active calculation configuration as JSON
+    #[wasm_bindgen(js_name = getConfig)]
+    pub fn get_config(&self) -> String {
+        serde_json::to_string(&*self.switchboard.config()).unwrap()
+    }
+
+    /// Restore the built-in default configuration
+    #[wasm_bindgen(js_name = resetConfig)]
+    pub fn reset_config(&self) {
+        self.switchboard.config_handle().reset();
+    }
+
+    /// Calculate freshness as of a fixed date or time
+    ///
+    /// # Arguments
+    /// * `as_of` - Date or time, e.g. `2024-06-01` or RFC 3339; dates
+    ///   without an offset are in the vault timezone. `undefined` returns
+    ///   to the current time
+    ///
+    /// # Returns
+    /// Error if the date is not recognized
+    #[wasm_bindgen(js_name = setAsOf)]
+    pub fn set_as_of(&self, as_of: Option<String>) -> Result<(), JsValue> {
+        let config = self.switchboard.config_handle();
+        let as_of = match as_of {
+            Some(text) => {
+                let timestamp =
+                    Timestamp::parse(&text).map_err(|e| JsValue::from_str(&e.to_string()))?;
+                Some(timestamp.resolve(config.get().vault.tz()))
+            }
+            None => None,
+        };
+        config.set_as_of(as_of);
+        Ok(())
     }
 
     /// Parse a markdown document and return L1 properties as JSON
@@ -48,8 +108,8 @@ impl DocDoctor {
     #[wasm_bindgen(js_name = parseDocument)]
     pub fn parse_document(&self, content: &str) -> Result<String, JsValue> {
         let props = self
-            .parser
-            .parse(content)
+            .switchboard
+            .parse_document(content)
             .map_err(|e| JsValue::from_str(&e.to_string()))?;
 
         let json = PropertiesJson::from_l1(&props);
@@ -65,9 +125,7 @@ impl DocDoctor {
     /// JSON string with full analysis result
     #[wasm_bindgen(js_name = analyzeDocument)]
     pub fn analyze_document(&self, content: &str) -> String {
-        let use_case = AnalyzeDocumentUseCase::new(Arc::clone(&self.parser) as Arc<dyn DocumentParser>);
-
-        let result = match use_case.analyze(content) {
+        let result = match self.switchboard.analyze_document(content) {
             Ok(analysis) => AnalysisResult::from_analysis(analysis),
             Err(e) => AnalysisResult::from_error(e.to_string()),
         };
@@ -87,12 +145,7 @@ impl DocDoctor {
     /// JSON string with validation result
     #[wasm_bindgen(js_name = validateDocument)]
     pub fn validate_document(&self, content: &str, strict: bool) -> String {
-        let use_case = ValidateDocumentUseCase::new(
-            Arc::clone(&self.parser) as Arc<dyn DocumentParser>,
-            Arc::clone(&self.schema_provider) as Arc<dyn SchemaProvider>,
-        );
-
-        match use_case.validate(content, strict) {
+        match self.switchboard.validate_document(content, strict) {
             Ok(result) => {
                 let json = ValidationResultJson {
                     is_valid: result.is_valid,
@@ -112,6 +165,8 @@ impl DocDoctor {
                         .map(|w| ValidationWarningJson {
                             message: w.message.clone(),
                             path: w.path.clone(),
+                            line: w.position.map(|p| p.line),
+                            column: w.position.map(|p| p.column),
                             suggestion: w.suggestion.clone(),
                         })
                         .collect(),
@@ -145,7 +200,7 @@ impl DocDoctor {
     #[wasm_bindgen(js_name = calculateHealth)]
     pub fn calculate_health(&self, refinement: f64, stubs_json: &str) -> Result<f64, JsValue> {
         let stubs = self.parse_stubs(stubs_json)?;
-        Ok(calculate_health(refinement, &stubs))
+        Ok(self.switchboard.calc_health(refinement, &stubs))
     }
 
     /// Calculate usefulness for an audience
@@ -162,7 +217,7 @@ impl DocDoctor {
             .parse()
             .map_err(|_| JsValue::from_str(&format!("Invalid audience: {}", audience)))?;
 
-        let usefulness = calculate_usefulness(refinement, audience);
+        let usefulness = self.switchboard.calc_usefulness(refinement, audience);
         let json = UsefulnessJson::from_usefulness(&usefulness);
 
         serde_json::to_string(&json).map_err(|e| JsValue::from_str(&e.to_string()))
@@ -195,7 +250,7 @@ impl DocDoctor {
             serde_json::from_str(context_json).map_err(|e| JsValue::from_str(&e.to_string()))?
         };
 
-        let physics = VectorPhysics::calculate(&stub, &context);
+        let physics = self.switchboard.calc_vector_physics(&stub, &context);
         let json = VectorPhysicsJson::from_physics(&physics);
 
         serde_json::to_string(&json).map_err(|e| JsValue::from_str(&e.to_string()))
@@ -213,7 +268,7 @@ impl DocDoctor {
         let props: doc_doctor_domain::L1Properties = serde_json::from_str(properties_json)
             .map_err(|e| JsValue::from_str(&e.to_string()))?;
 
-        let dims = doc_doctor_domain::StateDimensions::calculate(&props);
+        let dims = self.switchboard.calc_dimensions(&props);
         let json = DimensionsJson::from_state(&dims);
 
         serde_json::to_string(&json).map_err(|e| JsValue::from_str(&e.to_string()))
@@ -225,7 +280,7 @@ impl DocDoctor {
     /// JSON object with gate thresholds for each audience
     #[wasm_bindgen(js_name = getAudienceGates)]
     pub fn get_audience_gates(&self) -> String {
-        let gates = AudienceGatesJson::default();
+        let gates = AudienceGatesJson::from_config(&self.switchboard.config().audience_gates);
         serde_json::to_string(&gates).unwrap()
     }
 
@@ -301,6 +356,17 @@ mod tests {
         assert!(result.contains("\"health\""));
     }
 
+    #[test]
+    fn test_analyze_partial_document() {
+        let dd = DocDoctor::new();
+        let content = "---\ntitle: Test\nstubs:\n  - link: \"ok\"\n  - [broken]\n---\n";
+
+        let result = dd.analyze_document(content);
+        assert!(result.contains("\"success\":true"));
+        assert!(result.contains("\"partial\":true"));
+        assert!(result.contains("\"field\":\"stubs[1]\""));
+    }
+
     #[test]
     fn test_validate_document() {
         let dd = DocDoctor::new();
@@ -310,6 +376,18 @@ mod tests {
         assert!(result.contains("\"isValid\":true"));
     }
 
+    #[test]
+    fn test_validate_document_coercion_warning() {
+        let dd = DocDoctor::new();
+        let content = "---\ntitle: Test\norigin: humna\n---\n";
+
+        let result = dd.validate_document(content, false);
+        assert!(result.contains("\"isValid\":true"));
+        assert!(result.contains("\"path\":\"/origin\""));
+        assert!(result.contains("\"line\":3"));
+        assert!(result.contains("Did you mean 'human'?"));
+    }
+
     #[test]
     fn test_calculate_health() {
         let dd = DocDoctor::new();
@@ -332,6 +410,34 @@ mod tests {
         assert!(result.contains("\"public\":0.9"));
     }
 
+    #[test]
+    fn test_set_config() {
+        let dd = DocDoctor::new();
+        dd.set_config(r#"{"audience_gates":{"personal":0.4,"internal":0.6,"trusted":0.7,"public":0.85}}"#)
+            .unwrap();
+
+        assert!(dd.get_audience_gates().contains("\"public\":0.85"));
+        let result = dd.calculate_usefulness(0.86, "public").unwrap();
+        assert!(result.contains("\"isUseful\":true"));
+
+        dd.reset_config();
+        assert!(dd.get_config().contains("\"public\":0.9"));
+    }
+
+    #[test]
+    fn test_set_as_of() {
+        let dd = DocDoctor::new();
+        let content = "---\ntitle: Test\nmodified: 2024-01-01\n---\n";
+
+        dd.set_as_of(Some("2024-01-01".to_string())).unwrap();
+        let first = dd.analyze_document(content);
+        assert_eq!(dd.analyze_document(content), first);
+        assert!(first.contains("\"freshness\":1.0"));
+
+        dd.set_as_of(None).unwrap();
+        assert!(!dd.analyze_document(content).contains("\"freshness\":1.0"));
+    }
+
     #[test]
     fn test_version() {
         let dd = DocDoctor::new();
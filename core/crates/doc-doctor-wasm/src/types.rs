@@ -3,7 +3,8 @@
 //! These types provide a stable JSON interface for JavaScript consumers.
 
 use doc_doctor_domain::{
-    Audience, DocumentAnalysis, L1Properties, StateDimensions, Stub, Usefulness, VectorPhysics,
+    AudienceGatesConfig, DocumentAnalysis, L1Properties, ParseDiagnostic, StateDimensions, Stub,
+    Usefulness, VectorPhysics,
 };
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +21,10 @@ pub struct AnalysisResult {
     pub dimensions: Option<DimensionsJson>,
     /// Warnings
     pub warnings: Vec<String>,
+    /// Whether some fields or stubs were skipped as malformed
+    pub partial: bool,
+    /// One entry per skipped field or stub
+    pub diagnostics: Vec<DiagnosticJson>,
 }
 
 impl AnalysisResult {
@@ -34,6 +39,8 @@ impl AnalysisResult {
                 .iter()
                 .map(|w| w.message.clone())
                 .collect(),
+            partial: analysis.partial,
+            diagnostics: analysis.diagnostics.iter().map(DiagnosticJson::from_diagnostic).collect(),
         }
     }
 
@@ -44,6 +51,28 @@ impl AnalysisResult {
             properties: None,
             dimensions: None,
             warnings: Vec::new(),
+            partial: false,
+            diagnostics: Vec::new(),
+        }
+    }
+}
+
+/// Parse diagnostic (JSON-serializable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticJson {
+    pub field: String,
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl DiagnosticJson {
+    pub fn from_diagnostic(diagnostic: &ParseDiagnostic) -> Self {
+        Self {
+            field: diagnostic.field.clone(),
+            message: diagnostic.message.clone(),
+            line: diagnostic.position.map(|p| p.line),
+            column: diagnostic.position.map(|p| p.column),
         }
     }
 }
@@ -231,13 +260,13 @@ pub struct AudienceGatesJson {
     pub public: f64,
 }
 
-impl Default for AudienceGatesJson {
-    fn default() -> Self {
+impl AudienceGatesJson {
+    pub fn from_config(gates: &AudienceGatesConfig) -> Self {
         Self {
-            personal: Audience::Personal.gate(),
-            internal: Audience::Internal.gate(),
-            trusted: Audience::Trusted.gate(),
-            public: Audience::Public.gate(),
+            personal: gates.personal,
+            internal: gates.internal,
+            trusted: gates.trusted,
+            public: gates.public,
         }
     }
 }
@@ -265,5 +294,7 @@ pub struct ValidationErrorJson {
 pub struct ValidationWarningJson {
     pub message: String,
     pub path: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
     pub suggestion: Option<String>,
 }